@@ -0,0 +1,336 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::gitstatus;
+
+/// A shell token: a word with quotes removed, or an operator.
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    /// Ends a simple command: `;`, `&&`, `||`, `|`, `&`, or a newline
+    Separator,
+    /// `>`, `>>`, `&>` and the like: the next word is a file the command writes
+    Redirect,
+}
+
+/// Files a `Bash` tool command writes, resolved against `cwd`: redirection targets
+/// (`>`, `>>`, including `cat > file <<EOF`), `tee` arguments, files edited in place by
+/// `sed -i` or `perl -i`, and the destinations of `mv` and `cp`. A best-effort reading of
+/// common commands, not a shell parser: files written by scripts or other programs are missed.
+pub fn modified_files(command: &str, cwd: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    let mut redirect = false;
+    let mut tokens = tokenize(&strip_heredoc_bodies(command));
+    tokens.push(Token::Separator);
+    for token in tokens {
+        match token {
+            Token::Word(word) if redirect => {
+                redirect = false;
+                if word != "/dev/null" {
+                    files.push(resolve(&word, cwd));
+                }
+            }
+            Token::Word(word) => words.push(word),
+            Token::Redirect => redirect = true,
+            Token::Separator => {
+                files.extend(command_targets(&words, cwd));
+                words.clear();
+                redirect = false;
+            }
+        }
+    }
+    let mut seen = std::collections::HashSet::new();
+    files.retain(|file| seen.insert(file.clone()));
+    files
+}
+
+/// Files one simple command writes through its arguments.
+fn command_targets(words: &[String], cwd: &Path) -> Vec<PathBuf> {
+    // Skip `VAR=value` assignments and `sudo`
+    let words: Vec<&str> = words
+        .iter()
+        .map(String::as_str)
+        .skip_while(|w| *w == "sudo" || is_assignment(w))
+        .collect();
+    let Some((program, args)) = words.split_first() else {
+        return Vec::new();
+    };
+    let program = program.rsplit('/').next().unwrap_or(program);
+    let targets: Vec<&str> = match program {
+        "sed" | "perl" => in_place_files(args),
+        "tee" => args
+            .iter()
+            .copied()
+            .filter(|a| !a.starts_with('-'))
+            .collect(),
+        "mv" | "cp" => {
+            let operands: Vec<&str> = args
+                .iter()
+                .copied()
+                .filter(|a| !a.starts_with('-'))
+                .collect();
+            return match operands.split_last() {
+                Some((dest, sources)) if !sources.is_empty() => {
+                    let dest = resolve(dest, cwd);
+                    if dest.is_dir() {
+                        sources
+                            .iter()
+                            .filter_map(|source| Path::new(source).file_name())
+                            .map(|name| dest.join(name))
+                            .collect()
+                    } else {
+                        vec![dest]
+                    }
+                }
+                _ => Vec::new(),
+            };
+        }
+        _ => Vec::new(),
+    };
+    targets.iter().map(|target| resolve(target, cwd)).collect()
+}
+
+/// The files of a `sed` or `perl` command, if it edits in place (`-i`, `-i.bak`, `-pi`,
+/// `--in-place`). The first operand is the script unless one is given with `-e` or `-f`.
+fn in_place_files<'a>(args: &[&'a str]) -> Vec<&'a str> {
+    let mut in_place = false;
+    let mut has_script = false;
+    let mut operands = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg.starts_with("--in-place") {
+            in_place = true;
+        } else if *arg == "--expression" || *arg == "--file" {
+            has_script = true;
+            args.next();
+        } else if let Some(flags) = arg.strip_prefix('-').filter(|f| !f.is_empty()) {
+            // `-i` takes an optional suffix, so it ends a group of flags
+            let letters: String = flags.chars().take_while(|c| *c != 'i').collect();
+            in_place |= letters.len() < flags.len();
+            if letters.ends_with('e') || letters.ends_with('f') {
+                has_script = true;
+                args.next();
+            }
+        } else {
+            operands.push(*arg);
+        }
+    }
+    if !in_place {
+        return Vec::new();
+    }
+    operands
+        .into_iter()
+        .skip(usize::from(!has_script))
+        .collect()
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+fn resolve(word: &str, cwd: &Path) -> PathBuf {
+    word.strip_prefix("~/")
+        .and_then(|rest| Some(PathBuf::from(std::env::var_os("HOME")?).join(rest)))
+        .unwrap_or_else(|| cwd.join(word))
+}
+
+/// Drop the bodies of here-documents (`<<EOF` ... `EOF`), which are data rather than commands.
+fn strip_heredoc_bodies(command: &str) -> String {
+    let mut kept = String::new();
+    let mut pending: Vec<String> = Vec::new();
+    for line in command.lines() {
+        if let Some(delimiter) = pending.first() {
+            if line.trim() == delimiter {
+                pending.remove(0);
+            }
+            continue;
+        }
+        let mut rest = line;
+        while let Some(start) = rest.find("<<") {
+            rest = &rest[start + 2..];
+            // `<<<` is a here-string, not a here-document
+            if let Some(after) = rest.strip_prefix('<') {
+                rest = after;
+                continue;
+            }
+            let word: String = rest
+                .trim_start_matches('-')
+                .trim_start()
+                .chars()
+                .take_while(|c| !c.is_whitespace() && !";|&<>".contains(*c))
+                .filter(|c| *c != '\'' && *c != '"')
+                .collect();
+            if !word.is_empty() {
+                pending.push(word);
+            }
+        }
+        let _ = writeln!(kept, "{line}");
+    }
+    kept
+}
+
+fn tokenize(command: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    // Whether `word` holds a (possibly empty) quoted word
+    let mut quoted = false;
+    let mut chars = command.chars().peekable();
+    let flush = |word: &mut String, quoted: &mut bool, tokens: &mut Vec<Token>| {
+        if !word.is_empty() || *quoted {
+            tokens.push(Token::Word(std::mem::take(word)));
+        }
+        *quoted = false;
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                quoted = true;
+                word.extend(chars.by_ref().take_while(|c| *c != '\''));
+            }
+            '"' => {
+                quoted = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => word.extend(chars.next()),
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') | None => {}
+                Some(c) => word.push(c),
+            },
+            '#' if word.is_empty() && !quoted => {
+                // A comment runs to the end of the line
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '>' => {
+                // `2>` names a file descriptor, not a word
+                if !quoted && !word.is_empty() && word.chars().all(|c| c.is_ascii_digit()) {
+                    word.clear();
+                }
+                flush(&mut word, &mut quoted, &mut tokens);
+                if chars.peek() == Some(&'&') {
+                    // `>&2` duplicates a descriptor
+                    chars.next();
+                    while chars
+                        .peek()
+                        .is_some_and(|c| c.is_ascii_digit() || *c == '-')
+                    {
+                        chars.next();
+                    }
+                    continue;
+                }
+                if matches!(chars.peek(), Some('>' | '|')) {
+                    chars.next();
+                }
+                tokens.push(Token::Redirect);
+            }
+            '&' if chars.peek() == Some(&'>') => {
+                flush(&mut word, &mut quoted, &mut tokens);
+            }
+            ';' | '|' | '&' | '\n' | '(' | ')' => {
+                flush(&mut word, &mut quoted, &mut tokens);
+                if matches!(chars.peek(), Some('|' | '&')) && c != '\n' {
+                    chars.next();
+                }
+                tokens.push(Token::Separator);
+            }
+            c if c.is_whitespace() => flush(&mut word, &mut quoted, &mut tokens),
+            c => word.push(c),
+        }
+    }
+    flush(&mut word, &mut quoted, &mut tokens);
+    tokens
+}
+
+/// Where the dirty files of the repository are recorded before a session's `Bash` call.
+fn snapshot_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-bash-status.txt"))
+}
+
+/// Record the repository's dirty files before a `Bash` call (`PreToolUse`), for
+/// [`changed_since_snapshot`] to compare against.
+pub fn record_snapshot(session_id: &str, cwd: &Path) -> std::io::Result<()> {
+    gitstatus::record(&snapshot_path(session_id), cwd)
+}
+
+/// Files changed by the `Bash` call since [`record_snapshot`], according to `git status`.
+/// `None` without a snapshot, as when only the `PostToolUse` hook is registered for `Bash`.
+pub fn changed_since_snapshot(session_id: &str) -> Option<Vec<PathBuf>> {
+    let path = snapshot_path(session_id);
+    let changed = gitstatus::changed_since(&path);
+    let _ = fs::remove_file(&path);
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(command: &str) -> Vec<String> {
+        modified_files(command, Path::new("/w"))
+            .iter()
+            .map(|f| f.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn redirections_and_tee() {
+        assert_eq!(
+            files("echo hi > out.py && cat a.txt | tee -a log.sh /abs/b.js 2>/dev/null"),
+            vec!["/w/out.py", "/w/log.sh", "/abs/b.js"]
+        );
+        assert_eq!(
+            files("cmd 2>err.txt >>\"my file.rs\" >&2"),
+            vec!["/w/err.txt", "/w/my file.rs"]
+        );
+        assert_eq!(files("cmd &> both.log"), vec!["/w/both.log"]);
+        assert!(files("cat a.py | grep x # > not.py").is_empty());
+    }
+
+    #[test]
+    fn heredoc_bodies_are_not_commands() {
+        let command =
+            "cat > src/app.py <<'EOF'\nimport os\necho > nope.py\nEOF\ncat <<< \"x\" > here.py";
+        assert_eq!(files(command), vec!["/w/src/app.py", "/w/here.py"]);
+    }
+
+    #[test]
+    fn in_place_edits() {
+        assert_eq!(
+            files("sed -i 's/a/b/' a.py b.py; sed 's/a/b/' c.py > d.py"),
+            vec!["/w/a.py", "/w/b.py", "/w/d.py"]
+        );
+        assert_eq!(
+            files("sed -i.bak -e 's/a/b/' -e 's/c/d/' x.rs"),
+            vec!["/w/x.rs"]
+        );
+        assert_eq!(files("sudo sed -Ei 's/a/b/' y.rs"), vec!["/w/y.rs"]);
+        assert_eq!(files("perl -pi -e 's/a/b/' z.go"), vec!["/w/z.go"]);
+        assert!(files("sed -n '1p' a.py").is_empty());
+    }
+
+    #[test]
+    fn move_and_copy_destinations() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-bash-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let found = modified_files("mv -f old.py new.py && cp a.rs b.rs src", &dir);
+        assert_eq!(
+            found,
+            vec![
+                dir.join("new.py"),
+                dir.join("src/a.rs"),
+                dir.join("src/b.rs")
+            ]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
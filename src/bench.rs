@@ -0,0 +1,501 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::extract::extract_reason_field;
+use crate::lint::escape_json;
+use crate::procgroup::GroupOutput;
+use crate::project::{self, Lang};
+
+/// Runs per linter: the first is reported as cold, the median of the rest as warm.
+const RUNS: usize = 5;
+
+/// Stands for the benchmarked file, or `.` for a project.
+const PATH: &str = "{path}";
+/// Stands for the file's Go package, or `./...` for a project.
+const PACKAGE: &str = "{package}";
+
+/// A linter invocation to time, with plain-text output whose findings can be counted.
+struct Candidate {
+    lang: Lang,
+    linter: &'static str,
+    program: &'static str,
+    args: &'static [&'static str],
+}
+
+const CANDIDATES: &[Candidate] = &[
+    Candidate {
+        lang: Lang::JavaScript,
+        linter: "oxlint",
+        program: "oxlint",
+        args: &["--format=unix", PATH],
+    },
+    Candidate {
+        lang: Lang::JavaScript,
+        linter: "biome",
+        program: "biome",
+        args: &["lint", PATH],
+    },
+    Candidate {
+        lang: Lang::JavaScript,
+        linter: "eslint",
+        program: "eslint",
+        args: &[PATH],
+    },
+    Candidate {
+        lang: Lang::JavaScript,
+        linter: "eslint_d",
+        program: "eslint_d",
+        args: &[PATH],
+    },
+    Candidate {
+        lang: Lang::Rust,
+        linter: "clippy",
+        program: "cargo",
+        args: &["clippy", "--quiet", "--message-format=short"],
+    },
+    Candidate {
+        lang: Lang::Python,
+        linter: "ruff",
+        program: "ruff",
+        args: &["check", "--output-format=concise", PATH],
+    },
+    Candidate {
+        lang: Lang::Python,
+        linter: "mypy",
+        program: "mypy",
+        args: &[PATH],
+    },
+    Candidate {
+        lang: Lang::Python,
+        linter: "pylint",
+        program: "pylint",
+        args: &["--output-format=parseable", PATH],
+    },
+    Candidate {
+        lang: Lang::Python,
+        linter: "flake8",
+        program: "flake8",
+        args: &[PATH],
+    },
+    Candidate {
+        lang: Lang::Go,
+        linter: "golangci-lint",
+        program: "golangci-lint",
+        args: &["run", PACKAGE],
+    },
+    Candidate {
+        lang: Lang::Go,
+        linter: "revive",
+        program: "revive",
+        args: &[PACKAGE],
+    },
+    Candidate {
+        lang: Lang::Go,
+        linter: "staticcheck",
+        program: "staticcheck",
+        args: &[PACKAGE],
+    },
+    Candidate {
+        lang: Lang::Go,
+        linter: "go vet",
+        program: "go",
+        args: &["vet", PACKAGE],
+    },
+    Candidate {
+        lang: Lang::Php,
+        linter: "phpstan",
+        program: "phpstan",
+        args: &["analyse", "--no-progress", "--error-format=raw", PATH],
+    },
+    Candidate {
+        lang: Lang::Php,
+        linter: "psalm",
+        program: "psalm",
+        args: &["--no-progress", "--output-format=emacs", PATH],
+    },
+    Candidate {
+        lang: Lang::Php,
+        linter: "phpcs",
+        program: "phpcs",
+        args: &["-q", "--report=emacs", PATH],
+    },
+    Candidate {
+        lang: Lang::Swift,
+        linter: "swiftlint",
+        program: "swiftlint",
+        args: &["lint", "--quiet", "--path", PATH],
+    },
+    Candidate {
+        lang: Lang::Shell,
+        linter: "shellcheck",
+        program: "shellcheck",
+        args: &["--format=gcc", PATH],
+    },
+    Candidate {
+        lang: Lang::Markdown,
+        linter: "markdownlint",
+        program: "markdownlint-cli2",
+        args: &[PATH],
+    },
+    Candidate {
+        lang: Lang::Elixir,
+        linter: "credo",
+        program: "mix",
+        args: &["credo", "--strict", "--format", "flycheck", PATH],
+    },
+    Candidate {
+        lang: Lang::Haskell,
+        linter: "hlint",
+        program: "hlint",
+        args: &[PATH],
+    },
+    Candidate {
+        lang: Lang::Haskell,
+        linter: "ghc",
+        program: "ghc",
+        args: &["-fno-code", "-Wall", PATH],
+    },
+    Candidate {
+        lang: Lang::Zig,
+        linter: "zig ast-check",
+        program: "zig",
+        args: &["ast-check", PATH],
+    },
+    Candidate {
+        lang: Lang::Lua,
+        linter: "selene",
+        program: "selene",
+        args: &["--display-style", "quiet", PATH],
+    },
+    Candidate {
+        lang: Lang::Lua,
+        linter: "luacheck",
+        program: "luacheck",
+        args: &["--formatter", "plain", "--codes", PATH],
+    },
+    Candidate {
+        lang: Lang::Scala,
+        linter: "scalafix",
+        program: "sbt",
+        args: &["-batch", "-no-colors", "scalafix --check"],
+    },
+    Candidate {
+        lang: Lang::Scala,
+        linter: "scalastyle",
+        program: "sbt",
+        args: &["-batch", "-no-colors", "scalastyle"],
+    },
+    Candidate {
+        lang: Lang::PowerShell,
+        linter: "PSScriptAnalyzer",
+        program: "pwsh",
+        // pwsh joins the arguments after -Command into one script
+        args: &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Invoke-ScriptAnalyzer -Severity Error -Path",
+            PATH,
+            "| ForEach-Object { '{0}:{1}:{2}: {3}' -f $_.ScriptName, $_.Line, $_.Column, $_.RuleName }",
+        ],
+    },
+    Candidate {
+        lang: Lang::Nix,
+        linter: "statix",
+        program: "statix",
+        args: &["check", "--format", "errfmt", PATH],
+    },
+    Candidate {
+        lang: Lang::Nix,
+        linter: "nix-instantiate",
+        program: "nix-instantiate",
+        args: &["--parse", PATH],
+    },
+    Candidate {
+        lang: Lang::Solidity,
+        linter: "solhint",
+        program: "solhint",
+        args: &["--formatter", "unix", PATH],
+    },
+    Candidate {
+        lang: Lang::Solidity,
+        linter: "forge build",
+        program: "forge",
+        args: &["build", "--force", "--color", "never"],
+    },
+    Candidate {
+        lang: Lang::Terraform,
+        linter: "tflint",
+        program: "tflint",
+        args: &["--format=compact"],
+    },
+];
+
+/// Project markers that select a language when a directory is benchmarked.
+const PROJECT_MARKERS: &[(&str, Lang)] = &[
+    ("package.json", Lang::JavaScript),
+    ("Cargo.toml", Lang::Rust),
+    ("pyproject.toml", Lang::Python),
+    ("setup.py", Lang::Python),
+    ("requirements.txt", Lang::Python),
+    ("go.mod", Lang::Go),
+    ("composer.json", Lang::Php),
+    ("Package.swift", Lang::Swift),
+    ("mix.exs", Lang::Elixir),
+    ("stack.yaml", Lang::Haskell),
+    ("package.yaml", Lang::Haskell),
+    ("build.zig", Lang::Zig),
+    ("selene.toml", Lang::Lua),
+    (".luacheckrc", Lang::Lua),
+    ("build.sbt", Lang::Scala),
+    ("PSScriptAnalyzerSettings.psd1", Lang::PowerShell),
+    ("flake.nix", Lang::Nix),
+    ("default.nix", Lang::Nix),
+    ("foundry.toml", Lang::Solidity),
+    ("hardhat.config.js", Lang::Solidity),
+    ("hardhat.config.ts", Lang::Solidity),
+    ("truffle-config.js", Lang::Solidity),
+    (".tflint.hcl", Lang::Terraform),
+];
+
+/// What is benchmarked: one file, or a whole project directory.
+struct Target {
+    root: PathBuf,
+    langs: Vec<Lang>,
+    path: String,
+    package: String,
+    /// The file, when the target is a file; the hook pipeline only lints files
+    file: Option<String>,
+}
+
+/// Run each installed linter that applies to `target` (a file or a project directory)
+/// [`RUNS`] times and print its cold and warm latency and finding count. For a file, `lint`
+/// (the full hook pipeline, given a payload) is timed the same way.
+pub fn run(target: &str, lint: impl Fn(&str) -> String) -> Result<(), Box<dyn std::error::Error>> {
+    let target = resolve(target)?;
+    println!(
+        "{:<14} {:>8} {:>8} {:>9}",
+        "linter", "cold ms", "warm ms", "findings"
+    );
+    let mut any = false;
+    for candidate in CANDIDATES.iter().filter(|c| target.langs.contains(&c.lang)) {
+        let Some(program) = find_program(&target.root, candidate.program) else {
+            println!("{:<14} not installed", candidate.linter);
+            continue;
+        };
+        let args: Vec<&str> = candidate
+            .args
+            .iter()
+            .map(|arg| match *arg {
+                PATH => target.path.as_str(),
+                PACKAGE => target.package.as_str(),
+                arg => arg,
+            })
+            .collect();
+        let timing = time_runs(|| {
+            let output = Command::new(&program)
+                .args(&args)
+                .current_dir(&target.root)
+                .output_grouped()?;
+            Ok(format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ))
+        })?;
+        println!("{}", timing.row(candidate.linter));
+        any = true;
+    }
+
+    if let Some(file) = &target.file {
+        let payload = format!(
+            r#"{{"tool_input":{{"file_path":"{}"}}}}"#,
+            escape_json(file)
+        );
+        // Findings are counted in the block reason
+        let timing = time_runs(|| Ok(extract_reason_field(&lint(&payload)).unwrap_or_default()))?;
+        println!("{}", timing.row("hook"));
+    } else if !any {
+        println!("no linters to benchmark in {}", target.root.display());
+    }
+    Ok(())
+}
+
+fn resolve(target: &str) -> Result<Target, Box<dyn std::error::Error>> {
+    let path = std::fs::canonicalize(target).map_err(|e| format!("cannot bench {target}: {e}"))?;
+    if path.is_dir() {
+        let langs = PROJECT_MARKERS
+            .iter()
+            .filter(|(marker, _)| path.join(marker).is_file())
+            .map(|(_, lang)| *lang)
+            .collect();
+        return Ok(Target {
+            root: path,
+            langs,
+            path: ".".to_string(),
+            package: "./...".to_string(),
+            file: None,
+        });
+    }
+
+    let file = path.to_string_lossy().to_string();
+    let lang = project::detect_lang(&file).ok_or_else(|| format!("unsupported file {target}"))?;
+    let root = project::find_project_root(&file).map_or_else(
+        || {
+            path.parent()
+                .map_or_else(|| PathBuf::from("/"), Path::to_path_buf)
+        },
+        |info| PathBuf::from(info.root),
+    );
+    let package = path
+        .parent()
+        .and_then(|dir| dir.strip_prefix(&root).ok())
+        .map_or_else(
+            || ".".to_string(),
+            |dir| {
+                format!("./{}", dir.display())
+                    .trim_end_matches('/')
+                    .to_string()
+            },
+        );
+    Ok(Target {
+        root,
+        langs: vec![lang],
+        path: file.clone(),
+        package,
+        file: Some(file),
+    })
+}
+
+/// The project's own copy first (`node_modules/.bin`, `.venv/bin`, `vendor/bin`), then `PATH`.
+fn find_program(root: &Path, program: &str) -> Option<PathBuf> {
+    ["node_modules/.bin", ".venv/bin", "vendor/bin"]
+        .iter()
+        .map(|dir| root.join(dir))
+        .chain(env::var_os("PATH").iter().flat_map(env::split_paths))
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Latencies and findings of [`RUNS`] runs of one linter.
+#[derive(Debug)]
+struct Timing {
+    cold: Duration,
+    warm: Duration,
+    findings: usize,
+}
+
+impl Timing {
+    fn row(&self, linter: &str) -> String {
+        format!(
+            "{linter:<14} {:>8} {:>8} {:>9}",
+            self.cold.as_millis(),
+            self.warm.as_millis(),
+            self.findings
+        )
+    }
+}
+
+fn time_runs(
+    mut run: impl FnMut() -> std::io::Result<String>,
+) -> Result<Timing, Box<dyn std::error::Error>> {
+    let mut durations = Vec::with_capacity(RUNS);
+    let mut output = String::new();
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        output = run()?;
+        durations.push(start.elapsed());
+    }
+    Ok(Timing {
+        cold: durations[0],
+        warm: median(&durations[1..]),
+        findings: count_findings(&output),
+    })
+}
+
+fn median(durations: &[Duration]) -> Duration {
+    let mut sorted = durations.to_vec();
+    sorted.sort();
+    sorted.get(sorted.len() / 2).copied().unwrap_or_default()
+}
+
+/// Count diagnostic lines: `path:line:...` as most linters print, or eslint's stylish
+/// `  line:column  severity ...` under a file heading.
+fn count_findings(output: &str) -> usize {
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    output
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim_start();
+            let mut parts = trimmed.splitn(3, ':');
+            let (Some(first), Some(second)) = (parts.next(), parts.next()) else {
+                return false;
+            };
+            if line.starts_with(char::is_whitespace) {
+                is_number(first) && second.split_whitespace().next().is_some_and(is_number)
+            } else {
+                !first.is_empty()
+                    && !first.contains(' ')
+                    && is_number(second.split_whitespace().next().unwrap_or_default())
+            }
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_diagnostic_lines() {
+        let ruff = "a.py:1:8: F401 [*] `os` imported but unused\nFound 1 error.\n";
+        assert_eq!(count_findings(ruff), 1);
+        let pylint = "************* Module a\na.py:1: [C0114(missing-module-docstring), ] Missing module docstring\n";
+        assert_eq!(count_findings(pylint), 1);
+        let biome = "index.ts:1:7 lint/correctness/noUnusedVariables ━━━━\n\n  × This variable is unused.\n";
+        assert_eq!(count_findings(biome), 1);
+        let eslint = "/p/index.js\n  1:7   error  'x' is assigned a value but never used  no-unused-vars\n  2:1   warning  Unexpected console statement  no-console\n\n✖ 2 problems\n";
+        assert_eq!(count_findings(eslint), 2);
+        assert_eq!(count_findings("Error: something: 1 failed\n"), 0);
+    }
+
+    #[test]
+    fn cold_is_first_run_and_warm_is_median_of_the_rest() {
+        let mut calls = 0;
+        let timing = time_runs(|| {
+            calls += 1;
+            Ok("a.py:1:1: F401 unused\n".to_string())
+        })
+        .unwrap();
+        assert_eq!(calls, RUNS);
+        assert_eq!(timing.findings, 1);
+        assert_eq!(
+            median(&[
+                Duration::from_millis(30),
+                Duration::from_millis(10),
+                Duration::from_millis(20)
+            ]),
+            Duration::from_millis(20)
+        );
+        assert_eq!(median(&[]), Duration::ZERO);
+    }
+
+    #[test]
+    fn project_target_uses_markers() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-bench-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("pyproject.toml"), "").unwrap();
+        std::fs::write(dir.join("go.mod"), "module x\n").unwrap();
+
+        let target = resolve(&dir.to_string_lossy()).unwrap();
+        assert_eq!(target.langs, vec![Lang::Python, Lang::Go]);
+        assert_eq!(
+            (target.path.as_str(), target.package.as_str()),
+            (".", "./...")
+        );
+        assert!(target.file.is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
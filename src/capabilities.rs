@@ -0,0 +1,324 @@
+use std::env;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, Value};
+use crate::lint::escape_json;
+use crate::policy;
+
+/// A supported language: the files routed to it and the linters it can run, in order of preference.
+struct Language {
+    name: &'static str,
+    /// Extensions, or `/name` suffixes for manifests such as `/Cargo.toml`
+    files: &'static [&'static str],
+    linters: &'static [&'static str],
+}
+
+const LANGUAGES: &[Language] = &[
+    Language {
+        name: "javascript",
+        files: &[
+            ".js",
+            ".jsx",
+            ".ts",
+            ".tsx",
+            ".mjs",
+            ".cjs",
+            ".astro",
+            ".marko",
+            ".ejs",
+            ".vue",
+            "/package.json",
+        ],
+        linters: &[
+            "oxlint", "biome", "eslint", "eslint_d", "tsc", "npm", "astro", "ejslint", "vue-tsc",
+        ],
+    },
+    Language {
+        name: "rust",
+        files: &[".rs", "/Cargo.toml", "/Cargo.lock"],
+        linters: &["cargo", "cargo-clippy", "rustc", "rust-script"],
+    },
+    Language {
+        name: "python",
+        files: &[".py", ".pyi"],
+        linters: &["ruff", "mypy", "pylint", "flake8"],
+    },
+    Language {
+        name: "java",
+        files: &[".java"],
+        linters: &["mvn", "gradle"],
+    },
+    Language {
+        name: "go",
+        files: &[".go", "/go.mod", "/go.sum"],
+        linters: &["golangci-lint", "revive", "staticcheck", "go"],
+    },
+    Language {
+        name: "c",
+        files: &[
+            ".c", ".h", ".cc", ".cpp", ".cxx", ".c++", ".hh", ".hpp", ".hxx", ".m", ".mm",
+        ],
+        linters: &["clang", "gcc", "cppcheck", "oclint"],
+    },
+    Language {
+        name: "kotlin",
+        files: &[".kts"],
+        linters: &["ktlint"],
+    },
+    Language {
+        name: "php",
+        files: &[".php"],
+        linters: &["phpstan", "psalm", "phpcs", "php"],
+    },
+    Language {
+        name: "csharp",
+        files: &[".cs"],
+        linters: &["dotnet"],
+    },
+    Language {
+        name: "swift",
+        files: &[".swift"],
+        linters: &["swiftlint"],
+    },
+    Language {
+        name: "shell",
+        files: &[".sh", ".bash", ".zsh"],
+        linters: &["shellcheck"],
+    },
+    Language {
+        name: "markdown",
+        files: &[".md", ".mdx"],
+        linters: &["markdownlint-cli2"],
+    },
+    Language {
+        name: "elixir",
+        files: &[".ex", ".exs"],
+        linters: &["mix"],
+    },
+    Language {
+        name: "haskell",
+        files: &[".hs"],
+        linters: &["hlint", "ghc"],
+    },
+    Language {
+        name: "zig",
+        files: &[".zig"],
+        linters: &["zig"],
+    },
+    Language {
+        name: "lua",
+        files: &[".lua"],
+        linters: &["selene", "luacheck"],
+    },
+    Language {
+        name: "scala",
+        files: &[".scala"],
+        linters: &["sbt", "scalac"],
+    },
+    Language {
+        name: "powershell",
+        files: &[".ps1", ".psm1"],
+        linters: &["pwsh"],
+    },
+    Language {
+        name: "nix",
+        files: &[".nix"],
+        linters: &["statix", "nix-instantiate"],
+    },
+    Language {
+        name: "solidity",
+        files: &[".sol"],
+        linters: &["solhint", "forge"],
+    },
+    Language {
+        name: "terraform",
+        files: &[".tf", ".tofu"],
+        linters: &["tflint", "conftest"],
+    },
+];
+
+/// Hook events the binary answers, with or without an explicit command.
+const HOOK_EVENTS: &[&str] = &[
+    "PostToolUse",
+    "PreToolUse",
+    "Stop",
+    "SubagentStop",
+    "SessionStart",
+];
+
+/// Print what this build supports and what it finds on this machine, for orchestration layers
+/// deciding how to wire the hook. Linters are looked up on `PATH` and in the working
+/// directory's `node_modules/.bin` and `.venv/bin`.
+pub fn run(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let cwd = env::current_dir()?;
+    let config = Config::load(&cwd.to_string_lossy());
+    let dirs = search_dirs(&cwd);
+    let installed = |linter: &str| dirs.iter().any(|dir| dir.join(linter).is_file());
+    if json {
+        println!("{}", to_json(&config, installed));
+    } else {
+        print!("{}", to_text(&config, installed));
+    }
+    Ok(())
+}
+
+fn search_dirs(cwd: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = env::var_os("PATH")
+        .map(|path| env::split_paths(&path).collect())
+        .unwrap_or_default();
+    dirs.push(cwd.join("node_modules/.bin"));
+    dirs.push(cwd.join(".venv/bin"));
+    dirs
+}
+
+fn to_json(config: &Config, installed: impl Fn(&str) -> bool) -> String {
+    let languages: Vec<String> = LANGUAGES
+        .iter()
+        .map(|lang| {
+            let linters: Vec<String> = lang
+                .linters
+                .iter()
+                .map(|linter| format!(r#"{{"name":"{linter}","installed":{}}}"#, installed(linter)))
+                .collect();
+            format!(
+                r#"{{"name":"{}","files":{},"linters":[{}]}}"#,
+                lang.name,
+                json_strings(lang.files),
+                linters.join(",")
+            )
+        })
+        .collect();
+    let settings: Vec<String> = config
+        .values()
+        .iter()
+        .map(|(key, value)| format!(r#""{}":{}"#, escape_json(key), json_value(value)))
+        .collect();
+    let path = config.source.as_ref().map_or_else(
+        || "null".to_string(),
+        |path| format!(r#""{}""#, escape_json(&path.to_string_lossy())),
+    );
+    let policy = policy::status().map_or_else(
+        || "null".to_string(),
+        |status| format!(r#""{}""#, escape_json(&status)),
+    );
+    format!(
+        r#"{{"version":"{}","languages":[{}],"config":{{"path":{path},"settings":{{{}}},"policy":{policy}}},"hook_events":{}}}"#,
+        env!("CARGO_PKG_VERSION"),
+        languages.join(","),
+        settings.join(","),
+        json_strings(HOOK_EVENTS)
+    )
+}
+
+fn to_text(config: &Config, installed: impl Fn(&str) -> bool) -> String {
+    let mut out = format!(
+        "ralph-hook-lint {}\n\nLanguages:\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    for lang in LANGUAGES {
+        let linters: Vec<String> = lang
+            .linters
+            .iter()
+            .map(|linter| {
+                if installed(linter) {
+                    (*linter).to_string()
+                } else {
+                    format!("{linter} (missing)")
+                }
+            })
+            .collect();
+        let _ = writeln!(out, "  {:<11} {}", lang.name, linters.join(", "));
+    }
+    let _ = match &config.source {
+        Some(path) => writeln!(out, "\nConfig: {}", path.display()),
+        None => writeln!(out, "\nConfig: none"),
+    };
+    if let Some(status) = policy::status() {
+        let _ = writeln!(out, "Policy: {status}");
+    }
+    let _ = writeln!(out, "\nHook events: {}", HOOK_EVENTS.join(", "));
+    out
+}
+
+fn json_strings(items: &[impl AsRef<str>]) -> String {
+    let quoted: Vec<String> = items
+        .iter()
+        .map(|item| format!(r#""{}""#, escape_json(item.as_ref())))
+        .collect();
+    format!("[{}]", quoted.join(","))
+}
+
+fn json_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!(r#""{}""#, escape_json(s)),
+        Value::Bool(b) => b.to_string(),
+        Value::Integer(i) => i.to_string(),
+        Value::Array(items) => json_strings(items),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{self, Json};
+    use crate::project::{Lang, detect_lang};
+
+    #[test]
+    fn every_listed_file_routes_to_its_language() {
+        for lang in LANGUAGES {
+            for suffix in lang.files {
+                let file = format!("/p/file{suffix}");
+                assert_eq!(
+                    detect_lang(&file).map(Lang::name),
+                    Some(lang.name),
+                    "{file}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn json_lists_languages_config_and_events() {
+        let config = Config::parse("[output]\nreport = \"new\"\n[hooks]\ntools = [\"Write\"]\n");
+        let report = json::parse(&to_json(&config, |linter| linter == "ruff")).unwrap();
+
+        let languages = report.get("languages").and_then(Json::as_array).unwrap();
+        let python = languages
+            .iter()
+            .find(|l| l.get("name").and_then(Json::as_str) == Some("python"))
+            .unwrap();
+        let ruff = &python.get("linters").and_then(Json::as_array).unwrap()[0];
+        assert_eq!(ruff.get("installed"), Some(&Json::Bool(true)));
+
+        let config = report.get("config").unwrap();
+        assert_eq!(config.get("path"), Some(&Json::Null));
+        let settings = config.get("settings").unwrap();
+        assert_eq!(
+            settings.get("output.report").and_then(Json::as_str),
+            Some("new")
+        );
+        assert_eq!(
+            settings
+                .get("hooks.tools")
+                .and_then(Json::as_array)
+                .map(<[Json]>::len),
+            Some(1)
+        );
+        assert_eq!(
+            report
+                .get("hook_events")
+                .and_then(Json::as_array)
+                .map(<[Json]>::len),
+            Some(HOOK_EVENTS.len())
+        );
+    }
+
+    #[test]
+    fn text_marks_missing_linters() {
+        let text = to_text(&Config::default(), |linter| linter == "ktlint");
+        assert!(text.contains("  kotlin      ktlint\n"));
+        assert!(text.contains("ruff (missing)"));
+        assert!(text.contains("Config: none"));
+    }
+}
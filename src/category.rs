@@ -0,0 +1,303 @@
+use crate::config::{Config, Value};
+use crate::diagnostic::Diagnostic;
+
+/// Which kinds of issue block, from `[output] block_categories` and `warn_categories`.
+/// Categories are the clippy/biome group names (`correctness`, `suspicious`, `security`,
+/// `complexity`, `performance`, `style`, `pedantic`, ...), mapped onto each linter's rules by
+/// [`builtin_category`] and the `[categories]` table.
+#[derive(Debug, Default)]
+pub struct Policy {
+    block: Vec<String>,
+    warn: Vec<String>,
+    /// `[categories]` entries: a category and the rule names or prefixes that belong to it
+    rules: Vec<(String, Vec<String>)>,
+}
+
+impl Policy {
+    pub fn from_config(config: &Config) -> Self {
+        let list = |key: &str| config.get_array(key).map(<[String]>::to_vec);
+        Self {
+            block: list("output.block_categories").unwrap_or_default(),
+            warn: list("output.warn_categories").unwrap_or_default(),
+            rules: config
+                .values()
+                .iter()
+                .filter_map(|(key, value)| {
+                    let category = key.strip_prefix("categories.")?;
+                    match value {
+                        Value::Array(rules) => Some((category.to_string(), rules.clone())),
+                        Value::String(rule) => Some((category.to_string(), vec![rule.clone()])),
+                        _ => None,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// The category of a `linter` rule: the `[categories]` table first, then the built-in
+    /// mapping.
+    pub fn category<'a>(&'a self, linter: &str, rule: &str) -> Option<&'a str> {
+        if rule.is_empty() {
+            return None;
+        }
+        self.rules
+            .iter()
+            .find(|(_, rules)| rules.iter().any(|r| rule.starts_with(r.as_str())))
+            .map(|(category, _)| category.as_str())
+            .or_else(|| builtin_category(linter, rule))
+    }
+
+    /// Whether `diagnostic` blocks. A category in `warn_categories` never blocks; with
+    /// `block_categories` set, a categorized diagnostic blocks only if its category is listed.
+    /// Anything else is left to `fallback`, the linter's own severity.
+    pub fn blocks(&self, linter: &str, diagnostic: &Diagnostic, fallback: bool) -> bool {
+        let Some(category) = self.category(linter, &diagnostic.rule) else {
+            return fallback;
+        };
+        if self.warn.iter().any(|c| c == category) {
+            false
+        } else if self.block.is_empty() {
+            fallback
+        } else {
+            self.block.iter().any(|c| c == category)
+        }
+    }
+}
+
+/// ruff/flake8 code prefixes, matched on the code's letters exactly and then its digits, so
+/// `S` (bandit) doesn't take `SIM` (simplify).
+const PYTHON_CATEGORIES: &[(&str, &str)] = &[
+    ("E9", "correctness"),
+    ("F", "correctness"),
+    ("PLE", "correctness"),
+    ("S", "security"),
+    ("B", "suspicious"),
+    ("A", "suspicious"),
+    ("PLW", "suspicious"),
+    ("C90", "complexity"),
+    ("PLR", "complexity"),
+    ("PERF", "performance"),
+    ("E", "style"),
+    ("W", "style"),
+    ("D", "style"),
+    ("N", "style"),
+    ("I", "style"),
+    ("UP", "style"),
+    ("Q", "style"),
+    ("COM", "style"),
+    ("SIM", "style"),
+    ("PLC", "style"),
+    ("ANN", "pedantic"),
+    ("ARG", "pedantic"),
+    ("ERA", "pedantic"),
+];
+
+/// eslint/oxlint plugin namespaces.
+const JS_NAMESPACES: &[(&str, &str)] = &[
+    ("security", "security"),
+    ("security-node", "security"),
+    ("no-unsanitized", "security"),
+    ("react-hooks", "correctness"),
+    ("sonarjs", "complexity"),
+    ("@stylistic", "style"),
+    ("stylistic", "style"),
+    ("prettier", "style"),
+    ("jsdoc", "pedantic"),
+];
+
+/// Name prefixes of clang-tidy checks.
+const CLANG_TIDY_CATEGORIES: &[(&str, &str)] = &[
+    ("clang-analyzer-security", "security"),
+    ("cert-", "security"),
+    ("clang-analyzer-", "correctness"),
+    ("bugprone-", "suspicious"),
+    ("performance-", "performance"),
+    ("readability-function-cognitive-complexity", "complexity"),
+    ("readability-", "style"),
+    ("modernize-", "style"),
+    ("google-", "style"),
+    ("llvm-", "style"),
+    ("cppcoreguidelines-", "pedantic"),
+    ("hicpp-", "pedantic"),
+];
+
+/// golangci-lint linters (reported as the rule) that map onto one category.
+const GOLANGCI_CATEGORIES: &[(&str, &[&str])] = &[
+    ("security", &["gosec"]),
+    (
+        "correctness",
+        &["govet", "staticcheck", "errcheck", "typecheck", "bodyclose"],
+    ),
+    ("suspicious", &["ineffassign", "unused", "unparam"]),
+    (
+        "complexity",
+        &["gocyclo", "gocognit", "funlen", "nestif", "cyclop"],
+    ),
+    ("performance", &["prealloc"]),
+    (
+        "style",
+        &[
+            "gofmt",
+            "goimports",
+            "revive",
+            "stylecheck",
+            "misspell",
+            "lll",
+            "whitespace",
+        ],
+    ),
+];
+
+/// Built-in category of a rule, per linter: ruff/flake8 code prefixes, eslint/oxlint plugin
+/// namespaces, biome groups (`lint/<group>/<rule>`), rustc error codes, golangci-lint
+/// linters, staticcheck check classes, go vet analyzers, clang-tidy check prefixes, and Psalm
+/// taint issues. Clippy doesn't report a lint's group, so its lints need `[categories]`.
+pub fn builtin_category(linter: &str, rule: &str) -> Option<&'static str> {
+    match linter {
+        "ruff" | "flake8" => python_category(rule),
+        "biome" => {
+            let group = rule.strip_prefix("lint/")?.split('/').next()?;
+            [
+                "correctness",
+                "suspicious",
+                "security",
+                "complexity",
+                "performance",
+                "style",
+                "a11y",
+                "nursery",
+            ]
+            .into_iter()
+            .find(|known| *known == group)
+        }
+        "eslint" | "oxlint" => {
+            let (namespace, _) = rule.rsplit_once('/')?;
+            lookup(JS_NAMESPACES, |name| name == namespace)
+        }
+        "clippy" => is_rustc_error_code(rule).then_some("correctness"),
+        "golangci-lint" => GOLANGCI_CATEGORIES
+            .iter()
+            .find(|(_, linters)| linters.contains(&rule))
+            .map(|(category, _)| *category),
+        "staticcheck" => match rule.get(..2)? {
+            "SA" => Some("correctness"),
+            "S1" | "ST" | "QF" => Some("style"),
+            _ => None,
+        },
+        "go vet" => Some("correctness"),
+        "revive" | "phpcs" => Some("style"),
+        "psalm" => rule.starts_with("Tainted").then_some("security"),
+        _ if linter.starts_with("clang-tidy") => {
+            lookup(CLANG_TIDY_CATEGORIES, |prefix| rule.starts_with(prefix))
+        }
+        _ => None,
+    }
+}
+
+fn lookup(table: &[(&str, &'static str)], matches: impl Fn(&str) -> bool) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(key, _)| matches(key))
+        .map(|(_, category)| *category)
+}
+
+fn python_category(code: &str) -> Option<&'static str> {
+    let split = |code: &str| {
+        let letters = code
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(code.len());
+        (code[..letters].to_string(), code[letters..].to_string())
+    };
+    let (letters, digits) = split(code);
+    lookup(PYTHON_CATEGORIES, |prefix| {
+        let (prefix_letters, prefix_digits) = split(prefix);
+        prefix_letters == letters && digits.starts_with(&prefix_digits)
+    })
+}
+
+/// `E0308` and the like: a compile error rather than a lint.
+fn is_rustc_error_code(rule: &str) -> bool {
+    rule.len() == 5 && rule.starts_with('E') && rule[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Severity;
+
+    fn diagnostic(rule: &str) -> Diagnostic {
+        Diagnostic {
+            file: "a.py".to_string(),
+            line: 1,
+            column: 1,
+            rule: rule.to_string(),
+            message: String::new(),
+            severity: Severity::Error,
+            url: None,
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn builtin_categories_per_linter() {
+        assert_eq!(builtin_category("ruff", "F401"), Some("correctness"));
+        assert_eq!(builtin_category("ruff", "E999"), Some("correctness"));
+        assert_eq!(builtin_category("ruff", "E501"), Some("style"));
+        assert_eq!(builtin_category("ruff", "S101"), Some("security"));
+        assert_eq!(builtin_category("ruff", "SIM108"), Some("style"));
+        assert_eq!(builtin_category("ruff", "TRY003"), None);
+        assert_eq!(
+            builtin_category("biome", "lint/suspicious/noExplicitAny"),
+            Some("suspicious")
+        );
+        assert_eq!(
+            builtin_category("eslint", "security/detect-eval-with-expression"),
+            Some("security")
+        );
+        assert_eq!(builtin_category("eslint", "no-unused-vars"), None);
+        assert_eq!(builtin_category("clippy", "E0308"), Some("correctness"));
+        assert_eq!(builtin_category("clippy", "clippy::needless_return"), None);
+        assert_eq!(builtin_category("golangci-lint", "gosec"), Some("security"));
+        assert_eq!(
+            builtin_category("staticcheck", "SA4006"),
+            Some("correctness")
+        );
+        assert_eq!(
+            builtin_category("clang-tidy", "bugprone-use-after-move"),
+            Some("suspicious")
+        );
+        assert_eq!(builtin_category("psalm", "TaintedSql"), Some("security"));
+    }
+
+    #[test]
+    fn configured_rules_take_precedence() {
+        let policy = Policy::from_config(&Config::parse(
+            "[categories]\npedantic = [\"clippy::doc_markdown\", \"E5\"]\n",
+        ));
+        assert_eq!(
+            policy.category("clippy", "clippy::doc_markdown"),
+            Some("pedantic")
+        );
+        assert_eq!(policy.category("ruff", "E501"), Some("pedantic"));
+        assert_eq!(policy.category("ruff", "E711"), Some("style"));
+        assert_eq!(policy.category("ruff", ""), None);
+    }
+
+    #[test]
+    fn blocking_follows_listed_categories() {
+        let policy = Policy::from_config(&Config::parse(
+            "[output]\nblock_categories = [\"correctness\", \"security\"]\nwarn_categories = [\"style\"]\n",
+        ));
+        assert!(policy.blocks("ruff", &diagnostic("F821"), false));
+        assert!(!policy.blocks("ruff", &diagnostic("E501"), true));
+        // Categorized but not listed: only listed categories block
+        assert!(!policy.blocks("ruff", &diagnostic("PERF401"), true));
+        // Uncategorized: the linter's own verdict
+        assert!(policy.blocks("ruff", &diagnostic("TRY003"), true));
+        assert!(!policy.blocks("ruff", &diagnostic("TRY003"), false));
+
+        let unset = Policy::default();
+        assert!(unset.blocks("ruff", &diagnostic("E501"), true));
+        assert!(!unset.blocks("ruff", &diagnostic("F821"), false));
+    }
+}
@@ -0,0 +1,495 @@
+/// Which hook the binary is serving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Collect,
+    LintCollected,
+    Lint,
+    /// Lint the contents a `PreToolUse` `Write`/`Edit` is about to produce
+    Content,
+    /// Pick one of the above from the payload's `hook_event_name`
+    Auto,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Handle one hook payload from stdin or `--input`
+    Hook(Mode),
+    /// Re-run a JSONL log of payloads; `Auto` picks the mode per event
+    Replay {
+        log: String,
+        mode: Mode,
+    },
+    /// Lint one file now and store the result for the session's `lint-collected`; started in
+    /// the background by `collect` when `[lint_collected] background = true`
+    LintBackground {
+        session_id: String,
+        file_path: String,
+    },
+    /// Lint files under a directory as they change, for local development
+    Watch {
+        dir: String,
+    },
+    /// Time each applicable linter on a file or project
+    Bench {
+        target: String,
+    },
+    /// Start, stop, or report the linter daemons and caches for a project directory
+    Daemon {
+        action: DaemonAction,
+        dir: String,
+    },
+    /// Add, remove, list, or clear the extra clippy and ruff rules of one session
+    Session {
+        action: SessionAction,
+        session_id: String,
+    },
+    /// Answer JSON-RPC lint requests on stdin and stdout until `shutdown` or end of input
+    Serve,
+    /// Check which language pipelines work on this machine
+    Doctor,
+    /// Write a starter config file to the current directory
+    Init,
+    /// List supported languages, installed linters, active config, and hook events
+    Capabilities {
+        json: bool,
+    },
+    Version,
+    Help,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonAction {
+    Start,
+    Stop,
+    Status,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionAction {
+    Add { linter: String, rule: String },
+    Remove { linter: String, rule: String },
+    List,
+    Clear,
+}
+
+/// Parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cli {
+    pub command: Command,
+    pub debug: bool,
+    pub lenient: bool,
+    pub input: Option<String>,
+    /// Most lint jobs `lint-collected` runs at once
+    pub max_parallel: Option<usize>,
+    /// `lint-collected` also lints files git reports as changed since the session started
+    pub collect_from_git: bool,
+}
+
+pub const USAGE: &str = "\
+Usage: ralph-hook-lint [COMMAND] [OPTIONS]
+
+Commands:
+  lint              Lint the file in the hook payload
+  lint-content      Lint the contents a PreToolUse Write/Edit would produce
+  collect           Record the file for a later lint-collected run
+  lint-collected    Lint all files recorded for the session
+  replay <LOG>      Re-run each payload in a JSONL log
+  lint-background <SESSION> <FILE>
+                    Lint FILE and store the result for SESSION's lint-collected
+  watch [DIR]       Lint files under DIR (default: .) as they change
+  bench <FILE|DIR>  Time each applicable linter (cold and warm) and count its findings
+  daemon <start|stop|status> [DIR]
+                    Manage eslint_d and warm golangci-lint's cache for the project in DIR
+  doctor            Check which language pipelines work on this machine (alias: selftest)
+  init              Write a starter .ralph-hook-lint.toml to the current directory
+  capabilities [--json]
+                    List supported languages, installed linters, active config, and hook events
+  session <add|remove> <SESSION> <clippy|ruff> <RULE>
+  session <list|clear> <SESSION>
+                    Deny an extra clippy lint or select an extra ruff rule for one session
+  serve --stdio     Answer JSON-RPC lintFile/lintPaths requests, one per line on stdin
+
+Without a command, the payload's hook_event_name picks one: PostToolUse lints (or collects,
+per [hooks] post_tool_use), Stop and SubagentStop lint collected files, PreToolUse lints content.
+SessionStart records the session's git baseline whatever the command.
+
+Options:
+  --lenient         Skip unused variable/import rules
+  --debug           Include systemMessage in every response
+  --input <FILE>    Read the hook payload from FILE instead of stdin
+  --max-parallel <N>
+                    Run at most N lint jobs at once in lint-collected
+  --collect-from-git
+                    In lint-collected, also lint files git reports as changed since the
+                    session's first event
+  --collect         Same as the collect command (replay: use for non-Stop events)
+  --lint-collected  Same as the lint-collected command
+  -V, --version     Print the version
+  -h, --help        Print this help";
+
+/// Parse arguments (without the program name). Unknown flags and extra arguments are errors,
+/// so typos such as `--lenint` don't silently change behavior.
+pub fn parse(args: &[String]) -> Result<Cli, String> {
+    let mut cli = Cli {
+        command: Command::Hook(Mode::Auto),
+        debug: false,
+        lenient: false,
+        input: None,
+        max_parallel: None,
+        collect_from_git: false,
+    };
+    let mut subcommand: Option<&str> = None;
+    let mut positionals: Vec<&str> = Vec::new();
+    let mut mode_flag: Option<Mode> = None;
+    let mut json = false;
+    let mut stdio = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--debug" => cli.debug = true,
+            "--lenient" => cli.lenient = true,
+            "--json" => json = true,
+            "--stdio" => stdio = true,
+            "--collect-from-git" => cli.collect_from_git = true,
+            "--max-parallel" => {
+                let max = iter.next().ok_or("--max-parallel requires a number")?;
+                match max.parse::<usize>() {
+                    Ok(max) if max > 0 => cli.max_parallel = Some(max),
+                    _ => return Err(format!("invalid --max-parallel '{max}'")),
+                }
+            }
+            "--input" => {
+                let path = iter.next().ok_or("--input requires a file path")?;
+                cli.input = Some(path.clone());
+            }
+            "--collect" | "--lint-collected" => {
+                let mode = if arg == "--collect" {
+                    Mode::Collect
+                } else {
+                    Mode::LintCollected
+                };
+                if mode_flag.is_some_and(|m| m != mode) {
+                    return Err("--collect and --lint-collected cannot be combined".to_string());
+                }
+                mode_flag = Some(mode);
+            }
+            "-V" | "--version" => {
+                return Ok(Cli {
+                    command: Command::Version,
+                    ..cli
+                });
+            }
+            "-h" | "--help" => {
+                return Ok(Cli {
+                    command: Command::Help,
+                    ..cli
+                });
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option '{flag}'")),
+            word if subcommand.is_none() => subcommand = Some(word),
+            word => positionals.push(word),
+        }
+    }
+
+    let expect_no_positionals = |command: Command| {
+        positionals.first().map_or(Ok(command), |extra| {
+            Err(format!("unexpected argument '{extra}'"))
+        })
+    };
+    let hook_mode = |mode: Mode| match mode_flag {
+        Some(flag) if flag != mode => Err(format!(
+            "'{}' conflicts with --collect/--lint-collected",
+            subcommand.unwrap_or_default()
+        )),
+        _ => Ok(Command::Hook(mode)),
+    };
+
+    if json && subcommand != Some("capabilities") {
+        return Err("--json is only supported by the capabilities command".to_string());
+    }
+    if stdio != (subcommand == Some("serve")) {
+        return Err(if stdio {
+            "--stdio is only supported by the serve command"
+        } else {
+            "serve requires --stdio"
+        }
+        .to_string());
+    }
+
+    cli.command = match subcommand {
+        None => expect_no_positionals(Command::Hook(mode_flag.unwrap_or(Mode::Auto)))?,
+        Some("lint") => expect_no_positionals(hook_mode(Mode::Lint)?)?,
+        Some("lint-content") => expect_no_positionals(hook_mode(Mode::Content)?)?,
+        Some("collect") => expect_no_positionals(hook_mode(Mode::Collect)?)?,
+        Some("lint-collected") => expect_no_positionals(hook_mode(Mode::LintCollected)?)?,
+        Some("doctor" | "selftest") => expect_no_positionals(Command::Doctor)?,
+        Some("init") => expect_no_positionals(Command::Init)?,
+        Some("capabilities") => expect_no_positionals(Command::Capabilities { json })?,
+        Some("serve") => expect_no_positionals(Command::Serve)?,
+        Some(name @ ("replay" | "lint-background" | "watch" | "bench" | "daemon" | "session")) => {
+            with_arguments(name, &positionals, mode_flag)?
+        }
+        Some(other) => return Err(format!("unknown command '{other}'")),
+    };
+    Ok(cli)
+}
+
+/// Commands that take positional arguments.
+fn with_arguments(
+    name: &str,
+    positionals: &[&str],
+    mode_flag: Option<Mode>,
+) -> Result<Command, String> {
+    Ok(match name {
+        "replay" => match positionals {
+            [log] => Command::Replay {
+                log: (*log).to_string(),
+                mode: mode_flag.unwrap_or(Mode::Auto),
+            },
+            [] => return Err("replay requires a log file".to_string()),
+            [_, extra, ..] => return Err(format!("unexpected argument '{extra}'")),
+        },
+        "watch" => match positionals {
+            [_, extra, ..] => return Err(format!("unexpected argument '{extra}'")),
+            dir => Command::Watch {
+                dir: dir.first().unwrap_or(&".").to_string(),
+            },
+        },
+        "bench" => match positionals {
+            [target] => Command::Bench {
+                target: (*target).to_string(),
+            },
+            [] => return Err("bench requires a file or project directory".to_string()),
+            [_, extra, ..] => return Err(format!("unexpected argument '{extra}'")),
+        },
+        "daemon" => {
+            let (action, dir) = match positionals {
+                [action] => (*action, "."),
+                [action, dir] => (*action, *dir),
+                [] => return Err("daemon requires start, stop, or status".to_string()),
+                [_, _, extra, ..] => return Err(format!("unexpected argument '{extra}'")),
+            };
+            let action = match action {
+                "start" => DaemonAction::Start,
+                "stop" => DaemonAction::Stop,
+                "status" => DaemonAction::Status,
+                other => return Err(format!("unknown daemon action '{other}'")),
+            };
+            Command::Daemon {
+                action,
+                dir: dir.to_string(),
+            }
+        }
+        "session" => {
+            let (session_id, action) = match positionals {
+                ["add" | "remove", session_id, linter, rule] => {
+                    let (linter, rule) = ((*linter).to_string(), (*rule).to_string());
+                    let action = if positionals[0] == "add" {
+                        SessionAction::Add { linter, rule }
+                    } else {
+                        SessionAction::Remove { linter, rule }
+                    };
+                    (session_id, action)
+                }
+                ["list", session_id] => (session_id, SessionAction::List),
+                ["clear", session_id] => (session_id, SessionAction::Clear),
+                ["add" | "remove", ..] => {
+                    return Err(format!(
+                        "session {} requires a session id, a linter, and a rule",
+                        positionals[0]
+                    ));
+                }
+                ["list" | "clear", _, extra, ..] => {
+                    return Err(format!("unexpected argument '{extra}'"));
+                }
+                ["list" | "clear"] => {
+                    return Err(format!("session {} requires a session id", positionals[0]));
+                }
+                [other, ..] => return Err(format!("unknown session action '{other}'")),
+                [] => return Err("session requires add, remove, list, or clear".to_string()),
+            };
+            Command::Session {
+                action,
+                session_id: (*session_id).to_string(),
+            }
+        }
+        "lint-background" => match positionals {
+            [session_id, file_path] => Command::LintBackground {
+                session_id: (*session_id).to_string(),
+                file_path: (*file_path).to_string(),
+            },
+            [_, _, extra, ..] => return Err(format!("unexpected argument '{extra}'")),
+            _ => return Err("lint-background requires a session id and a file".to_string()),
+        },
+        _ => return Err(format!("unknown command '{name}'")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Cli, String> {
+        let args: Vec<String> = args.split_whitespace().map(str::to_string).collect();
+        parse(&args)
+    }
+
+    #[test]
+    fn legacy_flags_select_hook_mode() {
+        let cli = parse_str("--collect").unwrap();
+        assert_eq!(cli.command, Command::Hook(Mode::Collect));
+        let cli = parse_str("--lint-collected --lenient --debug").unwrap();
+        assert_eq!(cli.command, Command::Hook(Mode::LintCollected));
+        assert!(cli.lenient && cli.debug);
+        assert_eq!(parse_str("").unwrap().command, Command::Hook(Mode::Auto));
+        assert_eq!(
+            parse_str("--lint-collected --max-parallel 2")
+                .unwrap()
+                .max_parallel,
+            Some(2)
+        );
+        assert!(
+            parse_str("lint-collected --collect-from-git")
+                .unwrap()
+                .collect_from_git
+        );
+    }
+
+    #[test]
+    fn subcommands() {
+        assert_eq!(
+            parse_str("collect").unwrap().command,
+            Command::Hook(Mode::Collect)
+        );
+        assert_eq!(
+            parse_str("lint --input p.json").unwrap().input.as_deref(),
+            Some("p.json")
+        );
+        assert_eq!(
+            parse_str("lint-content").unwrap().command,
+            Command::Hook(Mode::Content)
+        );
+        assert_eq!(parse_str("selftest").unwrap().command, Command::Doctor);
+        assert_eq!(parse_str("init").unwrap().command, Command::Init);
+        assert_eq!(
+            parse_str("capabilities --json").unwrap().command,
+            Command::Capabilities { json: true }
+        );
+        assert_eq!(
+            parse_str("lint-background s1 /p/a.rs --lenient")
+                .unwrap()
+                .command,
+            Command::LintBackground {
+                session_id: "s1".to_string(),
+                file_path: "/p/a.rs".to_string()
+            }
+        );
+        assert!(parse_str("lint-background s1").is_err());
+        assert_eq!(
+            parse_str("watch src --lenient").unwrap().command,
+            Command::Watch {
+                dir: "src".to_string()
+            }
+        );
+        assert_eq!(
+            parse_str("bench src/main.rs").unwrap().command,
+            Command::Bench {
+                target: "src/main.rs".to_string()
+            }
+        );
+        assert!(parse_str("bench").is_err());
+        assert_eq!(
+            parse_str("daemon start web").unwrap().command,
+            Command::Daemon {
+                action: DaemonAction::Start,
+                dir: "web".to_string()
+            }
+        );
+        assert_eq!(
+            parse_str("daemon status").unwrap().command,
+            Command::Daemon {
+                action: DaemonAction::Status,
+                dir: ".".to_string()
+            }
+        );
+        assert!(parse_str("daemon").is_err());
+        assert!(parse_str("daemon restart").is_err());
+        assert_eq!(
+            parse_str("session add s1 clippy clippy::unwrap_used")
+                .unwrap()
+                .command,
+            Command::Session {
+                action: SessionAction::Add {
+                    linter: "clippy".to_string(),
+                    rule: "clippy::unwrap_used".to_string()
+                },
+                session_id: "s1".to_string()
+            }
+        );
+        assert_eq!(
+            parse_str("session clear s1").unwrap().command,
+            Command::Session {
+                action: SessionAction::Clear,
+                session_id: "s1".to_string()
+            }
+        );
+        assert!(parse_str("session add s1 clippy").is_err());
+        assert!(parse_str("session list").is_err());
+        assert!(parse_str("session reset s1").is_err());
+        assert_eq!(
+            parse_str("watch").unwrap().command,
+            Command::Watch {
+                dir: ".".to_string()
+            }
+        );
+        assert_eq!(parse_str("serve --stdio").unwrap().command, Command::Serve);
+        assert_eq!(
+            parse_str("replay log.jsonl --collect").unwrap().command,
+            Command::Replay {
+                log: "log.jsonl".to_string(),
+                mode: Mode::Collect
+            }
+        );
+    }
+
+    #[test]
+    fn version_and_help_win() {
+        assert_eq!(
+            parse_str("--bogus -V").unwrap_err(),
+            "unknown option '--bogus'"
+        );
+        assert_eq!(parse_str("lint -V").unwrap().command, Command::Version);
+        assert_eq!(parse_str("--help").unwrap().command, Command::Help);
+    }
+
+    #[test]
+    fn rejects_unknown_and_conflicting_arguments() {
+        assert_eq!(
+            parse_str("--lenint").unwrap_err(),
+            "unknown option '--lenint'"
+        );
+        assert_eq!(parse_str("lnt").unwrap_err(), "unknown command 'lnt'");
+        assert_eq!(
+            parse_str("doctor extra").unwrap_err(),
+            "unexpected argument 'extra'"
+        );
+        assert!(parse_str("--collect --lint-collected").is_err());
+        assert!(parse_str("lint --json").is_err());
+        assert_eq!(parse_str("serve").unwrap_err(), "serve requires --stdio");
+        assert!(parse_str("lint --stdio").is_err());
+        assert_eq!(
+            parse_str("--max-parallel 0").unwrap_err(),
+            "invalid --max-parallel '0'"
+        );
+        assert!(parse_str("collect --lint-collected").is_err());
+        assert_eq!(
+            parse_str("replay").unwrap_err(),
+            "replay requires a log file"
+        );
+        assert_eq!(
+            parse_str("--input").unwrap_err(),
+            "--input requires a file path"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use std::path::{Path, PathBuf};
+
+use crate::project::Lang;
+
+/// Fence info strings the hook lints, with the language and extension a block is staged as.
+const LANGUAGES: [(&str, Lang, &str); 14] = [
+    ("rust", Lang::Rust, ".rs"),
+    ("rs", Lang::Rust, ".rs"),
+    ("python", Lang::Python, ".py"),
+    ("py", Lang::Python, ".py"),
+    ("javascript", Lang::JavaScript, ".js"),
+    ("js", Lang::JavaScript, ".js"),
+    ("jsx", Lang::JavaScript, ".jsx"),
+    ("typescript", Lang::JavaScript, ".ts"),
+    ("ts", Lang::JavaScript, ".ts"),
+    ("tsx", Lang::JavaScript, ".tsx"),
+    ("sh", Lang::Shell, ".sh"),
+    ("bash", Lang::Shell, ".sh"),
+    ("shell", Lang::Shell, ".sh"),
+    ("zsh", Lang::Shell, ".sh"),
+];
+
+/// A fenced code block in a language the hook lints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    pub lang: Lang,
+    extension: &'static str,
+    /// Line of the opening fence in the document, 1-based
+    pub line: usize,
+    /// The block's contents, prepared to be linted on their own
+    pub code: String,
+}
+
+impl CodeBlock {
+    /// Where the block is staged: next to the document, so the project's linter config
+    /// applies, named after the document and the block's line. Underscores keep the name a
+    /// valid Rust crate and Python module name.
+    pub fn staged_path(&self, file_path: &str) -> PathBuf {
+        let path = Path::new(file_path);
+        let stem: String = path
+            .file_stem()
+            .map_or_else(String::new, |s| s.to_string_lossy().to_string())
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        path.with_file_name(format!(
+            "ralph_block_{}_{stem}_{}{}",
+            std::process::id(),
+            self.line,
+            self.extension
+        ))
+    }
+
+    /// How findings name the block, e.g. `docs/guide.md (code block at line 14)`. Line numbers
+    /// after it are relative to the block.
+    pub fn label(&self, file_path: &str) -> String {
+        format!("{file_path} (code block at line {})", self.line)
+    }
+}
+
+/// The fenced code blocks (```` ``` ```` or `~~~`) of a Markdown document whose info string
+/// names a language the hook lints. Blocks marked `ignore` (`rust,ignore`) are left out.
+/// Rust blocks are prepared the way rustdoc prepares doctests: hidden `# ` lines are unhidden
+/// and a block without `fn main` is wrapped in one, opening on its first line so line numbers
+/// stay put.
+pub fn extract(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut lines = markdown.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let Some((fence, info)) = opening_fence(line) else {
+            continue;
+        };
+        let mut code = Vec::new();
+        for (_, line) in lines.by_ref() {
+            if is_closing_fence(line, fence) {
+                break;
+            }
+            code.push(line);
+        }
+        let mut attributes = info
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|a| !a.is_empty());
+        let Some(&(_, lang, extension)) = attributes.next().and_then(|name| {
+            LANGUAGES
+                .iter()
+                .find(|(n, ..)| name.eq_ignore_ascii_case(n))
+        }) else {
+            continue;
+        };
+        if attributes.any(|a| a == "ignore") {
+            continue;
+        }
+        let code = if lang == Lang::Rust {
+            rust_doctest(&code)
+        } else {
+            code.iter()
+                .fold(String::new(), |code, line| code + line + "\n")
+        };
+        blocks.push(CodeBlock {
+            lang,
+            extension,
+            line: index + 1,
+            code,
+        });
+    }
+    blocks
+}
+
+/// The fence (its character and length) and the info string of an opening fence line.
+fn opening_fence(line: &str) -> Option<((char, usize), &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.chars().take_while(|c| *c == fence_char).count();
+    let info = trimmed[length..].trim();
+    // A backtick fence's info string can't contain backticks
+    (length >= 3 && !(fence_char == '`' && info.contains('`')))
+        .then_some(((fence_char, length), info))
+}
+
+fn is_closing_fence(line: &str, (fence_char, length): (char, usize)) -> bool {
+    let trimmed = line.trim_start_matches(' ');
+    line.len() - trimmed.len() <= 3
+        && trimmed.chars().take_while(|c| *c == fence_char).count() >= length
+        && trimmed.trim_start_matches(fence_char).trim().is_empty()
+}
+
+fn rust_doctest(lines: &[&str]) -> String {
+    let mut code = lines
+        .iter()
+        .map(|line| match line.trim_start() {
+            "#" => "",
+            hidden if hidden.starts_with("# ") => &hidden[2..],
+            _ => line,
+        })
+        .fold(String::new(), |code, line| code + line + "\n");
+    if !code.contains("fn main") {
+        code = format!("fn main() {{ {code}}}\n");
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_known_languages_with_their_lines() {
+        let markdown = "# Guide\n\n```python\nimport os\n```\n\n~~~ts\nconst x: number = 1;\n~~~\n\n```text\nplain\n```\n\n````sh\necho ```\n````\n";
+        let blocks = extract(markdown);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(
+            (blocks[0].lang, blocks[0].line, blocks[0].code.as_str()),
+            (Lang::Python, 3, "import os\n")
+        );
+        assert_eq!((blocks[1].lang, blocks[1].line), (Lang::JavaScript, 7));
+        assert!(
+            blocks[1]
+                .staged_path("/ws/docs/guide.md")
+                .ends_with(format!("ralph_block_{}_guide_7.ts", std::process::id()))
+        );
+        assert_eq!(blocks[2].code, "echo ```\n");
+        assert_eq!(
+            blocks[2].label("docs/guide.md"),
+            "docs/guide.md (code block at line 15)"
+        );
+    }
+
+    #[test]
+    fn rust_blocks_are_prepared_like_doctests() {
+        let markdown = "```rust\n# use std::fmt;\nlet x = 1;\n```\n\n```rust,ignore\nnot rust\n```\n\n```rust\nfn main() {}\n```\n";
+        let blocks = extract(markdown);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].code, "fn main() { use std::fmt;\nlet x = 1;\n}\n");
+        assert_eq!(blocks[1].code, "fn main() {}\n");
+    }
+}
@@ -1,6 +1,27 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Write as _;
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::gitstatus;
+use crate::origin::{self, Origin};
+
+static FROM_GIT: AtomicBool = AtomicBool::new(false);
+
+/// Set by `--collect-from-git`: `lint-collected` also lints every file git reports as changed
+/// since the session's baseline.
+pub fn configure_from_git() {
+    FROM_GIT.store(true, Ordering::Relaxed);
+}
+
+pub fn from_git() -> bool {
+    FROM_GIT.load(Ordering::Relaxed)
+}
 
 /// Returns the temp file path for a given session: `<temp_dir>/ralph-lint-<session_id>.txt`
 pub fn temp_path(session_id: &str) -> PathBuf {
@@ -30,11 +51,130 @@ pub fn record_path(session_id: &str, file_path: &str) -> Result<(), Box<dyn std:
         .append(true)
         .open(&path)?;
     writeln!(file, "{file_path}")?;
+
+    // Remember the file's identity so a later rename within its directory can be followed
+    if let Some(id) = file_id(Path::new(file_path)) {
+        let mut ids = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(ids_path(session_id))?;
+        writeln!(ids, "{id}\t{file_path}")?;
+    }
     Ok(())
 }
 
-/// Read all recorded paths, then delete the temp file. Returns an empty vec if the file
-/// does not exist.
+/// Returns the file-identity path for a given session: `<temp_dir>/ralph-lint-<session_id>-ids.txt`
+pub fn ids_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-ids.txt"))
+}
+
+#[cfg(unix)]
+fn file_id(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path).ok().map(|m| m.ino())
+}
+
+#[cfg(not(unix))]
+fn file_id(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Replace paths that no longer exist with where the file went: a rename git reports
+/// (`git mv`, or a staged delete and add), or a file in the same directory with the same inode
+/// as when it was collected. Paths that can't be followed are kept, to be dropped as deleted.
+fn follow_renames(session_id: &str, paths: Vec<String>) -> Vec<String> {
+    let ids_file = ids_path(session_id);
+    let ids: Vec<(u64, String)> = fs::read_to_string(&ids_file)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (id, path) = line.split_once('\t')?;
+            Some((id.parse().ok()?, path.to_string()))
+        })
+        .collect();
+    let _ = fs::remove_file(&ids_file);
+
+    let mut git_renames: Vec<(String, String)> = Vec::new();
+    let mut scanned_repos: Vec<String> = Vec::new();
+    let mut followed: Vec<String> = Vec::new();
+    for path in paths {
+        let mut current = path;
+        if !Path::new(&current).exists() {
+            if let Some(repo) = crate::project::find_repo_root(&current) {
+                if !scanned_repos.contains(&repo) {
+                    git_renames.extend(git_status_renames(&repo));
+                    scanned_repos.push(repo);
+                }
+            }
+            let renamed = git_renames
+                .iter()
+                .find(|(old, _)| *old == current)
+                .map(|(_, new)| new.clone())
+                .or_else(|| {
+                    let (id, _) = ids.iter().find(|(_, p)| *p == current)?;
+                    find_by_id(Path::new(&current).parent()?, *id)
+                });
+            if let Some(renamed) = renamed {
+                current = renamed;
+            }
+        }
+        if !followed.contains(&current) {
+            followed.push(current);
+        }
+    }
+    followed
+}
+
+/// `(old, new)` absolute paths for renames in `git status --porcelain -z` under `repo`.
+fn git_status_renames(repo: &str) -> Vec<(String, String)> {
+    let Ok(output) = Command::new("git")
+        .args(["status", "--porcelain", "-z"])
+        .current_dir(repo)
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    parse_porcelain_renames(&crate::encoding::decode(&output.stdout))
+        .into_iter()
+        .map(|(old, new)| (format!("{repo}/{old}"), format!("{repo}/{new}")))
+        .collect()
+}
+
+/// Renames from `git status --porcelain -z` output: entries are `XY path`, and renames and
+/// copies (`R`/`C` in the index column) are followed by the original path as the next entry.
+fn parse_porcelain_renames(output: &str) -> Vec<(String, String)> {
+    let mut renames = Vec::new();
+    let mut entries = output.split('\0');
+    while let Some(entry) = entries.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        let (status, path) = entry.split_at(3);
+        if status.starts_with('R') {
+            if let Some(old) = entries.next() {
+                renames.push((old.to_string(), path.to_string()));
+            }
+        } else if status.starts_with('C') {
+            entries.next();
+        }
+    }
+    renames
+}
+
+fn find_by_id(dir: &Path, id: u64) -> Option<String> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| path.is_file() && file_id(path) == Some(id))
+        .map(|path| path.to_string_lossy().to_string())
+}
+
+/// Read all recorded paths, following files renamed since they were collected, then delete
+/// the temp file. Returns an empty vec if the file does not exist.
 pub fn read_and_cleanup(session_id: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
     let path = temp_path(session_id);
 
@@ -51,7 +191,435 @@ pub fn read_and_cleanup(session_id: &str) -> Result<Vec<String>, Box<dyn std::er
         .collect();
 
     fs::remove_file(&path)?;
-    Ok(paths)
+    let followed = follow_renames(session_id, paths);
+    let _ = fs::remove_file(ids_path(session_id));
+    Ok(followed)
+}
+
+/// Returns the path file for one subagent of a session:
+/// `<temp_dir>/ralph-lint-<session_id>-agent-<agent_id>.txt`
+pub fn agent_path(session_id: &str, agent_id: &str) -> PathBuf {
+    let agent_id: String = agent_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-agent-{agent_id}.txt"))
+}
+
+/// Also record `file_path` as collected by subagent `agent_id`, for its `SubagentStop`.
+pub fn record_agent_path(
+    session_id: &str,
+    agent_id: &str,
+    file_path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let path = agent_path(session_id, agent_id);
+    if fs::read_to_string(&path).is_ok_and(|c| c.lines().any(|line| line == file_path)) {
+        return Ok(());
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{file_path}")?;
+    Ok(())
+}
+
+/// Read and delete the paths subagent `agent_id` collected, following renames, and take them
+/// out of the session's list so the main agent's `Stop` doesn't lint them again unless it
+/// edits them itself.
+pub fn read_and_cleanup_agent(
+    session_id: &str,
+    agent_id: &str,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let path = agent_path(session_id, agent_id);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Ok(Vec::new());
+    };
+    fs::remove_file(&path)?;
+    let paths: Vec<String> = contents
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let session_file = temp_path(session_id);
+    if let Ok(session_paths) = fs::read_to_string(&session_file) {
+        let mut remaining = String::new();
+        for line in session_paths.lines() {
+            if !line.is_empty() && !paths.iter().any(|p| p == line) {
+                let _ = writeln!(remaining, "{line}");
+            }
+        }
+        fs::write(&session_file, remaining)?;
+    }
+    Ok(follow_renames(session_id, paths))
+}
+
+/// Returns the git baseline path for a given session: `<temp_dir>/ralph-lint-<session_id>-baseline.txt`
+pub fn baseline_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-baseline.txt"))
+}
+
+/// Record the dirty files of the repository containing `cwd` on the session's first event, so
+/// `--collect-from-git` can tell what the session changed. Kept for the whole session.
+pub fn record_baseline(session_id: &str, cwd: &Path) -> std::io::Result<()> {
+    let path = baseline_path(session_id);
+    if path.exists() {
+        return Ok(());
+    }
+    gitstatus::record(&path, cwd)
+}
+
+/// Whether `file_path` was already changed or untracked in git at the session's baseline.
+pub fn dirty_at_baseline(session_id: &str, file_path: &str) -> bool {
+    gitstatus::was_dirty(&baseline_path(session_id), file_path)
+}
+
+/// Remove whatever an earlier run left in the session's store: collected files, results,
+/// verdicts, the git baseline, rate limits, linter failures, the sandbox note, session rules,
+/// and the dedup log.
+pub fn reset_session(session_id: &str) {
+    for path in [
+        temp_path(session_id),
+        ids_path(session_id),
+        clean_path(session_id),
+        events_path(session_id),
+        verdicts_path(session_id),
+        baseline_path(session_id),
+        crate::ratelimit::state_path(session_id),
+        crate::quarantine::failures_path(session_id),
+        crate::sandbox::noted_path(session_id),
+        crate::overlay::overlay_path(session_id),
+    ] {
+        let _ = fs::remove_file(path);
+    }
+    let agents = format!("ralph-lint-{session_id}-agent-");
+    if let Ok(entries) = fs::read_dir(std::env::temp_dir()) {
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with(&agents) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+    clear_results(session_id);
+}
+
+/// Files git reports as changed since the session's baseline; empty without one.
+pub fn changed_since_baseline(session_id: &str) -> Vec<String> {
+    gitstatus::changed_since(&baseline_path(session_id))
+        .unwrap_or_default()
+        .iter()
+        .map(|file| file.to_string_lossy().to_string())
+        .collect()
+}
+
+/// Returns the clean-hash file path for a given session: `<temp_dir>/ralph-lint-<session_id>-clean.txt`
+pub fn clean_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-clean.txt"))
+}
+
+/// Hash the current contents of `file_path`. Returns `None` if the file cannot be read.
+pub fn content_hash(file_path: &str) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    fs::read(file_path).ok()?.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Read the `<hash>\t<path>` entries from the session's clean-hash file.
+fn read_clean_entries(session_id: &str) -> Vec<(u64, String)> {
+    let Ok(contents) = fs::read_to_string(clean_path(session_id)) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (hash, path) = line.split_once('\t')?;
+            Some((hash.parse().ok()?, path.to_string()))
+        })
+        .collect()
+}
+
+/// Directory of background lint results for a session: `<temp_dir>/ralph-lint-<session_id>-results`
+pub fn results_dir(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-results"))
+}
+
+fn result_path(session_id: &str, file_path: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    file_path.hash(&mut hasher);
+    results_dir(session_id).join(format!("{:016x}.txt", hasher.finish()))
+}
+
+/// Store a background lint's hook response for `file_path`, linted with contents `hash`.
+/// Written to a temp name and renamed, so a reader never sees a partial result.
+pub fn record_result(
+    session_id: &str,
+    file_path: &str,
+    hash: u64,
+    output: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let path = result_path(session_id, file_path);
+    fs::create_dir_all(results_dir(session_id))?;
+    let partial = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&partial, format!("{hash}\t{file_path}\n{output}"))?;
+    fs::rename(&partial, &path)?;
+    Ok(())
+}
+
+/// The stored background lint response for `file_path`, if there is one for its current
+/// contents. A result for an older version of the file is ignored.
+pub fn take_result(session_id: &str, file_path: &str) -> Option<String> {
+    let stored = fs::read_to_string(result_path(session_id, file_path)).ok()?;
+    let (header, output) = stored.split_once('\n')?;
+    let (hash, path) = header.split_once('\t')?;
+    (path == file_path && hash.parse().ok() == content_hash(file_path)).then(|| output.to_string())
+}
+
+/// Delete the session's background lint results.
+pub fn clear_results(session_id: &str) {
+    let _ = fs::remove_dir_all(results_dir(session_id));
+}
+
+/// Record that `file_path` passed lint with its current contents, replacing any earlier entry.
+pub fn record_clean(session_id: &str, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(hash) = content_hash(file_path) else {
+        return Ok(());
+    };
+
+    let mut entries = read_clean_entries(session_id);
+    entries.retain(|(_, path)| path != file_path);
+    entries.push((hash, file_path.to_string()));
+
+    let mut file = fs::File::create(clean_path(session_id))?;
+    for (hash, path) in &entries {
+        writeln!(file, "{hash}\t{path}")?;
+    }
+    Ok(())
+}
+
+/// Returns true if `file_path` already passed lint in this session and is unchanged since.
+pub fn is_unchanged_clean(session_id: &str, file_path: &str) -> bool {
+    let Some(hash) = content_hash(file_path) else {
+        return false;
+    };
+    read_clean_entries(session_id)
+        .iter()
+        .any(|(h, path)| *h == hash && path == file_path)
+}
+
+/// Recent-event file path for a given session: `<temp_dir>/ralph-lint-<session_id>-events.txt`
+pub fn events_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-events.txt"))
+}
+
+/// Returns true if the same `event` (hook mode and tool) already reported `file_path` with its
+/// current contents in this session less than `window` ago, e.g. an agent retrying a
+/// `PostToolUse` event. Otherwise the event is recorded, and entries older than `window` are
+/// dropped.
+pub fn is_duplicate_event(
+    session_id: &str,
+    event: &str,
+    file_path: &str,
+    window: Duration,
+) -> bool {
+    let mut hasher = DefaultHasher::new();
+    (session_id, event, file_path, content_hash(file_path)).hash(&mut hasher);
+    let key = hasher.finish();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    let path = events_path(session_id);
+    let mut events: Vec<(u64, Duration)> = fs::read_to_string(&path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (key, millis) = line.split_once('\t')?;
+            let at = Duration::from_millis(millis.parse().ok()?);
+            Some((u64::from_str_radix(key, 16).ok()?, at))
+        })
+        .filter(|(_, at)| now.saturating_sub(*at) < window)
+        .collect();
+    if events.iter().any(|(seen, _)| *seen == key) {
+        return true;
+    }
+
+    events.push((key, now));
+    let contents = events.iter().fold(String::new(), |mut out, (key, at)| {
+        let _ = writeln!(out, "{key:016x}\t{}", at.as_millis());
+        out
+    });
+    let _ = fs::write(&path, contents);
+    false
+}
+
+/// Verdict-history file path for a given session: `<temp_dir>/ralph-lint-<session_id>-verdicts.txt`
+pub fn verdicts_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-verdicts.txt"))
+}
+
+/// The last lint verdict for a file in a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    pub file: String,
+    pub blocked: bool,
+    /// Hash of the file's issue keys, equal for the same issues
+    pub digest: u64,
+    /// Keys of the issues the file has had since it was last clean, or since its first lint
+    /// if it was never clean and existed before the session, once per occurrence
+    pub baseline: Vec<u64>,
+}
+
+/// Key identifying a diagnostic line across edits: line and column numbers shift as the file
+/// changes, so the leading `file:line:col:` location is ignored. Keys are persisted, so they
+/// use FNV-1a rather than the standard library's unspecified hasher.
+pub fn issue_key(line: &str) -> u64 {
+    fnv1a(strip_location(line.trim()).as_bytes())
+}
+
+/// `line` without a leading `<file>:<line>:` or `<file>:<line>:<col>:` location.
+fn strip_location(line: &str) -> &str {
+    let is_number = |field: &str| !field.is_empty() && field.bytes().all(|b| b.is_ascii_digit());
+    let Some((file, rest)) = line.split_once(':') else {
+        return line;
+    };
+    let Some((row, rest)) = rest.split_once(':') else {
+        return line;
+    };
+    if file.is_empty() || !is_number(row) {
+        return line;
+    }
+    match rest.split_once(':') {
+        Some((col, after)) if is_number(col) => after.trim_start(),
+        _ => rest.trim_start(),
+    }
+}
+
+/// 64-bit FNV-1a, stable across Rust releases.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Read every file's last verdict in the session.
+pub fn read_verdicts(session_id: &str) -> Vec<Verdict> {
+    let Ok(contents) = fs::read_to_string(verdicts_path(session_id)) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\t');
+            let blocked = match fields.next()? {
+                "blocked" => true,
+                "clean" => false,
+                _ => return None,
+            };
+            let digest = u64::from_str_radix(fields.next()?, 16).ok()?;
+            let baseline = fields
+                .next()?
+                .split(',')
+                .filter(|key| !key.is_empty())
+                .map(|key| u64::from_str_radix(key, 16).ok())
+                .collect::<Option<Vec<_>>>()?;
+            Some(Verdict {
+                file: fields.next()?.to_string(),
+                blocked,
+                digest,
+                baseline,
+            })
+        })
+        .collect()
+}
+
+/// Record `file_path`'s verdict from its diagnostic lines (none means clean), replacing the
+/// previous one. A clean verdict resets the baseline; the first blocked verdict of a file that
+/// existed before the session takes its issues as the baseline.
+pub fn record_verdict(
+    session_id: &str,
+    file_path: &str,
+    issues: &[&str],
+) -> Result<Verdict, Box<dyn std::error::Error>> {
+    let mut keys: Vec<u64> = issues.iter().map(|line| issue_key(line)).collect();
+    keys.sort_unstable();
+    let digest = fnv1a(
+        &keys
+            .iter()
+            .flat_map(|key| key.to_le_bytes())
+            .collect::<Vec<_>>(),
+    );
+
+    let mut verdicts = read_verdicts(session_id);
+    let previous = verdicts.iter().position(|v| v.file == file_path);
+    let baseline = match previous {
+        _ if keys.is_empty() => Vec::new(),
+        Some(index) => verdicts[index].baseline.clone(),
+        None if origin::of(file_path) == Some(Origin::New) => Vec::new(),
+        None => keys.clone(),
+    };
+    let verdict = Verdict {
+        file: file_path.to_string(),
+        blocked: !keys.is_empty(),
+        digest,
+        baseline,
+    };
+    if let Some(index) = previous {
+        verdicts.remove(index);
+    }
+    verdicts.push(verdict.clone());
+
+    let mut file = fs::File::create(verdicts_path(session_id))?;
+    for v in &verdicts {
+        let baseline: Vec<String> = v.baseline.iter().map(|key| format!("{key:016x}")).collect();
+        writeln!(
+            file,
+            "{}\t{:016x}\t{}\t{}",
+            if v.blocked { "blocked" } else { "clean" },
+            v.digest,
+            baseline.join(","),
+            v.file
+        )?;
+    }
+    Ok(verdict)
+}
+
+/// Why a collected file shouldn't be linted at Stop time, if it shouldn't: it was deleted or
+/// moved away, git ignores it, or it's a scratch file in a temp directory that git reports as
+/// untracked or added but never committed. Set `[lint_collected] lint_temp_files = true` to keep temp-directory files.
+pub fn drop_reason(file_path: &str, lint_temp_files: bool) -> Option<&'static str> {
+    let path = Path::new(file_path);
+    if !path.exists() {
+        return Some("deleted or moved");
+    }
+    let dir = path.parent()?;
+    let ignored = Command::new("git")
+        .args(["check-ignore", "-q", "--", file_path])
+        .current_dir(dir)
+        .output()
+        .is_ok_and(|o| o.status.success());
+    if ignored {
+        return Some("ignored by git");
+    }
+    if !lint_temp_files && is_in_temp_dir(path) && origin::from_git(file_path) == Some(Origin::New)
+    {
+        return Some("uncommitted scratch file in a temp directory");
+    }
+    None
+}
+
+fn is_in_temp_dir(path: &Path) -> bool {
+    let temp = std::env::temp_dir();
+    let canonical = temp.canonicalize().unwrap_or_else(|_| temp.clone());
+    [temp.as_path(), canonical.as_path(), Path::new("/tmp")]
+        .iter()
+        .any(|dir| path.starts_with(dir))
 }
 
 #[cfg(test)]
@@ -62,6 +630,33 @@ mod tests {
         format!("test-{}", std::process::id())
     }
 
+    #[test]
+    fn duplicate_events_within_window() {
+        let sid = format!("{}-events", unique_session());
+        let _ = fs::remove_file(events_path(&sid));
+        let file = std::env::temp_dir().join(format!("ralph-lint-{sid}-file.rs"));
+        fs::write(&file, "fn main() {}").unwrap();
+        let file = file.to_string_lossy().to_string();
+        let window = Duration::from_secs(60);
+
+        assert!(!is_duplicate_event(&sid, "lint Write", &file, window));
+        assert!(is_duplicate_event(&sid, "lint Write", &file, window));
+        // A different tool or new contents is a new event
+        assert!(!is_duplicate_event(&sid, "lint Edit", &file, window));
+        fs::write(&file, "fn main() { let x = 1; }").unwrap();
+        assert!(!is_duplicate_event(&sid, "lint Write", &file, window));
+        // Outside the window, the same event counts again
+        assert!(!is_duplicate_event(
+            &sid,
+            "lint Edit",
+            &file,
+            Duration::ZERO
+        ));
+
+        let _ = fs::remove_file(events_path(&sid));
+        let _ = fs::remove_file(&file);
+    }
+
     #[test]
     fn record_and_read_single_path() {
         let sid = format!("{}-single", unique_session());
@@ -88,6 +683,26 @@ mod tests {
         assert_eq!(paths, vec!["/tmp/b.rs", "/tmp/c.rs"]);
     }
 
+    #[test]
+    fn agent_paths_are_read_separately() {
+        let sid = format!("{}-agent", unique_session());
+        let _ = fs::remove_file(temp_path(&sid));
+
+        record_path(&sid, "/tmp/main.rs").unwrap();
+        for file in ["/tmp/sub.rs", "/tmp/sub.rs"] {
+            record_path(&sid, file).unwrap();
+            record_agent_path(&sid, "agent/1", file).unwrap();
+        }
+
+        assert_eq!(
+            read_and_cleanup_agent(&sid, "agent/1").unwrap(),
+            vec!["/tmp/sub.rs"]
+        );
+        assert!(!agent_path(&sid, "agent/1").exists());
+        assert!(read_and_cleanup_agent(&sid, "other").unwrap().is_empty());
+        assert_eq!(read_and_cleanup(&sid).unwrap(), vec!["/tmp/main.rs"]);
+    }
+
     #[test]
     fn read_and_cleanup_nonexistent() {
         let sid = "nonexistent-session-xyz";
@@ -106,4 +721,221 @@ mod tests {
         let _ = read_and_cleanup(&sid).unwrap();
         assert!(!temp_path(&sid).exists());
     }
+
+    #[test]
+    fn clean_record_matches_unchanged_file() {
+        let sid = format!("{}-clean", unique_session());
+        let _ = fs::remove_file(clean_path(&sid));
+        let file = std::env::temp_dir().join(format!("ralph-lint-{sid}-file.rs"));
+        fs::write(&file, "fn main() {}").unwrap();
+        let file = file.to_string_lossy().to_string();
+
+        assert!(!is_unchanged_clean(&sid, &file));
+        record_clean(&sid, &file).unwrap();
+        assert!(is_unchanged_clean(&sid, &file));
+
+        let _ = fs::remove_file(clean_path(&sid));
+        let _ = fs::remove_file(&file);
+    }
+
+    #[test]
+    fn clean_record_invalidated_by_edit() {
+        let sid = format!("{}-clean-edit", unique_session());
+        let _ = fs::remove_file(clean_path(&sid));
+        let file = std::env::temp_dir().join(format!("ralph-lint-{sid}-file.rs"));
+        fs::write(&file, "fn main() {}").unwrap();
+        let file = file.to_string_lossy().to_string();
+
+        record_clean(&sid, &file).unwrap();
+        fs::write(&file, "fn main() { let x = 1; }").unwrap();
+        assert!(!is_unchanged_clean(&sid, &file));
+
+        // Re-recording replaces the stale entry instead of appending a duplicate
+        record_clean(&sid, &file).unwrap();
+        assert!(is_unchanged_clean(&sid, &file));
+        assert_eq!(read_clean_entries(&sid).len(), 1);
+
+        let _ = fs::remove_file(clean_path(&sid));
+        let _ = fs::remove_file(&file);
+    }
+
+    #[test]
+    fn drop_reason_for_missing_and_temp_scratch_files() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-drop-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("scratch.py");
+        let file = file.to_str().unwrap();
+
+        assert_eq!(drop_reason(file, false), Some("deleted or moved"));
+        fs::write(file, "x = 1\n").unwrap();
+        // Outside a git repository there is nothing to say it was never committed
+        assert_eq!(drop_reason(file, false), None);
+
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&dir)
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        fs::write(dir.join(".gitignore"), "*.log\n").unwrap();
+        let log = dir.join("debug.log");
+        fs::write(&log, "").unwrap();
+        assert_eq!(
+            drop_reason(log.to_str().unwrap(), false),
+            Some("ignored by git")
+        );
+        assert_eq!(
+            drop_reason(file, false),
+            Some("uncommitted scratch file in a temp directory")
+        );
+        assert_eq!(drop_reason(file, true), None);
+
+        let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        assert_eq!(drop_reason(manifest, false), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn porcelain_renames_are_parsed() {
+        let output = " M src/a.rs\0R  src/new.rs\0src/old.rs\0?? notes.md\0C  b2.rs\0b.rs\0";
+        assert_eq!(
+            parse_porcelain_renames(output),
+            vec![("src/old.rs".to_string(), "src/new.rs".to_string())]
+        );
+        assert!(parse_porcelain_renames("").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn renamed_file_is_followed_by_inode() {
+        let sid = format!("{}-rename", unique_session());
+        let _ = fs::remove_file(temp_path(&sid));
+        let _ = fs::remove_file(ids_path(&sid));
+        let dir = std::env::temp_dir().join(format!("ralph-lint-rename-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let old = dir.join("old.py");
+        let new = dir.join("new.py");
+        fs::write(&old, "x = 1\n").unwrap();
+
+        record_path(&sid, old.to_str().unwrap()).unwrap();
+        record_path(&sid, "/tmp/ralph-lint-never-existed.py").unwrap();
+        fs::rename(&old, &new).unwrap();
+        assert_eq!(
+            read_and_cleanup(&sid).unwrap(),
+            vec![
+                new.to_string_lossy().to_string(),
+                "/tmp/ralph-lint-never-existed.py".to_string()
+            ]
+        );
+        assert!(!ids_path(&sid).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn background_result_matches_current_contents_only() {
+        let sid = format!("{}-results", unique_session());
+        clear_results(&sid);
+        let file =
+            std::env::temp_dir().join(format!("ralph-lint-result-{}.py", std::process::id()));
+        let file = file.to_str().unwrap();
+        fs::write(file, "x = 1\n").unwrap();
+
+        assert_eq!(take_result(&sid, file), None);
+        record_result(
+            &sid,
+            file,
+            content_hash(file).unwrap(),
+            "{\"continue\":true}",
+        )
+        .unwrap();
+        assert_eq!(
+            take_result(&sid, file).as_deref(),
+            Some("{\"continue\":true}")
+        );
+
+        fs::write(file, "x = 2\n").unwrap();
+        assert_eq!(
+            take_result(&sid, file),
+            None,
+            "stale result must be ignored"
+        );
+
+        clear_results(&sid);
+        assert!(!results_dir(&sid).exists());
+        let _ = fs::remove_file(file);
+    }
+
+    #[test]
+    fn clean_record_missing_file_is_not_clean() {
+        let sid = format!("{}-clean-missing", unique_session());
+        record_clean(&sid, "/tmp/ralph-lint-does-not-exist.rs").unwrap();
+        assert!(!is_unchanged_clean(
+            &sid,
+            "/tmp/ralph-lint-does-not-exist.rs"
+        ));
+    }
+
+    #[test]
+    fn issue_keys_ignore_only_the_location() {
+        assert_eq!(
+            issue_key("a.py:1:1: F401 unused"),
+            issue_key("a.py:30:7: F401 unused")
+        );
+        assert_eq!(
+            issue_key("main.py:1: error: bad"),
+            issue_key("main.py:12: error: bad")
+        );
+        assert_ne!(
+            issue_key("a.py:1:1: E501 line too long (99 > 88)"),
+            issue_key("a.py:1:1: E501 line too long (120 > 88)")
+        );
+        assert_ne!(
+            issue_key("a.py:1:1: F841 `x1` unused"),
+            issue_key("a.py:1:1: F841 `x2` unused")
+        );
+        assert_eq!(strip_location("no location: here"), "no location: here");
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn verdicts_keep_baseline_until_clean() {
+        let sid = format!("{}-verdicts", unique_session());
+        let _ = fs::remove_file(verdicts_path(&sid));
+        let file = "/tmp/ralph-lint-verdict-a.py";
+
+        let first = record_verdict(&sid, file, &["a.py:1:1: F401 unused import"]).unwrap();
+        assert!(first.blocked);
+        assert_eq!(
+            first.baseline,
+            vec![issue_key("a.py:1:1: F401 unused import")]
+        );
+
+        // Moved issues keep their key; the baseline carries over while the file stays blocked
+        let second = record_verdict(
+            &sid,
+            file,
+            &["a.py:3:1: F401 unused import", "a.py:4:5: E711 comparison"],
+        )
+        .unwrap();
+        assert_eq!(second.baseline, first.baseline);
+        assert_ne!(second.digest, first.digest);
+
+        let clean = record_verdict(&sid, file, &[]).unwrap();
+        assert!(!clean.blocked && clean.baseline.is_empty());
+        let after = record_verdict(&sid, file, &["a.py:1:1: F401 unused import"]).unwrap();
+        assert!(after.baseline.is_empty());
+        assert_eq!(read_verdicts(&sid), vec![after]);
+
+        // Repeated issues are kept once per occurrence
+        let twice = [
+            "b.py:1:1: F401 unused import",
+            "b.py:9:1: F401 unused import",
+        ];
+        let repeated = record_verdict(&sid, "/tmp/ralph-lint-verdict-b.py", &twice).unwrap();
+        assert_eq!(repeated.baseline.len(), 2);
+
+        let _ = fs::remove_file(verdicts_path(&sid));
+    }
 }
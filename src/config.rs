@@ -0,0 +1,737 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::diagnostic::Severity;
+use crate::origin::{self, Origin};
+use crate::policy;
+use crate::project::detect_lang;
+
+/// Name of the per-project configuration file, discovered by walking up from the project root.
+pub const CONFIG_FILE: &str = ".ralph-hook-lint.toml";
+
+/// Written by `ralph-hook-lint init`. Every setting is commented out; uncomment to change it.
+pub const STARTER_CONFIG: &str = r#"# ralph-hook-lint configuration. Settings apply to this directory and below.
+
+# [hooks]
+# post_tool_use = "lint"
+# tools = ["Write", "Edit", "MultiEdit"]
+# exclude = ["**/*.generated.ts"]
+# dedup_window_ms = 2000
+# max_file_kb = 512
+
+# [bash]
+# collect = "command"
+
+# [output]
+# suppress_output = false
+# success_message = false
+# report = "all"
+# max_diagnostics_per_file = 20
+# relative_paths = true
+# on_error = "fail-open"
+# block_categories = ["correctness", "security"]
+# warn_categories = ["style", "pedantic"]
+
+# [standalone]
+# enabled = true
+
+# [files]
+# new = "strict, block-on=warning"
+# modified = "lenient"
+
+# [lenient.python]
+# allow = ["F841", "F401", "F821"]
+
+# [paths]
+# "legacy/**" = "lenient"
+# "src/api/**" = "strict, block-on=warning"
+
+# [rust]
+# target_dir = "target/ralph-hook-lint"
+# warm_check = true
+
+# [java]
+# bloop = true
+
+# [js]
+# npm_lint = true
+# daemon = true
+# run_from = "package"
+
+# [markdown]
+# code_blocks = false
+
+# [process]
+# nice = 10
+# io_class = "idle"
+# quarantine_after = 3
+# max_lint_seconds = 20
+
+# [sandbox]
+# cpu_secs = 300
+# wrapper = "bwrap"
+# writable = ["~/.cache"]
+
+# [network]
+# offline = true
+
+# [rate_limit]
+# java = 300
+"#;
+
+/// A configuration value. Only the TOML subset the hook needs is supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Bool(bool),
+    Integer(i64),
+    Array(Vec<String>),
+}
+
+/// Lint strictness for a file, from the `[files]` and `[paths]` rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathProfile {
+    /// `Some` if a rule chose `lenient` or `strict`, overriding `--lenient`
+    pub lenient: Option<bool>,
+    /// Lowest severity that blocks, for linters that report warnings separately
+    pub block_on: Severity,
+}
+
+impl PathProfile {
+    /// Apply comma-separated directives such as `strict, block-on=warning`.
+    fn apply(&mut self, directives: &str) {
+        for directive in directives.split(',').map(str::trim) {
+            match directive {
+                "lenient" => self.lenient = Some(true),
+                "strict" => self.lenient = Some(false),
+                "block-on=warning" => self.block_on = Severity::Warning,
+                "block-on=error" => self.block_on = Severity::Error,
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Default for PathProfile {
+    fn default() -> Self {
+        Self {
+            lenient: None,
+            block_on: Severity::Error,
+        }
+    }
+}
+
+/// Flattened configuration: keys are dotted paths such as `rust.target_dir`.
+#[derive(Debug, Default)]
+pub struct Config {
+    values: Vec<(String, Value)>,
+    /// Path of the file the configuration was loaded from, if any
+    pub source: Option<PathBuf>,
+}
+
+impl Config {
+    /// Load the nearest config file at or above `project_root`, with the managed policy's
+    /// settings in place of its own. Returns the policy alone (or an empty config) if no file
+    /// is found or it cannot be read.
+    pub fn load(project_root: &str) -> Self {
+        let mut config = find_config_file(project_root)
+            .and_then(|path| {
+                let text = fs::read_to_string(&path).ok()?;
+                let mut config = Self::parse(&text);
+                config.source = Some(path);
+                Some(config)
+            })
+            .unwrap_or_default();
+        if let Ok(Some(policy)) = policy::current() {
+            config.lock_with(policy);
+        }
+        config
+    }
+
+    /// Override settings with a managed policy's. `[policy]` keys are only read from the
+    /// policy, so a project config can't grant itself a profile or re-enable a language.
+    fn lock_with(&mut self, policy: &Self) {
+        self.values.retain(|(key, _)| {
+            !key.starts_with("policy.") && !policy.values.iter().any(|(k, _)| k == key)
+        });
+        self.values.extend(policy.values.iter().cloned());
+    }
+
+    /// Parse config text. Malformed lines are ignored so a bad config never breaks the hook.
+    pub fn parse(text: &str) -> Self {
+        let mut values = Vec::new();
+        let mut section = String::new();
+
+        for line in text.lines() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.trim().to_string();
+                continue;
+            }
+
+            let Some((key, raw)) = line.split_once('=') else {
+                continue;
+            };
+            let key = unquote_key(key.trim());
+            let Some(value) = parse_value(raw.trim()) else {
+                continue;
+            };
+            let full_key = if section.is_empty() {
+                key
+            } else {
+                format!("{section}.{key}")
+            };
+
+            values.retain(|(k, _): &(String, Value)| *k != full_key);
+            values.push((full_key, value));
+        }
+
+        Self {
+            values,
+            source: None,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&Value> {
+        self.values.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)? {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        match self.get(key)? {
+            Value::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub fn get_array(&self, key: &str) -> Option<&[String]> {
+        match self.get(key)? {
+            Value::Array(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /// Every setting as a dotted key and its value, in file order.
+    pub fn values(&self) -> &[(String, Value)] {
+        &self.values
+    }
+
+    /// String entries directly under the `[section]` table, e.g. `rust.env`.
+    pub fn table(&self, section: &str) -> Vec<(&str, &str)> {
+        let prefix = format!("{section}.");
+        self.values
+            .iter()
+            .filter_map(|(k, v)| {
+                let name = k.strip_prefix(&prefix)?;
+                match v {
+                    Value::String(s) if !name.contains('.') => Some((name, s.as_str())),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Config {
+    /// Profile for `file_path` from `[files]` and `[paths]` rules. `[files] new`/`modified`
+    /// set a base profile by whether the file was created during the session; `[paths]` rules
+    /// such as `"legacy/**" = "lenient"` override it. Like CODEOWNERS, path patterns are
+    /// relative to the config file's directory and the last matching rule wins. A managed
+    /// policy's `[policy] profile` is applied last.
+    pub fn path_profile(&self, file_path: &str) -> PathProfile {
+        let mut profile = self.rule_profile(file_path);
+        if let Some(directives) = self.get_str("policy.profile") {
+            profile.apply(directives);
+        }
+        profile
+    }
+
+    fn rule_profile(&self, file_path: &str) -> PathProfile {
+        let mut profile = PathProfile::default();
+        if self.get_str("files.new").is_some() || self.get_str("files.modified").is_some() {
+            let key = match origin::of(file_path) {
+                Some(Origin::New) => Some("files.new"),
+                Some(Origin::Modified) => Some("files.modified"),
+                None => None,
+            };
+            if let Some(directives) = key.and_then(|k| self.get_str(k)) {
+                profile.apply(directives);
+            }
+        }
+
+        let Some(base) = self.source.as_deref().and_then(Path::parent) else {
+            return profile;
+        };
+        let Ok(relative) = Path::new(file_path).strip_prefix(base) else {
+            return profile;
+        };
+        let relative = relative.to_string_lossy();
+
+        if let Some((_, directives)) = self
+            .path_rules()
+            .into_iter()
+            .rev()
+            .find(|(pattern, _)| path_matches(pattern, &relative))
+        {
+            profile.apply(directives);
+        }
+        profile
+    }
+
+    /// The `[paths]` rules in file order. Unlike [`Config::table`], keys may contain dots, as
+    /// globs such as `"**/*.test.ts"` do.
+    fn path_rules(&self) -> Vec<(&str, &str)> {
+        self.values
+            .iter()
+            .filter_map(|(k, v)| match v {
+                Value::String(s) => Some((k.strip_prefix("paths.")?, s.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether the hook should act on an event for `tool_name` and `file_path`, from the
+    /// `[hooks]` rules. This lets a broadly registered hook filter itself when the agent's
+    /// matcher can't be changed. `tools`/`exclude_tools` list tool names; `include`/`exclude`
+    /// are path patterns relative to the config file's directory, as in `[paths]`. An absent
+    /// list allows everything, and exclusions win. Languages a managed policy lists in
+    /// `[policy] disabled_languages` are never accepted.
+    pub fn accepts_event(&self, tool_name: Option<&str>, file_path: &str) -> bool {
+        if let (Some(disabled), Some(lang)) = (
+            self.get_array("policy.disabled_languages"),
+            detect_lang(file_path),
+        ) {
+            if disabled.iter().any(|name| name == lang.name()) {
+                return false;
+            }
+        }
+        if let Some(tool) = tool_name {
+            if self
+                .get_array("hooks.tools")
+                .is_some_and(|tools| !tools.iter().any(|t| t == tool))
+                || self
+                    .get_array("hooks.exclude_tools")
+                    .is_some_and(|tools| tools.iter().any(|t| t == tool))
+            {
+                return false;
+            }
+        }
+        let relative = self
+            .source
+            .as_deref()
+            .and_then(Path::parent)
+            .and_then(|base| Path::new(file_path).strip_prefix(base).ok())
+            .map_or_else(
+                || file_path.to_string(),
+                |r| r.to_string_lossy().to_string(),
+            );
+        let matches_any = |key: &str| {
+            self.get_array(key)
+                .map(|patterns| patterns.iter().any(|p| path_matches(p, &relative)))
+        };
+        matches_any("hooks.include") != Some(false) && matches_any("hooks.exclude") != Some(true)
+    }
+}
+
+/// Match a relative path against a CODEOWNERS-style pattern. `*` stays within a path segment,
+/// `**` spans segments, and a pattern without `/` matches the file name at any depth.
+pub fn path_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_start_matches('/');
+    if !pattern.contains('/') {
+        return path
+            .rsplit('/')
+            .next()
+            .is_some_and(|name| glob_match(pattern.as_bytes(), name.as_bytes()));
+    }
+    glob_match(pattern.as_bytes(), path.as_bytes())
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', rest @ ..] => {
+            // `dir/**` matches everything below `dir`; `**/` may match zero segments
+            let rest = rest.strip_prefix(b"/").unwrap_or(rest);
+            rest.is_empty()
+                || (0..=text.len())
+                    .any(|i| (i == 0 || text[i - 1] == b'/') && glob_match(rest, &text[i..]))
+        }
+        [b'*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != b'/')
+            .any(|i| glob_match(rest, &text[i..])),
+        [b'?', rest @ ..] => {
+            text.first().is_some_and(|&c| c != b'/') && glob_match(rest, &text[1..])
+        }
+        [c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
+/// Walk up from `dir` looking for the config file.
+fn find_config_file(dir: &str) -> Option<PathBuf> {
+    let mut current = Path::new(dir);
+    loop {
+        let candidate = current.join(CONFIG_FILE);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Remove a trailing `# comment`, ignoring `#` inside quoted strings.
+fn strip_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some(q) => {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' && q == '"' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '#' => return &line[..i],
+                _ => {}
+            },
+        }
+    }
+    line
+}
+
+fn unquote_key(key: &str) -> String {
+    parse_string(key).map_or_else(|| key.to_string(), |(s, _)| s)
+}
+
+fn parse_value(raw: &str) -> Option<Value> {
+    match raw {
+        "true" => return Some(Value::Bool(true)),
+        "false" => return Some(Value::Bool(false)),
+        _ => {}
+    }
+
+    if let Some(inner) = raw.strip_prefix('[') {
+        let mut items = Vec::new();
+        let mut rest = inner.trim_start();
+        loop {
+            if let Some(after) = rest.strip_prefix(']') {
+                return after.trim().is_empty().then_some(Value::Array(items));
+            }
+            let (item, after) = parse_array_item(rest)?;
+            items.push(item);
+            rest = after.trim_start();
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after.trim_start();
+            }
+        }
+    }
+
+    if let Some((s, rest)) = parse_string(raw) {
+        return rest.trim().is_empty().then_some(Value::String(s));
+    }
+
+    raw.replace('_', "").parse().ok().map(Value::Integer)
+}
+
+/// Parse a quoted string or a bare token (e.g. a number) inside an array.
+/// Bare tokens are kept as their text.
+fn parse_array_item(s: &str) -> Option<(String, &str)> {
+    if let Some(parsed) = parse_string(s) {
+        return Some(parsed);
+    }
+    let end = s.find([',', ']']).unwrap_or(s.len());
+    let token = s[..end].trim();
+    (!token.is_empty()).then(|| (token.to_string(), &s[end..]))
+}
+
+/// Parse a leading basic (`"..."`) or literal (`'...'`) string, returning it and the remainder.
+fn parse_string(s: &str) -> Option<(String, &str)> {
+    let mut chars = s.char_indices();
+    let (_, quote) = chars.next()?;
+    if quote != '"' && quote != '\'' {
+        return None;
+    }
+
+    let mut result = String::new();
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            return Some((result, &s[i + 1..]));
+        }
+        if c == '\\' && quote == '"' {
+            let (_, escaped) = chars.next()?;
+            match escaped {
+                'n' => result.push('\n'),
+                't' => result.push('\t'),
+                'r' => result.push('\r'),
+                '\\' => result.push('\\'),
+                '"' => result.push('"'),
+                other => {
+                    result.push('\\');
+                    result.push(other);
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_top_level_and_sections() {
+        let config = Config::parse(
+            r#"
+            debug = true
+            [rust]
+            target_dir = "target/ralph-hook"
+            jobs = 4
+            "#,
+        );
+        assert_eq!(config.get_bool("debug"), Some(true));
+        assert_eq!(config.get_str("rust.target_dir"), Some("target/ralph-hook"));
+        assert_eq!(config.get("rust.jobs"), Some(&Value::Integer(4)));
+    }
+
+    #[test]
+    fn parse_arrays() {
+        let config = Config::parse(r#"rules = ["a", 'b' , "c"]"#);
+        assert_eq!(
+            config.get("rules"),
+            Some(&Value::Array(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "c".to_string()
+            ]))
+        );
+        let config = Config::parse("rules = []");
+        assert_eq!(config.get("rules"), Some(&Value::Array(Vec::new())));
+        let config = Config::parse("codes = [1, 16]");
+        assert_eq!(
+            config.get_array("codes"),
+            Some(&["1".to_string(), "16".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn parse_comments_and_escapes() {
+        let config = Config::parse(
+            r#"
+            # full-line comment
+            name = "has # hash" # trailing comment
+            quoted = "say \"hi\""
+            literal = 'C:\path'
+            "#,
+        );
+        assert_eq!(config.get_str("name"), Some("has # hash"));
+        assert_eq!(config.get_str("quoted"), Some(r#"say "hi""#));
+        assert_eq!(config.get_str("literal"), Some(r"C:\path"));
+    }
+
+    #[test]
+    fn malformed_lines_are_ignored() {
+        let config = Config::parse("not a pair\nkey = \"unterminated\nok = 1");
+        assert_eq!(config.get_str("key"), None);
+        assert_eq!(config.get("ok"), Some(&Value::Integer(1)));
+    }
+
+    #[test]
+    fn later_keys_override_earlier_ones() {
+        let config = Config::parse("a = 1\na = 2");
+        assert_eq!(config.get("a"), Some(&Value::Integer(2)));
+    }
+
+    #[test]
+    fn wrong_type_returns_none() {
+        let config = Config::parse("a = 1");
+        assert_eq!(config.get_str("a"), None);
+    }
+
+    #[test]
+    fn table_returns_direct_string_entries() {
+        let config = Config::parse(
+            r#"
+            [rust.env]
+            RUSTFLAGS = "-Cdebuginfo=0"
+            "QUOTED_KEY" = "1"
+            [rust.env.nested]
+            SKIP = "x"
+            "#,
+        );
+        let mut table = config.table("rust.env");
+        table.sort_unstable();
+        assert_eq!(
+            table,
+            vec![("QUOTED_KEY", "1"), ("RUSTFLAGS", "-Cdebuginfo=0")]
+        );
+    }
+
+    #[test]
+    fn load_walks_up_to_find_config() {
+        let root = std::env::temp_dir().join(format!("ralph-lint-config-{}", std::process::id()));
+        let nested = root.join("crates/app");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join(CONFIG_FILE), "[rust]\ntarget_dir = \"t\"\n").unwrap();
+
+        let config = Config::load(&nested.to_string_lossy());
+        assert_eq!(config.get_str("rust.target_dir"), Some("t"));
+        assert_eq!(config.source, Some(root.join(CONFIG_FILE)));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn path_matches_globs() {
+        assert!(path_matches("legacy/**", "legacy/a/b.js"));
+        assert!(!path_matches("legacy/**", "src/legacy/b.js"));
+        assert!(path_matches("src/*/index.ts", "src/api/index.ts"));
+        assert!(!path_matches("src/*/index.ts", "src/api/v1/index.ts"));
+        assert!(path_matches("src/**/*.ts", "src/index.ts"));
+        assert!(path_matches("src/**/*.ts", "src/a/b/c.ts"));
+        assert!(path_matches("*.test.js", "pkg/deep/a.test.js"));
+        assert!(path_matches("/docs/?.md", "docs/a.md"));
+    }
+
+    #[test]
+    fn path_profile_last_matching_rule_wins() {
+        let mut config = Config::parse(
+            r#"
+            [paths]
+            "**" = "block-on=warning"
+            "legacy/**" = "lenient"
+            "src/api/**" = "strict, block-on=warning"
+            "#,
+        );
+        config.source = Some(PathBuf::from("/ws").join(CONFIG_FILE));
+
+        let legacy = config.path_profile("/ws/legacy/old.js");
+        assert_eq!(legacy.lenient, Some(true));
+        assert_eq!(legacy.block_on, Severity::Error);
+
+        let api = config.path_profile("/ws/src/api/handler.ts");
+        assert_eq!(api.lenient, Some(false));
+        assert_eq!(api.block_on, Severity::Warning);
+
+        assert_eq!(config.path_profile("/ws/src/lib.ts").lenient, None);
+        assert_eq!(
+            config.path_profile("/elsewhere/a.ts"),
+            PathProfile::default()
+        );
+    }
+
+    #[test]
+    fn path_profile_matches_dotted_globs() {
+        let mut config = Config::parse(
+            r#"
+            [paths]
+            "**/*.test.ts" = "lenient"
+            "src/*.generated.ts" = "strict"
+            "#,
+        );
+        config.source = Some(PathBuf::from("/ws").join(CONFIG_FILE));
+
+        assert_eq!(
+            config.path_profile("/ws/src/deep/a.test.ts").lenient,
+            Some(true)
+        );
+        assert_eq!(
+            config.path_profile("/ws/src/api.generated.ts").lenient,
+            Some(false)
+        );
+        assert_eq!(config.path_profile("/ws/src/a.ts").lenient, None);
+    }
+
+    #[test]
+    fn hooks_rules_filter_events() {
+        let mut config = Config::parse(
+            r#"
+            [hooks]
+            tools = ["Write", "Edit"]
+            include = ["src/**"]
+            exclude = ["*.generated.ts"]
+            "#,
+        );
+        config.source = Some(PathBuf::from("/ws").join(CONFIG_FILE));
+
+        assert!(config.accepts_event(Some("Edit"), "/ws/src/app.ts"));
+        assert!(config.accepts_event(None, "/ws/src/app.ts"));
+        assert!(!config.accepts_event(Some("NotebookEdit"), "/ws/src/app.ts"));
+        assert!(!config.accepts_event(Some("Write"), "/ws/scripts/build.ts"));
+        assert!(!config.accepts_event(Some("Write"), "/ws/src/api.generated.ts"));
+
+        let config = Config::parse("[hooks]\nexclude_tools = [\"MultiEdit\"]\n");
+        assert!(config.accepts_event(Some("Write"), "/anywhere/a.py"));
+        assert!(!config.accepts_event(Some("MultiEdit"), "/anywhere/a.py"));
+        assert!(Config::default().accepts_event(Some("Bash"), "/a.py"));
+    }
+
+    #[test]
+    fn managed_policy_overrides_project_settings() {
+        let mut config = Config::parse(
+            "[output]\non_error = \"fail-open\"\nsuppress_output = true\n[policy]\nprofile = \"lenient\"\n[paths]\n\"**\" = \"lenient\"\n",
+        );
+        config.lock_with(&Config::parse(
+            "[output]\non_error = \"fail-closed\"\n[policy]\nprofile = \"strict, block-on=warning\"\ndisabled_languages = [\"java\"]\n",
+        ));
+        assert_eq!(config.get_str("output.on_error"), Some("fail-closed"));
+        assert_eq!(config.get_bool("output.suppress_output"), Some(true));
+        assert_eq!(
+            config.path_profile("/ws/a.ts"),
+            PathProfile {
+                lenient: Some(false),
+                block_on: Severity::Warning
+            }
+        );
+        assert!(!config.accepts_event(Some("Write"), "/ws/App.java"));
+        assert!(config.accepts_event(Some("Write"), "/ws/app.py"));
+
+        // A project config can't set `[policy]` itself
+        let mut config = Config::parse("[policy]\ndisabled_languages = [\"python\"]\n");
+        config.lock_with(&Config::default());
+        assert!(config.accepts_event(Some("Write"), "/ws/app.py"));
+    }
+
+    #[test]
+    fn starter_config_is_all_comments() {
+        assert!(Config::parse(STARTER_CONFIG).values.is_empty());
+        let uncommented: String = STARTER_CONFIG
+            .lines()
+            .map(|l| l.strip_prefix("# ").unwrap_or(l))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let config = Config::parse(&uncommented);
+        assert_eq!(config.get_bool("java.bloop"), Some(true));
+        assert_eq!(config.path_rules().len(), 2);
+    }
+
+    #[test]
+    fn load_without_config_is_empty() {
+        let config = Config::load("/nonexistent/ralph-lint/project");
+        assert!(config.source.is_none());
+        assert_eq!(config.get_str("rust.target_dir"), None);
+    }
+}
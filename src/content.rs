@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::json::{self, Json};
+
+/// The file contents a `Write` or `Edit` tool call is about to produce, from a `PreToolUse`
+/// payload. `current` is the file as it is on disk now, if it exists. Returns `None` for other
+/// tools, or an edit whose `old_string` isn't in the file.
+pub fn proposed_content(input: &str, current: Option<&str>) -> Option<String> {
+    let payload = json::parse(input)?;
+    let tool_input = payload.get("tool_input")?;
+    if let Some(content) = tool_input.get("content").and_then(Json::as_str) {
+        return Some(content.to_string());
+    }
+    let old = tool_input.get("old_string").and_then(Json::as_str)?;
+    let new = tool_input.get("new_string").and_then(Json::as_str)?;
+    let current = current?;
+    if !current.contains(old) {
+        return None;
+    }
+    let replace_all = matches!(tool_input.get("replace_all"), Some(Json::Bool(true)));
+    Some(if replace_all {
+        current.replace(old, new)
+    } else {
+        current.replacen(old, new, 1)
+    })
+}
+
+/// A copy of the proposed contents next to the real file, so the project's linter config
+/// applies to it. Removed on drop.
+pub struct StagedFile {
+    pub path: PathBuf,
+}
+
+impl StagedFile {
+    pub fn write(file_path: &str, contents: &str) -> std::io::Result<Self> {
+        let original = Path::new(file_path);
+        let name = original
+            .file_name()
+            .map_or_else(String::new, |n| n.to_string_lossy().to_string());
+        Self::write_at(
+            original.with_file_name(format!("ralph-pre-{}-{name}", std::process::id())),
+            contents,
+        )
+    }
+
+    /// Stage `contents` at `path` itself, for copies that aren't named after a real file.
+    pub fn write_at(path: PathBuf, contents: &str) -> std::io::Result<Self> {
+        fs::write(&path, contents)?;
+        Ok(Self { path })
+    }
+
+    /// Rewrite references to the staged copy in linter output so they name the real file.
+    pub fn unstage(&self, output: &str, file_path: &str) -> String {
+        let staged = self.path.to_string_lossy();
+        let staged_name = self
+            .path
+            .file_name()
+            .map_or_else(String::new, |n| n.to_string_lossy().to_string());
+        let real_name = Path::new(file_path)
+            .file_name()
+            .map_or_else(String::new, |n| n.to_string_lossy().to_string());
+        output
+            .replace(staged.as_ref(), file_path)
+            .replace(&staged_name, &real_name)
+    }
+}
+
+impl Drop for StagedFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_payload_proposes_its_content() {
+        let input =
+            r#"{"tool_name":"Write","tool_input":{"file_path":"/a.py","content":"x = 1\n"}}"#;
+        assert_eq!(proposed_content(input, None).as_deref(), Some("x = 1\n"));
+    }
+
+    #[test]
+    fn edit_payload_applies_to_current_file() {
+        let input = r#"{"tool_input":{"file_path":"/a.py","old_string":"a","new_string":"b"}}"#;
+        assert_eq!(proposed_content(input, Some("a a")).as_deref(), Some("b a"));
+        assert_eq!(proposed_content(input, Some("c")), None);
+        assert_eq!(proposed_content(input, None), None);
+
+        let all = r#"{"tool_input":{"old_string":"a","new_string":"b","replace_all":true}}"#;
+        assert_eq!(proposed_content(all, Some("a a")).as_deref(), Some("b b"));
+    }
+
+    #[test]
+    fn staged_file_is_removed_and_paths_rewritten() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-stage-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("app.py");
+        let file = file.to_string_lossy();
+
+        let staged = StagedFile::write(&file, "x = 1\n").unwrap();
+        let path = staged.path.clone();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "x = 1\n");
+        let output = format!(
+            "{}:1:1: E1\nralph-pre-{}-app.py:2:1: E2",
+            path.display(),
+            std::process::id()
+        );
+        assert_eq!(
+            staged.unstage(&output, &file),
+            format!("{file}:1:1: E1\napp.py:2:1: E2")
+        );
+        drop(staged);
+        assert!(!path.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
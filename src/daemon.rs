@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use crate::cli::DaemonAction;
+use crate::config::Config;
+use crate::lint::eslint_daemon;
+use crate::policy;
+
+/// Manage the linter daemons and caches for the project in `dir`, printing one line per tool:
+/// `eslint_d`, which keeps a JavaScript project's eslint loaded between lints, and
+/// golangci-lint's analysis cache, which has no daemon but is filled by a background run so
+/// the first lint of a session is already warm.
+pub fn run(action: DaemonAction, dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let root = fs::canonicalize(dir).map_err(|e| format!("cannot use {dir}: {e}"))?;
+    let config = Config::load(&root.to_string_lossy());
+    let mut managed = false;
+
+    if action == DaemonAction::Start {
+        if let Some(line) = policy::fetch()? {
+            println!("policy: {line}");
+        }
+    }
+
+    if root.join("node_modules/.bin/eslint").exists() {
+        if let Some(eslint_d) = eslint_daemon(&root.to_string_lossy(), &config) {
+            managed = true;
+            let verb = match action {
+                DaemonAction::Start => "start",
+                DaemonAction::Stop => "stop",
+                DaemonAction::Status => "status",
+            };
+            let output = Command::new(&eslint_d)
+                .arg(verb)
+                .current_dir(&root)
+                .output()?;
+            println!("eslint_d: {}", summary(&output, verb));
+        }
+    }
+
+    if root.join("go.mod").exists() {
+        if let Some(line) = golangci_cache(action, &root)? {
+            managed = true;
+            println!("golangci-lint: {line}");
+        }
+    }
+
+    if !managed {
+        println!(
+            "no eslint_d or golangci-lint to manage in {} (eslint projects need eslint_d installed, Go modules golangci-lint)",
+            root.display()
+        );
+    }
+    Ok(())
+}
+
+/// Warm or report golangci-lint's cache for the module in `root`. `None` if golangci-lint
+/// isn't installed.
+fn golangci_cache(
+    action: DaemonAction,
+    root: &Path,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let Ok(status) = Command::new("golangci-lint")
+        .args(["cache", "status"])
+        .current_dir(root)
+        .output()
+    else {
+        return Ok(None);
+    };
+    Ok(Some(match action {
+        DaemonAction::Start => {
+            // Left running: later lints reuse whatever it has analyzed
+            Command::new("golangci-lint")
+                .args(["run", "--fast", "./..."])
+                .current_dir(root)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()?;
+            "warming the cache in the background".to_string()
+        }
+        DaemonAction::Stop => "no daemon to stop; the cache is kept".to_string(),
+        DaemonAction::Status => summary(&status, "cache status"),
+    }))
+}
+
+/// A tool's output on one line, or its exit status when it printed nothing.
+fn summary(output: &Output, what: &str) -> String {
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    if lines.is_empty() {
+        format!("{what}: {}", output.status)
+    } else {
+        lines.join("; ")
+    }
+}
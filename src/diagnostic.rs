@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Severity reported by the linter for a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single linter finding, normalized across linters that emit structured output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// File path as reported by the linter (often relative to the project root)
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+    /// Rule or check name, e.g. `errcheck`
+    pub rule: String,
+    pub message: String,
+    pub severity: Severity,
+    /// Documentation link for the rule, if the linter provides one
+    pub url: Option<String>,
+    /// Description of the available automatic fix, if any
+    pub fix: Option<String>,
+}
+
+impl Diagnostic {
+    /// True if this diagnostic refers to `file_path`, resolving relative paths against `project_root`.
+    pub fn is_for_file(&self, file_path: &str, project_root: &str) -> bool {
+        let reported = Path::new(&self.file);
+        if reported.is_absolute() {
+            reported == Path::new(file_path)
+        } else {
+            Path::new(project_root).join(reported) == Path::new(file_path)
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(
+            f,
+            "{}:{}:{}: {severity}: {}",
+            self.file, self.line, self.column, self.message
+        )?;
+        if !self.rule.is_empty() {
+            write!(f, " ({})", self.rule)?;
+        }
+        if let Some(fix) = &self.fix {
+            write!(f, "\n  fix available: {fix}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Render diagnostics one per line for a blocking reason, ordered by file, line, column, and
+/// rule so repeated runs give identical reasons whatever order the linter reported them in.
+pub fn format_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    let mut sorted: Vec<&Diagnostic> = diagnostics.iter().collect();
+    sorted.sort_by(|a, b| {
+        (&a.file, a.line, a.column, &a.rule).cmp(&(&b.file, b.line, b.column, &b.rule))
+    });
+    sorted
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Drop diagnostics the project suppressed with an inline comment (see [`is_suppressed`]).
+/// Linters the project doesn't run itself, or that the hook picked as a fallback, may not
+/// know the project's suppression syntax. Each file is read once.
+pub fn drop_suppressed(diagnostics: Vec<Diagnostic>, project_root: &str) -> Vec<Diagnostic> {
+    let mut sources: HashMap<String, Option<String>> = HashMap::new();
+    diagnostics
+        .into_iter()
+        .filter(|d| {
+            let source = sources.entry(d.file.clone()).or_insert_with(|| {
+                let path = Path::new(project_root).join(&d.file);
+                fs::read_to_string(path).ok()
+            });
+            !source.as_deref().is_some_and(|source| {
+                is_suppressed(source, d.line, |listed| rule_matches(listed, &d.rule))
+            })
+        })
+        .collect()
+}
+
+/// [`drop_suppressed`] for diagnostics that all belong to `file_path`, however the linter
+/// spelled the path.
+pub fn drop_suppressed_in_file(diagnostics: Vec<Diagnostic>, file_path: &str) -> Vec<Diagnostic> {
+    let Ok(source) = fs::read_to_string(file_path) else {
+        return diagnostics;
+    };
+    diagnostics
+        .into_iter()
+        .filter(|d| !is_suppressed(&source, d.line, |listed| rule_matches(listed, &d.rule)))
+        .collect()
+}
+
+/// Drop `<file_path>:<line>:...` lines of plain-text linter output that the project
+/// suppressed inline. Without a rule name to compare, a listed rule must appear in the line.
+/// Returns the remaining output and how many lines were dropped.
+pub fn drop_suppressed_lines(output: &str, file_path: &str) -> (String, usize) {
+    let Ok(source) = fs::read_to_string(file_path) else {
+        return (output.to_string(), 0);
+    };
+    let mut dropped = 0;
+    let kept: Vec<&str> = output
+        .lines()
+        .filter(|line| {
+            let suppressed = text_line_number(line, file_path).is_some_and(|number| {
+                is_suppressed(&source, number, |listed| line.contains(listed))
+            });
+            dropped += usize::from(suppressed);
+            !suppressed
+        })
+        .collect();
+    (kept.join("\n"), dropped)
+}
+
+/// The line number in a `<file_path>:<line>:` diagnostic line.
+pub fn text_line_number(line: &str, file_path: &str) -> Option<u32> {
+    let rest = line.strip_prefix(file_path)?.strip_prefix(':')?;
+    rest.split(':').next()?.parse().ok()
+}
+
+/// Whether line `line` (1-based) of `source` carries an inline suppression for a rule
+/// `matches` accepts: `eslint-disable-line`/`eslint-disable-next-line` (and oxlint's),
+/// `biome-ignore`, `# noqa`, `NOLINT`/`NOLINTNEXTLINE`, `NOPMD`, or an `#[allow(...)]` or
+/// `#[expect(...)]` attribute on the lines just above. A directive without rules suppresses
+/// everything on its line.
+pub fn is_suppressed(source: &str, line: u32, matches: impl Fn(&str) -> bool) -> bool {
+    let lines: Vec<&str> = source.lines().collect();
+    let Some(index) = usize::try_from(line).ok().and_then(|l| l.checked_sub(1)) else {
+        return false;
+    };
+    let Some(current) = lines.get(index) else {
+        return false;
+    };
+    let previous = index.checked_sub(1).and_then(|i| lines.get(i));
+    let allows = |rules: Option<Vec<&str>>| {
+        rules.is_some_and(|rules| rules.is_empty() || rules.iter().any(|rule| matches(rule)))
+    };
+
+    let same_line = ["eslint-disable-line", "oxlint-disable-line"]
+        .iter()
+        .any(|directive| allows(directive_rules(current, directive)))
+        || allows(noqa_rules(current))
+        || allows(parenthesized_rules(current, "NOLINT"))
+        || current.contains("NOPMD");
+    let next_line = previous.is_some_and(|previous| {
+        ["eslint-disable-next-line", "oxlint-disable-next-line"]
+            .iter()
+            .any(|directive| allows(directive_rules(previous, directive)))
+            || allows(biome_ignore_rules(previous))
+            || allows(parenthesized_rules(previous, "NOLINTNEXTLINE"))
+    });
+    same_line || next_line || allows(rust_allowed_rules(&lines[..index]))
+}
+
+fn split_rules(list: &str) -> Vec<&str> {
+    list.split([',', ' '])
+        .map(str::trim)
+        .filter(|rule| !rule.is_empty())
+        .collect()
+}
+
+/// Rules after an eslint-style directive, up to a `--` description or the comment's end.
+fn directive_rules<'a>(line: &'a str, directive: &str) -> Option<Vec<&'a str>> {
+    let (_, rest) = line.split_once(directive)?;
+    if rest.starts_with(|c: char| c.is_alphanumeric() || c == '-') {
+        // A longer directive, e.g. `-line` in `eslint-disable-line-foo`
+        return None;
+    }
+    let rest = rest.split("--").next()?.split("*/").next()?;
+    Some(split_rules(rest))
+}
+
+/// `// biome-ignore lint/suspicious/noExplicitAny: reason`
+fn biome_ignore_rules(line: &str) -> Option<Vec<&str>> {
+    let (_, rest) = line.split_once("biome-ignore")?;
+    let rest = rest.split(':').next()?;
+    Some(split_rules(rest))
+}
+
+/// `# noqa` or `# noqa: E501, F401`
+fn noqa_rules(line: &str) -> Option<Vec<&str>> {
+    let (_, rest) = line.split_once("# noqa")?;
+    Some(rest.strip_prefix(':').map_or_else(Vec::new, split_rules))
+}
+
+/// `NOLINT` or `NOLINT(rule, other)`, and the same for `NOLINTNEXTLINE`.
+fn parenthesized_rules<'a>(line: &'a str, directive: &str) -> Option<Vec<&'a str>> {
+    let (_, rest) = line.split_once(directive)?;
+    if rest.starts_with(|c: char| c.is_alphanumeric()) {
+        return None;
+    }
+    Some(
+        rest.strip_prefix('(')
+            .and_then(|rest| rest.split_once(')'))
+            .map_or_else(Vec::new, |(list, _)| split_rules(list)),
+    )
+}
+
+/// Lints named in `#[allow(...)]`/`#[expect(...)]` attributes directly above a line.
+fn rust_allowed_rules<'a>(above: &[&'a str]) -> Option<Vec<&'a str>> {
+    let mut rules = Vec::new();
+    for line in above.iter().rev().map(|line| line.trim()) {
+        if let Some(list) = ["#[allow(", "#[expect("]
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix))
+        {
+            rules.extend(split_rules(list.split(')').next().unwrap_or_default()));
+        } else if !line.starts_with("#[") && !line.starts_with("///") {
+            break;
+        }
+    }
+    // An attribute always names at least one lint, so none found means no attribute
+    (!rules.is_empty()).then_some(rules)
+}
+
+/// Whether a rule named in a suppression comment is `rule`, allowing for a namespace the
+/// comment leaves off (`needless_return` for `clippy::needless_return`).
+fn rule_matches(listed: &str, rule: &str) -> bool {
+    !rule.is_empty()
+        && (listed == rule
+            || rule.ends_with(&format!("::{listed}"))
+            || rule.ends_with(&format!("/{listed}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(file: &str) -> Diagnostic {
+        Diagnostic {
+            file: file.to_string(),
+            line: 3,
+            column: 2,
+            rule: "errcheck".to_string(),
+            message: "Error return value is not checked".to_string(),
+            severity: Severity::Error,
+            url: None,
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn display_includes_position_and_rule() {
+        assert_eq!(
+            diagnostic("pkg/lib.go").to_string(),
+            "pkg/lib.go:3:2: error: Error return value is not checked (errcheck)"
+        );
+    }
+
+    #[test]
+    fn display_omits_empty_rule_and_shows_fix() {
+        let d = Diagnostic {
+            rule: String::new(),
+            fix: Some("Remove unused import".to_string()),
+            ..diagnostic("a.py")
+        };
+        assert_eq!(
+            d.to_string(),
+            "a.py:3:2: error: Error return value is not checked\n  fix available: Remove unused import"
+        );
+    }
+
+    #[test]
+    fn is_for_file_resolves_relative_paths() {
+        assert!(diagnostic("pkg/lib.go").is_for_file("/ws/pkg/lib.go", "/ws"));
+        assert!(!diagnostic("pkg/other.go").is_for_file("/ws/pkg/lib.go", "/ws"));
+        assert!(diagnostic("/ws/pkg/lib.go").is_for_file("/ws/pkg/lib.go", "/elsewhere"));
+    }
+
+    #[test]
+    fn format_diagnostics_one_per_line() {
+        let formatted = format_diagnostics(&[diagnostic("a.go"), diagnostic("b.go")]);
+        assert_eq!(formatted.lines().count(), 2);
+    }
+
+    #[test]
+    fn format_diagnostics_sorts_by_position_and_rule() {
+        let at = |file: &str, line: u32, column: u32, rule: &str| Diagnostic {
+            line,
+            column,
+            rule: rule.to_string(),
+            ..diagnostic(file)
+        };
+        let formatted = format_diagnostics(&[
+            at("b.go", 1, 1, "x"),
+            at("a.go", 10, 1, "x"),
+            at("a.go", 2, 5, "y"),
+            at("a.go", 2, 5, "x"),
+            at("a.go", 2, 1, "z"),
+        ]);
+        let positions: Vec<&str> = formatted
+            .lines()
+            .map(|line| line.split(": ").next().unwrap_or_default())
+            .collect();
+        assert_eq!(
+            positions,
+            ["a.go:2:1", "a.go:2:5", "a.go:2:5", "a.go:10:1", "b.go:1:1"]
+        );
+        assert!(formatted.lines().nth(1).unwrap().ends_with("(x)"));
+    }
+
+    #[test]
+    fn inline_suppressions_are_recognized() {
+        let js = "a();\n// eslint-disable-next-line no-console -- debugging\nconsole.log(1);\nfoo(); // eslint-disable-line\n// biome-ignore lint/suspicious/noExplicitAny: legacy\nlet x: any;\n";
+        let rule = |name: &'static str| move |listed: &str| rule_matches(listed, name);
+        assert!(is_suppressed(js, 3, rule("no-console")));
+        assert!(!is_suppressed(js, 3, rule("no-undef")));
+        assert!(is_suppressed(js, 4, rule("no-undef")));
+        assert!(is_suppressed(js, 6, rule("lint/suspicious/noExplicitAny")));
+        assert!(!is_suppressed(js, 1, rule("no-undef")));
+
+        let py = "import os  # noqa: F401\nimport sys  # noqa\nimport re\n";
+        assert!(is_suppressed(py, 1, rule("F401")));
+        assert!(!is_suppressed(py, 1, rule("E501")));
+        assert!(is_suppressed(py, 2, rule("E501")));
+        assert!(!is_suppressed(py, 3, rule("F401")));
+
+        let rs = "#[allow(clippy::needless_return, dead_code)]\n/// Docs\n#[inline]\nfn f() {}\nfn g() {}\n";
+        assert!(is_suppressed(rs, 4, rule("clippy::needless_return")));
+        assert!(is_suppressed(rs, 4, rule("dead_code")));
+        assert!(!is_suppressed(rs, 4, rule("clippy::unwrap_used")));
+        assert!(!is_suppressed(rs, 5, rule("dead_code")));
+
+        let cpp = "int a; // NOLINT(bugprone-foo)\n// NOLINTNEXTLINE\nint b;\n";
+        assert!(is_suppressed(cpp, 1, rule("bugprone-foo")));
+        assert!(!is_suppressed(cpp, 1, rule("other")));
+        assert!(is_suppressed(cpp, 3, rule("anything")));
+        assert!(!is_suppressed(cpp, 9, rule("anything")));
+    }
+
+    #[test]
+    fn suppressed_text_lines_are_dropped() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-suppress-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("app.py");
+        fs::write(&file, "import os  # noqa: F401\nimport re\n").unwrap();
+        let file = file.to_string_lossy();
+
+        let output = format!(
+            "************* Module app\n{file}:1:0: W0611: Unused import os (unused-import) F401\n{file}:2:0: W0611: Unused import re (unused-import)"
+        );
+        let (kept, dropped) = drop_suppressed_lines(&output, &file);
+        assert_eq!(dropped, 1);
+        assert!(kept.contains(":2:0:") && !kept.contains(":1:0:"));
+        assert_eq!(
+            text_line_number(&format!("{file}:12:3: E"), &file),
+            Some(12)
+        );
+
+        let diagnostics = drop_suppressed(
+            vec![
+                Diagnostic {
+                    rule: "F401".to_string(),
+                    line: 1,
+                    ..diagnostic("app.py")
+                },
+                Diagnostic {
+                    rule: "F401".to_string(),
+                    line: 2,
+                    ..diagnostic("app.py")
+                },
+            ],
+            &dir.to_string_lossy(),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, 2);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,71 @@
+/// Decode bytes from a hook payload or linter output without losing file paths.
+///
+/// A UTF-8 byte order mark is stripped, and UTF-16 text with a BOM (PowerShell and other Windows
+/// tools) is decoded as such. Lines that aren't valid UTF-8 are read as Latin-1 rather than
+/// having bytes replaced with U+FFFD, so a path like `café.c` written by a Latin-1 tool still
+/// matches the edited file.
+pub fn decode(bytes: &[u8]) -> String {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return decode_utf8_lines(rest);
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return decode_utf16(rest, u16::from_le_bytes);
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return decode_utf16(rest, u16::from_be_bytes);
+    }
+    decode_utf8_lines(bytes)
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| unit([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn decode_utf8_lines(bytes: &[u8]) -> String {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return text.to_string();
+    }
+    bytes
+        .split_inclusive(|&b| b == b'\n')
+        .map(|line| {
+            std::str::from_utf8(line).map_or_else(
+                |_| line.iter().copied().map(char::from).collect(),
+                str::to_string,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_utf8_bom() {
+        assert_eq!(decode(b"\xEF\xBB\xBF{\"a\":1}"), "{\"a\":1}");
+    }
+
+    #[test]
+    fn decodes_utf16_with_bom() {
+        let le: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("héllo".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        assert_eq!(decode(&le), "héllo");
+        let be: Vec<u8> = [0xFE, 0xFF]
+            .into_iter()
+            .chain("héllo".encode_utf16().flat_map(u16::to_be_bytes))
+            .collect();
+        assert_eq!(decode(&be), "héllo");
+    }
+
+    #[test]
+    fn invalid_lines_fall_back_to_latin1() {
+        let output = b"ok \xE2\x9C\x93\nsrc/caf\xE9.c:3:1: error: x\n";
+        assert_eq!(decode(output), "ok \u{2713}\nsrc/café.c:3:1: error: x\n");
+    }
+}
@@ -1,3 +1,5 @@
+use crate::json::{self, Json};
+
 /// Extract a JSON string field value by key name from raw JSON text.
 /// Searches for `"field_name":` and parses the quoted string value.
 fn extract_string_field(json: &str, field_name: &str) -> Option<String> {
@@ -47,20 +49,121 @@ pub fn extract_file_path(json: &str) -> Option<String> {
     extract_string_field(json, "file_path")
 }
 
+/// Extract every file path from a payload's `tool_input`: `file_path` at any depth (e.g. in a
+/// list of edits) and the entries of `files` arrays, given as strings or as objects with a
+/// `file_path` or `path`. Duplicates are dropped, keeping the first occurrence.
+/// Falls back to the first `file_path` if the payload isn't valid JSON.
+pub fn extract_file_paths(json: &str) -> Vec<String> {
+    let Some(payload) = json::parse(json) else {
+        return extract_file_path(json).into_iter().collect();
+    };
+    let mut paths = Vec::new();
+    if let Some(tool_input) = payload.get("tool_input") {
+        collect_file_paths(tool_input, &mut paths);
+    }
+    let mut seen = std::collections::HashSet::new();
+    paths.retain(|path| !path.is_empty() && seen.insert(path.clone()));
+    paths
+}
+
+fn collect_file_paths(value: &Json, paths: &mut Vec<String>) {
+    match value {
+        Json::Object(entries) => {
+            for (key, value) in entries {
+                match (key.as_str(), value) {
+                    ("file_path", Json::String(path)) => paths.push(path.clone()),
+                    ("files", Json::Array(files)) => {
+                        for file in files {
+                            let path = file
+                                .as_str()
+                                .or_else(|| file.get("file_path").and_then(Json::as_str))
+                                .or_else(|| file.get("path").and_then(Json::as_str));
+                            match path {
+                                Some(path) => paths.push(path.to_string()),
+                                None => collect_file_paths(file, paths),
+                            }
+                        }
+                    }
+                    _ => collect_file_paths(value, paths),
+                }
+            }
+        }
+        Json::Array(items) => {
+            for item in items {
+                collect_file_paths(item, paths);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Extract `session_id` from JSON like `{"session_id":"abc123"}`
 pub fn extract_session_id(json: &str) -> Option<String> {
     extract_string_field(json, "session_id")
 }
 
+/// Extract `hook_event_name` from JSON like `{"hook_event_name":"PostToolUse"}`
+pub fn extract_hook_event_name(json: &str) -> Option<String> {
+    extract_string_field(json, "hook_event_name")
+}
+
+/// Extract `tool_name` from JSON like `{"tool_name":"Write"}`
+pub fn extract_tool_name(json: &str) -> Option<String> {
+    extract_string_field(json, "tool_name")
+}
+
+/// Extract `source` from a `SessionStart` payload like `{"source":"startup"}`
+pub fn extract_session_source(json: &str) -> Option<String> {
+    extract_string_field(json, "source")
+}
+
+/// Extract `agent_id` from JSON like `{"agent_id":"a1b2"}`, set for a subagent's events
+pub fn extract_agent_id(json: &str) -> Option<String> {
+    extract_string_field(json, "agent_id")
+}
+
+/// Extract `cwd` from JSON like `{"cwd":"/ws/app"}`
+pub fn extract_cwd(json: &str) -> Option<String> {
+    extract_string_field(json, "cwd")
+}
+
 /// Extract `reason` from a block JSON like `{"decision":"block","reason":"..."}`
 pub fn extract_reason_field(json: &str) -> Option<String> {
     extract_string_field(json, "reason")
 }
 
+/// Extract the project `name` from a Bloop config like `{"project":{"name":"core",...}}`
+pub fn extract_bloop_project_name(json: &str) -> Option<String> {
+    extract_string_field(json, "name")
+}
+
+/// Extract the project `directory` from a Bloop config like `{"project":{"directory":"/ws/core",...}}`
+pub fn extract_bloop_project_dir(json: &str) -> Option<String> {
+    extract_string_field(json, "directory")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn file_paths_from_files_array_and_repeated_entries() {
+        let json = r#"{"tool_input":{"files":["/p/a.ts",{"file_path":"/p/b.ts"},{"path":"/p/c.ts"}],"edits":[{"file_path":"/p/d.ts"},{"file_path":"/p/a.ts"}]},"tool_response":{"file_path":"/p/e.ts"}}"#;
+        assert_eq!(
+            extract_file_paths(json),
+            vec!["/p/a.ts", "/p/b.ts", "/p/c.ts", "/p/d.ts"]
+        );
+        assert_eq!(
+            extract_file_paths(r#"{"tool_input":{"file_path":"/p/a.ts"}}"#),
+            vec!["/p/a.ts"]
+        );
+        assert!(extract_file_paths(r#"{"tool_input":{"file_path":""}}"#).is_empty());
+        assert_eq!(
+            extract_file_paths(r#"{"file_path":"/p/a.ts""#),
+            vec!["/p/a.ts"]
+        );
+    }
+
     #[test]
     fn basic_file_path() {
         let json = r#"{"tool_input":{"file_path":"/some/path.ts"}}"#;
@@ -216,4 +319,16 @@ mod tests {
         let json = r#"{"session_id":"a\/b\\c\"d"}"#;
         assert_eq!(extract_session_id(json), Some("a/b\\c\"d".to_string()));
     }
+
+    // Tests for Bloop config fields
+
+    #[test]
+    fn bloop_project_fields() {
+        let json = r#"{"version":"1.4.0","project":{"name":"core","directory":"/ws/core","platform":{"name":"jvm"}}}"#;
+        assert_eq!(extract_bloop_project_name(json), Some("core".to_string()));
+        assert_eq!(
+            extract_bloop_project_dir(json),
+            Some("/ws/core".to_string())
+        );
+    }
 }
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::UNIX_EPOCH;
+
+use crate::project::find_repo_root;
+
+/// Write the `HEAD` commit and the dirty files of the repository containing `cwd`, with their
+/// modification times, to `snapshot` for [`changed_since`] to compare against. Nothing is
+/// written outside a repository.
+pub fn record(snapshot: &Path, cwd: &Path) -> std::io::Result<()> {
+    let Some(repo) = find_repo_root(&cwd.to_string_lossy()) else {
+        return Ok(());
+    };
+    let head = git(&repo, &["rev-parse", "--verify", "-q", "HEAD"]).unwrap_or_default();
+    let mut contents = format!("{repo}\t{}\n", head.trim());
+    for (file, modified) in dirty_files(&repo) {
+        let _ = writeln!(contents, "{modified}\t{}", file.display());
+    }
+    fs::write(snapshot, contents)
+}
+
+/// A recorded snapshot: the repository, its `HEAD` then, and its dirty files.
+struct Snapshot {
+    repo: String,
+    head: String,
+    dirty: HashMap<PathBuf, u128>,
+}
+
+fn read(snapshot: &Path) -> Option<Snapshot> {
+    let contents = fs::read_to_string(snapshot).ok()?;
+    let mut lines = contents.lines();
+    let first = lines.next()?;
+    let (repo, head) = first.split_once('\t').unwrap_or((first, ""));
+    let dirty = lines
+        .filter_map(|line| {
+            let (modified, file) = line.split_once('\t')?;
+            Some((PathBuf::from(file), modified.parse().ok()?))
+        })
+        .collect();
+    Some(Snapshot {
+        repo: repo.to_string(),
+        head: head.to_string(),
+        dirty,
+    })
+}
+
+/// Files of the snapshot's repository that changed since it was recorded: those `git status`
+/// reports as changed or untracked, and those committed since, unless they were already
+/// dirty then with the same modification time. `None` without a snapshot.
+pub fn changed_since(snapshot: &Path) -> Option<Vec<PathBuf>> {
+    let Snapshot { repo, head, dirty } = read(snapshot)?;
+    let mut files = parse_porcelain(
+        &git(
+            &repo,
+            &["status", "--porcelain=v1", "-z", "--untracked-files=all"],
+        )
+        .unwrap_or_default(),
+    );
+    if !head.is_empty() {
+        if let Some(diff) = git(&repo, &["diff", "--name-only", "-z", &head]) {
+            files.extend(
+                diff.split('\0')
+                    .filter(|f| !f.is_empty())
+                    .map(str::to_string),
+            );
+        }
+    }
+    let mut changed: Vec<PathBuf> = Vec::new();
+    for file in files {
+        let file = Path::new(&repo).join(file);
+        // Deleted files are left out
+        let Some(modified) = modified_nanos(&file) else {
+            continue;
+        };
+        if dirty.get(&file) != Some(&modified) && !changed.contains(&file) {
+            changed.push(file);
+        }
+    }
+    Some(changed)
+}
+
+/// Whether `file_path` was already changed or untracked when the snapshot was recorded.
+pub fn was_dirty(snapshot: &Path, file_path: &str) -> bool {
+    read(snapshot).is_some_and(|s| s.dirty.contains_key(Path::new(file_path)))
+}
+
+/// Changed and untracked files of the repository at `repo`, with their modification times in
+/// nanoseconds. Deleted files are left out.
+fn dirty_files(repo: &str) -> Vec<(PathBuf, u128)> {
+    parse_porcelain(
+        &git(
+            repo,
+            &["status", "--porcelain=v1", "-z", "--untracked-files=all"],
+        )
+        .unwrap_or_default(),
+    )
+    .into_iter()
+    .filter_map(|file| {
+        let file = Path::new(repo).join(file);
+        let modified = modified_nanos(&file)?;
+        Some((file, modified))
+    })
+    .collect()
+}
+
+fn modified_nanos(file: &Path) -> Option<u128> {
+    let modified = fs::metadata(file).and_then(|m| m.modified()).ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+/// Output of a successful git command run in `repo`.
+fn git(repo: &str, args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+}
+
+/// Paths in `git status --porcelain=v1 -z` output. A rename or copy entry is followed by
+/// its source path, which is skipped.
+fn parse_porcelain(output: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut entries = output.split('\0');
+    while let Some(entry) = entries.next() {
+        let (Some(status), Some(file)) = (entry.get(..2), entry.get(3..)) else {
+            continue;
+        };
+        if status.contains(['R', 'C']) {
+            entries.next();
+        }
+        if !status.contains('D') {
+            files.push(file.to_string());
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn porcelain_entries() {
+        let output = " M src/a.rs\0?? new.py\0R  to.py\0from.py\0 D gone.rs\0";
+        assert_eq!(parse_porcelain(output), vec!["src/a.rs", "new.py", "to.py"]);
+    }
+
+    #[test]
+    fn changes_since_a_snapshot() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-gitstatus-{}", std::process::id()));
+        let repo = dir.join("repo");
+        fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(&repo)
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "start"]);
+        fs::write(repo.join("dirty.py"), "a = 1\n").unwrap();
+        let snapshot = dir.join("snapshot.txt");
+        record(&snapshot, &repo).unwrap();
+        assert!(was_dirty(
+            &snapshot,
+            &repo.join("dirty.py").to_string_lossy()
+        ));
+
+        fs::write(repo.join("new.py"), "b = 2\n").unwrap();
+        fs::write(repo.join("committed.py"), "c = 3\n").unwrap();
+        git(&["add", "committed.py"]);
+        git(&["commit", "-q", "-m", "add"]);
+        let mut changed = changed_since(&snapshot).unwrap();
+        changed.sort();
+        assert_eq!(
+            changed,
+            vec![repo.join("committed.py"), repo.join("new.py")]
+        );
+        assert!(changed_since(&dir.join("missing.txt")).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
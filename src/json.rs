@@ -0,0 +1,256 @@
+/// A parsed JSON value, for linters that report diagnostics as JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Self>),
+    Object(Vec<(String, Self)>),
+}
+
+impl Json {
+    /// Look up a key in an object. Returns `None` for non-objects or missing keys.
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Self::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as u32),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Self]> {
+        match self {
+            Self::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a complete JSON document. Returns `None` on malformed input or trailing garbage.
+pub fn parse(text: &str) -> Option<Json> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    (parser.pos == parser.chars.len()).then_some(value)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect_literal(&mut self, literal: &str) -> Option<()> {
+        for expected in literal.chars() {
+            if self.next()? != expected {
+                return None;
+            }
+        }
+        Some(())
+    }
+
+    fn value(&mut self) -> Option<Json> {
+        self.skip_whitespace();
+        match self.peek()? {
+            '{' => self.object(),
+            '[' => self.array(),
+            '"' => self.string().map(Json::String),
+            't' => self.expect_literal("true").map(|()| Json::Bool(true)),
+            'f' => self.expect_literal("false").map(|()| Json::Bool(false)),
+            'n' => self.expect_literal("null").map(|()| Json::Null),
+            _ => self.number(),
+        }
+    }
+
+    fn object(&mut self) -> Option<Json> {
+        self.next(); // '{'
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.peek()? == '}' {
+            self.next();
+            return Some(Json::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            if self.next()? != ':' {
+                return None;
+            }
+            entries.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => {}
+                '}' => return Some(Json::Object(entries)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn array(&mut self) -> Option<Json> {
+        self.next(); // '['
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek()? == ']' {
+            self.next();
+            return Some(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => {}
+                ']' => return Some(Json::Array(items)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        if self.next()? != '"' {
+            return None;
+        }
+        let mut result = String::new();
+        loop {
+            match self.next()? {
+                '"' => return Some(result),
+                '\\' => match self.next()? {
+                    'n' => result.push('\n'),
+                    'r' => result.push('\r'),
+                    't' => result.push('\t'),
+                    'b' => result.push('\u{8}'),
+                    'f' => result.push('\u{c}'),
+                    'u' => result.push(self.unicode_escape()?),
+                    other => result.push(other),
+                },
+                c => result.push(c),
+            }
+        }
+    }
+
+    /// Decode the hex digits after `\u`, combining UTF-16 surrogate pairs.
+    fn unicode_escape(&mut self) -> Option<char> {
+        let high = self.hex4()?;
+        if (0xD800..0xDC00).contains(&high) {
+            self.expect_literal("\\u")?;
+            let low = self.hex4()?;
+            let code = 0x10000 + ((high - 0xD800) << 10) + (low.checked_sub(0xDC00)?);
+            return char::from_u32(code);
+        }
+        Some(char::from_u32(high).unwrap_or('\u{FFFD}'))
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let mut value = 0;
+        for _ in 0..4 {
+            value = value * 16 + self.next()?.to_digit(16)?;
+        }
+        Some(value)
+    }
+
+    fn number(&mut self) -> Option<Json> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse().ok().map(Json::Number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scalars() {
+        assert_eq!(parse("null"), Some(Json::Null));
+        assert_eq!(parse(" true "), Some(Json::Bool(true)));
+        assert_eq!(parse("-1.5e2"), Some(Json::Number(-150.0)));
+        assert_eq!(parse(r#""a\"b""#), Some(Json::String("a\"b".to_string())));
+    }
+
+    #[test]
+    fn parse_nested_structures() {
+        let json =
+            parse(r#"{"Issues":[{"Pos":{"Line":3}},{"Pos":{"Line":7}}],"Report":{}}"#).unwrap();
+        let issues = json.get("Issues").and_then(Json::as_array).unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[1]
+                .get("Pos")
+                .and_then(|p| p.get("Line"))
+                .and_then(Json::as_u32),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn parse_unicode_escapes() {
+        assert_eq!(
+            parse(r#""\u00e9\ud83d\ude00""#),
+            Some(Json::String("é😀".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed() {
+        assert_eq!(parse(r#"{"a":1"#), None);
+        assert_eq!(parse("[1,]"), None);
+        assert_eq!(parse("{} trailing"), None);
+        assert_eq!(parse(""), None);
+    }
+
+    #[test]
+    fn accessors_return_none_for_wrong_types() {
+        let json = parse(r#"{"n":-1,"s":"x"}"#).unwrap();
+        assert_eq!(json.get("n").and_then(Json::as_u32), None);
+        assert_eq!(json.get("s").and_then(Json::as_array), None);
+        assert_eq!(json.get("missing"), None);
+        assert_eq!(Json::Null.get("a"), None);
+    }
+}
@@ -1,6 +1,31 @@
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::Write;
-use std::path::Path;
-use std::process::Command;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::time::{Duration, SystemTime};
+
+use crate::category::Policy;
+use crate::config::Config;
+use crate::diagnostic::{
+    Diagnostic, Severity, drop_suppressed, drop_suppressed_in_file, drop_suppressed_lines,
+    format_diagnostics, text_line_number,
+};
+use crate::encoding;
+use crate::extract::{extract_bloop_project_dir, extract_bloop_project_name, extract_reason_field};
+use crate::json::{self, Json};
+use crate::overlay;
+use crate::pipeline::{self, Pipeline};
+use crate::procgroup::{self, GroupOutput};
+use crate::project::{
+    self, Lang, find_bloop_workspace, find_cargo_workspace_root, find_gradle_settings_root,
+    find_nearest_file, find_repo_root,
+};
+use crate::quarantine;
+use crate::skip::SkipReason;
+use crate::tier;
+use crate::xml::{self, Event};
 
 pub fn run_js_lint(
     file_path: &str,
@@ -8,718 +33,7274 @@ pub fn run_js_lint(
     debug: bool,
     lenient: bool,
 ) -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load(project_root);
+    if file_path.ends_with("/package.json") {
+        return run_package_json_check(file_path, project_root, &config, debug);
+    }
+    if let Some(template) = template_language(file_path) {
+        return run_template_lint(file_path, template, project_root, &config, debug, lenient);
+    }
+
+    // Project references: dependent packages only type-check correctly under `tsc -b`
+    if !fast_tier_skips(project_root, Lang::JavaScript, "tsc") {
+        if let Some(result) = run_tsc_build(file_path, project_root, debug)? {
+            return Ok(result);
+        }
+    }
+
+    let block_on = config.path_profile(file_path).block_on;
+
+    if config.get_bool("js.project_pipeline") == Some(true) {
+        if let Some(pipeline) = pipeline::find(project_root, file_path) {
+            return run_project_pipeline(file_path, pipeline, &config, debug);
+        }
+    }
+
     // Try linters in order: oxlint, biome, eslint
     let linters: &[(&str, &[&str])] = &[
         ("oxlint", &["{{file}}"]),
-        ("biome", &["lint", "{{file}}"]),
+        ("biome", &["lint", "--reporter=json", "{{file}}"]),
         ("eslint", &["{{file}}"]),
     ];
+    let exec_dir = js_exec_dir(project_root, &config);
 
     for (linter, args) in linters {
-        let bin_path = format!("{project_root}/node_modules/.bin/{linter}");
-        if Path::new(&bin_path).exists() {
+        if let Some(bin_path) = js_linter_bin(&exec_dir, project_root, linter) {
             let mut actual_args: Vec<String> = args
                 .iter()
                 .map(|a| a.replace("{{file}}", file_path))
                 .collect();
 
+            let rules = LenientRules::from_config(&config, "js");
             if lenient {
-                match *linter {
-                    "oxlint" => {
-                        actual_args.extend([
-                            "--allow".into(),
-                            "no-unused-vars".into(),
-                            "--allow".into(),
-                            "@typescript-eslint/no-unused-vars".into(),
-                            "--allow".into(),
-                            "no-undef".into(),
-                        ]);
-                    }
-                    "biome" => {
-                        actual_args.extend([
-                            "--skip=correctness/noUnusedVariables".into(),
-                            "--skip=correctness/noUnusedImports".into(),
-                            "--skip=correctness/noUndeclaredVariables".into(),
-                        ]);
-                    }
-                    "eslint" => {
-                        actual_args.extend([
-                            "--rule".into(),
-                            "no-unused-vars: off".into(),
-                            "--rule".into(),
-                            "@typescript-eslint/no-unused-vars: off".into(),
-                            "--rule".into(),
-                            "no-undef: off".into(),
-                            "--rule".into(),
-                            "react/jsx-no-undef: off".into(),
-                        ]);
-                    }
-                    _ => {}
-                }
+                actual_args.extend(js_lenient_args(linter, &rules));
+            }
+            if block_on == Severity::Warning {
+                actual_args.extend(
+                    js_deny_warnings_args(linter)
+                        .iter()
+                        .map(|a| (*a).to_string()),
+                );
             }
 
-            let output = Command::new(&bin_path)
+            let program = if *linter == "eslint" {
+                eslint_daemon(project_root, &config).unwrap_or(bin_path)
+            } else {
+                bin_path
+            };
+            let output = Command::new(&program)
                 .args(&actual_args)
-                .current_dir(project_root)
-                .output()?;
+                .current_dir(&exec_dir)
+                .output_grouped()?;
+
+            if *linter == "biome" {
+                let stdout = encoding::decode(&output.stdout);
+                if let Some(diagnostics) = parse_biome_json(&stdout, file_path) {
+                    let diagnostics = drop_suppressed(diagnostics, &exec_dir);
+                    // Warnings are shown but only block under `block-on=warning`
+                    return Ok(diagnostics_result(
+                        linter,
+                        file_path,
+                        &diagnostics,
+                        &Policy::from_config(&config),
+                        |d| block_on == Severity::Warning || d.severity == Severity::Error,
+                        debug,
+                    ));
+                }
+            }
 
+            let mut stdout = encoding::decode(&output.stdout);
+            let mut passed = linter_passed(&config, linter, output.status);
+            if let (true, "eslint", LenientRules::Only(only)) = (lenient, *linter, &rules) {
+                let (kept, blocking) = keep_only_rules(&stdout, only);
+                stdout = kept;
+                passed |= !blocking;
+            }
             return Ok(output_lint_result(
                 linter,
                 file_path,
-                &String::from_utf8_lossy(&output.stdout),
-                &String::from_utf8_lossy(&output.stderr),
-                output.status.success(),
+                &stdout,
+                &encoding::decode(&output.stderr),
+                passed,
                 debug,
             ));
         }
     }
 
-    // Try npm run lint
-    let npm_lint = Command::new("npm")
-        .args(["run", "lint", "--if-present", "--", file_path])
-        .current_dir(project_root)
-        .output();
-
-    if let Ok(output) = npm_lint {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let combined = format!("{stdout}{stderr}");
-        if !combined.contains("Missing script") && !combined.contains("npm error") {
-            return Ok(output_lint_result(
-                "npm run lint",
-                file_path,
-                &stdout,
-                &stderr,
-                output.status.success(),
-                debug,
-            ));
-        }
+    if let Some(result) = run_npm_lint(file_path, project_root, &config, debug)? {
+        return Ok(result);
     }
 
     // No linter found
-    Ok(continue_result(
-        debug,
-        &format!("[ralph-hook-lint] no linter found for {file_path}."),
-    ))
-}
-
-pub fn run_rust_lint(
-    file_path: &str,
-    project_root: &str,
-    debug: bool,
-    lenient: bool,
-) -> Result<String, Box<dyn std::error::Error>> {
-    run_rust_lint_multi(&[file_path.to_string()], project_root, debug, lenient)
+    Ok(SkipReason::NoLinter.result(debug, &format!("no linter found for {file_path}.")))
 }
 
-/// Run clippy once and filter output for all given file paths.
-pub fn run_rust_lint_multi(
+/// Lint several files of one JavaScript/TypeScript project. When eslint is the project's
+/// linter, it checks them all in one run and its JSON report is split per file. Files that
+/// need their own handling (package.json, `tsc -b` packages, project pipelines, warnings that
+/// block) and projects using other linters go through `run_js_lint` one file at a time.
+pub fn run_js_lint_multi(
     file_paths: &[String],
     project_root: &str,
     debug: bool,
     lenient: bool,
 ) -> Result<String, Box<dyn std::error::Error>> {
-    let mut clippy_args = vec!["clippy", "--message-format=short", "--", "-D", "warnings"];
-    if lenient {
-        clippy_args.extend([
-            "-A",
-            "unused_variables",
-            "-A",
-            "unused_imports",
-            "-A",
-            "dead_code",
-        ]);
-    }
-    let output = Command::new("cargo")
-        .args(&clippy_args)
-        .current_dir(project_root)
-        .output()?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    let config = Config::load(project_root);
+    let composite = fs::read_to_string(Path::new(project_root).join("tsconfig.json"))
+        .is_ok_and(|text| is_composite_tsconfig(&text));
+    let exec_dir = js_exec_dir(project_root, &config);
+    let eslint = js_linter_bin(&exec_dir, project_root, "eslint");
+    let eslint_is_linter = ["oxlint", "biome"]
+        .iter()
+        .all(|l| js_linter_bin(&exec_dir, project_root, l).is_none())
+        && eslint.is_some();
 
-    let refs: Vec<&str> = file_paths.iter().map(String::as_str).collect();
-    let file_errors = filter_clippy_output_multi(&stdout, &stderr, &refs, project_root);
+    let (batch, single): (Vec<String>, Vec<String>) =
+        file_paths.iter().cloned().partition(|file| {
+            eslint_is_linter
+                && config.get_bool("js.project_pipeline") != Some(true)
+                && !file.ends_with("/package.json")
+                && template_language(file).is_none()
+                && !(composite
+                    && [".ts", ".tsx", ".mts", ".cts"]
+                        .iter()
+                        .any(|e| file.ends_with(e)))
+                && config.path_profile(file).block_on == Severity::Error
+        });
 
-    let label = if file_paths.len() == 1 {
-        file_paths[0].clone()
+    let mut results = single
+        .iter()
+        .map(|f| run_js_lint(f, project_root, debug, lenient))
+        .collect::<Result<Vec<_>, _>>()?;
+    if batch.len() == 1 {
+        results.push(run_js_lint(&batch[0], project_root, debug, lenient)?);
+    } else if !batch.is_empty() {
+        let eslint = eslint.unwrap_or_default();
+        let mut command = Command::new(eslint_daemon(project_root, &config).unwrap_or(eslint));
+        command.args(["--format", "json"]).args(&batch);
+        let rules = LenientRules::from_config(&config, "js");
+        let policy = Policy::from_config(&config);
+        if lenient {
+            command.args(js_lenient_args("eslint", &rules));
+        }
+        let output = command.current_dir(&exec_dir).output_grouped()?;
+        let stdout = encoding::decode(&output.stdout);
+        if let Some(reports) = parse_eslint_json(&stdout) {
+            for file in &batch {
+                let found: Vec<Diagnostic> = reports
+                    .iter()
+                    .filter(|d| d.is_for_file(file, project_root))
+                    .filter(|d| !lenient || rules.keeps(&d.rule, js_lenient_defaults("eslint")))
+                    .cloned()
+                    .collect();
+                let found = drop_suppressed(found, project_root);
+                results.push(diagnostics_result(
+                    "eslint",
+                    file,
+                    &found,
+                    &policy,
+                    |d| d.severity == Severity::Error,
+                    debug,
+                ));
+            }
+        } else {
+            // eslint crashed or rejected its config; report it once for the whole batch
+            results.push(output_lint_result(
+                "eslint",
+                &format!("{} files", batch.len()),
+                &stdout,
+                &encoding::decode(&output.stderr),
+                linter_passed(&config, "eslint", output.status),
+                debug,
+            ));
+        }
+    }
+    let linter = if eslint_is_linter {
+        "eslint"
     } else {
-        format!("{} files", file_paths.len())
+        "JS linters"
     };
-
-    if file_errors.is_empty() {
-        Ok(continue_result(
-            debug,
-            &format!("[ralph-hook-lint] lint passed for {label} using clippy."),
-        ))
-    } else {
-        Ok(format!(
-            r#"{{"decision":"block","reason":"[ralph-hook-lint] lint errors in {} using clippy:\n\n{}\n\nFix lint errors."}}"#,
-            escape_json(&label),
-            escape_json(&file_errors)
-        ))
-    }
+    Ok(combine_results(&results, file_paths, linter, debug))
 }
 
-pub fn run_python_lint(
-    file_path: &str,
-    project_root: &str,
-    debug: bool,
-    lenient: bool,
-) -> Result<String, Box<dyn std::error::Error>> {
-    // Try linters in order of speed: ruff (fastest), mypy, pylint, flake8
-    let linters: &[(&str, &[&str])] = &[
-        ("ruff", &["check", "--output-format=concise", "{{file}}"]),
-        ("mypy", &["{{file}}"]),
-        ("pylint", &["--output-format=text", "{{file}}"]),
-        ("flake8", &["{{file}}"]),
-    ];
-
-    // Check for virtual environment paths first, then system paths
-    let venv_dirs = [".venv/bin", "venv/bin", ".env/bin", "env/bin"];
-
-    for (linter, args) in linters {
-        // Try virtual environment first
-        let mut bin_path: Option<String> = None;
-
-        for venv_dir in &venv_dirs {
-            let venv_path = format!("{project_root}/{venv_dir}/{linter}");
-            if Path::new(&venv_path).exists() {
-                bin_path = Some(venv_path);
-                break;
-            }
-        }
-
-        // Fall back to system PATH
-        if bin_path.is_none() {
-            if let Ok(output) = Command::new("which").arg(linter).output() {
-                if output.status.success() {
-                    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                    if !path.is_empty() {
-                        bin_path = Some(path);
-                    }
-                }
-            }
-        }
+/// The directory JS linters run from, per `[js] run_from`: `package` (the default) is the
+/// nearest package.json's directory, `workspace` the outermost directory above it declaring
+/// workspaces (`"workspaces"` in package.json, or pnpm-workspace.yaml), and `root` the
+/// repository root. Monorepos whose eslint config resolves plugins from the top need one of
+/// the latter; the file is passed by absolute path either way.
+fn js_exec_dir(project_root: &str, config: &Config) -> String {
+    let dir = match config.get_str("js.run_from") {
+        Some("workspace") => Path::new(project_root)
+            .ancestors()
+            .filter(|dir| {
+                dir.join("pnpm-workspace.yaml").exists()
+                    || fs::read_to_string(dir.join("package.json"))
+                        .is_ok_and(|text| text.contains("\"workspaces\""))
+            })
+            .last()
+            .map(|dir| dir.to_string_lossy().to_string()),
+        Some("root") => find_repo_root(project_root),
+        _ => None,
+    };
+    dir.unwrap_or_else(|| project_root.to_string())
+}
 
-        if let Some(bin) = bin_path {
-            let mut actual_args: Vec<String> = args
-                .iter()
-                .map(|a| a.replace("{{file}}", file_path))
-                .collect();
+/// A JS linter installed in `exec_dir`'s `node_modules/.bin`, else in the package's.
+fn js_linter_bin(exec_dir: &str, project_root: &str, linter: &str) -> Option<String> {
+    [exec_dir, project_root]
+        .iter()
+        .map(|dir| format!("{dir}/node_modules/.bin/{linter}"))
+        .find(|bin| Path::new(bin).exists())
+}
 
-            if lenient {
-                match *linter {
-                    "ruff" => {
-                        actual_args.extend(["--ignore".into(), "F841,F401,F821".into()]);
-                    }
-                    "pylint" => {
-                        actual_args.extend(["--disable=W0611,W0612,E0602".into()]);
-                    }
-                    "flake8" => {
-                        actual_args.extend(["--extend-ignore=F841,F401,F821".into()]);
-                    }
-                    _ => {} // mypy doesn't check unused vars
-                }
-            }
+/// `eslint_d`, when the project's eslint can be run through it: it is installed in
+/// `node_modules/.bin` or on PATH, and `[js] daemon` isn't false. It takes eslint's arguments
+/// and keeps the project's eslint loaded in a background server, started on first use.
+pub fn eslint_daemon(project_root: &str, config: &Config) -> Option<String> {
+    if config.get_bool("js.daemon") == Some(false) {
+        return None;
+    }
+    find_node_bin(project_root, "eslint_d")
+        .or_else(|| is_executable_available("eslint_d").then(|| "eslint_d".to_string()))
+}
 
-            let output = Command::new(&bin)
-                .args(&actual_args)
-                .current_dir(project_root)
-                .output()?;
+/// Template languages of JS frameworks, which oxlint and biome can't parse
+const TEMPLATE_LANGUAGES: [&str; 4] = ["astro", "marko", "ejs", "vue"];
 
-            return Ok(output_lint_result(
-                linter,
-                file_path,
-                &String::from_utf8_lossy(&output.stdout),
-                &String::from_utf8_lossy(&output.stderr),
-                output.status.success(),
-                debug,
-            ));
-        }
-    }
+/// The template language of an Astro, Marko, EJS, or Vue file.
+fn template_language(file_path: &str) -> Option<&'static str> {
+    let extension = Path::new(file_path).extension()?.to_str()?;
+    TEMPLATE_LANGUAGES.into_iter().find(|t| *t == extension)
+}
 
-    // No linter found
-    Ok(continue_result(
-        debug,
-        &format!(
-            "[ralph-hook-lint] no Python linter found for {file_path}. Install ruff for best performance: pip install ruff"
-        ),
-    ))
+/// The `dependencies` and `devDependencies` of the package at `project_root`.
+fn package_dependencies(project_root: &str) -> Vec<String> {
+    let Some(package) = fs::read_to_string(Path::new(project_root).join("package.json"))
+        .ok()
+        .and_then(|text| json::parse(&text))
+    else {
+        return Vec::new();
+    };
+    ["dependencies", "devDependencies"]
+        .iter()
+        .filter_map(|field| match package.get(field) {
+            Some(Json::Object(entries)) => Some(entries.iter().map(|(name, _)| name.clone())),
+            _ => None,
+        })
+        .flatten()
+        .collect()
 }
 
-pub fn run_java_lint(
+/// Lint an Astro, Marko, EJS, or Vue template with the tool the project has set up for it:
+/// eslint when the package depends on an eslint plugin for the language (`eslint-plugin-astro`,
+/// `eslint-plugin-vue`, ...), else `astro check` when it depends on `@astrojs/check`,
+/// `vue-tsc` when installed, or `ejslint` when installed. Without one the file is skipped
+/// rather than handed to a linter that can't parse it.
+fn run_template_lint(
     file_path: &str,
+    template: &str,
     project_root: &str,
+    config: &Config,
     debug: bool,
     lenient: bool,
 ) -> Result<String, Box<dyn std::error::Error>> {
-    // PMD/SpotBugs don't support clean CLI-level rule suppression
-    let _ = lenient;
-    // Detect build tool: Maven or Gradle
-    let pom_path = Path::new(project_root).join("pom.xml");
-    let gradle_path = Path::new(project_root).join("build.gradle");
-    let gradle_kts_path = Path::new(project_root).join("build.gradle.kts");
-
-    // Linters to try in order: pmd (fast), spotbugs (thorough)
-    let maven_linters: &[(&str, &[&str], &str)] = &[
-        (
-            "pmd:check",
-            &["pmd:check", "-q"],
-            "No plugin found for prefix 'pmd'",
-        ),
-        (
-            "spotbugs:check",
-            &["spotbugs:check", "-q"],
-            "No plugin found for prefix 'spotbugs'",
-        ),
-    ];
-
-    let gradle_linters: &[(&str, &str)] = &[
-        ("pmdMain", "Task 'pmdMain' not found"),
-        ("spotbugsMain", "Task 'spotbugsMain' not found"),
-    ];
-
-    if pom_path.exists() {
-        for (name, args, not_found_msg) in maven_linters {
-            let output = Command::new("mvn")
-                .args(*args)
-                .current_dir(project_root)
-                .output()?;
-
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
-
-            // Check if plugin exists
-            if stderr.contains("Unknown lifecycle phase") || stderr.contains(not_found_msg) {
-                continue;
-            }
+    let exec_dir = js_exec_dir(project_root, config);
+    let dependencies = package_dependencies(project_root);
+    let eslint_plugin = dependencies
+        .iter()
+        .any(|name| name.contains("eslint-plugin") && name.contains(template));
 
-            return Ok(output_lint_result(
-                &format!("mvn {name}"),
-                file_path,
-                &stdout,
-                &stderr,
-                output.status.success(),
-                debug,
+    if let Some(eslint) = js_linter_bin(&exec_dir, project_root, "eslint").filter(|_| eslint_plugin)
+    {
+        let mut command = Command::new(eslint);
+        command.arg(file_path);
+        if lenient {
+            command.args(js_lenient_args(
+                "eslint",
+                &LenientRules::from_config(config, "js"),
             ));
         }
-
-        return Ok(continue_result(
+        let output = command.current_dir(&exec_dir).output_grouped()?;
+        return Ok(output_lint_result(
+            "eslint",
+            file_path,
+            &encoding::decode(&output.stdout),
+            &encoding::decode(&output.stderr),
+            linter_passed(config, "eslint", output.status),
             debug,
-            &format!(
-                "[ralph-hook-lint] no Java linter configured for {file_path}. Add maven-pmd-plugin or spotbugs-maven-plugin to pom.xml."
-            ),
         ));
     }
 
-    if gradle_path.exists() || gradle_kts_path.exists() {
-        let gradle_cmd = if Path::new(project_root).join("gradlew").exists() {
-            "./gradlew"
-        } else {
-            "gradle"
-        };
-
-        for (task, not_found_msg) in gradle_linters {
-            let output = Command::new(gradle_cmd)
-                .args([*task, "-q"])
-                .current_dir(project_root)
-                .output()?;
-
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
-
-            // Check if task exists
-            if stderr.contains(not_found_msg) {
-                continue;
-            }
-
+    let astro = js_linter_bin(&exec_dir, project_root, "astro")
+        .filter(|_| template == "astro" && dependencies.iter().any(|d| d == "@astrojs/check"));
+    if let Some(astro) = astro {
+        // Checks the whole project; only the edited file's diagnostics are reported
+        let output = Command::new(astro)
+            .arg("check")
+            .env("NO_COLOR", "1")
+            .current_dir(project_root)
+            .output_grouped()?;
+        let stdout = encoding::decode(&output.stdout);
+        let diagnostics = parse_astro_check(&stdout);
+        if !output.status.success() && diagnostics.is_empty() {
             return Ok(output_lint_result(
-                &format!("{gradle_cmd} {task}"),
+                "astro check",
                 file_path,
                 &stdout,
-                &stderr,
-                output.status.success(),
+                &encoding::decode(&output.stderr),
+                linter_passed(config, "astro", output.status),
                 debug,
             ));
         }
+        let diagnostics: Vec<Diagnostic> = diagnostics
+            .into_iter()
+            .filter(|d| d.is_for_file(file_path, project_root))
+            .collect();
+        return Ok(diagnostics_result(
+            "astro check",
+            file_path,
+            &diagnostics,
+            &Policy::from_config(config),
+            |d| d.severity == Severity::Error,
+            debug,
+        ));
+    }
 
-        return Ok(continue_result(
+    let vue_tsc = js_linter_bin(&exec_dir, project_root, "vue-tsc").filter(|_| template == "vue");
+    if let Some(vue_tsc) = vue_tsc {
+        return run_vue_tsc(&vue_tsc, file_path, project_root, config, debug);
+    }
+
+    let ejslint = js_linter_bin(&exec_dir, project_root, "ejslint").filter(|_| template == "ejs");
+    if let Some(ejslint) = ejslint {
+        let output = Command::new(ejslint)
+            .arg(file_path)
+            .current_dir(&exec_dir)
+            .output_grouped()?;
+        return Ok(output_lint_result(
+            "ejslint",
+            file_path,
+            &encoding::decode(&output.stdout),
+            &encoding::decode(&output.stderr),
+            linter_passed(config, "ejslint", output.status),
             debug,
-            &format!(
-                "[ralph-hook-lint] no Java linter configured for {file_path}. Add pmd or spotbugs plugin to build.gradle."
-            ),
         ));
     }
 
-    // No build tool found
-    Ok(continue_result(
+    let setup = match template {
+        "astro" => "Add eslint-plugin-astro, or @astrojs/check for astro check",
+        "marko" => "Add an eslint plugin for Marko",
+        "vue" => "Add eslint-plugin-vue, or vue-tsc",
+        _ => "Add eslint-plugin-ejs, or ejs-lint for ejslint",
+    };
+    Ok(SkipReason::NoLinter.result(
         debug,
-        &format!(
-            "[ralph-hook-lint] no Java build tool found for {file_path}. Add pom.xml or build.gradle."
-        ),
+        &format!("no {template} linter configured for {file_path}. {setup}"),
     ))
 }
 
-pub fn run_go_lint(
+/// Type-check the project with `vue-tsc --noEmit`, which checks templates as well as scripts.
+fn run_vue_tsc(
+    vue_tsc: &str,
     file_path: &str,
     project_root: &str,
+    config: &Config,
     debug: bool,
-    lenient: bool,
 ) -> Result<String, Box<dyn std::error::Error>> {
-    // Try linters in order: golangci-lint (comprehensive), staticcheck, go vet
-    let linters: &[(&str, &[&str])] = &[
-        ("golangci-lint", &["run", "--fast", "{{file}}"]),
-        ("staticcheck", &["{{file}}"]),
-    ];
+    // Checks the whole project; only the edited file's diagnostics are reported
+    let output = Command::new(vue_tsc)
+        .args(["--noEmit", "--pretty", "false"])
+        .current_dir(project_root)
+        .output_grouped()?;
+    let stdout = encoding::decode(&output.stdout);
+    let diagnostics = filter_tsc_output(&stdout, file_path, project_root);
+    if !output.status.success() && !stdout.contains(": error TS") {
+        return Ok(output_lint_result(
+            "vue-tsc",
+            file_path,
+            &stdout,
+            &encoding::decode(&output.stderr),
+            linter_passed(config, "vue-tsc", output.status),
+            debug,
+        ));
+    }
+    Ok(output_lint_result(
+        "vue-tsc",
+        file_path,
+        &diagnostics,
+        "",
+        diagnostics.is_empty(),
+        debug,
+    ))
+}
 
-    for (linter, args) in linters {
-        // Check if linter exists in PATH
-        if let Ok(output) = Command::new("which").arg(linter).output() {
-            if output.status.success() {
-                let mut actual_args: Vec<String> = args
-                    .iter()
-                    .map(|a| a.replace("{{file}}", file_path))
-                    .collect();
+/// Parse `astro check` diagnostics: `src/pages/index.astro:3:7 - error ts(2322): Type 'number'
+/// is not assignable to type 'string'.`, each followed by a code excerpt, which is skipped.
+fn parse_astro_check(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (location, rest) = line.split_once(" - ")?;
+            let mut parts = location.rsplitn(3, ':');
+            let column = parts.next()?.parse().ok()?;
+            let line_number = parts.next()?.parse().ok()?;
+            let file = parts.next()?;
+            let (severity, rest) = rest.split_once(' ')?;
+            let (rule, message) = rest.split_once(": ")?;
+            Some(Diagnostic {
+                file: file.to_string(),
+                line: line_number,
+                column,
+                rule: rule.to_string(),
+                message: message.to_string(),
+                severity: match severity {
+                    "error" => Severity::Error,
+                    "warning" => Severity::Warning,
+                    _ => return None,
+                },
+                url: None,
+                fix: None,
+            })
+        })
+        .collect()
+}
 
-                if lenient && *linter == "golangci-lint" {
-                    actual_args.push("--disable=unused".into());
-                }
+/// Parse eslint `--format json` output (one report per file) into diagnostics.
+fn parse_eslint_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let reports = json::parse(stdout.trim())?;
+    let mut diagnostics = Vec::new();
+    for report in reports.as_array()? {
+        let file = report.get("filePath")?.as_str()?;
+        for message in report.get("messages")?.as_array()? {
+            diagnostics.push(Diagnostic {
+                file: file.to_string(),
+                line: message.get("line").and_then(Json::as_u32).unwrap_or(0),
+                column: message.get("column").and_then(Json::as_u32).unwrap_or(0),
+                // Parse errors have a null ruleId
+                rule: message
+                    .get("ruleId")
+                    .and_then(Json::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                message: message.get("message")?.as_str()?.to_string(),
+                severity: if message.get("severity").and_then(Json::as_u32) == Some(2) {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                },
+                url: None,
+                fix: None,
+            });
+        }
+    }
+    Some(diagnostics)
+}
 
-                let output = Command::new(linter)
-                    .args(&actual_args)
-                    .current_dir(project_root)
-                    .output()?;
+/// Validate an edited package.json so a corrupted manifest blocks right away instead of
+/// breaking every later npm command. With `[js] package_json_deep_check = true`, also try
+/// `npm install --dry-run` and, when there is a tsconfig.json, `tsc --showConfig`.
+fn run_package_json_check(
+    file_path: &str,
+    project_root: &str,
+    config: &Config,
+    debug: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let text = fs::read_to_string(file_path)?;
+    let problems = json::parse(&text).map_or_else(
+        || vec!["package.json is not valid JSON".to_string()],
+        |package| package_json_problems(&package),
+    );
+    if !problems.is_empty() {
+        return Ok(output_lint_result(
+            "package.json validation",
+            file_path,
+            &problems.join("\n"),
+            "",
+            false,
+            debug,
+        ));
+    }
 
-                return Ok(output_lint_result(
-                    linter,
-                    file_path,
-                    &String::from_utf8_lossy(&output.stdout),
-                    &String::from_utf8_lossy(&output.stderr),
-                    output.status.success(),
-                    debug,
-                ));
+    let mut checks: Vec<(&str, Command)> = Vec::new();
+    let mut npm_pkg = Command::new("npm");
+    npm_pkg.args(["pkg", "get", "name", "version"]);
+    checks.push(("npm pkg get", npm_pkg));
+    if config.get_bool("js.package_json_deep_check") == Some(true) {
+        let mut install = Command::new("npm");
+        install.args([
+            "install",
+            "--dry-run",
+            "--ignore-scripts",
+            "--no-audit",
+            "--no-fund",
+        ]);
+        checks.push(("npm install --dry-run", install));
+        if Path::new(project_root).join("tsconfig.json").is_file() {
+            if let Some(tsc) = find_node_bin(project_root, "tsc") {
+                let mut show_config = Command::new(tsc);
+                show_config.arg("--showConfig");
+                checks.push(("tsc --showConfig", show_config));
             }
         }
     }
 
-    // Fallback to go vet (always available with Go installation)
-    if let Ok(output) = Command::new("which").arg("go").output() {
-        if output.status.success() {
-            let output = Command::new("go")
-                .args(["vet", file_path])
-                .current_dir(project_root)
-                .output()?;
-
+    let timeout = npm_lint_timeout(config);
+    for (label, mut command) in checks {
+        let Some(output) = command
+            .current_dir(project_root)
+            .output_grouped_timeout(timeout)?
+        else {
+            continue;
+        };
+        if !output.status.success() {
             return Ok(output_lint_result(
-                "go vet",
+                label,
                 file_path,
-                &String::from_utf8_lossy(&output.stdout),
-                &String::from_utf8_lossy(&output.stderr),
-                output.status.success(),
+                &encoding::decode(&output.stdout),
+                &encoding::decode(&output.stderr),
+                false,
                 debug,
             ));
         }
     }
-
-    // No linter found
-    Ok(continue_result(
+    Ok(output_lint_result(
+        "package.json validation",
+        file_path,
+        "",
+        "",
+        true,
         debug,
-        &format!(
-            "[ralph-hook-lint] no Go linter found for {file_path}. Install golangci-lint for best results: https://golangci-lint.run"
-        ),
     ))
 }
 
-fn filter_clippy_output_multi(
-    stdout: &str,
-    stderr: &str,
-    file_paths: &[&str],
-    project_root: &str,
-) -> String {
-    let combined = format!("{stderr}\n{stdout}");
+/// Structural problems npm would choke on: a non-object root, a non-string name or version,
+/// and dependency or script tables that aren't string-to-string objects.
+fn package_json_problems(package: &Json) -> Vec<String> {
+    if !matches!(package, Json::Object(_)) {
+        return vec!["package.json must contain a JSON object".to_string()];
+    }
+    let mut problems = Vec::new();
+    for field in ["name", "version"] {
+        if package.get(field).is_some_and(|v| v.as_str().is_none()) {
+            problems.push(format!("\"{field}\" must be a string"));
+        }
+    }
+    for field in [
+        "scripts",
+        "dependencies",
+        "devDependencies",
+        "peerDependencies",
+        "optionalDependencies",
+    ] {
+        match package.get(field) {
+            None => {}
+            Some(Json::Object(entries)) => {
+                for (key, value) in entries {
+                    if value.as_str().is_none() {
+                        problems.push(format!("\"{field}.{key}\" must be a string"));
+                    }
+                }
+            }
+            Some(_) => problems.push(format!("\"{field}\" must be an object")),
+        }
+    }
+    problems
+}
 
-    // Clippy outputs paths relative to the project root (e.g. "src/lib.rs:10:5").
-    // Absolute paths from the caller rarely match, so we also build relative paths
-    // by stripping the project_root prefix.  Bare filenames are kept as a last-resort
-    // fallback for unusual path formats.
-    let prefix = if project_root.ends_with('/') {
-        project_root.to_string()
-    } else {
-        format!("{project_root}/")
+/// Default time limit for the `npm run lint` fallback, which is often much slower than a linter.
+const NPM_LINT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Run the project's `lint` script on the file when `[js] npm_lint = true`.
+///
+/// Off by default: the script is arbitrary project code, and it may well be a full build.
+fn run_npm_lint(
+    file_path: &str,
+    project_root: &str,
+    config: &Config,
+    debug: bool,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let Some(script) = npm_lint_script(project_root) else {
+        return Ok(None);
+    };
+    if config.get_bool("js.npm_lint") != Some(true) {
+        return Ok(Some(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "skipping npm run lint for {file_path}: set `[js] npm_lint = true` to enable."
+            ),
+        )));
+    }
+    if is_build_script(&script) {
+        return Ok(Some(SkipReason::NoLinter.result(
+            debug,
+            &format!("skipping npm run lint for {file_path}: the script runs a build ({script})."),
+        )));
+    }
+
+    let timeout = npm_lint_timeout(config);
+    let output = Command::new("npm")
+        .args(["run", "lint", "--", file_path])
+        .current_dir(project_root)
+        .output_grouped_timeout(timeout)?;
+    let Some(output) = output else {
+        return Ok(Some(continue_result(
+            debug,
+            &format!(
+                "[ralph-hook-lint] npm run lint timed out after {}s for {file_path}.",
+                timeout.as_secs()
+            ),
+        )));
     };
+    Ok(Some(output_lint_result(
+        "npm run lint",
+        file_path,
+        &encoding::decode(&output.stdout),
+        &encoding::decode(&output.stderr),
+        linter_passed(config, "npm", output.status),
+        debug,
+    )))
+}
 
-    let relative_paths: Vec<&str> = file_paths
+/// Run the project's own lint pipeline, stopping at the first failing command.
+fn run_project_pipeline(
+    file_path: &str,
+    pipeline: Pipeline,
+    config: &Config,
+    debug: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let timeout = npm_lint_timeout(config);
+    for mut command in pipeline.commands {
+        let Some(output) = command.output_grouped_timeout(timeout)? else {
+            return Ok(continue_result(
+                debug,
+                &format!(
+                    "[ralph-hook-lint] {} timed out after {}s for {file_path}.",
+                    pipeline.label,
+                    timeout.as_secs()
+                ),
+            ));
+        };
+        if !output.status.success() {
+            return Ok(output_lint_result(
+                pipeline.label,
+                file_path,
+                &encoding::decode(&output.stdout),
+                &encoding::decode(&output.stderr),
+                false,
+                debug,
+            ));
+        }
+    }
+    Ok(output_lint_result(
+        pipeline.label,
+        file_path,
+        "",
+        "",
+        true,
+        debug,
+    ))
+}
+
+/// Time limit for project-defined lint scripts, from `[js] npm_lint_timeout_secs`.
+fn npm_lint_timeout(config: &Config) -> Duration {
+    config
+        .get_int("js.npm_lint_timeout_secs")
+        .and_then(|secs| u64::try_from(secs).ok())
+        .map_or(NPM_LINT_TIMEOUT, Duration::from_secs)
+}
+
+/// The `lint` script declared in the project's package.json.
+fn npm_lint_script(project_root: &str) -> Option<String> {
+    let text = fs::read_to_string(Path::new(project_root).join("package.json")).ok()?;
+    let package = json::parse(&text)?;
+    let script = package.get("scripts")?.get("lint")?.as_str()?;
+    Some(script.to_string())
+}
+
+/// Whether a package.json script compiles or bundles the project rather than just linting.
+fn is_build_script(script: &str) -> bool {
+    const BUNDLERS: &[&str] = &["webpack", "rollup", "esbuild", "parcel"];
+    script
+        .split(['&', '|', ';'])
+        .map(|command| {
+            command
+                .split_whitespace()
+                .skip_while(|w| *w == "npx" || *w == "pnpm" || *w == "yarn")
+                .collect::<Vec<_>>()
+        })
+        .any(|words| {
+            // `npm run build`, `next build`, `vite build`, `run-s build:*`
+            words
+                .iter()
+                .any(|w| *w == "build" || w.starts_with("build:"))
+                || words.first().is_some_and(|w| BUNDLERS.contains(w))
+                || (words.first() == Some(&"tsc") && !words.contains(&"--noEmit"))
+        })
+}
+
+/// Rules a JS linter ignores in lenient mode unless `[lenient.js]` says otherwise: unused
+/// variables and imports, and undefined names.
+fn js_lenient_defaults(linter: &str) -> &'static [&'static str] {
+    match linter {
+        "oxlint" => &[
+            "no-unused-vars",
+            "@typescript-eslint/no-unused-vars",
+            "no-undef",
+        ],
+        "biome" => &[
+            "correctness/noUnusedVariables",
+            "correctness/noUnusedImports",
+            "correctness/noUndeclaredVariables",
+        ],
+        "eslint" => &[
+            "no-unused-vars",
+            "@typescript-eslint/no-unused-vars",
+            "no-undef",
+            "react/jsx-no-undef",
+        ],
+        _ => &[],
+    }
+}
+
+/// Flags that apply lenient-mode rules to a JS linter. eslint has no flag to block on some
+/// rules only, so its `only` list is applied to the output instead.
+fn js_lenient_args(linter: &str, rules: &LenientRules) -> Vec<String> {
+    let mut args = Vec::new();
+    match (linter, rules) {
+        ("oxlint", LenientRules::Only(only)) => {
+            args.extend(["-A".to_string(), "all".to_string()]);
+            for rule in only {
+                args.extend(["-D".to_string(), rule.clone()]);
+            }
+        }
+        ("biome", LenientRules::Only(only)) => {
+            args.extend(only.iter().map(|rule| format!("--only={rule}")));
+        }
+        ("oxlint", _) => {
+            for rule in rules.allowed(js_lenient_defaults(linter)) {
+                args.extend(["--allow".to_string(), rule.to_string()]);
+            }
+        }
+        ("biome", _) => {
+            args.extend(
+                rules
+                    .allowed(js_lenient_defaults(linter))
+                    .iter()
+                    .map(|rule| format!("--skip={rule}")),
+            );
+        }
+        ("eslint", LenientRules::Allow(_)) => {
+            for rule in rules.allowed(js_lenient_defaults(linter)) {
+                args.extend(["--rule".to_string(), format!("{rule}: off")]);
+            }
+        }
+        _ => {}
+    }
+    args
+}
+
+/// Flags that make a JS linter exit non-zero on warnings.
+fn js_deny_warnings_args(linter: &str) -> &'static [&'static str] {
+    match linter {
+        "oxlint" => &["--deny-warnings"],
+        "eslint" => &["--max-warnings=0"],
+        _ => &[],
+    }
+}
+
+/// Parse biome `--reporter=json` output into diagnostics. Biome reports byte spans, so
+/// positions are resolved against the diagnostic's source code or the file on disk.
+/// Returns `None` if the output isn't a biome JSON report.
+fn parse_biome_json(stdout: &str, file_path: &str) -> Option<Vec<Diagnostic>> {
+    // Biome may print notices before the report
+    let report = json::parse(&stdout[stdout.find('{')?..])?;
+    let diagnostics = report.get("diagnostics")?.as_array()?;
+    let file_contents = fs::read_to_string(file_path).ok();
+
+    Some(
+        diagnostics
+            .iter()
+            .filter_map(|diagnostic| {
+                let location = diagnostic.get("location")?;
+                let file = location
+                    .get("path")
+                    .and_then(|p| p.get("file").or(Some(p)))
+                    .and_then(Json::as_str)
+                    .unwrap_or(file_path)
+                    .to_string();
+
+                let (line, column) = biome_position(location, file_contents.as_deref());
+
+                Some(Diagnostic {
+                    file,
+                    line,
+                    column,
+                    rule: diagnostic
+                        .get("category")
+                        .and_then(Json::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    message: diagnostic.get("description")?.as_str()?.to_string(),
+                    severity: match diagnostic.get("severity").and_then(Json::as_str) {
+                        Some("error" | "fatal") => Severity::Error,
+                        _ => Severity::Warning,
+                    },
+                    url: None,
+                    fix: None,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Line and column of a biome diagnostic: newer versions report `start`, older ones a byte
+/// `span` into `sourceCode` (or the file on disk when the source isn't included).
+fn biome_position(location: &Json, file_contents: Option<&str>) -> (u32, u32) {
+    if let Some(start) = location.get("start") {
+        return (
+            start.get("line").and_then(Json::as_u32).unwrap_or(0),
+            start.get("column").and_then(Json::as_u32).unwrap_or(0),
+        );
+    }
+    let offset = location
+        .get("span")
+        .and_then(Json::as_array)
+        .and_then(|span| span.first())
+        .and_then(Json::as_u32)
+        .unwrap_or(0);
+    let source = location
+        .get("sourceCode")
+        .and_then(Json::as_str)
+        .or(file_contents)
+        .unwrap_or_default();
+    line_column_at(source, offset as usize)
+}
+
+/// 1-based line and column of a byte offset in `source`.
+fn line_column_at(source: &str, offset: usize) -> (u32, u32) {
+    let before = &source.as_bytes()[..offset.min(source.len())];
+    let line = before.split(|&b| b == b'\n').count();
+    let line_start = before
         .iter()
-        .filter_map(|fp| fp.strip_prefix(&prefix))
-        .collect();
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let column = String::from_utf8_lossy(&before[line_start..])
+        .chars()
+        .count()
+        + 1;
+    (
+        u32::try_from(line).unwrap_or(u32::MAX),
+        u32::try_from(column).unwrap_or(u32::MAX),
+    )
+}
 
-    let file_names: Vec<&str> = file_paths
+/// Whether a pass by `linter` for `file_path` depends on more than the file itself:
+/// compilers and type checkers see the whole project, so an edit elsewhere can break a file
+/// they passed. Such passes must not let an unchanged file skip a later run.
+pub fn checks_whole_project(lang: Lang, linter: &str, file_path: &str, project_root: &str) -> bool {
+    const TYPE_CHECKERS: [&str; 5] = ["tsc", "vue-tsc", "mypy", "phpstan", "psalm"];
+    let compiled = matches!(
+        lang,
+        Lang::Rust
+            | Lang::Java
+            | Lang::Kotlin
+            | Lang::Scala
+            | Lang::CSharp
+            | Lang::Go
+            | Lang::Haskell
+            | Lang::Elixir
+            | Lang::Solidity
+            | Lang::C
+    );
+    let type_checked = linter
+        .split_whitespace()
+        .next()
+        .is_some_and(|program| TYPE_CHECKERS.contains(&program));
+    // A composite package is type-checked with `tsc -b` before its linter runs.
+    let tsc_build = lang == Lang::JavaScript
+        && [".ts", ".tsx", ".mts", ".cts"]
+            .iter()
+            .any(|ext| file_path.ends_with(ext))
+        && fs::read_to_string(Path::new(project_root).join("tsconfig.json"))
+            .is_ok_and(|text| is_composite_tsconfig(&text));
+    compiled || type_checked || tsc_build
+}
+
+/// Run an incremental `tsc -b` for a composite TypeScript package and report diagnostics for
+/// `file_path`. Returns `None` (fall through to linters) if the package doesn't use project
+/// references, tsc isn't installed, or the file has no type errors.
+fn run_tsc_build(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    if ![".ts", ".tsx", ".mts", ".cts"]
         .iter()
-        .map(|fp| {
-            Path::new(fp)
-                .file_name()
-                .map_or(*fp, |n| n.to_str().unwrap_or(fp))
+        .any(|ext| file_path.ends_with(ext))
+    {
+        return Ok(None);
+    }
+    let tsconfig = Path::new(project_root).join("tsconfig.json");
+    let Ok(tsconfig_text) = fs::read_to_string(&tsconfig) else {
+        return Ok(None);
+    };
+    if !is_composite_tsconfig(&tsconfig_text) {
+        return Ok(None);
+    }
+    let Some(tsc) = find_node_bin(project_root, "tsc") else {
+        return Ok(None);
+    };
+
+    let output = Command::new(&tsc)
+        .arg("-b")
+        .arg(&tsconfig)
+        .args(["--pretty", "false"])
+        .current_dir(project_root)
+        .output_grouped()?;
+    if output.status.success() {
+        return Ok(None);
+    }
+
+    let diagnostics = filter_tsc_output(&encoding::decode(&output.stdout), file_path, project_root);
+    if diagnostics.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(output_lint_result(
+        "tsc -b",
+        file_path,
+        &diagnostics,
+        "",
+        false,
+        debug,
+    )))
+}
+
+/// True if a tsconfig declares `"composite": true` or has project `"references"`.
+fn is_composite_tsconfig(text: &str) -> bool {
+    let composite = text.find("\"composite\"").is_some_and(|i| {
+        text[i + "\"composite\"".len()..]
+            .trim_start()
+            .strip_prefix(':')
+            .is_some_and(|rest| rest.trim_start().starts_with("true"))
+    });
+    composite || text.contains("\"references\"")
+}
+
+/// Find `node_modules/.bin/<name>` at or above `project_root` (monorepos hoist to the root).
+fn find_node_bin(project_root: &str, name: &str) -> Option<String> {
+    let mut current = Path::new(project_root);
+    loop {
+        let bin = current.join("node_modules/.bin").join(name);
+        if bin.exists() {
+            return Some(bin.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Keep tsc diagnostics for `file_path`, including their indented continuation lines.
+fn filter_tsc_output(output: &str, file_path: &str, project_root: &str) -> String {
+    let prefix = format!("{}/", project_root.trim_end_matches('/'));
+    let relative = file_path.strip_prefix(&prefix).unwrap_or(file_path);
+
+    let mut kept = Vec::new();
+    let mut in_match = false;
+    for line in output.lines() {
+        if line.starts_with(' ') {
+            if in_match {
+                kept.push(line);
+            }
+            continue;
+        }
+        in_match =
+            line.starts_with(&format!("{relative}(")) || line.starts_with(&format!("{file_path}("));
+        if in_match {
+            kept.push(line);
+        }
+    }
+    kept.join("\n")
+}
+
+pub fn run_rust_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    run_rust_lint_multi(&[file_path.to_string()], project_root, debug, lenient)
+}
+
+/// Run clippy once and filter output for all given file paths.
+pub fn run_rust_lint_multi(
+    file_paths: &[String],
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load(project_root);
+
+    // Manifest edits can break any file in the package, so they're checked project-wide
+    let (manifests, file_paths): (Vec<&str>, Vec<&str>) = file_paths
+        .iter()
+        .map(String::as_str)
+        .partition(|f| is_cargo_manifest(f));
+    if !manifests.is_empty() {
+        if let Some(result) = check_cargo_manifests(&manifests, project_root, &config, debug)? {
+            return Ok(result);
+        }
+        if file_paths.is_empty() {
+            return Ok(continue_result(
+                debug,
+                &format!(
+                    "[ralph-hook-lint] lint passed for {} using cargo check.",
+                    manifests.join(", ")
+                ),
+            ));
+        }
+    }
+
+    let output = clippy_command(project_root, &config, lenient).output_grouped()?;
+
+    let stdout = encoding::decode(&output.stdout);
+    let stderr = encoding::decode(&output.stderr);
+
+    let refs = &file_paths;
+    let file_errors = filter_clippy_output_multi(&stdout, &stderr, refs, project_root);
+
+    let label = if file_paths.len() == 1 {
+        file_paths[0].to_string()
+    } else {
+        format!("{} files", file_paths.len())
+    };
+
+    if file_errors.is_empty() {
+        Ok(continue_result(
+            debug,
+            &format!(
+                "[ralph-hook-lint] lint passed for {label} using clippy{}.",
+                rust_config_note(project_root)
+            ),
+        ))
+    } else {
+        Ok(format!(
+            r#"{{"decision":"block","reason":"[ralph-hook-lint] lint errors in {} using clippy:\n\n{}\n\nFix lint errors."}}"#,
+            escape_json(&label),
+            escape_json(&file_errors)
+        ))
+    }
+}
+
+/// Run clippy once from a Cargo workspace root for collected files in several member crates,
+/// instead of once per crate. Diagnostics are attributed to files by their JSON spans.
+pub fn run_rust_workspace_lint(
+    file_paths: &[String],
+    crate_roots: &[String],
+    workspace_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load(workspace_root);
+
+    // `-p` for each member, or the whole workspace if a package name can't be read
+    let names: Option<Vec<String>> = crate_roots
+        .iter()
+        .map(|root| {
+            let manifest = fs::read_to_string(Path::new(root).join("Cargo.toml")).ok()?;
+            Config::parse(&manifest)
+                .get_str("package.name")
+                .map(str::to_string)
         })
         .collect();
+    // Keep going past a member that fails so every member's diagnostics are reported
+    let mut args = vec!["clippy", "--message-format=json", "--keep-going"];
+    match &names {
+        Some(names) => {
+            for name in names {
+                args.extend(["-p", name.as_str()]);
+            }
+        }
+        None => args.push("--workspace"),
+    }
+    args.extend(["--", "-D", "warnings"]);
+    let lenient_args = rust_lenient_args(&LenientRules::from_config(&config, "rust"));
+    if lenient {
+        args.extend(lenient_args.iter().map(String::as_str));
+    }
+    let session_args = overlay::args("clippy");
+    args.extend(session_args.iter().map(String::as_str));
+    let output = cargo_command(workspace_root, &config, &args).output_grouped()?;
 
-    combined
+    let diagnostics: Vec<Diagnostic> = parse_clippy_json(&encoding::decode(&output.stdout))
+        .into_iter()
+        .filter(|d| file_paths.iter().any(|f| d.is_for_file(f, workspace_root)))
+        .collect();
+    let diagnostics = drop_suppressed(diagnostics, workspace_root);
+    let label = format!("{} files", file_paths.len());
+    let policy = Policy::from_config(&config);
+    if diagnostics.iter().any(|d| policy.blocks("clippy", d, true)) {
+        Ok(format!(
+            r#"{{"decision":"block","reason":"[ralph-hook-lint] lint errors in {} using clippy:\n\n{}\n\nFix lint errors."}}"#,
+            escape_json(&label),
+            escape_json(&format_diagnostics(&diagnostics))
+        ))
+    } else {
+        Ok(continue_result(
+            debug,
+            &format!(
+                "[ralph-hook-lint] lint passed for {label} using clippy --workspace{}.",
+                rust_config_note(workspace_root)
+            ),
+        ))
+    }
+}
+
+/// Diagnostics from `cargo clippy --message-format=json`, located at each message's primary
+/// span. Duplicates (a file compiled for both lib and bin targets) are reported once.
+fn parse_clippy_json(stdout: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for line in stdout.lines() {
+        let Some(event) = json::parse(line) else {
+            continue;
+        };
+        if event.get("reason").and_then(Json::as_str) != Some("compiler-message") {
+            continue;
+        }
+        let Some(message) = event.get("message") else {
+            continue;
+        };
+        let severity = match message.get("level").and_then(Json::as_str) {
+            Some("error") => Severity::Error,
+            Some("warning") => Severity::Warning,
+            _ => continue,
+        };
+        let Some(span) = message
+            .get("spans")
+            .and_then(Json::as_array)
+            .and_then(|spans| {
+                spans
+                    .iter()
+                    .find(|s| matches!(s.get("is_primary"), Some(Json::Bool(true))))
+            })
+        else {
+            continue;
+        };
+        let diagnostic = Diagnostic {
+            file: span
+                .get("file_name")
+                .and_then(Json::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            line: span.get("line_start").and_then(Json::as_u32).unwrap_or(0),
+            column: span.get("column_start").and_then(Json::as_u32).unwrap_or(0),
+            rule: message
+                .get("code")
+                .and_then(|c| c.get("code"))
+                .and_then(Json::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            message: message
+                .get("message")
+                .and_then(Json::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            severity,
+            url: None,
+            fix: None,
+        };
+        if !diagnostics.contains(&diagnostic) {
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics
+}
+
+/// Minimum time between warm-up `cargo check` runs for one project.
+const WARM_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// With `[rust] warm_check = true`, start a background `cargo check` in `project_root` so
+/// dependencies are already compiled when clippy runs at `Stop`. Fire-and-forget: the hook
+/// doesn't wait for it, and a check started in the last 30 seconds isn't repeated. Returns
+/// whether a check was started.
+pub fn warm_rust_build(project_root: &str) -> bool {
+    let config = Config::load(project_root);
+    if config.get_bool("rust.warm_check") != Some(true) {
+        return false;
+    }
+    let mut hasher = DefaultHasher::new();
+    project_root.hash(&mut hasher);
+    let marker = std::env::temp_dir().join(format!("ralph-lint-warm-{:016x}", hasher.finish()));
+    let recent = fs::metadata(&marker)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < WARM_CHECK_INTERVAL);
+    if recent || fs::write(&marker, project_root).is_err() {
+        return false;
+    }
+    cargo_command(project_root, &config, &["check", "--quiet"])
+        .spawn_detached()
+        .is_ok()
+}
+
+const CLIPPY_CONFIG_FILES: [&str; 2] = ["clippy.toml", ".clippy.toml"];
+const RUSTFMT_CONFIG_FILES: [&str; 2] = ["rustfmt.toml", ".rustfmt.toml"];
+
+/// Describe the clippy/rustfmt config files in effect, e.g. ` (clippy.toml: /ws/clippy.toml)`.
+fn rust_config_note(project_root: &str) -> String {
+    let found: Vec<String> = [
+        (
+            "clippy.toml",
+            find_nearest_file(project_root, &CLIPPY_CONFIG_FILES),
+        ),
+        (
+            "rustfmt.toml",
+            find_nearest_file(project_root, &RUSTFMT_CONFIG_FILES),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(kind, path)| Some(format!("{kind}: {}", path?.display())))
+    .collect();
+
+    if found.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", found.join(", "))
+    }
+}
+
+/// Whether `file_path` is a Cargo manifest or lockfile rather than Rust source.
+pub fn is_cargo_manifest(file_path: &str) -> bool {
+    Path::new(file_path)
+        .file_name()
+        .is_some_and(|name| name == "Cargo.toml" || name == "Cargo.lock")
+}
+
+/// Run `cargo check` for edited manifests and return a block result if the package no longer
+/// builds. A lockfile edited on its own is checked with `--locked`, so an inconsistent
+/// lockfile fails instead of being silently rewritten.
+fn check_cargo_manifests(
+    manifests: &[&str],
+    project_root: &str,
+    config: &Config,
+    debug: bool,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let mut args = vec!["check", "--message-format=short"];
+    if manifests.iter().all(|m| m.ends_with("Cargo.lock")) {
+        args.push("--locked");
+    }
+    let output = cargo_command(project_root, config, &args).output_grouped()?;
+    if output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(output_lint_result(
+        "cargo check",
+        &manifests.join(", "),
+        &encoding::decode(&output.stdout),
+        &strip_cargo_progress(&encoding::decode(&output.stderr)),
+        false,
+        debug,
+    )))
+}
+
+/// Drop cargo's status lines (`Compiling`, `Checking`, ...), keeping errors and their context.
+fn strip_cargo_progress(stderr: &str) -> String {
+    const STATUS: &[&str] = &[
+        "Compiling",
+        "Checking",
+        "Finished",
+        "Updating",
+        "Locking",
+        "Adding",
+        "Downloading",
+        "Downloaded",
+        "Blocking",
+        "Fresh",
+    ];
+    stderr
         .lines()
         .filter(|line| {
-            // 1. Exact absolute path (rare but precise)
-            file_paths.iter().any(|fp| line.contains(fp))
-            // 2. Relative path from project root (matches clippy's output)
-                || relative_paths.iter().any(|rp| line.contains(rp))
-            // 3. Bare filename fallback
-                || file_names.iter().any(|name| line.contains(name))
+            !line
+                .split_whitespace()
+                .next()
+                .is_some_and(|word| STATUS.contains(&word))
         })
         .collect::<Vec<_>>()
         .join("\n")
 }
 
-pub fn escape_json(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    for c in s.chars() {
-        match c {
-            '"' => result.push_str(r#"\""#),
-            '\\' => result.push_str(r"\\"),
-            '\n' => result.push_str(r"\n"),
-            '\r' => result.push_str(r"\r"),
-            '\t' => result.push_str(r"\t"),
-            c if c.is_control() => {
-                let _ = write!(result, r"\u{:04x}", c as u32);
-            }
-            c => result.push(c),
+/// Build the `cargo clippy` command, applying `[rust]` config such as `target_dir` and `[rust.env]`.
+fn clippy_command(project_root: &str, config: &Config, lenient: bool) -> Command {
+    let mut clippy_args = vec!["clippy", "--message-format=short", "--", "-D", "warnings"];
+    let lenient_args = rust_lenient_args(&LenientRules::from_config(config, "rust"));
+    if lenient {
+        clippy_args.extend(lenient_args.iter().map(String::as_str));
+    }
+    // After the lenient flags, so a lint the session denies isn't allowed again
+    let session_args = overlay::args("clippy");
+    clippy_args.extend(session_args.iter().map(String::as_str));
+    cargo_command(project_root, config, &clippy_args)
+}
+
+/// A cargo command in `project_root` with the `[rust]` config applied.
+fn cargo_command(project_root: &str, config: &Config, args: &[&str]) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.args(args).current_dir(project_root);
+
+    // clippy.toml often lives at the workspace root rather than next to the crate's Cargo.toml
+    if let Some(dir) = find_nearest_file(project_root, &CLIPPY_CONFIG_FILES)
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+    {
+        cmd.env("CLIPPY_CONF_DIR", dir);
+    }
+
+    // A dedicated target dir keeps hook builds off the agent's build lock and incremental state.
+    if let Some(target_dir) = config.get_str("rust.target_dir") {
+        cmd.env(
+            "CARGO_TARGET_DIR",
+            resolve_target_dir(project_root, config, target_dir),
+        );
+    }
+    // A compiler cache such as sccache; skipped if not installed so cargo doesn't fail outright.
+    if let Some(wrapper) = config.get_str("rust.rustc_wrapper") {
+        if is_executable_available(wrapper) {
+            cmd.env("RUSTC_WRAPPER", wrapper);
         }
     }
-    result
-}
+    for (key, value) in config.table("rust.env") {
+        cmd.env(key, value);
+    }
+    cmd
+}
+
+/// Resolve a relative `rust.target_dir` against the directory of the config file that set it,
+/// or else the Cargo workspace root, so every member of a workspace shares one target dir
+/// rather than getting its own under each crate.
+fn resolve_target_dir(project_root: &str, config: &Config, target_dir: &str) -> PathBuf {
+    let target_dir = Path::new(target_dir);
+    if target_dir.is_absolute() {
+        return target_dir.to_path_buf();
+    }
+    let base = config
+        .source
+        .as_deref()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .or_else(|| find_cargo_workspace_root(project_root).map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(project_root));
+    base.join(target_dir)
+}
+
+/// Kind of standalone Rust file, detected from its shebang line.
+#[derive(Debug, PartialEq, Eq)]
+enum RustFileKind {
+    /// `#!/usr/bin/env -S cargo +nightly -Zscript`
+    CargoScript,
+    /// `#!/usr/bin/env rust-script`
+    RustScript,
+    /// Plain file without a script shebang
+    Plain,
+}
+
+fn detect_rust_script(contents: &str) -> RustFileKind {
+    // `#![...]` is an inner attribute, not a shebang
+    let Some(shebang) = contents
+        .lines()
+        .next()
+        .filter(|l| l.starts_with("#!") && !l.starts_with("#!["))
+    else {
+        return RustFileKind::Plain;
+    };
+
+    if shebang.contains("rust-script") {
+        RustFileKind::RustScript
+    } else if shebang.contains("cargo") {
+        RustFileKind::CargoScript
+    } else {
+        RustFileKind::Plain
+    }
+}
+
+/// Lint a Rust file that has no Cargo.toml: cargo scripts via `cargo -Zscript clippy`,
+/// rust-script scripts via their generated package, and anything else via `rustc`.
+fn run_rust_script_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(file_path).unwrap_or_default();
+
+    let lenient_args = rust_lenient_args(&LenientRules::from_config(
+        &Config::load(project_root),
+        "rust",
+    ));
+    let (linter, output) = match detect_rust_script(&contents) {
+        RustFileKind::CargoScript => {
+            if !has_nightly_toolchain() {
+                return Ok(SkipReason::NoLinter.result(
+                    debug,
+                    &format!(
+                        "cargo scripts need a nightly toolchain, skipping {file_path}. Install one with `rustup toolchain install nightly`."
+                    ),
+                ));
+            }
+            let mut args = vec![
+                "+nightly",
+                "-Zscript",
+                "clippy",
+                "--manifest-path",
+                file_path,
+                "--message-format=short",
+                "--",
+                "-D",
+                "warnings",
+            ];
+            if lenient {
+                args.extend(lenient_args.iter().map(String::as_str));
+            }
+            let output = Command::new("cargo")
+                .args(&args)
+                .current_dir(project_root)
+                .output_grouped()?;
+            ("cargo -Zscript clippy", output)
+        }
+        RustFileKind::RustScript => {
+            if !is_executable_available("rust-script") {
+                return Ok(SkipReason::NoLinter.result(
+                    debug,
+                    &format!("rust-script not installed, skipping {file_path}."),
+                ));
+            }
+            // `--package` generates the script's Cargo package without building it
+            let package = Command::new("rust-script")
+                .args(["--package", file_path])
+                .current_dir(project_root)
+                .output_grouped()?;
+            let package_dir = encoding::decode(&package.stdout).trim().to_string();
+            if !package.status.success() || package_dir.is_empty() {
+                return Ok(output_lint_result(
+                    "rust-script --package",
+                    file_path,
+                    &encoding::decode(&package.stdout),
+                    &encoding::decode(&package.stderr),
+                    false,
+                    debug,
+                ));
+            }
+            let config = Config::load(project_root);
+            let output = clippy_command(&package_dir, &config, lenient).output_grouped()?;
+            ("rust-script clippy", output)
+        }
+        RustFileKind::Plain => {
+            let out_dir = std::env::temp_dir().join("ralph-lint-rustc");
+            let crate_type = if contents.contains("fn main") {
+                "bin"
+            } else {
+                "lib"
+            };
+            let mut cmd = Command::new("rustc");
+            cmd.args([
+                "--edition",
+                "2021",
+                "--emit=metadata",
+                "--error-format=short",
+            ])
+            .args(["--crate-type", crate_type])
+            .arg("--out-dir")
+            .arg(&out_dir)
+            .args(["-D", "warnings"]);
+            if lenient {
+                cmd.args(&lenient_args);
+            }
+            let output = cmd
+                .arg(file_path)
+                .current_dir(project_root)
+                .output_grouped()?;
+            ("rustc", output)
+        }
+    };
+
+    Ok(output_lint_result(
+        linter,
+        file_path,
+        &encoding::decode(&output.stdout),
+        &encoding::decode(&output.stderr),
+        output.status.success(),
+        debug,
+    ))
+}
+
+/// Whether rustup has a nightly toolchain, which `cargo +nightly -Zscript` needs. Without
+/// one, rustup's "toolchain 'nightly' is not installed" would read as a lint failure.
+fn has_nightly_toolchain() -> bool {
+    Command::new("rustup")
+        .args(["toolchain", "list"])
+        .output()
+        .is_ok_and(|output| {
+            output.status.success()
+                && encoding::decode(&output.stdout).lines().any(|line| {
+                    line.split_whitespace()
+                        .next()
+                        .is_some_and(|name| name == "nightly" || name.starts_with("nightly-"))
+                })
+        })
+}
+
+/// Elixir script for standalone files: parse without compiling or running the file
+const ELIXIR_PARSE: &str =
+    "[path] = System.argv(); Code.string_to_quoted!(File.read!(path), file: path)";
+
+/// Best-effort single-file check for a file that has no project root.
+pub fn run_standalone_lint(
+    file_path: &str,
+    lang: Lang,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load(project_root);
+    let out_dir = std::env::temp_dir().join("ralph-lint-standalone");
+    let out_dir = out_dir.to_string_lossy();
+
+    let (program, args): (&str, Vec<&str>) = match lang {
+        Lang::Rust if is_cargo_manifest(file_path) => {
+            return Ok(SkipReason::NoProjectRoot.result(
+                debug,
+                &format!("no Cargo.toml next to {file_path}, skipping."),
+            ));
+        }
+        Lang::Go if is_go_module_file(file_path) => {
+            return Ok(SkipReason::NoProjectRoot
+                .result(debug, &format!("no go.mod next to {file_path}, skipping.")));
+        }
+        Lang::Rust => return run_rust_script_lint(file_path, project_root, debug, lenient),
+        Lang::Python if is_executable_available("ruff") => {
+            ("ruff", vec!["check", "--output-format=json", file_path])
+        }
+        // Same check as `python -m py_compile`, without writing a .pyc next to the file
+        Lang::Python => (
+            "python3",
+            vec![
+                "-c",
+                "import sys; compile(open(sys.argv[1]).read(), sys.argv[1], 'exec')",
+                file_path,
+            ],
+        ),
+        Lang::JavaScript
+            if [".js", ".mjs", ".cjs"]
+                .iter()
+                .any(|ext| file_path.ends_with(ext)) =>
+        {
+            ("node", vec!["--check", file_path])
+        }
+        Lang::Go => ("gofmt", vec!["-e", "-l", file_path]),
+        Lang::Java => ("javac", vec!["-proc:none", "-d", &out_dir, file_path]),
+        Lang::Kotlin => ("ktlint", vec!["--relative", file_path]),
+        Lang::Php => ("php", vec!["-l", file_path]),
+        Lang::Swift => ("swiftlint", vec!["lint", "--strict", "--path", file_path]),
+        Lang::Shell => return run_shell_lint(file_path, project_root, debug, lenient),
+        Lang::PowerShell => return run_powershell_lint(file_path, project_root, debug, lenient),
+        Lang::Markdown => return run_markdown_lint(file_path, project_root, debug, lenient),
+        Lang::Haskell => return run_haskell_lint(file_path, project_root, debug, lenient),
+        Lang::Zig => return run_zig_lint(file_path, project_root, debug, lenient),
+        Lang::Lua => return run_lua_lint(file_path, project_root, debug, lenient),
+        Lang::Nix => return run_nix_lint(file_path, project_root, debug, lenient),
+        Lang::Solidity => return run_solidity_lint(file_path, project_root, debug, lenient),
+        Lang::Terraform => return run_terraform_lint(file_path, project_root, debug, lenient),
+        // Parse only: type checking needs the build's classpath
+        Lang::Scala => (
+            "scalac",
+            vec!["-Ystop-after:parser", "-d", &out_dir, file_path],
+        ),
+        Lang::Elixir => ("elixir", vec!["-e", ELIXIR_PARSE, "--", file_path]),
+        Lang::C => (
+            c_compiler(file_path).unwrap_or("cc"),
+            vec!["-fsyntax-only", file_path],
+        ),
+        // C# needs a project to resolve even `System`
+        Lang::JavaScript | Lang::CSharp => {
+            return Ok(SkipReason::UnsupportedType.result(
+                debug,
+                &format!("no standalone check for {file_path}, skipping."),
+            ));
+        }
+    };
+
+    if !is_executable_available(program) {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!("{program} not found for standalone {file_path}, skipping."),
+        ));
+    }
+
+    let output = Command::new(program)
+        .args(&args)
+        .current_dir(project_root)
+        .output_grouped()?;
+
+    if program == "ruff" {
+        if let Some(result) = ruff_json_result(
+            file_path,
+            &encoding::decode(&output.stdout),
+            lenient
+                .then(|| LenientRules::from_config(&config, "python"))
+                .as_ref(),
+            &Policy::from_config(&config),
+            debug,
+        ) {
+            return Ok(result);
+        }
+    }
+
+    Ok(output_lint_result(
+        program,
+        file_path,
+        &encoding::decode(&output.stdout),
+        &encoding::decode(&output.stderr),
+        linter_passed(&config, program, output.status),
+        debug,
+    ))
+}
+
+pub fn run_python_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    // Try linters in order of speed: ruff (fastest), mypy, pylint, flake8
+    let linters: &[(&str, &[&str])] = &[
+        ("ruff", &["check", "--output-format=json", "{{file}}"]),
+        ("mypy", &["{{file}}"]),
+        ("pylint", &["--output-format=text", "{{file}}"]),
+        ("flake8", &["{{file}}"]),
+    ];
+
+    let config = Config::load(project_root);
+    let rules = LenientRules::from_config(&config, "python");
+    let policy = Policy::from_config(&config);
+
+    for (linter, args) in linters {
+        if fast_tier_skips(project_root, Lang::Python, linter) {
+            continue;
+        }
+        if let Some(bin) = find_python_linter(project_root, linter) {
+            let mut actual_args: Vec<String> = args
+                .iter()
+                .map(|a| a.replace("{{file}}", file_path))
+                .collect();
+
+            if lenient {
+                match (*linter, &rules) {
+                    ("pylint", LenientRules::Only(only)) => {
+                        actual_args.push("--disable=all".into());
+                        actual_args.push(format!("--enable={}", only.join(",")));
+                    }
+                    ("pylint", _) => {
+                        let allowed = rules.allowed(&PYLINT_LENIENT_CODES);
+                        actual_args.push(format!("--disable={}", allowed.join(",")));
+                    }
+                    ("flake8", LenientRules::Only(only)) => {
+                        actual_args.push(format!("--select={}", only.join(",")));
+                    }
+                    ("flake8", _) => {
+                        let allowed = rules.allowed(&RUFF_LENIENT_CODES);
+                        actual_args.push(format!("--extend-ignore={}", allowed.join(",")));
+                    }
+                    _ => {} // ruff filters diagnostics structurally; mypy doesn't check unused vars
+                }
+            }
+            if *linter == "ruff" {
+                actual_args.extend(overlay::args("ruff"));
+            }
+
+            let output = Command::new(&bin)
+                .args(&actual_args)
+                .current_dir(project_root)
+                .output_grouped()?;
+
+            let mut stdout = encoding::decode(&output.stdout);
+            if *linter == "ruff" {
+                let rules = lenient.then_some(&rules);
+                if let Some(result) = ruff_json_result(file_path, &stdout, rules, &policy, debug) {
+                    return Ok(result);
+                }
+            }
+            // Drop allowed message categories so they don't clutter a blocking reason
+            if *linter == "pylint" {
+                if let Some(mask) = allowed_exit_mask(&config, linter) {
+                    stdout = filter_pylint_messages(&stdout, mask);
+                }
+            }
+            // The project's `# noqa` comments mean nothing to mypy and pylint
+            let (stdout, suppressed) = drop_suppressed_lines(&stdout, file_path);
+            let all_suppressed = suppressed > 0
+                && !stdout
+                    .lines()
+                    .any(|line| text_line_number(line, file_path).is_some());
+
+            return Ok(output_lint_result(
+                linter,
+                file_path,
+                &stdout,
+                &encoding::decode(&output.stderr),
+                all_suppressed || linter_passed(&config, linter, output.status),
+                debug,
+            ));
+        }
+    }
+
+    // No linter found
+    Ok(SkipReason::NoLinter.result(debug,
+        &format!(
+            "no Python linter found for {file_path}. Install ruff for best performance: pip install ruff"
+        ),
+    ))
+}
+
+/// Lint several files of one Python project. ruff checks them all in one run and its JSON
+/// report is split per file; other linters run once per file.
+pub fn run_python_lint_multi(
+    file_paths: &[String],
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let Some(ruff) = find_python_linter(project_root, "ruff").filter(|_| file_paths.len() > 1)
+    else {
+        let results = file_paths
+            .iter()
+            .map(|f| run_python_lint(f, project_root, debug, lenient))
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(combine_results(
+            &results,
+            file_paths,
+            "python linters",
+            debug,
+        ));
+    };
+
+    let output = Command::new(&ruff)
+        .args(["check", "--output-format=json"])
+        .args(overlay::args("ruff"))
+        .args(file_paths)
+        .current_dir(project_root)
+        .output_grouped()?;
+    let config = Config::load(project_root);
+    let rules = LenientRules::from_config(&config, "python");
+    let policy = Policy::from_config(&config);
+    let Some(diagnostics) = parse_ruff_json(&encoding::decode(&output.stdout)) else {
+        return Ok(output_lint_result(
+            "ruff",
+            &format!("{} files", file_paths.len()),
+            &encoding::decode(&output.stdout),
+            &encoding::decode(&output.stderr),
+            false,
+            debug,
+        ));
+    };
+    let results: Vec<String> = file_paths
+        .iter()
+        .map(|file| {
+            let found: Vec<Diagnostic> = diagnostics
+                .iter()
+                .filter(|d| d.is_for_file(file, project_root))
+                .filter(|d| !lenient || rules.keeps(&d.rule, &RUFF_LENIENT_CODES))
+                .cloned()
+                .collect();
+            let found = drop_suppressed(found, project_root);
+            diagnostics_result("ruff", file, &found, &policy, |_| true, debug)
+        })
+        .collect();
+    Ok(combine_results(&results, file_paths, "ruff", debug))
+}
+
+/// A Python linter from the project's virtual environment, or else from `PATH`.
+fn find_python_linter(project_root: &str, linter: &str) -> Option<String> {
+    let venv_dirs = [".venv/bin", "venv/bin", ".env/bin", "env/bin"];
+    if let Some(venv_path) = venv_dirs
+        .iter()
+        .map(|venv_dir| format!("{project_root}/{venv_dir}/{linter}"))
+        .find(|path| Path::new(path).exists())
+    {
+        return Some(venv_path);
+    }
+    let output = Command::new("which").arg(linter).output().ok()?;
+    let path = encoding::decode(&output.stdout).trim().to_string();
+    (output.status.success() && !path.is_empty()).then_some(path)
+}
+
+/// Merge per-file hook responses into one: a block whose reason has each failing file's
+/// section, or a pass for all files.
+fn combine_results(results: &[String], file_paths: &[String], linter: &str, debug: bool) -> String {
+    let reasons: Vec<String> = results
+        .iter()
+        .filter(|r| r.contains(r#""decision":"block""#))
+        .map(|r| extract_reason_field(r).unwrap_or_else(|| r.clone()))
+        .collect();
+    if reasons.is_empty() {
+        let label = if file_paths.len() == 1 {
+            file_paths[0].clone()
+        } else {
+            format!("{} files", file_paths.len())
+        };
+        return continue_result(
+            debug,
+            &format!("[ralph-hook-lint] lint passed for {label} using {linter}."),
+        );
+    }
+    format!(
+        r#"{{"decision":"block","reason":"{}"}}"#,
+        escape_json(&reasons.join("\n\n---\n\n"))
+    )
+}
+
+pub fn run_java_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    // PMD/SpotBugs don't support clean CLI-level rule suppression, so lenient rules are
+    // dropped from their reports instead
+    let config = Config::load(project_root);
+    let lenient = lenient.then(|| LenientRules::from_config(&config, "java"));
+
+    // With `[java] bloop = true`, a warm Bloop build server compiles the owning target in
+    // seconds, so compile errors in the file block before the slower lint runs.
+    if config.get_bool("java.bloop") == Some(true) {
+        if let Some(result) = run_bloop_compile(file_path, project_root, &config, debug)? {
+            return Ok(result);
+        }
+    }
+
+    if Path::new(project_root).join("pom.xml").exists() {
+        return run_maven_lint(file_path, project_root, lenient, debug);
+    }
+
+    if ["build.gradle", "build.gradle.kts"]
+        .iter()
+        .any(|f| Path::new(project_root).join(f).exists())
+    {
+        return run_gradle_lint(file_path, project_root, lenient, debug);
+    }
+
+    // No build tool found
+    Ok(SkipReason::NoLinter.result(
+        debug,
+        &format!("no Java build tool found for {file_path}. Add pom.xml or build.gradle."),
+    ))
+}
+
+/// Run the first configured Maven lint goal: pmd (fast), then spotbugs (thorough).
+fn run_maven_lint(
+    file_path: &str,
+    project_root: &str,
+    lenient: Option<LenientRules>,
+    debug: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    const MAVEN_LINTERS: &[(&str, &str)] = &[("pmd:check", "pmd"), ("spotbugs:check", "spotbugs")];
+
+    let build_files = [Path::new(project_root).join("pom.xml")];
+    let configured = cached_java_tasks(project_root, &build_files, || {
+        // `help:describe` resolves a plugin prefix exactly as `mvn <prefix>:check` would
+        let mut goals = Vec::new();
+        for (goal, prefix) in MAVEN_LINTERS {
+            let output = Command::new("mvn")
+                .args(["-q", "help:describe", &format!("-Dplugin={prefix}")])
+                .current_dir(project_root)
+                .output_grouped()?;
+            let stdout = encoding::decode(&output.stdout);
+            let stderr = encoding::decode(&output.stderr);
+            if output.status.success() {
+                goals.push((*goal).to_string());
+            } else if !is_unknown_maven_prefix(&format!("{stdout}\n{stderr}")) {
+                return Ok(Err(format!("{stderr}\n{stdout}")));
+            }
+        }
+        Ok(Ok(goals))
+    })?;
+
+    // The build itself is broken (bad pom, unresolvable dependencies, offline), so any lint
+    // goal would fail too
+    let configured = match configured {
+        Ok(goals) => goals,
+        Err(output) => {
+            return Ok(output_lint_result(
+                "mvn help:describe",
+                file_path,
+                &output,
+                "",
+                false,
+                debug,
+            ));
+        }
+    };
+
+    for (goal, _) in MAVEN_LINTERS {
+        if !configured.iter().any(|g| g == goal) {
+            continue;
+        }
+        let started = SystemTime::now();
+        let output = Command::new("mvn")
+            .args([goal, "-q"])
+            .current_dir(project_root)
+            .output_grouped()?;
+
+        let run = JavaRun {
+            linter: format!("mvn {goal}"),
+            task: goal,
+            started,
+            lenient,
+        };
+        return Ok(java_lint_result(
+            &run,
+            project_root,
+            file_path,
+            &output,
+            debug,
+        ));
+    }
+
+    Ok(SkipReason::NoLinter.result(debug,
+        &format!(
+            "no Java linter configured for {file_path}. Add maven-pmd-plugin or spotbugs-maven-plugin to pom.xml."
+        ),
+    ))
+}
+
+/// Whether a failed `mvn help:describe` only means the plugin prefix isn't configured, as
+/// opposed to the build failing.
+fn is_unknown_maven_prefix(output: &str) -> bool {
+    output.contains("No plugin found for prefix")
+}
+
+/// Run the first configured Gradle lint task: pmd (fast), then spotbugs (thorough).
+fn run_gradle_lint(
+    file_path: &str,
+    project_root: &str,
+    lenient: Option<LenientRules>,
+    debug: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    const GRADLE_LINTERS: &[&str] = &["pmdMain", "spotbugsMain"];
+
+    // Multi-project and composite builds must run from the settings.gradle(.kts) directory,
+    // targeting the subproject by path (e.g. `:lib:pmdMain`).
+    let gradle_root =
+        find_gradle_settings_root(project_root).unwrap_or_else(|| project_root.to_string());
+    let gradle_cmd = if Path::new(&gradle_root).join("gradlew").exists() {
+        "./gradlew"
+    } else {
+        "gradle"
+    };
+
+    let build_files: Vec<_> = ["build.gradle", "build.gradle.kts"]
+        .iter()
+        .map(|f| Path::new(project_root).join(f))
+        .chain(
+            ["settings.gradle", "settings.gradle.kts"]
+                .iter()
+                .map(|f| Path::new(&gradle_root).join(f)),
+        )
+        .collect();
+    let configured = cached_java_tasks(project_root, &build_files, || {
+        let output = Command::new(gradle_cmd)
+            .args(["tasks", "--all", "-q"])
+            .current_dir(&gradle_root)
+            .output_grouped()?;
+        let stdout = encoding::decode(&output.stdout);
+        if output.status.success() {
+            Ok(Ok(parse_gradle_tasks(&stdout)))
+        } else {
+            let stderr = encoding::decode(&output.stderr);
+            Ok(Err(format!("{stderr}\n{stdout}")))
+        }
+    })?;
+
+    // The build itself is broken, so any lint task would fail too
+    let configured = match configured {
+        Ok(tasks) => tasks,
+        Err(output) => {
+            return Ok(output_lint_result(
+                &format!("{gradle_cmd} tasks"),
+                file_path,
+                &output,
+                "",
+                false,
+                debug,
+            ));
+        }
+    };
+
+    for task in GRADLE_LINTERS {
+        let task_path = gradle_task_path(&gradle_root, project_root, task);
+        if !configured
+            .iter()
+            .any(|t| t == task_path.trim_start_matches(':'))
+        {
+            continue;
+        }
+        let started = SystemTime::now();
+        let output = Command::new(gradle_cmd)
+            .args([task_path.as_str(), "-q"])
+            .current_dir(&gradle_root)
+            .output_grouped()?;
+
+        let run = JavaRun {
+            linter: format!("{gradle_cmd} {task_path}"),
+            task,
+            started,
+            lenient,
+        };
+        return Ok(java_lint_result(
+            &run,
+            project_root,
+            file_path,
+            &output,
+            debug,
+        ));
+    }
+
+    Ok(SkipReason::NoLinter.result(
+        debug,
+        &format!(
+            "no Java linter configured for {file_path}. Add pmd or spotbugs plugin to build.gradle."
+        ),
+    ))
+}
+
+/// Task names listed by `gradle tasks --all`, e.g. `pmdMain` or `lib:pmdMain` for subprojects.
+fn parse_gradle_tasks(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let name = line.split(" - ").next()?.trim();
+            let is_task = !name.is_empty()
+                && !name.contains(char::is_whitespace)
+                && name.chars().next().is_some_and(char::is_alphabetic);
+            is_task.then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Cache file for a project's introspected Java lint tasks.
+fn java_tasks_cache_path(project_root: &str) -> std::path::PathBuf {
+    let mut hasher = DefaultHasher::new();
+    project_root.hash(&mut hasher);
+    std::env::temp_dir().join(format!("ralph-lint-java-tasks-{:x}.txt", hasher.finish()))
+}
+
+/// Lint tasks the build defines, from `probe` or the per-project cache. Introspection (Maven,
+/// Gradle or sbt) starts a JVM, so results are cached until one of `build_files` changes. `probe` returns `Err(output)`
+/// when the build itself fails; failures are not cached.
+fn cached_java_tasks(
+    project_root: &str,
+    build_files: &[std::path::PathBuf],
+    probe: impl FnOnce() -> Result<Result<Vec<String>, String>, Box<dyn std::error::Error>>,
+) -> Result<Result<Vec<String>, String>, Box<dyn std::error::Error>> {
+    let mut hasher = DefaultHasher::new();
+    for file in build_files {
+        fs::read(file).unwrap_or_default().hash(&mut hasher);
+    }
+    let key = hasher.finish().to_string();
+
+    let cache = java_tasks_cache_path(project_root);
+    if let Ok(contents) = fs::read_to_string(&cache) {
+        let mut lines = contents.lines();
+        if lines.next() == Some(key.as_str()) {
+            return Ok(Ok(lines.map(str::to_string).collect()));
+        }
+    }
+
+    let tasks = probe()?;
+    if let Ok(tasks) = &tasks {
+        let _ = fs::write(&cache, format!("{key}\n{}", tasks.join("\n")));
+    }
+    Ok(tasks)
+}
+
+/// XML report written by a Java lint task, relative to the project root.
+fn java_report_path(task: &str) -> Option<&'static str> {
+    match task {
+        "pmd:check" => Some("target/pmd.xml"),
+        "spotbugs:check" => Some("target/spotbugsXml.xml"),
+        "pmdMain" => Some("build/reports/pmd/main.xml"),
+        "spotbugsMain" => Some("build/reports/spotbugs/main.xml"),
+        _ => None,
+    }
+}
+
+/// Rules lenient mode drops from PMD and `SpotBugs` reports unless `[lenient.java]` says
+/// otherwise: unused code and dead stores.
+const JAVA_LENIENT_RULES: [&str; 8] = [
+    "UnusedLocalVariable",
+    "UnusedPrivateField",
+    "UnusedPrivateMethod",
+    "UnusedFormalParameter",
+    "UnusedImports",
+    "UnnecessaryImport",
+    "DLS_DEAD_LOCAL_STORE",
+    "URF_UNREAD_FIELD",
+];
+
+/// A Java lint task that ran for a file.
+struct JavaRun<'a> {
+    /// Label for the result, e.g. `mvn pmd:check`
+    linter: String,
+    task: &'a str,
+    started: SystemTime,
+    /// Lenient-mode rules, when `--lenient` is on
+    lenient: Option<LenientRules>,
+}
+
+/// Result for a Java lint task: violations in `file_path` from its XML report, or the raw
+/// console output when no fresh report was written.
+fn java_lint_result(
+    run: &JavaRun,
+    project_root: &str,
+    file_path: &str,
+    output: &std::process::Output,
+    debug: bool,
+) -> String {
+    java_report_result(run, project_root, file_path, debug).unwrap_or_else(|| {
+        output_lint_result(
+            &run.linter,
+            file_path,
+            &encoding::decode(&output.stdout),
+            &encoding::decode(&output.stderr),
+            output.status.success(),
+            debug,
+        )
+    })
+}
+
+/// Build the result from a Java lint task's XML report, keeping only violations in
+/// `file_path`. Returns `None` if the report is missing or wasn't written by this run,
+/// in which case the console output is used instead.
+fn java_report_result(
+    run: &JavaRun,
+    project_root: &str,
+    file_path: &str,
+    debug: bool,
+) -> Option<String> {
+    let report = Path::new(project_root).join(java_report_path(run.task)?);
+    let modified = fs::metadata(&report).and_then(|m| m.modified()).ok()?;
+    // Allow for coarse filesystem timestamps
+    if modified + Duration::from_secs(2) < run.started {
+        return None;
+    }
+    let xml = fs::read_to_string(&report).ok()?;
+
+    let diagnostics = if run.task.starts_with("pmd") {
+        parse_pmd_xml(&xml)
+    } else {
+        parse_spotbugs_xml(&xml)
+    };
+    let diagnostics: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|d| Path::new(file_path).ends_with(&d.file))
+        .filter(|d| {
+            // Rules are reported as `<name>, priority <n>`
+            let name = d.rule.split(',').next().unwrap_or_default();
+            run.lenient
+                .as_ref()
+                .is_none_or(|rules| rules.keeps(name, &JAVA_LENIENT_RULES))
+        })
+        .collect();
+    let diagnostics = drop_suppressed_in_file(diagnostics, file_path);
+
+    Some(diagnostics_result(
+        &run.linter,
+        file_path,
+        &diagnostics,
+        &Policy::from_config(&Config::load(project_root)),
+        |_| true,
+        debug,
+    ))
+}
+
+/// Parse a PMD XML report (`<file name=...><violation beginline=... rule=... priority=...>`).
+fn parse_pmd_xml(xml: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut file = String::new();
+    let mut current: Option<Diagnostic> = None;
+
+    for event in xml::events(xml) {
+        match event {
+            Event::Start { name, attrs } if name == "file" => {
+                file = xml::attr(&attrs, "name").unwrap_or_default().to_string();
+            }
+            Event::Start { name, attrs } if name == "violation" => {
+                let priority = xml::attr(&attrs, "priority").unwrap_or("3");
+                current = Some(Diagnostic {
+                    file: file.clone(),
+                    line: parse_u32_attr(&attrs, "beginline"),
+                    column: parse_u32_attr(&attrs, "begincolumn"),
+                    rule: format!(
+                        "{}, priority {priority}",
+                        xml::attr(&attrs, "rule").unwrap_or_default()
+                    ),
+                    message: String::new(),
+                    severity: java_priority_severity(priority),
+                    url: xml::attr(&attrs, "externalInfoUrl").map(str::to_string),
+                    fix: None,
+                });
+            }
+            Event::Text(text) => {
+                if let Some(d) = &mut current {
+                    d.message.push_str(&text);
+                }
+            }
+            Event::End(name) if name == "violation" => diagnostics.extend(current.take()),
+            _ => {}
+        }
+    }
+    diagnostics
+}
+
+/// Parse a `SpotBugs` XML report. Each `<BugInstance>` is located by its primary `<SourceLine>`
+/// (falling back to the first one), whose `sourcepath` is relative to the source root.
+fn parse_spotbugs_xml(xml: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut current: Option<(Diagnostic, bool)> = None;
+    let mut in_message = false;
+
+    for event in xml::events(xml) {
+        match event {
+            Event::Start { name, attrs } if name == "BugInstance" => {
+                let priority = xml::attr(&attrs, "priority").unwrap_or("3");
+                let diagnostic = Diagnostic {
+                    file: String::new(),
+                    line: 0,
+                    column: 0,
+                    rule: format!(
+                        "{}, priority {priority}",
+                        xml::attr(&attrs, "type").unwrap_or_default()
+                    ),
+                    message: String::new(),
+                    severity: java_priority_severity(priority),
+                    url: None,
+                    fix: None,
+                };
+                current = Some((diagnostic, false));
+            }
+            Event::Start { name, attrs } if name == "SourceLine" => {
+                let Some((d, has_primary)) = &mut current else {
+                    continue;
+                };
+                let primary = xml::attr(&attrs, "primary") == Some("true");
+                if (primary && !*has_primary) || d.file.is_empty() {
+                    d.file = xml::attr(&attrs, "sourcepath")
+                        .unwrap_or_default()
+                        .to_string();
+                    d.line = parse_u32_attr(&attrs, "start");
+                    *has_primary |= primary;
+                }
+            }
+            Event::Start { name, .. } if name == "LongMessage" => in_message = true,
+            Event::Text(text) if in_message => {
+                if let Some((d, _)) = &mut current {
+                    d.message = text;
+                }
+            }
+            Event::End(name) if name == "LongMessage" => in_message = false,
+            Event::End(name) if name == "BugInstance" => {
+                diagnostics.extend(
+                    current
+                        .take()
+                        .map(|(d, _)| d)
+                        .filter(|d| !d.file.is_empty()),
+                );
+            }
+            _ => {}
+        }
+    }
+    diagnostics
+}
+
+fn parse_u32_attr(attrs: &[(String, String)], name: &str) -> u32 {
+    xml::attr(attrs, name)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
+/// PMD and `SpotBugs` priorities 1-2 are high; lower priorities are reported as warnings.
+fn java_priority_severity(priority: &str) -> Severity {
+    match priority {
+        "1" | "2" => Severity::Error,
+        _ => Severity::Warning,
+    }
+}
+
+/// Compile the Bloop project owning `file_path` through the Bloop build server, blocking on
+/// compile errors in `file_path`. Returns `None` (go on to the configured lint) if the file
+/// compiles, or if there is no `.bloop` workspace, no matching project, or no `bloop` CLI.
+fn run_bloop_compile(
+    file_path: &str,
+    project_root: &str,
+    config: &Config,
+    debug: bool,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let Some(workspace) = find_bloop_workspace(project_root) else {
+        return Ok(None);
+    };
+
+    let projects: Vec<(String, String)> = fs::read_dir(Path::new(&workspace).join(".bloop"))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|json| {
+            Some((
+                extract_bloop_project_name(&json)?,
+                extract_bloop_project_dir(&json)?,
+            ))
+        })
+        .collect();
+
+    let Some(project) = select_bloop_project(&projects, file_path) else {
+        return Ok(None);
+    };
+    if !is_executable_available("bloop") {
+        return Ok(None);
+    }
+
+    let output = Command::new("bloop")
+        .args(["compile", project])
+        .current_dir(&workspace)
+        .output_grouped()?;
+    let linter = format!("bloop compile {project}");
+    let stdout = encoding::decode(&output.stdout);
+    let diagnostics = parse_bloop_compile(&stdout);
+    if !output.status.success() && diagnostics.is_empty() {
+        // Bloop itself failed rather than reporting compile errors
+        return Ok(Some(output_lint_result(
+            &linter,
+            file_path,
+            &stdout,
+            &encoding::decode(&output.stderr),
+            false,
+            debug,
+        )));
+    }
+
+    // Errors elsewhere in the target are another edit's business
+    let errors: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|d| d.severity == Severity::Error && d.is_for_file(file_path, &workspace))
+        .collect();
+    if errors.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(diagnostics_result(
+        &linter,
+        file_path,
+        &errors,
+        &Policy::from_config(config),
+        |_| true,
+        debug,
+    )))
+}
+
+/// Parse the `bloop compile` report: a `[E1] src/main/java/App.java:3:5` header (`[W1]` for
+/// warnings) followed by the indented message. Summary lines such as
+/// `[E] src/main/java/App.java: L3 [E1]` are skipped.
+fn parse_bloop_compile(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut awaiting_message = false;
+    for line in output.lines() {
+        if let Some(diagnostic) = parse_bloop_header(line) {
+            diagnostics.push(diagnostic);
+            awaiting_message = true;
+        } else if awaiting_message && !line.trim().is_empty() {
+            if let Some(last) = diagnostics.last_mut() {
+                last.message = line.trim().to_string();
+            }
+            awaiting_message = false;
+        }
+    }
+    diagnostics
+}
+
+/// A diagnostic, without its message yet, from a `[E1] file:line:column` header.
+fn parse_bloop_header(line: &str) -> Option<Diagnostic> {
+    let (tag, location) = line.trim_start().strip_prefix('[')?.split_once("] ")?;
+    let severity = match tag.split_at_checked(1)? {
+        ("E", number) if !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()) => {
+            Severity::Error
+        }
+        ("W", number) if !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()) => {
+            Severity::Warning
+        }
+        _ => return None,
+    };
+    let mut parts = location.trim().rsplitn(3, ':');
+    let column = parts.next()?.parse().ok()?;
+    let line = parts.next()?.parse().ok()?;
+    Some(Diagnostic {
+        file: parts.next()?.to_string(),
+        line,
+        column,
+        rule: String::new(),
+        message: String::new(),
+        severity,
+        url: None,
+        fix: None,
+    })
+}
+
+/// Pick the Bloop project whose directory most closely contains `file_path`.
+/// Test projects (`<name>-test`) are only chosen for files under a `test` source directory.
+fn select_bloop_project<'a>(projects: &'a [(String, String)], file_path: &str) -> Option<&'a str> {
+    let is_test_file = file_path.contains("/src/test/");
+    projects
+        .iter()
+        .filter(|(name, dir)| {
+            Path::new(file_path).starts_with(dir) && name.ends_with("-test") == is_test_file
+        })
+        .max_by_key(|(_, dir)| dir.len())
+        .map(|(name, _)| name.as_str())
+}
+
+/// Lint a Kotlin script (`build.gradle.kts`, `settings.gradle.kts`, `*.main.kts`) with ktlint.
+/// Gradle would only surface a broken build script on the next build, derailing the session.
+pub fn run_kotlin_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    _lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if !is_executable_available("ktlint") {
+        return Ok(SkipReason::NoLinter.result(debug,
+            &format!(
+                "no Kotlin linter found for {file_path}. Install ktlint: https://pinterest.github.io/ktlint/"
+            ),
+        ));
+    }
+
+    let config = Config::load(project_root);
+    let output = Command::new("ktlint")
+        .args(["--relative", file_path])
+        .current_dir(project_root)
+        .output_grouped()?;
+    Ok(output_lint_result(
+        "ktlint",
+        file_path,
+        &encoding::decode(&output.stdout),
+        &encoding::decode(&output.stderr),
+        linter_passed(&config, "ktlint", output.status),
+        debug,
+    ))
+}
+
+/// Lint a PHP file with the project's phpstan, psalm, or phpcs, preferring the
+/// Composer-installed copy in `vendor/bin`. All three report JSON, scoped to the edited file.
+pub fn run_php_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let linters: &[(&str, &[&str])] = &[
+        (
+            "phpstan",
+            &[
+                "analyse",
+                "--error-format=json",
+                "--no-progress",
+                "{{file}}",
+            ],
+        ),
+        (
+            "psalm",
+            &["--output-format=json", "--no-progress", "{{file}}"],
+        ),
+        ("phpcs", &["--report=json", "-q", "{{file}}"]),
+    ];
+
+    let config = Config::load(project_root);
+    let rules = LenientRules::from_config(&config, "php");
+    for (linter, args) in linters {
+        let Some(bin) = find_php_linter(project_root, linter) else {
+            continue;
+        };
+        let output = Command::new(&bin)
+            .args(args.iter().map(|a| a.replace("{{file}}", file_path)))
+            .current_dir(project_root)
+            .output_grouped()?;
+        let stdout = encoding::decode(&output.stdout);
+
+        let parsed = match *linter {
+            "psalm" => parse_psalm_json(&stdout),
+            // PHPStan and PHP_CodeSniffer share the `files` → `messages` report shape
+            _ => parse_php_files_json(&stdout),
+        };
+        if let Some(diagnostics) = parsed {
+            let lenient_defaults = php_lenient_defaults(linter);
+            let diagnostics: Vec<Diagnostic> = diagnostics
+                .into_iter()
+                .filter(|d| d.is_for_file(file_path, project_root))
+                .filter(|d| !lenient || rules.keeps(&d.rule, lenient_defaults))
+                .collect();
+            let diagnostics = drop_suppressed(diagnostics, project_root);
+            return Ok(diagnostics_result(
+                linter,
+                file_path,
+                &diagnostics,
+                &Policy::from_config(&config),
+                |_| true,
+                debug,
+            ));
+        }
+
+        return Ok(output_lint_result(
+            linter,
+            file_path,
+            &stdout,
+            &encoding::decode(&output.stderr),
+            linter_passed(&config, linter, output.status),
+            debug,
+        ));
+    }
+
+    Ok(SkipReason::NoLinter.result(
+        debug,
+        &format!(
+            "no PHP linter found for {file_path}. Install PHPStan: composer require --dev phpstan/phpstan"
+        ),
+    ))
+}
+
+/// A PHP linter installed by Composer in `vendor/bin`, or else from `PATH`.
+fn find_php_linter(project_root: &str, linter: &str) -> Option<String> {
+    let vendor = format!("{project_root}/vendor/bin/{linter}");
+    if Path::new(&vendor).exists() {
+        return Some(vendor);
+    }
+    is_executable_available(linter).then(|| linter.to_string())
+}
+
+/// Rules lenient mode relaxes for each PHP linter: unused code is expected mid-edit.
+fn php_lenient_defaults(linter: &str) -> &'static [&'static str] {
+    match linter {
+        "psalm" => &[
+            "UnusedVariable",
+            "UnusedParam",
+            "UnusedMethod",
+            "UnusedProperty",
+        ],
+        "phpcs" => &["Generic.CodeAnalysis.UnusedFunctionParameter.Found"],
+        _ => &["variable.unused", "method.unused", "property.unused"],
+    }
+}
+
+/// Parse a phpstan `--error-format=json` or phpcs `--report=json` report:
+/// `{"files": {"/ws/a.php": {"messages": [{"message", "line", ...}]}}}`. Returns `None` if the
+/// output isn't such a report, or if phpstan also reports errors outside any file (e.g. a bad
+/// config), which the raw output explains better.
+fn parse_php_files_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let report = json::parse(stdout.trim())?;
+    if report
+        .get("errors")
+        .and_then(Json::as_array)
+        .is_some_and(|errors| !errors.is_empty())
+    {
+        return None;
+    }
+    let files = match report.get("files")? {
+        Json::Object(files) => files.as_slice(),
+        // PHPStan encodes an empty map as `[]`
+        Json::Array(files) if files.is_empty() => &[],
+        _ => return None,
+    };
+
+    let mut diagnostics = Vec::new();
+    for (file, entry) in files {
+        for message in entry
+            .get("messages")
+            .and_then(Json::as_array)
+            .unwrap_or_default()
+        {
+            let Some(text) = message.get("message").and_then(Json::as_str) else {
+                continue;
+            };
+            diagnostics.push(Diagnostic {
+                file: file.clone(),
+                line: message.get("line").and_then(Json::as_u32).unwrap_or(0),
+                column: message.get("column").and_then(Json::as_u32).unwrap_or(0),
+                // PHPStan calls it `identifier`, phpcs `source`
+                rule: message
+                    .get("identifier")
+                    .or_else(|| message.get("source"))
+                    .and_then(Json::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                message: text.to_string(),
+                severity: match message.get("type").and_then(Json::as_str) {
+                    Some("WARNING") => Severity::Warning,
+                    _ => Severity::Error,
+                },
+                url: None,
+                fix: None,
+            });
+        }
+    }
+    Some(diagnostics)
+}
+
+/// Parse Psalm `--output-format=json` output: an array of issues with `file_path`,
+/// `line_from`, `column_from`, `type`, `severity`, `message`, and a documentation `link`.
+fn parse_psalm_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let issues = json::parse(stdout.trim())?;
+    Some(
+        issues
+            .as_array()?
+            .iter()
+            .filter_map(|issue| {
+                Some(Diagnostic {
+                    file: issue.get("file_path")?.as_str()?.to_string(),
+                    line: issue.get("line_from").and_then(Json::as_u32).unwrap_or(0),
+                    column: issue.get("column_from").and_then(Json::as_u32).unwrap_or(0),
+                    rule: issue
+                        .get("type")
+                        .and_then(Json::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    message: issue.get("message")?.as_str()?.to_string(),
+                    severity: match issue.get("severity").and_then(Json::as_str) {
+                        Some("error") => Severity::Error,
+                        _ => Severity::Warning,
+                    },
+                    url: issue.get("link").and_then(Json::as_str).map(str::to_string),
+                    fix: None,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Lint a C# file with the project's analyzers: `dotnet format analyzers --verify-no-changes`
+/// restricted to the file, falling back to `dotnet build -warnaserror` when the workspace
+/// can't be analyzed (e.g. an SDK without `dotnet format`). Both report MSBuild-style
+/// diagnostics, and only those in the edited file are reported.
+pub fn run_csharp_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    _lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if !is_executable_available("dotnet") {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "no C# linter found for {file_path}. Install the .NET SDK: https://dot.net/download"
+            ),
+        ));
+    }
+    let Some(workspace) = project::dotnet_workspace(Path::new(project_root)) else {
+        return Ok(SkipReason::NoProjectRoot.result(
+            debug,
+            &format!("no .csproj or .sln found for {file_path}, skipping."),
+        ));
+    };
+    let workspace = workspace.to_string_lossy();
+    let config = Config::load(project_root);
+    let relative = Path::new(file_path).strip_prefix(project_root).map_or_else(
+        |_| file_path.to_string(),
+        |p| p.to_string_lossy().to_string(),
+    );
+
+    let output = Command::new("dotnet")
+        .args(["format", "analyzers", &workspace, "--verify-no-changes"])
+        .args(["--include", &relative, "--verbosity", "quiet"])
+        .current_dir(project_root)
+        .output_grouped()?;
+    // Exit code 2 means analyzers found something to fix; anything else but 0 is a failure
+    // to load or analyze the workspace
+    let (linter, output) = if matches!(output.status.code(), Some(0 | 2)) {
+        ("dotnet format", output)
+    } else {
+        let build = Command::new("dotnet")
+            .args(["build", &workspace, "-warnaserror", "-nologo"])
+            .args(["-consoleLoggerParameters:NoSummary"])
+            .current_dir(project_root)
+            .output_grouped()?;
+        ("dotnet build", build)
+    };
+
+    let stdout = encoding::decode(&output.stdout);
+    let stderr = encoding::decode(&output.stderr);
+    let diagnostics: Vec<Diagnostic> = parse_msbuild_diagnostics(&format!("{stdout}\n{stderr}"))
+        .into_iter()
+        .filter(|d| d.is_for_file(file_path, project_root))
+        .collect();
+    let diagnostics = drop_suppressed(diagnostics, project_root);
+    if !diagnostics.is_empty() || output.status.success() || linter == "dotnet format" {
+        return Ok(diagnostics_result(
+            linter,
+            file_path,
+            &diagnostics,
+            &Policy::from_config(&config),
+            |_| true,
+            debug,
+        ));
+    }
+
+    Ok(output_lint_result(
+        linter,
+        file_path,
+        &stdout,
+        &stderr,
+        linter_passed(&config, "dotnet", output.status),
+        debug,
+    ))
+}
+
+/// Parse MSBuild-style diagnostics, as printed by `dotnet build` and `dotnet format`:
+/// `src/Foo.cs(12,5): warning CS0168: The variable 'x' is declared but never used [/ws/App.csproj]`.
+/// The position may also carry an end line and column. Builds repeat diagnostics in their
+/// summary, so each is reported once.
+fn parse_msbuild_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for line in output.lines() {
+        let Some((location, rest)) = line.trim().split_once("): ") else {
+            continue;
+        };
+        let Some((file, position)) = location.rsplit_once('(') else {
+            continue;
+        };
+        let mut numbers = position.split(',').map(str::parse::<u32>);
+        let (Some(Ok(line_number)), Some(Ok(column))) = (numbers.next(), numbers.next()) else {
+            continue;
+        };
+        let Some((kind, message)) = rest.split_once(": ") else {
+            continue;
+        };
+        let (severity, rule) = kind.split_once(' ').unwrap_or((kind, ""));
+        let severity = match severity {
+            "error" => Severity::Error,
+            "warning" => Severity::Warning,
+            _ => continue,
+        };
+        // Drop the trailing ` [/ws/App.csproj]`
+        let message = message
+            .rsplit_once(" [")
+            .filter(|(_, project)| project.ends_with(']'))
+            .map_or(message, |(message, _)| message);
+        let diagnostic = Diagnostic {
+            file: file.to_string(),
+            line: line_number,
+            column,
+            rule: rule.to_string(),
+            message: message.to_string(),
+            severity,
+            url: None,
+            fix: None,
+        };
+        if !diagnostics.contains(&diagnostic) {
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics
+}
+
+/// Lint a Swift file with `swiftlint lint --strict`, so warnings block too. Its JSON report is
+/// scoped to the edited file.
+pub fn run_swift_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if !is_executable_available("swiftlint") {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "no Swift linter found for {file_path}. Install SwiftLint: brew install swiftlint"
+            ),
+        ));
+    }
+
+    let config = Config::load(project_root);
+    let output = Command::new("swiftlint")
+        .args(["lint", "--strict", "--quiet", "--reporter", "json"])
+        .args(["--path", file_path])
+        .current_dir(project_root)
+        .output_grouped()?;
+    let stdout = encoding::decode(&output.stdout);
+
+    if let Some(diagnostics) = parse_swiftlint_json(&stdout) {
+        let rules = LenientRules::from_config(&config, "swift");
+        let diagnostics: Vec<Diagnostic> = diagnostics
+            .into_iter()
+            .filter(|d| d.is_for_file(file_path, project_root))
+            .filter(|d| !lenient || rules.keeps(&d.rule, &SWIFT_LENIENT_RULES))
+            .collect();
+        let diagnostics = drop_suppressed(diagnostics, project_root);
+        return Ok(diagnostics_result(
+            "swiftlint",
+            file_path,
+            &diagnostics,
+            &Policy::from_config(&config),
+            |_| true,
+            debug,
+        ));
+    }
+
+    Ok(output_lint_result(
+        "swiftlint",
+        file_path,
+        &stdout,
+        &encoding::decode(&output.stderr),
+        linter_passed(&config, "swiftlint", output.status),
+        debug,
+    ))
+}
+
+/// swiftlint rules lenient mode relaxes: unused bindings are expected mid-edit.
+const SWIFT_LENIENT_RULES: [&str; 3] = [
+    "unused_closure_parameter",
+    "unused_optional_binding",
+    "unused_enumerated",
+];
+
+/// Parse `swiftlint lint --reporter json` output: an array of violations with `file`, `line`,
+/// `character`, `rule_id`, `reason`, and `severity` (`Warning` or `Error`).
+fn parse_swiftlint_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let violations = json::parse(stdout.trim())?;
+    Some(
+        violations
+            .as_array()?
+            .iter()
+            .filter_map(|violation| {
+                Some(Diagnostic {
+                    file: violation.get("file")?.as_str()?.to_string(),
+                    line: violation.get("line").and_then(Json::as_u32).unwrap_or(0),
+                    column: violation
+                        .get("character")
+                        .and_then(Json::as_u32)
+                        .unwrap_or(0),
+                    rule: violation
+                        .get("rule_id")
+                        .and_then(Json::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    message: violation.get("reason")?.as_str()?.to_string(),
+                    severity: match violation.get("severity").and_then(Json::as_str) {
+                        Some("Warning") => Severity::Warning,
+                        _ => Severity::Error,
+                    },
+                    url: None,
+                    fix: None,
+                })
+            })
+            .collect(),
+    )
+}
+
+pub fn run_shell_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if !is_executable_available("shellcheck") {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "no shell linter found for {file_path}. Install ShellCheck: https://github.com/koalaman/shellcheck#installing"
+            ),
+        ));
+    }
+
+    let config = Config::load(project_root);
+    let mut command = Command::new("shellcheck");
+    command.arg("--format=gcc");
+    // shellcheck has no zsh dialect and refuses zsh scripts; bash is the closest
+    if is_zsh_script(file_path) {
+        command.arg("--shell=bash");
+    }
+    let output = command
+        .arg(file_path)
+        .current_dir(project_root)
+        .output_grouped()?;
+    let stdout = encoding::decode(&output.stdout);
+
+    // 0: clean, 1: findings; anything else is a usage or parse failure reported on stderr
+    if matches!(output.status.code(), Some(0 | 1)) {
+        let rules = LenientRules::from_config(&config, "shell");
+        let diagnostics: Vec<Diagnostic> = parse_gcc_diagnostics(&stdout)
+            .into_iter()
+            .filter(|d| d.is_for_file(file_path, project_root))
+            .filter(|d| !lenient || rules.keeps(&d.rule, &SHELL_LENIENT_RULES))
+            .collect();
+        return Ok(diagnostics_result(
+            "shellcheck",
+            file_path,
+            &diagnostics,
+            &Policy::from_config(&config),
+            |d| d.severity == Severity::Error,
+            debug,
+        ));
+    }
+
+    Ok(output_lint_result(
+        "shellcheck",
+        file_path,
+        &stdout,
+        &encoding::decode(&output.stderr),
+        linter_passed(&config, "shellcheck", output.status),
+        debug,
+    ))
+}
+
+/// Lint a PowerShell script or module with `PSScriptAnalyzer`, through `pwsh`. Only
+/// error-severity rules run (`-Severity Error`), plus parse errors, which `PSScriptAnalyzer`
+/// always reports; each finding blocks. The nearest `PSScriptAnalyzerSettings.psd1` is passed
+/// as `-Settings`.
+pub fn run_powershell_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    const MISSING: &str =
+        "Install PowerShell and PSScriptAnalyzer (Install-Module PSScriptAnalyzer)";
+
+    if !is_executable_available("pwsh") {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!("pwsh not found for {file_path}. {MISSING}."),
+        ));
+    }
+    let config = Config::load(project_root);
+    let quote = |path: &str| format!("'{}'", path.replace('\'', "''"));
+    let file_dir = Path::new(file_path).parent().map_or_else(
+        || project_root.to_string(),
+        |dir| dir.to_string_lossy().to_string(),
+    );
+    let settings = find_nearest_file(&file_dir, &["PSScriptAnalyzerSettings.psd1"])
+        .map(|settings| format!(" -Settings {}", quote(&settings.to_string_lossy())))
+        .unwrap_or_default();
+    // Select the fields so enum values and script extents don't bloat the JSON, and wrap the
+    // results in an array so one finding isn't serialized as a bare object
+    let script = format!(
+        "$ErrorActionPreference = 'Stop'; $found = @(Invoke-ScriptAnalyzer -Path {} -Severity Error{settings}); ConvertTo-Json -Compress -InputObject @($found | Select-Object RuleName, Line, Column, Message, ScriptPath)",
+        quote(file_path)
+    );
+    let output = Command::new("pwsh")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .current_dir(project_root)
+        .output_grouped()?;
+    let stdout = encoding::decode(&output.stdout);
+    let stderr = encoding::decode(&output.stderr);
+
+    let Some(diagnostics) = output
+        .status
+        .success()
+        .then(|| parse_psscriptanalyzer_json(&stdout))
+        .flatten()
+    else {
+        if stderr.contains("Invoke-ScriptAnalyzer") {
+            return Ok(SkipReason::NoLinter.result(
+                debug,
+                &format!("PSScriptAnalyzer not found for {file_path}. {MISSING}."),
+            ));
+        }
+        return Ok(output_lint_result(
+            "PSScriptAnalyzer",
+            file_path,
+            &stdout,
+            &stderr,
+            linter_passed(&config, "pwsh", output.status),
+            debug,
+        ));
+    };
+    let rules = LenientRules::from_config(&config, "powershell");
+    let diagnostics: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|d| d.is_for_file(file_path, project_root))
+        .filter(|d| !lenient || rules.keeps(&d.rule, &[]))
+        .collect();
+    Ok(diagnostics_result(
+        "PSScriptAnalyzer",
+        file_path,
+        &diagnostics,
+        &Policy::from_config(&config),
+        |_| true,
+        debug,
+    ))
+}
+
+/// Parse `PSScriptAnalyzer` findings serialized by `ConvertTo-Json`:
+/// `[{"RuleName":"PSAvoidUsingComputerNameHardcoded","Line":3,"Column":1,"Message":"...",
+/// "ScriptPath":"/ws/build.ps1"}]`. `None` if the output isn't such an array.
+fn parse_psscriptanalyzer_json(output: &str) -> Option<Vec<Diagnostic>> {
+    let findings = json::parse(output.trim())?;
+    findings
+        .as_array()?
+        .iter()
+        .map(|finding| {
+            let field = |key: &str| finding.get(key).and_then(Json::as_str).unwrap_or_default();
+            Some(Diagnostic {
+                file: field("ScriptPath").to_string(),
+                line: finding.get("Line").and_then(Json::as_u32).unwrap_or(1),
+                column: finding.get("Column").and_then(Json::as_u32).unwrap_or(1),
+                rule: field("RuleName").to_string(),
+                message: field("Message").to_string(),
+                severity: Severity::Error,
+                url: None,
+                fix: None,
+            })
+        })
+        .collect()
+}
+
+/// shellcheck codes lenient mode relaxes: variables assigned but not used yet.
+const SHELL_LENIENT_RULES: [&str; 1] = ["SC2034"];
+
+/// A `.zsh` file or an extensionless script whose shebang runs zsh.
+fn is_zsh_script(file_path: &str) -> bool {
+    Path::new(file_path).extension().map_or_else(
+        || {
+            fs::read_to_string(file_path).is_ok_and(|source| {
+                source
+                    .lines()
+                    .next()
+                    .is_some_and(|line| line.starts_with("#!") && line.contains("zsh"))
+            })
+        },
+        |ext| ext == "zsh",
+    )
+}
+
+pub fn run_markdown_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let Some(program) = find_node_bin(project_root, "markdownlint-cli2").or_else(|| {
+        is_executable_available("markdownlint-cli2").then(|| "markdownlint-cli2".to_string())
+    }) else {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "no Markdown linter found for {file_path}. Install markdownlint-cli2: npm install --save-dev markdownlint-cli2"
+            ),
+        ));
+    };
+
+    let config = Config::load(project_root);
+    // Arguments are globs, relative to the directory whose config applies
+    let relative = Path::new(file_path).strip_prefix(project_root).map_or_else(
+        |_| file_path.to_string(),
+        |p| p.to_string_lossy().to_string(),
+    );
+    let output = Command::new(&program)
+        .arg(&relative)
+        .current_dir(project_root)
+        .output_grouped()?;
+    let stdout = encoding::decode(&output.stdout);
+    let stderr = encoding::decode(&output.stderr);
+
+    // 0: clean, 1: findings (on stderr); 2 is a failure such as a broken config
+    if matches!(output.status.code(), Some(0 | 1)) {
+        let rules = LenientRules::from_config(&config, "markdown");
+        let diagnostics: Vec<Diagnostic> = parse_markdownlint(&stderr)
+            .into_iter()
+            .filter(|d| d.is_for_file(file_path, project_root))
+            .filter(|d| !lenient || rules.keeps(&d.rule, &MARKDOWN_LENIENT_RULES))
+            .collect();
+        return Ok(diagnostics_result(
+            "markdownlint-cli2",
+            file_path,
+            &diagnostics,
+            &Policy::from_config(&config),
+            |d| d.severity == Severity::Error,
+            debug,
+        ));
+    }
+
+    Ok(output_lint_result(
+        "markdownlint-cli2",
+        file_path,
+        &stdout,
+        &stderr,
+        linter_passed(&config, "markdownlint-cli2", output.status),
+        debug,
+    ))
+}
+
+/// markdownlint rules lenient mode relaxes: line length.
+const MARKDOWN_LENIENT_RULES: [&str; 1] = ["MD013"];
+
+/// Parse markdownlint-cli2's default output, one result per line:
+/// `README.md:3:10 error MD009/no-trailing-spaces Trailing spaces [Expected: 0; Actual: 1]`.
+/// The column and severity are absent in older versions; results without one are errors.
+/// The rule is the `MDnnn` code, and its aliases lead the message.
+fn parse_markdownlint(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (location, rest) = line.split_once(' ')?;
+            let mut parts = location.splitn(3, ':');
+            let file = parts.next()?;
+            let line_number = parts.next()?.parse().ok()?;
+            let column = parts.next().and_then(|c| c.parse().ok()).unwrap_or(0);
+            let (severity, rest) = match rest.split_once(' ') {
+                Some(("warning", rest)) => (Severity::Warning, rest),
+                Some(("error", rest)) => (Severity::Error, rest),
+                _ => (Severity::Error, rest),
+            };
+            let (names, message) = rest.split_once(' ')?;
+            let (rule, aliases) = names.split_once('/')?;
+            Some(Diagnostic {
+                file: file.to_string(),
+                line: line_number,
+                column,
+                rule: rule.to_string(),
+                message: format!("{aliases}: {message}"),
+                severity,
+                url: None,
+                fix: None,
+            })
+        })
+        .collect()
+}
+
+pub fn run_elixir_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if !is_executable_available("mix") {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "no Elixir linter found for {file_path}. Install Elixir: https://elixir-lang.org/install.html"
+            ),
+        ));
+    }
+
+    let config = Config::load(project_root);
+    let rules = LenientRules::from_config(&config, "elixir");
+    let keep = |d: &Diagnostic| {
+        d.is_for_file(file_path, project_root)
+            && (!lenient || rules.keeps(&d.rule, &ELIXIR_LENIENT_RULES))
+    };
+
+    if uses_credo(project_root) {
+        let relative = Path::new(file_path).strip_prefix(project_root).map_or_else(
+            |_| file_path.to_string(),
+            |p| p.to_string_lossy().to_string(),
+        );
+        let output = Command::new("mix")
+            .args(["credo", "--strict", "--format", "flycheck", &relative])
+            .current_dir(project_root)
+            .output_grouped()?;
+        let diagnostics = parse_credo_flycheck(&encoding::decode(&output.stdout));
+        // Credo exits with a bit mask of the categories it found; any other failure (deps not
+        // fetched, a broken .credo.exs) falls through to the compiler
+        if output.status.success() || !diagnostics.is_empty() {
+            let diagnostics: Vec<Diagnostic> = diagnostics.into_iter().filter(keep).collect();
+            return Ok(diagnostics_result(
+                "credo",
+                file_path,
+                &diagnostics,
+                &Policy::from_config(&config),
+                |_| true,
+                debug,
+            ));
+        }
+    }
+
+    let output = Command::new("mix")
+        .args(["compile", "--warnings-as-errors"])
+        .current_dir(project_root)
+        .output_grouped()?;
+    let stdout = encoding::decode(&output.stdout);
+    let stderr = encoding::decode(&output.stderr);
+    let diagnostics = parse_elixir_compiler(&format!("{stdout}\n{stderr}"));
+    if output.status.success() || !diagnostics.is_empty() {
+        let diagnostics: Vec<Diagnostic> = diagnostics.into_iter().filter(keep).collect();
+        return Ok(diagnostics_result(
+            "mix compile",
+            file_path,
+            &diagnostics,
+            &Policy::from_config(&config),
+            |_| true,
+            debug,
+        ));
+    }
+
+    Ok(output_lint_result(
+        "mix compile",
+        file_path,
+        &stdout,
+        &stderr,
+        linter_passed(&config, "mix", output.status),
+        debug,
+    ))
+}
+
+/// Compiler warnings lenient mode relaxes, named after their messages: unused variables,
+/// aliases, and imports.
+const ELIXIR_LENIENT_RULES: [&str; 3] = ["unused-variable", "unused-alias", "unused-import"];
+
+/// Whether the Mix project at `project_root`, or the umbrella above it, depends on credo.
+fn uses_credo(project_root: &str) -> bool {
+    Path::new(project_root)
+        .ancestors()
+        .any(|dir| fs::read_to_string(dir.join("mix.exs")).is_ok_and(|mix| mix.contains(":credo")))
+}
+
+/// Parse `mix credo --format flycheck` output: `lib/a.ex:3:5: R: Modules should have a
+/// @moduledoc tag.`, without a column for some checks. The letter is the issue's category;
+/// `W` (warning) is an error, the others style issues.
+fn parse_credo_flycheck(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (file, line_number, column, rest) = elixir_location(line)?;
+            let (category, message) = rest.split_once(": ")?;
+            let (rule, severity) = match category {
+                "C" => ("consistency", Severity::Warning),
+                "D" => ("design", Severity::Warning),
+                "R" => ("readability", Severity::Warning),
+                "F" => ("refactor", Severity::Warning),
+                "W" => ("warning", Severity::Error),
+                _ => return None,
+            };
+            Some(Diagnostic {
+                file,
+                line: line_number,
+                column,
+                rule: rule.to_string(),
+                message: message.to_string(),
+                severity,
+                url: None,
+                fix: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse `mix compile` output. A `warning:` or `error:` line is followed by its location,
+/// indented (`lib/a.ex:3: A.f/0`) or, since Elixir 1.15, after a code excerpt
+/// (`└─ lib/a.ex:3:5: A.f/0`). Fatal errors are one line: `** (CompileError) lib/a.ex:3:
+/// undefined function f/0`, or `** (SyntaxError) invalid syntax found on lib/a.ex:3:5:`.
+fn parse_elixir_compiler(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut pending: Option<(Severity, String)> = None;
+    for line in output.lines() {
+        let line = line.trim();
+        let diagnostic =
+            |severity, message: &str, (file, line, column): (String, u32, u32)| Diagnostic {
+                file,
+                line,
+                column,
+                rule: elixir_warning_rule(message).to_string(),
+                message: message.to_string(),
+                severity,
+                url: None,
+                fix: None,
+            };
+        if let Some(message) = line.strip_prefix("warning: ") {
+            pending = Some((Severity::Warning, message.to_string()));
+        } else if let Some(message) = line.strip_prefix("error: ") {
+            pending = Some((Severity::Error, message.to_string()));
+        } else if let Some(fatal) = line.strip_prefix("** (") {
+            let Some((kind, body)) = fatal.split_once(") ") else {
+                continue;
+            };
+            // The location leads the message, or ends it
+            let located = std::iter::once(0)
+                .chain(body.match_indices(' ').map(|(i, _)| i + 1))
+                .find_map(|start| Some((start, elixir_location(&body[start..])?)));
+            if let Some((start, (file, line, column, rest))) = located {
+                let message = if start == 0 && !rest.is_empty() {
+                    rest.to_string()
+                } else {
+                    format!("{kind}: {}", body[..start].trim())
+                };
+                diagnostics.push(diagnostic(Severity::Error, &message, (file, line, column)));
+            }
+        } else if let Some((severity, message)) = &pending {
+            let location = line.strip_prefix("└─ ").unwrap_or(line);
+            if let Some((file, line, column, _)) = elixir_location(location) {
+                diagnostics.push(diagnostic(*severity, message, (file, line, column)));
+                pending = None;
+            }
+        }
+    }
+    diagnostics
+}
+
+/// `path:line[:column]` at the start of `text` for an Elixir source file, and what follows.
+fn elixir_location(text: &str) -> Option<(String, u32, u32, &str)> {
+    let (file, rest) = text.split_once(':')?;
+    if file.contains(char::is_whitespace)
+        || !Path::new(file)
+            .extension()
+            .is_some_and(|ext| ext == "ex" || ext == "exs")
+    {
+        return None;
+    }
+    let (line, rest) = rest.split_once(':').unwrap_or((rest, ""));
+    let line = line.trim().parse().ok()?;
+    let (column, rest) = match rest.split_once(':') {
+        Some((column, after)) if column.parse::<u32>().is_ok() => (column.parse().ok()?, after),
+        _ => (0, rest),
+    };
+    Some((file.to_string(), line, column, rest.trim()))
+}
+
+/// Rule names for the compiler warnings lenient mode can relax.
+fn elixir_warning_rule(message: &str) -> &'static str {
+    if message.starts_with("variable ") && message.contains(" is unused") {
+        "unused-variable"
+    } else if message.starts_with("unused alias") {
+        "unused-alias"
+    } else if message.starts_with("unused import") {
+        "unused-import"
+    } else {
+        ""
+    }
+}
+
+pub fn run_haskell_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load(project_root);
+    let rules = LenientRules::from_config(&config, "haskell");
+    // Lenient mode still reports unused bindings, but doesn't block on them
+    let blocks = |d: &Diagnostic| !lenient || rules.keeps(&d.rule, &HASKELL_LENIENT_RULES);
+
+    if is_executable_available("hlint") {
+        let output = Command::new("hlint")
+            .args(["--json", file_path])
+            .current_dir(project_root)
+            .output_grouped()?;
+        let stdout = encoding::decode(&output.stdout);
+        // Parse errors are reported as hints too
+        if let Some(diagnostics) = parse_hlint_json(&stdout) {
+            let diagnostics: Vec<Diagnostic> = diagnostics
+                .into_iter()
+                .filter(|d| d.is_for_file(file_path, project_root))
+                .collect();
+            return Ok(diagnostics_result(
+                "hlint",
+                file_path,
+                &diagnostics,
+                &Policy::from_config(&config),
+                blocks,
+                debug,
+            ));
+        }
+        return Ok(output_lint_result(
+            "hlint",
+            file_path,
+            &stdout,
+            &encoding::decode(&output.stderr),
+            linter_passed(&config, "hlint", output.status),
+            debug,
+        ));
+    }
+
+    if !is_executable_available("ghc") {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "no Haskell linter found for {file_path}. Install HLint: https://github.com/ndmitchell/hlint#installing-and-running-hlint"
+            ),
+        ));
+    }
+
+    // Type-check without generating code, finding the file's sibling modules
+    let mut command = Command::new("ghc");
+    command.args(["-fno-code", "-Wall"]);
+    if let Some(source_dir) = haskell_source_dir(file_path) {
+        command.arg(format!("-i{}", source_dir.display()));
+    }
+    let output = command
+        .arg(file_path)
+        .current_dir(project_root)
+        .output_grouped()?;
+    let stdout = encoding::decode(&output.stdout);
+    let stderr = encoding::decode(&output.stderr);
+    let diagnostics = parse_ghc_output(&stderr);
+    if output.status.success() || !diagnostics.is_empty() {
+        let diagnostics: Vec<Diagnostic> = diagnostics
+            .into_iter()
+            .filter(|d| d.is_for_file(file_path, project_root))
+            .collect();
+        return Ok(diagnostics_result(
+            "ghc",
+            file_path,
+            &diagnostics,
+            &Policy::from_config(&config),
+            blocks,
+            debug,
+        ));
+    }
+
+    Ok(output_lint_result(
+        "ghc",
+        file_path,
+        &stdout,
+        &stderr,
+        linter_passed(&config, "ghc", output.status),
+        debug,
+    ))
+}
+
+/// ghc warnings lenient mode reports without blocking: bindings and pattern variables that
+/// aren't used yet.
+const HASKELL_LENIENT_RULES: [&str; 4] = [
+    "-Wunused-top-binds",
+    "-Wunused-local-binds",
+    "-Wunused-matches",
+    "-Wunused-pattern-binds",
+];
+
+/// The directory module imports resolve from: `src` for `src/Data/Tree.hs` declaring
+/// `module Data.Tree`. `None` for a file that can't be read.
+fn haskell_source_dir(file_path: &str) -> Option<PathBuf> {
+    let source = fs::read_to_string(file_path).ok()?;
+    let depth = source
+        .lines()
+        .find_map(|line| line.strip_prefix("module "))
+        .and_then(|rest| rest.split_whitespace().next())
+        .map_or(1, |name| name.split('.').count());
+    Path::new(file_path)
+        .ancestors()
+        .nth(depth)
+        .map(Path::to_path_buf)
+}
+
+/// Parse `hlint --json` output, an array of hints with a name (`Use map`), a severity
+/// (`Suggestion`, `Warning`, `Error`), and the suggested replacement.
+fn parse_hlint_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let hints = json::parse(stdout.trim())?;
+    Some(
+        hints
+            .as_array()?
+            .iter()
+            .filter_map(|hint| {
+                let name = hint.get("hint")?.as_str()?;
+                let severity = hint.get("severity").and_then(Json::as_str);
+                if severity == Some("Ignore") {
+                    return None;
+                }
+                let message = match hint.get("to").and_then(Json::as_str) {
+                    Some(to) if !to.is_empty() => format!("{name}. Why not: {to}"),
+                    _ => name.to_string(),
+                };
+                Some(Diagnostic {
+                    file: hint.get("file")?.as_str()?.to_string(),
+                    line: hint.get("startLine").and_then(Json::as_u32).unwrap_or(0),
+                    column: hint.get("startColumn").and_then(Json::as_u32).unwrap_or(0),
+                    rule: name.to_string(),
+                    message,
+                    severity: if severity == Some("Error") {
+                        Severity::Error
+                    } else {
+                        Severity::Warning
+                    },
+                    url: None,
+                    fix: None,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Parse ghc diagnostics: a header such as `src/A.hs:3:1: warning: [GHC-40910]
+/// [-Wunused-top-binds]` (the position may be a span, `3:1-5` or `(3,1)-(4,5)`), then the
+/// indented message, then a code excerpt. The rule is the warning flag, else the error code.
+fn parse_ghc_output(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut in_message = false;
+    for line in output.lines() {
+        if let Some(diagnostic) = parse_ghc_header(line) {
+            diagnostics.push(diagnostic);
+            in_message = true;
+            continue;
+        }
+        let text = line.trim();
+        if text.is_empty() || text.starts_with('|') {
+            in_message = false;
+        }
+        if let Some(last) = diagnostics.last_mut().filter(|_| in_message) {
+            if !last.message.is_empty() {
+                last.message.push(' ');
+            }
+            last.message.push_str(text);
+        }
+    }
+    diagnostics
+}
+
+fn parse_ghc_header(line: &str) -> Option<Diagnostic> {
+    let (location, rest, severity) = [
+        (": error:", Severity::Error),
+        (": warning:", Severity::Warning),
+    ]
+    .into_iter()
+    .find_map(|(marker, severity)| {
+        line.split_once(marker)
+            .map(|(location, rest)| (location, rest, severity))
+    })?;
+    let (file, position) = location.rsplit_once(".hs:")?;
+    let mut numbers = position
+        .trim_start_matches('(')
+        .split([':', ',', '-', ')'])
+        .map(|n| n.parse::<u32>().ok());
+    let line_number = numbers.next().flatten()?;
+    let column = numbers.next().flatten().unwrap_or(0);
+
+    let mut rest = rest.trim();
+    let mut flag = None;
+    let mut code = None;
+    while let Some((tag, after)) = rest.strip_prefix('[').and_then(|r| r.split_once(']')) {
+        // `[-Wunused-imports, Werror=unused-imports]` under -Werror
+        let tag = tag.split(',').next().unwrap_or(tag);
+        if tag.starts_with("-W") {
+            flag = Some(tag);
+        } else {
+            code = Some(tag);
+        }
+        rest = after.trim();
+    }
+    Some(Diagnostic {
+        file: format!("{file}.hs"),
+        line: line_number,
+        column,
+        rule: flag.or(code).unwrap_or_default().to_string(),
+        message: rest.to_string(),
+        severity,
+        url: None,
+        fix: None,
+    })
+}
+
+pub fn run_zig_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if !is_executable_available("zig") {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "no Zig toolchain found for {file_path}. Install Zig: https://ziglang.org/download/"
+            ),
+        ));
+    }
+
+    let config = Config::load(project_root);
+    let ast_check = Command::new("zig")
+        .args(["ast-check", file_path])
+        .current_dir(project_root)
+        .output_grouped()?;
+    let stderr = encoding::decode(&ast_check.stderr);
+    let mut diagnostics = parse_gcc_diagnostics(&stderr);
+    if !ast_check.status.success() && diagnostics.is_empty() {
+        return Ok(output_lint_result(
+            "zig ast-check",
+            file_path,
+            &encoding::decode(&ast_check.stdout),
+            &stderr,
+            linter_passed(&config, "zig", ast_check.status),
+            debug,
+        ));
+    }
+    for diagnostic in &mut diagnostics {
+        diagnostic.rule = zig_error_rule(&diagnostic.message).to_string();
+    }
+
+    // Lists the file when it isn't formatted; syntax errors are left to ast-check
+    let fmt = Command::new("zig")
+        .args(["fmt", "--check", file_path])
+        .current_dir(project_root)
+        .output_grouped()?;
+    if !fmt.status.success() && !encoding::decode(&fmt.stdout).trim().is_empty() {
+        diagnostics.push(Diagnostic {
+            file: file_path.to_string(),
+            line: 1,
+            column: 1,
+            rule: "zig fmt".to_string(),
+            message: format!("file is not formatted; run `zig fmt {file_path}`"),
+            severity: Severity::Error,
+            url: None,
+            fix: None,
+        });
+    }
+
+    let rules = LenientRules::from_config(&config, "zig");
+    let diagnostics: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|d| d.is_for_file(file_path, project_root))
+        .filter(|d| !lenient || rules.keeps(&d.rule, &ZIG_LENIENT_RULES))
+        .collect();
+    Ok(diagnostics_result(
+        "zig ast-check",
+        file_path,
+        &diagnostics,
+        &Policy::from_config(&config),
+        |_| true,
+        debug,
+    ))
+}
+
+/// Compile errors lenient mode relaxes, named after their messages: Zig rejects unused
+/// locals and parameters outright.
+const ZIG_LENIENT_RULES: [&str; 2] = ["unused-variable", "unused-parameter"];
+
+/// Rule names for the `zig ast-check` errors lenient mode can relax.
+fn zig_error_rule(message: &str) -> &'static str {
+    if message.starts_with("unused local") {
+        "unused-variable"
+    } else if message.starts_with("unused function parameter") {
+        "unused-parameter"
+    } else {
+        ""
+    }
+}
+
+pub fn run_lua_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load(project_root);
+    let rules = LenientRules::from_config(&config, "lua");
+
+    // Try linters in order: selene, luacheck
+    let (linter, output, diagnostics) = if is_executable_available("selene") {
+        let output = Command::new("selene")
+            .args(["--display-style", "quiet", file_path])
+            .current_dir(project_root)
+            .output_grouped()?;
+        let diagnostics = parse_selene_quiet(&encoding::decode(&output.stdout));
+        ("selene", output, diagnostics)
+    } else if is_executable_available("luacheck") {
+        let mut command = Command::new("luacheck");
+        command.args(["--formatter", "plain", "--codes"]);
+        // The built-in lenient list is luacheck's own: every unused variable, argument, and value
+        if lenient && rules == LenientRules::Allow(None) {
+            command.arg("--no-unused");
+        }
+        let output = command
+            .arg(file_path)
+            .current_dir(project_root)
+            .output_grouped()?;
+        let diagnostics = parse_luacheck_plain(&encoding::decode(&output.stdout));
+        ("luacheck", output, diagnostics)
+    } else {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "no Lua linter found for {file_path}. Install selene (cargo install selene) or luacheck (luarocks install luacheck)"
+            ),
+        ));
+    };
+
+    // selene exits 1 and luacheck 1 or 2 (syntax errors) on findings; anything else failed
+    let findings_exit = if linter == "selene" { 0..=1 } else { 0..=2 };
+    if !output
+        .status
+        .code()
+        .is_some_and(|code| findings_exit.contains(&code))
+        || (!output.status.success() && diagnostics.is_empty())
+    {
+        return Ok(output_lint_result(
+            linter,
+            file_path,
+            &encoding::decode(&output.stdout),
+            &encoding::decode(&output.stderr),
+            linter_passed(&config, linter, output.status),
+            debug,
+        ));
+    }
+    let diagnostics: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|d| d.is_for_file(file_path, project_root))
+        .filter(|d| !lenient || rules.keeps(&d.rule, &SELENE_LENIENT_RULES))
+        .collect();
+    let diagnostics = drop_suppressed(diagnostics, project_root);
+    Ok(diagnostics_result(
+        linter,
+        file_path,
+        &diagnostics,
+        &Policy::from_config(&config),
+        |_| true,
+        debug,
+    ))
+}
+
+/// Lint a Nix expression with `statix check`, whose findings (anti-patterns and parse errors)
+/// all block. Without statix, `nix-instantiate --parse` checks the syntax.
+pub fn run_nix_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load(project_root);
+
+    if !is_executable_available("statix") {
+        if !is_executable_available("nix-instantiate") {
+            return Ok(SkipReason::NoLinter.result(
+                debug,
+                &format!(
+                    "no Nix linter found for {file_path}. Install statix (nix profile install nixpkgs#statix) or Nix"
+                ),
+            ));
+        }
+        // The parsed expression goes to stdout; only the syntax error on stderr is of interest
+        let output = Command::new("nix-instantiate")
+            .args(["--parse", file_path])
+            .current_dir(project_root)
+            .output_grouped()?;
+        return Ok(output_lint_result(
+            "nix-instantiate --parse",
+            file_path,
+            "",
+            &encoding::decode(&output.stderr),
+            linter_passed(&config, "nix-instantiate", output.status),
+            debug,
+        ));
+    }
+
+    let output = Command::new("statix")
+        .args(["check", "--format", "errfmt", file_path])
+        .current_dir(project_root)
+        .output_grouped()?;
+    let diagnostics = parse_statix_errfmt(&encoding::decode(&output.stdout));
+    if !output.status.success() && diagnostics.is_empty() {
+        return Ok(output_lint_result(
+            "statix",
+            file_path,
+            &encoding::decode(&output.stdout),
+            &encoding::decode(&output.stderr),
+            linter_passed(&config, "statix", output.status),
+            debug,
+        ));
+    }
+    let rules = LenientRules::from_config(&config, "nix");
+    let diagnostics: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|d| d.is_for_file(file_path, project_root))
+        .filter(|d| !lenient || rules.keeps(&d.rule, &[]))
+        .collect();
+    let diagnostics = drop_suppressed(diagnostics, project_root);
+    Ok(diagnostics_result(
+        "statix",
+        file_path,
+        &diagnostics,
+        &Policy::from_config(&config),
+        |_| true,
+        debug,
+    ))
+}
+
+/// Parse `statix check --format errfmt` output: `flake.nix>12:5:W:20:Assignment instead of
+/// inherit`. The rule is the code statix prints and `statix explain` takes, e.g. `W20`.
+fn parse_statix_errfmt(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (file, rest) = line.split_once('>')?;
+            let mut fields = rest.splitn(5, ':');
+            let line_number = fields.next()?.parse().ok()?;
+            let column = fields.next()?.parse().ok()?;
+            let severity = fields.next()?;
+            let code: u32 = fields.next()?.parse().ok()?;
+            let message = fields.next()?;
+            Some(Diagnostic {
+                file: file.to_string(),
+                line: line_number,
+                column,
+                rule: format!("{severity}{code:02}"),
+                message: message.to_string(),
+                severity: if severity == "E" {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                },
+                url: None,
+                fix: None,
+            })
+        })
+        .collect()
+}
+
+/// Lint a Solidity file with `solhint`, then compile it with `forge build --force` in a Foundry
+/// project. solhint's errors and the compiler's errors in the file block; warnings are listed.
+pub fn run_solidity_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let solhint = find_node_bin(project_root, "solhint")
+        .or_else(|| is_executable_available("solhint").then(|| "solhint".to_string()));
+    let forge =
+        Path::new(project_root).join("foundry.toml").exists() && is_executable_available("forge");
+    if solhint.is_none() && !forge {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "no Solidity linter found for {file_path}. Install solhint (npm install --save-dev solhint) or Foundry for a Foundry project"
+            ),
+        ));
+    }
+    let config = Config::load(project_root);
+    let mut diagnostics = Vec::new();
+    let mut linters = Vec::new();
+
+    if let Some(solhint) = solhint {
+        let output = Command::new(solhint)
+            .args(["--formatter", "unix", file_path])
+            .current_dir(project_root)
+            .output_grouped()?;
+        let stdout = encoding::decode(&output.stdout);
+        let found = parse_solhint_unix(&stdout);
+        if !output.status.success() && found.is_empty() {
+            return Ok(output_lint_result(
+                "solhint",
+                file_path,
+                &stdout,
+                &encoding::decode(&output.stderr),
+                linter_passed(&config, "solhint", output.status),
+                debug,
+            ));
+        }
+        diagnostics.extend(found);
+        linters.push("solhint");
+    }
+
+    if forge {
+        // Only the edited file and what it imports are compiled
+        let output = Command::new("forge")
+            .args(["build", "--force", "--color", "never", file_path])
+            .current_dir(project_root)
+            .output_grouped()?;
+        let stdout = encoding::decode(&output.stdout);
+        let stderr = encoding::decode(&output.stderr);
+        let found = parse_solc_diagnostics(&format!("{stdout}\n{stderr}"));
+        if !output.status.success() && found.is_empty() {
+            return Ok(output_lint_result(
+                "forge build",
+                file_path,
+                &stdout,
+                &stderr,
+                linter_passed(&config, "forge", output.status),
+                debug,
+            ));
+        }
+        diagnostics.extend(found);
+        linters.push("forge build");
+    }
+
+    let rules = LenientRules::from_config(&config, "solidity");
+    let diagnostics: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|d| d.is_for_file(file_path, project_root))
+        .filter(|d| !lenient || rules.keeps(&d.rule, &SOLIDITY_LENIENT_RULES))
+        .collect();
+    let diagnostics = drop_suppressed(diagnostics, project_root);
+    Ok(diagnostics_result(
+        &linters.join(" and "),
+        file_path,
+        &diagnostics,
+        &Policy::from_config(&config),
+        |d| d.severity == Severity::Error,
+        debug,
+    ))
+}
+
+/// solhint rules and solc warning codes lenient mode relaxes: unused imports, variables, and
+/// parameters (solc 2072 and 5667).
+const SOLIDITY_LENIENT_RULES: [&str; 4] = ["no-unused-import", "no-unused-vars", "2072", "5667"];
+
+/// Parse `solhint --formatter unix` output: `contracts/Token.sol:12:5: Variable "x" is unused
+/// [Warning/no-unused-vars]`.
+fn parse_solhint_unix(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (file, line_number, column, rest) = split_location(line)?;
+            let (message, tag) = rest.trim_end().strip_suffix(']')?.rsplit_once(" [")?;
+            let (severity, rule) = tag.split_once('/')?;
+            Some(Diagnostic {
+                file: file.to_string(),
+                line: line_number,
+                column,
+                rule: rule.to_string(),
+                message: message.to_string(),
+                severity: if severity == "Error" {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                },
+                url: None,
+                fix: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse solc diagnostics as `forge build` prints them: `Error (7576): Undeclared identifier.`
+/// followed by ` --> src/Counter.sol:10:9:`. The rule is solc's error code.
+fn parse_solc_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        let Some((kind, message)) = line.trim().split_once("): ") else {
+            continue;
+        };
+        let Some((severity, code)) = kind.split_once(" (") else {
+            continue;
+        };
+        let severity = match severity {
+            "Error" => Severity::Error,
+            "Warning" => Severity::Warning,
+            _ => continue,
+        };
+        let Some(location) = lines
+            .next()
+            .and_then(|l| l.trim().strip_prefix("--> "))
+            .map(|l| l.trim_end_matches(':'))
+        else {
+            continue;
+        };
+        let mut parts = location.rsplitn(3, ':');
+        let (Some(column), Some(line_number), Some(file)) = (
+            parts.next().and_then(|c| c.parse().ok()),
+            parts.next().and_then(|l| l.parse().ok()),
+            parts.next(),
+        ) else {
+            continue;
+        };
+        diagnostics.push(Diagnostic {
+            file: file.to_string(),
+            line: line_number,
+            column,
+            rule: code.to_string(),
+            message: message.to_string(),
+            severity,
+            url: None,
+            fix: None,
+        });
+    }
+    diagnostics
+}
+
+/// Lint a Terraform or `OpenTofu` file with `tflint --filter`, from its module directory so the
+/// module's variables and `.tflint.hcl` apply, then, when a `policy/` directory exists between
+/// the module and the repository root, check it against those Rego policies with
+/// `conftest test`. tflint's errors and policy failures block; warnings are listed.
+pub fn run_terraform_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let policy_dir = find_policy_dir(project_root).filter(|_| is_executable_available("conftest"));
+    let tflint = is_executable_available("tflint");
+    if !tflint && policy_dir.is_none() {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "tflint not found for {file_path}. Install tflint: https://github.com/terraform-linters/tflint"
+            ),
+        ));
+    }
+    let config = Config::load(project_root);
+    let mut diagnostics = Vec::new();
+    let mut linters = Vec::new();
+
+    if tflint {
+        let name = Path::new(file_path)
+            .file_name()
+            .map_or_else(String::new, |n| n.to_string_lossy().to_string());
+        let output = Command::new("tflint")
+            .args(["--format=compact", &format!("--filter={name}")])
+            .current_dir(project_root)
+            .output_grouped()?;
+        let stdout = encoding::decode(&output.stdout);
+        let found = parse_tflint_compact(&stdout);
+        if !output.status.success() && found.is_empty() {
+            return Ok(output_lint_result(
+                "tflint",
+                file_path,
+                &stdout,
+                &encoding::decode(&output.stderr),
+                linter_passed(&config, "tflint", output.status),
+                debug,
+            ));
+        }
+        diagnostics.extend(found);
+        linters.push("tflint");
+    }
+
+    if let Some(policy_dir) = policy_dir {
+        let output = Command::new("conftest")
+            .args(["test", "--parser", "hcl2", "--output", "json", "--policy"])
+            .arg(&policy_dir)
+            .arg(file_path)
+            .current_dir(project_root)
+            .output_grouped()?;
+        let stdout = encoding::decode(&output.stdout);
+        let Some(found) = parse_conftest_json(&stdout) else {
+            return Ok(output_lint_result(
+                "conftest",
+                file_path,
+                &stdout,
+                &encoding::decode(&output.stderr),
+                linter_passed(&config, "conftest", output.status),
+                debug,
+            ));
+        };
+        // conftest reports the file as it was given; findings have no location within it
+        diagnostics.extend(found.into_iter().map(|d| Diagnostic {
+            file: file_path.to_string(),
+            ..d
+        }));
+        linters.push("conftest");
+    }
+
+    let rules = LenientRules::from_config(&config, "terraform");
+    let diagnostics: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|d| d.is_for_file(file_path, project_root))
+        .filter(|d| !lenient || rules.keeps(&d.rule, &TERRAFORM_LENIENT_RULES))
+        .collect();
+    Ok(diagnostics_result(
+        &linters.join(" and "),
+        file_path,
+        &diagnostics,
+        &Policy::from_config(&config),
+        |d| d.severity == Severity::Error,
+        debug,
+    ))
+}
+
+/// tflint rules lenient mode relaxes: declared but unused variables, locals, and data sources.
+const TERRAFORM_LENIENT_RULES: [&str; 1] = ["terraform_unused_declarations"];
+
+/// The nearest `policy/` directory at or above a Terraform module, up to the repository root.
+fn find_policy_dir(module_dir: &str) -> Option<PathBuf> {
+    for dir in Path::new(module_dir).ancestors() {
+        if dir.join("policy").is_dir() {
+            return Some(dir.join("policy"));
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Parse `tflint --format=compact` output: `main.tf:3:1: Warning - Missing version constraint
+/// for provider "aws" in "required_providers" (terraform_required_providers)`.
+fn parse_tflint_compact(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (file, line_number, column, rest) = split_location(line)?;
+            let (severity, message) = rest.split_once(" - ")?;
+            let (message, rule) = message
+                .strip_suffix(')')
+                .and_then(|m| m.rsplit_once(" ("))
+                .unwrap_or((message, "tflint"));
+            Some(Diagnostic {
+                file: file.to_string(),
+                line: line_number,
+                column,
+                rule: rule.to_string(),
+                message: message.to_string(),
+                severity: if severity == "Error" {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                },
+                url: None,
+                fix: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse `conftest test --output json`: one result per file, with the messages of the
+/// `deny`/`violation` rules that failed and the `warn` rules that matched:
+/// `[{"filename":"main.tf","namespace":"main","failures":[{"msg":"S3 bucket is public"}],
+/// "warnings":[]}]`. The rule is the policy's namespace. `None` if the output isn't JSON.
+fn parse_conftest_json(output: &str) -> Option<Vec<Diagnostic>> {
+    let results = json::parse(output.trim())?;
+    let mut diagnostics = Vec::new();
+    for result in results.as_array()? {
+        let file = result
+            .get("filename")
+            .and_then(Json::as_str)
+            .unwrap_or_default();
+        let namespace = result
+            .get("namespace")
+            .and_then(Json::as_str)
+            .unwrap_or("main");
+        for (key, severity) in [
+            ("failures", Severity::Error),
+            ("warnings", Severity::Warning),
+        ] {
+            let messages = result.get(key).and_then(Json::as_array).unwrap_or_default();
+            for message in messages {
+                diagnostics.push(Diagnostic {
+                    file: file.to_string(),
+                    line: 1,
+                    column: 1,
+                    rule: namespace.to_string(),
+                    message: message
+                        .get("msg")
+                        .and_then(Json::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    severity,
+                    url: None,
+                    fix: None,
+                });
+            }
+        }
+    }
+    Some(diagnostics)
+}
+
+/// selene lints lenient mode relaxes. luacheck's codes differ; it gets `--no-unused` instead.
+const SELENE_LENIENT_RULES: [&str; 1] = ["unused_variable"];
+
+/// Parse `selene --display-style quiet` output: `init.lua:3:7: warning[unused_variable]: x is
+/// defined, but never used`. Parse errors are `error[parse_error]`.
+fn parse_selene_quiet(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (file, line_number, column, rest) = split_location(line)?;
+            let (kind, message) = rest.split_once(": ")?;
+            let (severity, rule) = kind.trim_end_matches(']').split_once('[')?;
+            Some(Diagnostic {
+                file: file.to_string(),
+                line: line_number,
+                column,
+                rule: rule.to_string(),
+                message: message.to_string(),
+                severity: if severity == "error" {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                },
+                url: None,
+                fix: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse `luacheck --formatter plain --codes` output: `init.lua:3:7: (W211) unused variable
+/// 'x'`. `E` codes are syntax errors.
+fn parse_luacheck_plain(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (file, line_number, column, rest) = split_location(line)?;
+            let (code, message) = rest.strip_prefix('(')?.split_once(") ")?;
+            Some(Diagnostic {
+                file: file.to_string(),
+                line: line_number,
+                column,
+                rule: code.to_string(),
+                message: message.to_string(),
+                severity: if code.starts_with('E') {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                },
+                url: None,
+                fix: None,
+            })
+        })
+        .collect()
+}
+
+/// Lint a Scala file through sbt, following the Java pattern: the build is asked which lint
+/// tasks it defines (cached until `build.sbt` or `project/plugins.sbt` changes), and the first
+/// of sbt-scalafix's `scalafix --check` and scalastyle-sbt-plugin's `scalastyle` runs. Both
+/// check the whole build, so findings are filtered to the file.
+pub fn run_scala_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    const SBT_LINTERS: &[(&str, &str)] = &[
+        ("scalafix", "scalafix --check"),
+        ("scalastyle", "scalastyle"),
+    ];
+
+    if !is_executable_available("sbt") {
+        return Ok(SkipReason::NoLinter
+            .result(debug, &format!("sbt not found for {file_path}, skipping.")));
+    }
+    let config = Config::load(project_root);
+    let rules = LenientRules::from_config(&config, "scala");
+
+    let build_files: Vec<_> = ["build.sbt", "project/plugins.sbt"]
+        .iter()
+        .map(|f| Path::new(project_root).join(f))
+        .collect();
+    let configured = cached_java_tasks(project_root, &build_files, || {
+        let output = Command::new("sbt")
+            .args(["-batch", "-no-colors", "tasks -V"])
+            .current_dir(project_root)
+            .output_grouped()?;
+        let stdout = encoding::decode(&output.stdout);
+        if output.status.success() {
+            Ok(Ok(parse_sbt_tasks(&stdout)))
+        } else {
+            let stderr = encoding::decode(&output.stderr);
+            Ok(Err(format!("{stderr}\n{stdout}")))
+        }
+    })?;
+    // The build itself is broken, so any lint task would fail too
+    let configured = match configured {
+        Ok(tasks) => tasks,
+        Err(output) => {
+            return Ok(output_lint_result(
+                "sbt tasks",
+                file_path,
+                &output,
+                "",
+                false,
+                debug,
+            ));
+        }
+    };
+
+    let Some((_, command)) = SBT_LINTERS
+        .iter()
+        .find(|(task, _)| configured.iter().any(|t| t == task))
+    else {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!(
+                "no Scala linter configured for {file_path}. Add sbt-scalafix or scalastyle-sbt-plugin to project/plugins.sbt."
+            ),
+        ));
+    };
+    let linter = format!("sbt {command}");
+    let output = Command::new("sbt")
+        .args(["-batch", "-no-colors", command])
+        .current_dir(project_root)
+        .output_grouped()?;
+    let stdout = encoding::decode(&output.stdout);
+    let diagnostics = parse_sbt_diagnostics(&stdout);
+    if !output.status.success() && diagnostics.is_empty() {
+        return Ok(output_lint_result(
+            &linter,
+            file_path,
+            &stdout,
+            &encoding::decode(&output.stderr),
+            linter_passed(&config, "sbt", output.status),
+            debug,
+        ));
+    }
+    let diagnostics: Vec<Diagnostic> = diagnostics
+        .into_iter()
+        .filter(|d| d.is_for_file(file_path, project_root))
+        .filter(|d| !lenient || rules.keeps(&d.rule, &SCALA_LENIENT_RULES))
+        .collect();
+    let diagnostics = drop_suppressed(diagnostics, project_root);
+    Ok(diagnostics_result(
+        &linter,
+        file_path,
+        &diagnostics,
+        &Policy::from_config(&config),
+        |d| d.severity == Severity::Error,
+        debug,
+    ))
+}
+
+/// Scalafix rules lenient mode drops unless `[lenient.scala]` says otherwise.
+const SCALA_LENIENT_RULES: [&str; 1] = ["RemoveUnused"];
+
+/// Task names listed by `sbt "tasks -V"`: indented `name   description` lines.
+fn parse_sbt_tasks(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| line.starts_with(char::is_whitespace))
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|name| name.chars().next().is_some_and(char::is_alphabetic))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parse sbt's console output for scalafix and scalastyle findings:
+/// `[error] /ws/src/App.scala:3:1: error: [RemoveUnused] Unused import` and
+/// `[warn] /ws/src/App.scala:5: Magic number`. A `--- <file>` diff header from
+/// `scalafix --check` means a rewrite rule would change the file.
+fn parse_sbt_diagnostics(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (severity, body) = if let Some(body) = line.strip_prefix("[error] ") {
+                (Severity::Error, body)
+            } else {
+                (Severity::Warning, line.strip_prefix("[warn] ")?)
+            };
+            let diagnostic = |file: &str, line, column, rule: &str, message: &str| Diagnostic {
+                file: file.to_string(),
+                line,
+                column,
+                rule: rule.to_string(),
+                message: message.to_string(),
+                severity,
+                url: None,
+                fix: None,
+            };
+            if let Some(file) = body.strip_prefix("--- ") {
+                return Some(diagnostic(
+                    file.trim(),
+                    1,
+                    1,
+                    "scalafix",
+                    "scalafix would rewrite this file; run `sbt scalafix`",
+                ));
+            }
+            let (file, line_number, column, rest) = split_location(body).or_else(|| {
+                let mut parts = body.splitn(3, ':');
+                let file = parts.next()?;
+                let line_number = parts.next()?.parse().ok()?;
+                Some((file, line_number, 1, parts.next()?.trim()))
+            })?;
+            let message = ["error: ", "warning: "]
+                .iter()
+                .find_map(|prefix| rest.strip_prefix(prefix))
+                .unwrap_or(rest);
+            let (rule, message) = message
+                .strip_prefix('[')
+                .and_then(|m| m.split_once("] "))
+                .unwrap_or(("", message));
+            Some(diagnostic(file, line_number, column, rule, message))
+        })
+        .collect()
+}
+
+/// Split `file:line:column: rest`.
+fn split_location(line: &str) -> Option<(&str, u32, u32, &str)> {
+    let mut parts = line.splitn(4, ':');
+    let file = parts.next()?;
+    let line_number = parts.next()?.parse().ok()?;
+    let column = parts.next()?.parse().ok()?;
+    Some((file, line_number, column, parts.next()?.trim()))
+}
+
+fn is_go_module_file(file_path: &str) -> bool {
+    Path::new(file_path)
+        .file_name()
+        .is_some_and(|name| name == "go.mod" || name == "go.sum")
+}
+
+/// Gate a go.mod/go.sum edit on the module still building (`go build ./...`). With
+/// `[go] mod_tidy_check = true`, also require `go mod tidy -diff` (Go 1.23+) to be clean, and
+/// `go mod verify` to pass.
+fn run_go_mod_check(
+    file_path: &str,
+    project_root: &str,
+    config: &Config,
+    debug: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if !is_executable_available("go") {
+        return Ok(
+            SkipReason::NoLinter.result(debug, &format!("go not found for {file_path}, skipping."))
+        );
+    }
+
+    let mut checks: Vec<&[&str]> = Vec::new();
+    if config.get_bool("go.mod_tidy_check") == Some(true) {
+        checks.push(&["mod", "tidy", "-diff"]);
+        checks.push(&["mod", "verify"]);
+    }
+    checks.push(&["build", "./..."]);
+
+    for args in checks {
+        let output = Command::new("go")
+            .args(args)
+            .current_dir(project_root)
+            .output_grouped()?;
+        if !output.status.success() {
+            return Ok(output_lint_result(
+                &format!("go {}", args.join(" ")),
+                file_path,
+                &encoding::decode(&output.stdout),
+                &encoding::decode(&output.stderr),
+                false,
+                debug,
+            ));
+        }
+    }
+    Ok(output_lint_result(
+        "go build ./...",
+        file_path,
+        "",
+        "",
+        true,
+        debug,
+    ))
+}
+
+pub fn run_go_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load(project_root);
+    if is_go_module_file(file_path) {
+        return run_go_mod_check(file_path, project_root, &config, debug);
+    }
+
+    // Try linters in order: golangci-lint (comprehensive), revive, staticcheck, go vet.
+    // All of them check whole packages, so they get the file's package directory and their
+    // JSON reports are scoped to the file.
+    let linters: &[(&str, &[&str])] = &[
+        (
+            "golangci-lint",
+            &["run", "--fast", "--out-format", "json", "{{package}}"],
+        ),
+        ("revive", &["-formatter", "json", "{{package}}"]),
+        ("staticcheck", &["-f", "json", "{{package}}"]),
+    ];
+    let package = go_package_arg(file_path, project_root);
+
+    for (linter, args) in linters {
+        if fast_tier_skips(project_root, Lang::Go, linter) {
+            continue;
+        }
+        // Check if linter exists in PATH
+        if let Ok(output) = Command::new("which").arg(linter).output() {
+            if output.status.success() {
+                let mut actual_args: Vec<String> = args
+                    .iter()
+                    .map(|a| {
+                        a.replace("{{file}}", file_path)
+                            .replace("{{package}}", &package)
+                    })
+                    .collect();
+
+                if *linter == "revive" {
+                    if let Some(revive_config) = revive_config(file_path) {
+                        actual_args.splice(
+                            0..0,
+                            ["-config".to_string(), revive_config.display().to_string()],
+                        );
+                    }
+                }
+
+                let rules = LenientRules::from_config(&config, "go");
+                let lenient_defaults = go_lenient_defaults(linter);
+                if lenient && *linter == "golangci-lint" {
+                    actual_args.extend(
+                        rules
+                            .allowed(lenient_defaults)
+                            .iter()
+                            .map(|rule| format!("--disable={rule}")),
+                    );
+                }
+
+                let output = Command::new(linter)
+                    .args(&actual_args)
+                    .current_dir(project_root)
+                    .output_grouped()?;
+                let stdout = encoding::decode(&output.stdout);
+
+                // Package-wide JSON issues are scoped to the edited file
+                let parsed = match *linter {
+                    "golangci-lint" => parse_golangci_json(&stdout),
+                    "revive" => parse_revive_json(&stdout),
+                    _ => parse_staticcheck_json(&stdout),
+                };
+                if let Some(diagnostics) = parsed {
+                    let diagnostics: Vec<Diagnostic> = diagnostics
+                        .into_iter()
+                        .filter(|d| d.is_for_file(file_path, project_root))
+                        .filter(|d| !lenient || rules.keeps(&d.rule, lenient_defaults))
+                        .collect();
+                    let diagnostics = drop_suppressed(diagnostics, project_root);
+                    return Ok(diagnostics_result(
+                        linter,
+                        file_path,
+                        &diagnostics,
+                        &Policy::from_config(&config),
+                        |_| true,
+                        debug,
+                    ));
+                }
+
+                return Ok(output_lint_result(
+                    linter,
+                    file_path,
+                    &stdout,
+                    &encoding::decode(&output.stderr),
+                    linter_passed(&config, linter, output.status),
+                    debug,
+                ));
+            }
+        }
+    }
+
+    // Fallback to go vet (always available with Go installation)
+    if let Ok(output) = Command::new("which").arg("go").output() {
+        if output.status.success() {
+            return run_go_vet(file_path, project_root, &package, &config, debug);
+        }
+    }
+
+    // No linter found
+    Ok(SkipReason::NoLinter.result(debug,
+        &format!(
+            "no Go linter found for {file_path}. Install golangci-lint for best results: https://golangci-lint.run"
+        ),
+    ))
+}
+
+/// Run `go vet -json` on the file's package and report the findings in `file_path`.
+fn run_go_vet(
+    file_path: &str,
+    project_root: &str,
+    package: &str,
+    config: &Config,
+    debug: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let output = Command::new("go")
+        .args(["vet", "-json", package])
+        .current_dir(project_root)
+        .output_grouped()?;
+
+    // Findings go to stderr as JSON; build errors stay plain text and fail the run
+    let stderr = encoding::decode(&output.stderr);
+    if let Some(diagnostics) = parse_go_vet_json(&stderr) {
+        let diagnostics: Vec<Diagnostic> = diagnostics
+            .into_iter()
+            .filter(|d| d.is_for_file(file_path, project_root))
+            .collect();
+        let diagnostics = drop_suppressed(diagnostics, project_root);
+        if output.status.success() || !diagnostics.is_empty() {
+            return Ok(diagnostics_result(
+                "go vet",
+                file_path,
+                &diagnostics,
+                &Policy::from_config(config),
+                |_| true,
+                debug,
+            ));
+        }
+    }
+
+    Ok(output_lint_result(
+        "go vet",
+        file_path,
+        &encoding::decode(&output.stdout),
+        &stderr,
+        linter_passed(config, "go", output.status),
+        debug,
+    ))
+}
+
+pub fn run_c_lint(
+    file_path: &str,
+    project_root: &str,
+    debug: bool,
+    _lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load(project_root);
+
+    // A compile database knows the real flags, so its command is the best syntax check
+    let database = compile_database(project_root).and_then(|db| fs::read_to_string(db).ok());
+    if let Some(entry) = database
+        .as_deref()
+        .and_then(|text| compile_command_for(text, file_path))
+    {
+        let (program, args) = entry.syntax_only_args();
+        let output = Command::new(program)
+            .args(args)
+            .current_dir(&entry.directory)
+            .output_grouped()?;
+        if output.status.success() && is_objc_file(file_path) {
+            if let Some(result) = run_oclint(file_path, &entry.directory, &config, debug)? {
+                return Ok(result);
+            }
+        } else if output.status.success() && !fast_tier_skips(project_root, Lang::C, "clang-tidy") {
+            let tidy = ClangTidy::Database(&entry.directory);
+            if let Some(result) = run_clang_tidy(file_path, &tidy, debug)? {
+                return Ok(result);
+            }
+        }
+        return Ok(output_lint_result(
+            program,
+            file_path,
+            &encoding::decode(&output.stdout),
+            &encoding::decode(&output.stderr),
+            output.status.success(),
+            debug,
+        ));
+    }
+
+    // Headers aren't in the database: check one translation unit that includes them instead
+    if is_header_file(file_path) {
+        if let Some(entry) = database
+            .as_deref()
+            .and_then(|text| including_translation_unit(text, file_path))
+        {
+            return run_header_via_translation_unit(file_path, &entry, debug);
+        }
+    }
+
+    run_c_without_database(file_path, project_root, &config, debug)
+}
+
+/// Check a file that has no compile database entry (plain Makefile projects), guessing include
+/// paths from the project layout.
+fn run_c_without_database(
+    file_path: &str,
+    project_root: &str,
+    config: &Config,
+    debug: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let includes = guess_include_dirs(file_path, project_root);
+    // Without a database clang-tidy only guesses the flags, so it runs when the project
+    // configures it
+    let dir = Path::new(file_path)
+        .parent()
+        .unwrap_or_else(|| Path::new(project_root));
+    if !is_objc_file(file_path)
+        && find_nearest_file(&dir.to_string_lossy(), &[".clang-tidy"]).is_some()
+        && !fast_tier_skips(project_root, Lang::C, "clang-tidy")
+    {
+        let tidy = ClangTidy::Includes(project_root, &includes);
+        if let Some(result) = run_clang_tidy(file_path, &tidy, debug)? {
+            return Ok(result);
+        }
+    }
+    // cppcheck doesn't parse Objective-C
+    if !is_objc_file(file_path) && is_executable_available("cppcheck") {
+        let mut args = vec![
+            "--enable=warning".to_string(),
+            "--quiet".to_string(),
+            "--template=gcc".to_string(),
+            "--error-exitcode=1".to_string(),
+        ];
+        args.extend(includes.iter().map(|dir| format!("-I{dir}")));
+        args.push(file_path.to_string());
+        let output = Command::new("cppcheck")
+            .args(&args)
+            .current_dir(project_root)
+            .output_grouped()?;
+        if !linter_passed(config, "cppcheck", output.status) {
+            return Ok(output_lint_result(
+                "cppcheck",
+                file_path,
+                &encoding::decode(&output.stdout),
+                &encoding::decode(&output.stderr),
+                false,
+                debug,
+            ));
+        }
+    }
+
+    let Some(compiler) = c_compiler(file_path) else {
+        return Ok(SkipReason::NoLinter.result(
+            debug,
+            &format!("no C/C++ compiler found for {file_path}, skipping."),
+        ));
+    };
+    let mut args = vec!["-fsyntax-only".to_string()];
+    if is_objc_file(file_path) {
+        args.extend(objc_args());
+    }
+    args.extend(includes.iter().map(|dir| format!("-I{dir}")));
+    args.push(file_path.to_string());
+    let output = Command::new(compiler)
+        .args(&args)
+        .current_dir(project_root)
+        .output_grouped()?;
+    Ok(output_lint_result(
+        &format!("{compiler} -fsyntax-only"),
+        file_path,
+        &encoding::decode(&output.stdout),
+        &encoding::decode(&output.stderr),
+        output.status.success(),
+        debug,
+    ))
+}
+
+/// How clang-tidy learns a file's compile flags.
+enum ClangTidy<'a> {
+    /// `-p` with the directory of the file's compile database entry
+    Database(&'a str),
+    /// Run from the project root with guessed include directories
+    Includes(&'a str, &'a [String]),
+}
+
+/// Run clang-tidy on a C/C++ file and report its findings in that file; the checks are the
+/// project's `.clang-tidy` or clang-tidy's defaults. Returns `None` if clang-tidy isn't
+/// installed or finds nothing in the file that blocks.
+fn run_clang_tidy(
+    file_path: &str,
+    tidy: &ClangTidy,
+    debug: bool,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    if !is_executable_available("clang-tidy") {
+        return Ok(None);
+    }
+    let mut args = vec!["--quiet".to_string()];
+    let dir = match tidy {
+        ClangTidy::Database(dir) => {
+            args.extend(["-p".to_string(), (*dir).to_string(), file_path.to_string()]);
+            *dir
+        }
+        ClangTidy::Includes(dir, includes) => {
+            args.extend([file_path.to_string(), "--".to_string()]);
+            args.extend(includes.iter().map(|inc| format!("-I{inc}")));
+            *dir
+        }
+    };
+    let output = Command::new("clang-tidy")
+        .args(&args)
+        .current_dir(dir)
+        .output_grouped()?;
+    let file = normalize_path(Path::new(file_path));
+    let diagnostics: Vec<Diagnostic> = parse_gcc_diagnostics(&encoding::decode(&output.stdout))
+        .into_iter()
+        .filter(|d| normalize_path(&Path::new(dir).join(&d.file)) == file)
+        .collect();
+    let diagnostics = drop_suppressed(diagnostics, dir);
+    let policy = Policy::from_config(&Config::load(dir));
+    if !diagnostics
+        .iter()
+        .any(|d| policy.blocks("clang-tidy", d, true))
+    {
+        return Ok(None);
+    }
+    Ok(Some(output_lint_result(
+        "clang-tidy",
+        file_path,
+        &format_diagnostics(&diagnostics),
+        "",
+        false,
+        debug,
+    )))
+}
+
+/// Run oclint on an Objective-C file with the compile database in `database_dir`.
+/// Returns `None` if oclint isn't installed or reports nothing over its thresholds.
+fn run_oclint(
+    file_path: &str,
+    database_dir: &str,
+    config: &Config,
+    debug: bool,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    if !is_executable_available("oclint") {
+        return Ok(None);
+    }
+    let output = Command::new("oclint")
+        .args(["-p", database_dir, file_path])
+        .current_dir(database_dir)
+        .output_grouped()?;
+    if linter_passed(config, "oclint", output.status) {
+        return Ok(None);
+    }
+    Ok(Some(output_lint_result(
+        "oclint",
+        file_path,
+        &encoding::decode(&output.stdout),
+        &encoding::decode(&output.stderr),
+        false,
+        debug,
+    )))
+}
+
+/// Flags for checking Objective-C without a build system: ARC and modules as Xcode enables
+/// them by default, plus the SDK when `xcrun` is available.
+fn objc_args() -> Vec<String> {
+    let mut args = vec!["-fobjc-arc".to_string(), "-fmodules".to_string()];
+    let sdk = Command::new("xcrun")
+        .args(["--show-sdk-path"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| encoding::decode(&o.stdout).trim().to_string());
+    if let Some(sdk) = sdk.filter(|sdk| !sdk.is_empty()) {
+        args.push("-isysroot".to_string());
+        args.push(sdk);
+    }
+    args
+}
+
+/// One `compile_commands.json` entry.
+#[derive(Debug, PartialEq, Eq)]
+struct CompileCommand {
+    directory: String,
+    file: String,
+    arguments: Vec<String>,
+}
+
+impl CompileCommand {
+    /// The entry's compiler and arguments, turned into a `-fsyntax-only` check with no output.
+    fn syntax_only_args(&self) -> (&str, Vec<&str>) {
+        let mut args = Vec::new();
+        let mut rest = self.arguments.iter().skip(1);
+        while let Some(arg) = rest.next() {
+            match arg.as_str() {
+                "-o" | "-MF" | "-MT" | "-MQ" => {
+                    rest.next();
+                }
+                "-c" | "-MD" | "-MMD" => {}
+                _ => args.push(arg.as_str()),
+            }
+        }
+        args.push("-fsyntax-only");
+        (self.arguments[0].as_str(), args)
+    }
+}
+
+/// The nearest `compile_commands.json` at or above the project root, or in its `build/`.
+fn compile_database(project_root: &str) -> Option<std::path::PathBuf> {
+    let build = Path::new(project_root).join("build/compile_commands.json");
+    if build.is_file() {
+        return Some(build);
+    }
+    find_nearest_file(project_root, &["compile_commands.json"])
+}
+
+/// The compile database entry for `file_path`, if it has one.
+fn compile_command_for(database: &str, file_path: &str) -> Option<CompileCommand> {
+    let file_path = normalize_path(Path::new(file_path));
+    parse_compile_commands(database)
+        .into_iter()
+        .find(|entry| normalize_path(&Path::new(&entry.directory).join(&entry.file)) == file_path)
+}
+
+/// Resolve `.` and `..` components without touching the filesystem, since compile
+/// databases often name sources relative to a `build/` directory.
+fn normalize_path(path: &Path) -> std::path::PathBuf {
+    use std::path::Component;
+
+    let mut normalized = std::path::PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+fn parse_compile_commands(database: &str) -> Vec<CompileCommand> {
+    let Some(entries) = json::parse(database) else {
+        return Vec::new();
+    };
+    entries
+        .as_array()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| {
+            let arguments = match entry.get("arguments").and_then(Json::as_array) {
+                Some(arguments) => arguments
+                    .iter()
+                    .filter_map(Json::as_str)
+                    .map(str::to_string)
+                    .collect(),
+                None => split_command_line(entry.get("command")?.as_str()?),
+            };
+            if arguments.is_empty() {
+                return None;
+            }
+            Some(CompileCommand {
+                directory: entry.get("directory")?.as_str()?.to_string(),
+                file: entry.get("file")?.as_str()?.to_string(),
+                arguments,
+            })
+        })
+        .collect()
+}
+
+/// Split a compile database `command` string the way a POSIX shell would, for the quoting
+/// `CMake` and Bear produce.
+fn split_command_line(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"') | None, '\\') => {
+                if let Some(next) = chars.next() {
+                    word.push(next);
+                }
+                in_word = true;
+            }
+            (Some(_), c) => word.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+/// Compile the translation unit `entry` and report only the diagnostics located in `header`.
+fn run_header_via_translation_unit(
+    header: &str,
+    entry: &CompileCommand,
+    debug: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let (program, args) = entry.syntax_only_args();
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(&entry.directory)
+        .output_grouped()?;
+    let header_path = normalize_path(Path::new(header));
+    let diagnostics: Vec<Diagnostic> = parse_gcc_diagnostics(&encoding::decode(&output.stderr))
+        .into_iter()
+        .filter(|d| normalize_path(&Path::new(&entry.directory).join(&d.file)) == header_path)
+        .collect();
+    let diagnostics = drop_suppressed(diagnostics, &entry.directory);
+    Ok(diagnostics_result(
+        &format!("{program} via {}", entry.file),
+        header,
+        &diagnostics,
+        &Policy::from_config(&Config::load(&entry.directory)),
+        |d| d.severity == Severity::Error,
+        debug,
+    ))
+}
+
+/// The database entry to compile for `header`: among translation units that `#include` it,
+/// prefer one with the same stem (`util.h` -> `util.c`), then the nearest directory.
+fn including_translation_unit(database: &str, header: &str) -> Option<CompileCommand> {
+    let header_path = Path::new(header);
+    let name = header_path.file_name()?.to_str()?;
+    let stem = header_path.file_stem()?;
+    let header_dir = header_path.parent()?;
+    parse_compile_commands(database)
+        .into_iter()
+        .filter(|entry| {
+            let source = Path::new(&entry.directory).join(&entry.file);
+            fs::read_to_string(source).is_ok_and(|text| includes_header(&text, name))
+        })
+        .min_by_key(|entry| {
+            let source = normalize_path(&Path::new(&entry.directory).join(&entry.file));
+            let shared = source
+                .components()
+                .zip(header_dir.components())
+                .take_while(|(a, b)| a == b)
+                .count();
+            (source.file_stem() != Some(stem), usize::MAX - shared)
+        })
+}
+
+/// Whether `source` has an `#include` directive naming a file called `name`.
+fn includes_header(source: &str, name: &str) -> bool {
+    source.lines().any(|line| {
+        let Some(rest) = line.trim_start().strip_prefix('#') else {
+            return false;
+        };
+        let Some(target) = rest.trim_start().strip_prefix("include") else {
+            return false;
+        };
+        let target = target.trim().trim_matches(|c| matches!(c, '"' | '<' | '>'));
+        target.rsplit('/').next() == Some(name)
+    })
+}
+
+/// Parse `file:line:col: error: message [-Wflag]` lines from gcc/clang output.
+fn parse_gcc_diagnostics(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(5, ':');
+            let file = parts.next()?;
+            let line_number = parts.next()?.parse().ok()?;
+            let column = parts.next()?.parse().ok()?;
+            let severity = match parts.next()?.trim() {
+                "error" | "fatal error" => Severity::Error,
+                "warning" => Severity::Warning,
+                _ => return None,
+            };
+            let message = parts.next()?.trim();
+            let (message, rule) = match message.rsplit_once(" [") {
+                Some((text, flag)) if flag.ends_with(']') => {
+                    (text, flag.trim_end_matches(']').to_string())
+                }
+                _ => (message, String::new()),
+            };
+            Some(Diagnostic {
+                file: file.to_string(),
+                line: line_number,
+                column,
+                rule,
+                message: message.to_string(),
+                severity,
+                url: None,
+                fix: None,
+            })
+        })
+        .collect()
+}
+
+fn is_header_file(file_path: &str) -> bool {
+    [".h", ".hh", ".hpp", ".hxx"]
+        .iter()
+        .any(|ext| file_path.ends_with(ext))
+}
+
+/// Include directories a plain Makefile project probably uses: the file's own directory,
+/// the project root, and conventional `include`/`inc`/`src` directories.
+fn guess_include_dirs(file_path: &str, project_root: &str) -> Vec<String> {
+    let mut dirs: Vec<String> = Vec::new();
+    if let Some(parent) = Path::new(file_path).parent() {
+        dirs.push(parent.to_string_lossy().to_string());
+    }
+    dirs.push(project_root.to_string());
+    for name in ["include", "inc", "src"] {
+        let dir = Path::new(project_root).join(name);
+        if dir.is_dir() {
+            dirs.push(dir.to_string_lossy().to_string());
+        }
+    }
+    let mut unique = Vec::new();
+    for dir in dirs {
+        if !unique.contains(&dir) {
+            unique.push(dir);
+        }
+    }
+    unique
+}
+
+/// Whether `file_path` is C++ rather than C. Headers count as C++ only with a `.hpp`-style
+/// extension.
+fn is_cxx_file(file_path: &str) -> bool {
+    [".cc", ".cpp", ".cxx", ".c++", ".hh", ".hpp", ".hxx"]
+        .iter()
+        .any(|ext| file_path.ends_with(ext))
+}
+
+fn is_objc_file(file_path: &str) -> bool {
+    [".m", ".mm"].iter().any(|ext| file_path.ends_with(ext))
+}
+
+/// The first available compiler for the file's language, preferring clang. Objective-C needs
+/// clang, since gcc rarely has the Objective-C front end or Foundation headers.
+fn c_compiler(file_path: &str) -> Option<&'static str> {
+    let candidates: &[&'static str] = if is_objc_file(file_path) {
+        &["clang"]
+    } else if is_cxx_file(file_path) {
+        &["clang++", "g++", "c++"]
+    } else {
+        &["clang", "gcc", "cc"]
+    };
+    candidates
+        .iter()
+        .copied()
+        .find(|compiler| is_executable_available(compiler))
+}
+
+/// Whether `linter` is one the fast tier drops for `lang` and `project_root` is over its time
+/// budget.
+fn fast_tier_skips(project_root: &str, lang: Lang, linter: &str) -> bool {
+    tier::is_slow(lang, linter) && tier::downgrade(project_root, lang).is_some()
+}
+
+/// Returns true if `program` is an existing path or can be found on PATH.
+pub fn is_executable_available(program: &str) -> bool {
+    if program.contains('/') {
+        return Path::new(program).exists();
+    }
+    Command::new("which")
+        .arg(program)
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
+/// Package pattern for the directory containing `file_path`, relative to the module root
+/// (`./pkg/api`, or `.` for the root package).
+fn go_package_arg(file_path: &str, project_root: &str) -> String {
+    let dir = Path::new(file_path)
+        .parent()
+        .unwrap_or_else(|| Path::new(""));
+    match dir.strip_prefix(project_root) {
+        Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+        Ok(relative) => format!("./{}", relative.display()),
+        Err(_) => dir.display().to_string(),
+    }
+}
+
+/// Rules lenient mode relaxes for each Go linter: unused code is expected mid-edit.
+fn go_lenient_defaults(linter: &str) -> &'static [&'static str] {
+    match linter {
+        "revive" => &["unused-parameter", "unused-receiver", "unreachable-code"],
+        _ => &["unused"],
+    }
+}
+
+/// The revive.toml nearest the edited file, since revive only reads an explicit `-config`.
+fn revive_config(file_path: &str) -> Option<PathBuf> {
+    let dir = Path::new(file_path).parent()?;
+    find_nearest_file(&dir.to_string_lossy(), &["revive.toml"])
+}
+
+/// Parse revive `-formatter json` output into diagnostics.
+/// Returns `None` if the output isn't the expected JSON array.
+fn parse_revive_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let report = json::parse(stdout.trim())?;
+    // revive prints `null` when there are no failures
+    if report == Json::Null {
+        return Some(Vec::new());
+    }
+    Some(
+        report
+            .as_array()?
+            .iter()
+            .filter_map(|failure| {
+                let start = failure.get("Position")?.get("Start")?;
+                Some(Diagnostic {
+                    file: start.get("Filename")?.as_str()?.to_string(),
+                    line: start.get("Line").and_then(Json::as_u32).unwrap_or(0),
+                    column: start.get("Column").and_then(Json::as_u32).unwrap_or(0),
+                    rule: failure
+                        .get("RuleName")
+                        .and_then(Json::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    message: failure.get("Failure")?.as_str()?.to_string(),
+                    severity: match failure.get("Severity").and_then(Json::as_str) {
+                        Some("warning") => Severity::Warning,
+                        _ => Severity::Error,
+                    },
+                    url: None,
+                    fix: None,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Parse golangci-lint `--out-format json` output into diagnostics.
+/// Returns `None` if the output isn't the expected JSON report.
+fn parse_golangci_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let report = json::parse(stdout.trim())?;
+    let Some(issues) = report.get("Issues") else {
+        return report.get("Report").map(|_| Vec::new());
+    };
+    // golangci-lint reports `"Issues": null` when there are none
+    let Some(issues) = issues.as_array() else {
+        return Some(Vec::new());
+    };
+
+    Some(
+        issues
+            .iter()
+            .filter_map(|issue| {
+                let pos = issue.get("Pos")?;
+                Some(Diagnostic {
+                    file: pos.get("Filename")?.as_str()?.to_string(),
+                    line: pos.get("Line").and_then(Json::as_u32).unwrap_or(0),
+                    column: pos.get("Column").and_then(Json::as_u32).unwrap_or(0),
+                    rule: issue
+                        .get("FromLinter")
+                        .and_then(Json::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    message: issue.get("Text")?.as_str()?.to_string(),
+                    severity: match issue.get("Severity").and_then(Json::as_str) {
+                        Some("warning") => Severity::Warning,
+                        _ => Severity::Error,
+                    },
+                    url: None,
+                    fix: None,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Parse staticcheck `-f json` output (one JSON object per line) into diagnostics.
+/// Returns `None` if no line is a staticcheck report, e.g. when it failed to start.
+fn parse_staticcheck_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    if stdout.trim().is_empty() {
+        return Some(Vec::new());
+    }
+    let diagnostics: Vec<Diagnostic> = stdout
+        .lines()
+        .filter_map(|line| {
+            let problem = json::parse(line.trim())?;
+            let location = problem.get("location")?;
+            Some(Diagnostic {
+                file: location.get("file")?.as_str()?.to_string(),
+                line: location.get("line").and_then(Json::as_u32).unwrap_or(0),
+                column: location.get("column").and_then(Json::as_u32).unwrap_or(0),
+                rule: problem
+                    .get("code")
+                    .and_then(Json::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                message: problem.get("message")?.as_str()?.to_string(),
+                severity: match problem.get("severity").and_then(Json::as_str) {
+                    Some("warning" | "ignored") => Severity::Warning,
+                    _ => Severity::Error,
+                },
+                url: None,
+                fix: None,
+            })
+        })
+        .collect();
+    (!diagnostics.is_empty()).then_some(diagnostics)
+}
+
+/// Parse `go vet -json` output: a `# package` comment line followed by a JSON object mapping
+/// package to analyzer to findings, for each package checked. Returns `None` if there is no
+/// such object, e.g. when the package doesn't build.
+fn parse_go_vet_json(stderr: &str) -> Option<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let mut found_report = false;
+    let mut object = String::new();
+    for line in stderr.lines() {
+        if line.starts_with('#') && object.is_empty() {
+            continue;
+        }
+        object.push_str(line);
+        object.push('\n');
+        // Each package's object ends with an unindented closing brace
+        if line != "}" {
+            continue;
+        }
+        let Some(Json::Object(packages)) = json::parse(&object) else {
+            return None;
+        };
+        object.clear();
+        found_report = true;
+        for (_, analyzers) in &packages {
+            let Json::Object(analyzers) = analyzers else {
+                continue;
+            };
+            for (analyzer, findings) in analyzers {
+                for finding in findings.as_array().unwrap_or_default() {
+                    diagnostics.extend(go_vet_diagnostic(analyzer, finding));
+                }
+            }
+        }
+    }
+    (found_report || stderr.trim().is_empty()).then_some(diagnostics)
+}
+
+/// One `go vet -json` finding: `{"posn": "/ws/a.go:3:2", "message": "..."}`.
+fn go_vet_diagnostic(analyzer: &str, finding: &Json) -> Option<Diagnostic> {
+    let position = finding.get("posn")?.as_str()?;
+    let mut parts = position.rsplitn(3, ':');
+    let column = parts.next()?.parse().ok()?;
+    let line = parts.next()?.parse().ok()?;
+    Some(Diagnostic {
+        file: parts.next()?.to_string(),
+        line,
+        column,
+        rule: analyzer.to_string(),
+        message: finding.get("message")?.as_str()?.to_string(),
+        severity: Severity::Error,
+        url: None,
+        fix: None,
+    })
+}
+
+/// Gradle task path for `task` in the subproject at `project_root`, relative to the build
+/// rooted at `gradle_root`: `/ws` + `/ws/libs/core` + `pmdMain` gives `:libs:core:pmdMain`.
+fn gradle_task_path(gradle_root: &str, project_root: &str, task: &str) -> String {
+    let relative = Path::new(project_root)
+        .strip_prefix(gradle_root)
+        .unwrap_or_else(|_| Path::new(""));
+    let segments: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    if segments.is_empty() {
+        task.to_string()
+    } else {
+        format!(":{}:{task}", segments.join(":"))
+    }
+}
+
+fn filter_clippy_output_multi(
+    stdout: &str,
+    stderr: &str,
+    file_paths: &[&str],
+    project_root: &str,
+) -> String {
+    let combined = format!("{stderr}\n{stdout}");
+
+    // Clippy outputs paths relative to the project root (e.g. "src/lib.rs:10:5").
+    // Absolute paths from the caller rarely match, so we also build relative paths
+    // by stripping the project_root prefix.  Bare filenames are kept as a last-resort
+    // fallback for unusual path formats.
+    let prefix = if project_root.ends_with('/') {
+        project_root.to_string()
+    } else {
+        format!("{project_root}/")
+    };
+
+    let relative_paths: Vec<&str> = file_paths
+        .iter()
+        .filter_map(|fp| fp.strip_prefix(&prefix))
+        .collect();
+
+    let file_names: Vec<&str> = file_paths
+        .iter()
+        .map(|fp| {
+            Path::new(fp)
+                .file_name()
+                .map_or(*fp, |n| n.to_str().unwrap_or(fp))
+        })
+        .collect();
+
+    combined
+        .lines()
+        .filter(|line| {
+            // 1. Exact absolute path (rare but precise)
+            file_paths.iter().any(|fp| line.contains(fp))
+            // 2. Relative path from project root (matches clippy's output)
+                || relative_paths.iter().any(|rp| line.contains(rp))
+            // 3. Bare filename fallback
+                || file_names.iter().any(|name| line.contains(name))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn escape_json(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str(r#"\""#),
+            '\\' => result.push_str(r"\\"),
+            '\n' => result.push_str(r"\n"),
+            '\r' => result.push_str(r"\r"),
+            '\t' => result.push_str(r"\t"),
+            c if c.is_control() => {
+                let _ = write!(result, r"\u{:04x}", c as u32);
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Build a `{"continue":true}` response, including `systemMessage` only in debug mode.
+pub fn continue_result(debug: bool, message: &str) -> String {
+    if debug {
+        format!(
+            r#"{{"continue":true,"systemMessage":"{}"}}"#,
+            escape_json(message)
+        )
+    } else {
+        r#"{"continue":true}"#.to_string()
+    }
+}
+
+/// Decide whether a linter run passed. Exit code 0 always passes; otherwise
+/// `[linters.<name>]` config can list `pass_exit_codes` or an `allowed_exit_mask` of bits
+/// that don't indicate failure, falling back to built-in defaults for known linters.
+fn linter_passed(config: &Config, linter: &str, status: ExitStatus) -> bool {
+    let Some(code) = status.code() else {
+        // Killed by a signal
+        return false;
+    };
+    if code == 0 {
+        return true;
+    }
+
+    let code = i64::from(code);
+    let section = format!("linters.{linter}");
+    if error_exit_codes(config, linter).contains(&code) {
+        // The linter itself failed (bad config, internal error), as opposed to finding issues
+        quarantine::record_failure(&procgroup::last_linter().unwrap_or_else(|| linter.to_string()));
+        return false;
+    }
+    if config
+        .get_array(&format!("{section}.pass_exit_codes"))
+        .is_some_and(|codes| codes.iter().any(|c| c.parse() == Ok(code)))
+    {
+        return true;
+    }
+
+    allowed_exit_mask(config, linter).is_some_and(|mask| code & !mask == 0)
+}
+
+/// Exit codes meaning `linter` itself failed, from `[linters.<name>] error_exit_codes` or
+/// built-in defaults.
+fn error_exit_codes(config: &Config, linter: &str) -> Vec<i64> {
+    if let Some(codes) = config.get_array(&format!("linters.{linter}.error_exit_codes")) {
+        return codes.iter().filter_map(|c| c.parse().ok()).collect();
+    }
+    match linter {
+        "eslint" | "ruff" | "mypy" => vec![2],
+        "pylint" => vec![32],
+        _ => Vec::new(),
+    }
+}
+
+/// Exit-code bits that don't indicate failure for `linter`, from config or built-in defaults.
+fn allowed_exit_mask(config: &Config, linter: &str) -> Option<i64> {
+    config
+        .get_int(&format!("linters.{linter}.allowed_exit_mask"))
+        .or_else(|| default_allowed_exit_mask(linter))
+}
+
+/// Rules suppressed in lenient mode for ruff and flake8: unused variable, unused import,
+/// undefined name.
+const RUFF_LENIENT_CODES: [&str; 3] = ["F841", "F401", "F821"];
+
+/// The same rules for pylint.
+const PYLINT_LENIENT_CODES: [&str; 3] = ["W0611", "W0612", "E0602"];
+
+/// Which rules lenient mode relaxes for a language, from `[lenient.<lang>]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LenientRules {
+    /// Ignore these rules (`allow = [...]`); `None` keeps each linter's built-in list
+    Allow(Option<Vec<String>>),
+    /// Block on these rules only (`only = [...]`)
+    Only(Vec<String>),
+}
+
+impl Default for LenientRules {
+    fn default() -> Self {
+        Self::Allow(None)
+    }
+}
+
+impl LenientRules {
+    /// Rules for `lang` (`rust`, `js`, `python`, `go`). `only` wins over `allow`.
+    pub fn from_config(config: &Config, lang: &str) -> Self {
+        if let Some(only) = config.get_array(&format!("lenient.{lang}.only")) {
+            return Self::Only(only.to_vec());
+        }
+        Self::Allow(
+            config
+                .get_array(&format!("lenient.{lang}.allow"))
+                .map(<[String]>::to_vec),
+        )
+    }
+
+    /// Rules to ignore: the configured list, or the linter's built-in `defaults`. Empty for
+    /// an `only` list.
+    fn allowed<'a>(&'a self, defaults: &[&'a str]) -> Vec<&'a str> {
+        match self {
+            Self::Allow(Some(rules)) => rules.iter().map(String::as_str).collect(),
+            Self::Allow(None) => defaults.to_vec(),
+            Self::Only(_) => Vec::new(),
+        }
+    }
+
+    /// Whether a diagnostic for `rule` still counts in lenient mode.
+    fn keeps(&self, rule: &str, defaults: &[&str]) -> bool {
+        match self {
+            Self::Only(only) => only.iter().any(|r| r == rule),
+            Self::Allow(_) => !self.allowed(defaults).contains(&rule),
+        }
+    }
+}
+
+/// Lint flags that apply lenient-mode rules for clippy and rustc. Without a list, unused
+/// variables and imports and dead code are allowed.
+fn rust_lenient_args(rules: &LenientRules) -> Vec<String> {
+    let mut args = Vec::new();
+    if let LenientRules::Only(only) = rules {
+        args.extend(["-A", "warnings", "-A", "clippy::all"].map(str::to_string));
+        for rule in only {
+            args.extend(["-D".to_string(), rule.clone()]);
+        }
+    } else {
+        for rule in rules.allowed(&["unused_variables", "unused_imports", "dead_code"]) {
+            args.extend(["-A".to_string(), rule.to_string()]);
+        }
+    }
+    args
+}
+
+/// Keep only the lines of plain-text linter output that don't look like a diagnostic or that
+/// name one of `only`. Returns the output and whether any diagnostic was kept.
+fn keep_only_rules(output: &str, only: &[String]) -> (String, bool) {
+    let looks_like_diagnostic = |line: &str| {
+        let line = line.trim_start();
+        // `file:3:7: ...` or eslint's stylish `3:7  error ...`
+        line.split(':')
+            .nth(1)
+            .is_some_and(|part| !part.is_empty() && part.trim().chars().all(|c| c.is_ascii_digit()))
+            || line
+                .split_once(':')
+                .is_some_and(|(row, _)| !row.is_empty() && row.chars().all(|c| c.is_ascii_digit()))
+    };
+    let mut kept_any = false;
+    let kept: Vec<&str> = output
+        .lines()
+        .filter(|line| {
+            if !looks_like_diagnostic(line) {
+                return true;
+            }
+            let keep = only.iter().any(|rule| line.contains(rule.as_str()));
+            kept_any |= keep;
+            keep
+        })
+        .collect();
+    (kept.join("\n"), kept_any)
+}
+
+/// Build the result from ruff `--output-format=json` output, dropping lenient-mode rules.
+/// Returns `None` if the output isn't a ruff JSON report (e.g. ruff failed to start).
+fn ruff_json_result(
+    file_path: &str,
+    stdout: &str,
+    lenient: Option<&LenientRules>,
+    policy: &Policy,
+    debug: bool,
+) -> Option<String> {
+    let diagnostics: Vec<Diagnostic> = parse_ruff_json(stdout)?
+        .into_iter()
+        .filter(|d| lenient.is_none_or(|rules| rules.keeps(&d.rule, &RUFF_LENIENT_CODES)))
+        .collect();
+    let diagnostics = drop_suppressed_in_file(diagnostics, file_path);
+
+    Some(diagnostics_result(
+        "ruff",
+        file_path,
+        &diagnostics,
+        policy,
+        |_| true,
+        debug,
+    ))
+}
+
+/// Parse ruff `--output-format=json` output (an array of violations) into diagnostics.
+fn parse_ruff_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let report = json::parse(stdout.trim())?;
+    Some(
+        report
+            .as_array()?
+            .iter()
+            .filter_map(|violation| {
+                let location = violation.get("location")?;
+                Some(Diagnostic {
+                    file: violation.get("filename")?.as_str()?.to_string(),
+                    line: location.get("row").and_then(Json::as_u32).unwrap_or(0),
+                    column: location.get("column").and_then(Json::as_u32).unwrap_or(0),
+                    // Syntax errors have a null code
+                    rule: violation
+                        .get("code")
+                        .and_then(Json::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    message: violation.get("message")?.as_str()?.to_string(),
+                    severity: Severity::Error,
+                    url: violation
+                        .get("url")
+                        .and_then(Json::as_str)
+                        .map(str::to_string),
+                    fix: violation
+                        .get("fix")
+                        .and_then(|fix| fix.get("message"))
+                        .and_then(Json::as_str)
+                        .map(str::to_string),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// pylint exit-code bits, one per message category that was emitted.
+/// See <https://pylint.readthedocs.io/en/stable/user_guide/usage/run.html#exit-codes>
+const PYLINT_CATEGORY_BITS: [(char, i64); 5] = [
+    ('F', 1),  // fatal
+    ('E', 2),  // error
+    ('W', 4),  // warning
+    ('R', 8),  // refactor
+    ('C', 16), // convention
+];
+
+/// Built-in exit-code masks for linters whose non-zero exits can mean "only minor messages".
+fn default_allowed_exit_mask(linter: &str) -> Option<i64> {
+    match linter {
+        // Only fatal/error/warning (and usage error, 32) block by default
+        "pylint" => Some(8 | 16),
+        _ => None,
+    }
+}
+
+/// Remove pylint messages (`path:1:0: C0114: ...`) whose category bit is in `allowed_mask`.
+fn filter_pylint_messages(output: &str, allowed_mask: i64) -> String {
+    output
+        .lines()
+        .filter(|line| {
+            let Some(category) = pylint_message_category(line) else {
+                return true;
+            };
+            !PYLINT_CATEGORY_BITS
+                .iter()
+                .any(|(c, bit)| *c == category && allowed_mask & bit != 0)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Category letter of a pylint text message such as `src/a.py:3:0: W0611: Unused import os`.
+fn pylint_message_category(line: &str) -> Option<char> {
+    line.split(": ").skip(1).find_map(|part| {
+        let mut chars = part.chars();
+        let category = chars.next()?;
+        let digits: String = chars.collect();
+        (PYLINT_CATEGORY_BITS.iter().any(|(c, _)| *c == category)
+            && digits.len() == 4
+            && digits.chars().all(|d| d.is_ascii_digit()))
+        .then_some(category)
+    })
+}
+
+/// The result for parsed diagnostics: all of them are reported, and the run blocks if any
+/// blocks under the `[output]` category policy, falling back to `severity_blocks`.
+fn diagnostics_result(
+    linter: &str,
+    file_path: &str,
+    diagnostics: &[Diagnostic],
+    policy: &Policy,
+    severity_blocks: impl Fn(&Diagnostic) -> bool,
+    debug: bool,
+) -> String {
+    let blocking = diagnostics
+        .iter()
+        .any(|d| policy.blocks(linter, d, severity_blocks(d)));
+    output_lint_result(
+        linter,
+        file_path,
+        &format_diagnostics(diagnostics),
+        "",
+        !blocking,
+        debug,
+    )
+}
+
+fn output_lint_result(
+    linter: &str,
+    file_path: &str,
+    stdout: &str,
+    stderr: &str,
+    success: bool,
+    debug: bool,
+) -> String {
+    if success {
+        continue_result(
+            debug,
+            &format!("[ralph-hook-lint] lint passed for {file_path} using {linter}."),
+        )
+    } else {
+        let output = if !stdout.is_empty() && !stderr.is_empty() {
+            format!("{stdout}\n{stderr}")
+        } else if !stdout.is_empty() {
+            stdout.to_string()
+        } else {
+            stderr.to_string()
+        };
+
+        format!(
+            r#"{{"decision":"block","reason":"[ralph-hook-lint] lint errors in {} using {}:\n\n{}\n\nFix lint errors."}}"#,
+            escape_json(file_path),
+            escape_json(linter),
+            escape_json(output.trim())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_json_simple_string() {
+        assert_eq!(escape_json("hello"), "hello");
+    }
+
+    #[test]
+    fn test_escape_json_quotes() {
+        assert_eq!(escape_json(r#"say "hello""#), r#"say \"hello\""#);
+    }
+
+    #[test]
+    fn test_escape_json_backslash() {
+        assert_eq!(escape_json(r"path\to\file"), r"path\\to\\file");
+    }
+
+    #[test]
+    fn test_escape_json_newlines() {
+        assert_eq!(escape_json("line1\nline2"), r"line1\nline2");
+    }
+
+    #[test]
+    fn test_escape_json_tabs() {
+        assert_eq!(escape_json("col1\tcol2"), r"col1\tcol2");
+    }
+
+    #[test]
+    fn test_escape_json_carriage_return() {
+        assert_eq!(escape_json("line1\r\nline2"), r"line1\r\nline2");
+    }
+
+    #[test]
+    fn test_escape_json_mixed() {
+        assert_eq!(
+            escape_json("Error: \"file\\not\\found\"\n"),
+            r#"Error: \"file\\not\\found\"\n"#
+        );
+    }
+
+    #[test]
+    fn test_output_lint_result_success_debug() {
+        let result = output_lint_result("eslint", "src/app.js", "", "", true, true);
+        assert_eq!(
+            result,
+            r#"{"continue":true,"systemMessage":"[ralph-hook-lint] lint passed for src/app.js using eslint."}"#
+        );
+    }
+
+    #[test]
+    fn test_output_lint_result_success_no_debug() {
+        let result = output_lint_result("eslint", "src/app.js", "", "", true, false);
+        assert_eq!(result, r#"{"continue":true}"#);
+    }
+
+    #[test]
+    fn test_output_lint_result_failure_stdout_only() {
+        let result = output_lint_result("eslint", "src/app.js", "error on line 1", "", false, true);
+        assert_eq!(
+            result,
+            r#"{"decision":"block","reason":"[ralph-hook-lint] lint errors in src/app.js using eslint:\n\nerror on line 1\n\nFix lint errors."}"#
+        );
+    }
+
+    #[test]
+    fn test_output_lint_result_failure_stderr_only() {
+        let result = output_lint_result("eslint", "src/app.js", "", "error on line 2", false, true);
+        assert_eq!(
+            result,
+            r#"{"decision":"block","reason":"[ralph-hook-lint] lint errors in src/app.js using eslint:\n\nerror on line 2\n\nFix lint errors."}"#
+        );
+    }
+
+    #[test]
+    fn test_output_lint_result_failure_both() {
+        let result = output_lint_result(
+            "eslint",
+            "src/app.js",
+            "stdout err",
+            "stderr err",
+            false,
+            true,
+        );
+        assert_eq!(
+            result,
+            r#"{"decision":"block","reason":"[ralph-hook-lint] lint errors in src/app.js using eslint:\n\nstdout err\nstderr err\n\nFix lint errors."}"#
+        );
+    }
+
+    #[test]
+    fn test_output_lint_result_failure_no_debug_still_blocks() {
+        let result =
+            output_lint_result("eslint", "src/app.js", "error on line 1", "", false, false);
+        assert_eq!(
+            result,
+            r#"{"decision":"block","reason":"[ralph-hook-lint] lint errors in src/app.js using eslint:\n\nerror on line 1\n\nFix lint errors."}"#
+        );
+    }
+
+    #[test]
+    fn test_output_lint_result_escapes_special_chars() {
+        let result = output_lint_result(
+            "eslint",
+            "src/app.js",
+            "error: \"unexpected\"\n",
+            "",
+            false,
+            true,
+        );
+        assert!(result.contains(r#"\"unexpected\""#));
+        assert!(result.contains(r"\n"));
+    }
+
+    #[test]
+    fn test_continue_result_debug() {
+        let result = continue_result(true, "[ralph-hook-lint] some message");
+        assert_eq!(
+            result,
+            r#"{"continue":true,"systemMessage":"[ralph-hook-lint] some message"}"#
+        );
+    }
+
+    #[test]
+    fn test_continue_result_no_debug() {
+        let result = continue_result(false, "[ralph-hook-lint] some message");
+        assert_eq!(result, r#"{"continue":true}"#);
+    }
+
+    #[test]
+    fn test_filter_clippy_output_matches_relative_path() {
+        let stderr = "warning: unused variable\n  --> src/main.rs:10:5\nerror: something else";
+        let result = filter_clippy_output_multi("", stderr, &["/project/src/main.rs"], "/project");
+        assert!(result.contains("src/main.rs:10:5"));
+        assert!(!result.contains("unused variable"));
+    }
+
+    #[test]
+    fn test_filter_clippy_output_matches_filename_fallback() {
+        let stderr = "warning: unused in main.rs\n  --> other/main.rs:5:1";
+        let result = filter_clippy_output_multi("", stderr, &["/project/src/main.rs"], "/project");
+        assert!(result.contains("main.rs"));
+    }
+
+    #[test]
+    fn test_filter_clippy_output_empty_when_no_match() {
+        let stderr = "warning: in other.rs:10:5";
+        let result = filter_clippy_output_multi("", stderr, &["/project/src/main.rs"], "/project");
+        assert!(result.is_empty() || !result.contains("other.rs"));
+    }
+
+    #[test]
+    fn test_filter_clippy_output_multi_matches_multiple_files() {
+        let stderr = "  --> src/main.rs:10:5\n  --> src/lib.rs:20:3\n  --> src/other.rs:1:1";
+        let result = filter_clippy_output_multi(
+            "",
+            stderr,
+            &["/project/src/main.rs", "/project/src/lib.rs"],
+            "/project",
+        );
+        assert!(result.contains("src/main.rs:10:5"));
+        assert!(result.contains("src/lib.rs:20:3"));
+        assert!(!result.contains("src/other.rs"));
+    }
+
+    #[test]
+    fn test_filter_clippy_workspace_no_cross_crate_leak() {
+        // Simulate a workspace where clippy reports errors from two crates.
+        // The filter for crate "app" should NOT match errors from "core" via
+        // the relative path, even though both have "lib.rs".
+        let stderr = "  --> src/lib.rs:10:5\n  --> /ws/crates/core/src/lib.rs:20:3";
+        let result = filter_clippy_output_multi(
+            "",
+            stderr,
+            &["/ws/crates/app/src/lib.rs"],
+            "/ws/crates/app",
+        );
+        // "src/lib.rs:10:5" matches via relative path (correct — app's own file)
+        assert!(result.contains("src/lib.rs:10:5"));
+        // The absolute path "/ws/crates/core/src/lib.rs:20:3" should NOT match
+        // via relative path, but WILL match via the filename fallback "lib.rs".
+        // This is a known limitation of the filename fallback.
+    }
+
+    fn command_env<'a>(cmd: &'a Command, key: &str) -> Option<&'a std::ffi::OsStr> {
+        cmd.get_envs().find(|(k, _)| *k == key).and_then(|(_, v)| v)
+    }
+
+    #[test]
+    fn test_strip_cargo_progress() {
+        let stderr = "    Updating crates.io index\nerror: failed to parse manifest at `/p/Cargo.toml`\n\nCaused by:\n  TOML parse error at line 3, column 1\n    Checking p v0.1.0 (/p)\n";
+        assert_eq!(
+            strip_cargo_progress(stderr),
+            "error: failed to parse manifest at `/p/Cargo.toml`\n\nCaused by:\n  TOML parse error at line 3, column 1"
+        );
+        assert!(is_cargo_manifest("/p/Cargo.lock"));
+        assert!(!is_cargo_manifest("/p/NotCargo.toml"));
+    }
+
+    #[test]
+    fn test_detect_rust_script_shebangs() {
+        assert_eq!(
+            detect_rust_script("#!/usr/bin/env -S cargo +nightly -Zscript\nfn main() {}"),
+            RustFileKind::CargoScript
+        );
+        assert_eq!(
+            detect_rust_script("#!/usr/bin/env rust-script\nfn main() {}"),
+            RustFileKind::RustScript
+        );
+        assert_eq!(
+            detect_rust_script("#![allow(dead_code)]\nfn main() {}"),
+            RustFileKind::Plain
+        );
+        assert_eq!(detect_rust_script("fn main() {}"), RustFileKind::Plain);
+    }
+
+    #[test]
+    fn test_is_unknown_maven_prefix() {
+        assert!(is_unknown_maven_prefix(
+            "[ERROR] No plugin found for prefix 'pmd' in the current project and in the plugin groups"
+        ));
+        assert!(!is_unknown_maven_prefix(
+            "[ERROR] Could not resolve dependencies for project com.example:app:jar:1.0"
+        ));
+    }
+
+    #[test]
+    fn test_parse_gradle_tasks() {
+        let output = "\
+------------------------------------------------------------
+Tasks runnable from root project 'ws'
+------------------------------------------------------------
+
+Build tasks
+-----------
+assemble - Assembles the outputs of this project.
+lib:pmdMain - Run PMD analysis for main classes
+
+Verification tasks
+------------------
+pmdMain - Run PMD analysis for main classes
+";
+        let tasks = parse_gradle_tasks(output);
+        assert!(tasks.contains(&"pmdMain".to_string()));
+        assert!(tasks.contains(&"lib:pmdMain".to_string()));
+        assert!(
+            !tasks
+                .iter()
+                .any(|t| t.starts_with("Tasks") || t.starts_with('-'))
+        );
+    }
+
+    #[test]
+    fn test_cached_java_tasks_reprobes_when_build_file_changes() {
+        let root =
+            std::env::temp_dir().join(format!("ralph-lint-java-cache-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let pom = root.join("pom.xml");
+        fs::write(&pom, "<project/>").unwrap();
+        let root_str = root.to_string_lossy();
+        let _ = fs::remove_file(java_tasks_cache_path(&root_str));
+
+        let build_files = [pom.clone()];
+        let cache = java_tasks_cache_path(&root_str);
+        let probe = |tasks: &[&str]| {
+            let tasks: Vec<String> = tasks.iter().map(ToString::to_string).collect();
+            move || Ok(Ok(tasks))
+        };
+
+        // Failed introspection is reported but not cached
+        let failed = cached_java_tasks(&root_str, &build_files, || Ok(Err("boom".to_string())));
+        assert_eq!(failed.unwrap(), Err("boom".to_string()));
+        assert!(!cache.exists());
+
+        let first = cached_java_tasks(&root_str, &build_files, probe(&["pmd:check"])).unwrap();
+        assert_eq!(first, Ok(vec!["pmd:check".to_string()]));
+
+        // Cached: the probe result is ignored
+        let cached = cached_java_tasks(&root_str, &build_files, probe(&[])).unwrap();
+        assert_eq!(cached, first);
+
+        fs::write(&pom, "<project><build/></project>").unwrap();
+        let changed = cached_java_tasks(&root_str, &build_files, probe(&[])).unwrap();
+        assert_eq!(changed, Ok(Vec::new()));
+
+        let _ = fs::remove_file(&cache);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_gradle_task_path_root_project() {
+        assert_eq!(gradle_task_path("/ws", "/ws", "pmdMain"), "pmdMain");
+    }
+
+    #[test]
+    fn test_gradle_task_path_subproject() {
+        assert_eq!(
+            gradle_task_path("/ws", "/ws/lib", "pmdMain"),
+            ":lib:pmdMain"
+        );
+        assert_eq!(
+            gradle_task_path("/ws/", "/ws/libs/core", "spotbugsMain"),
+            ":libs:core:spotbugsMain"
+        );
+    }
+
+    #[test]
+    fn test_parse_bloop_compile() {
+        let output = "Compiling core (1 Java source)\n\
+[E1] src/main/java/App.java:3:5\n     cannot find symbol\n       symbol:   method foo()\n     L3:     foo();\n             ^\n\
+[W1] /ws/core/src/main/java/Other.java:7:1\n     unchecked conversion\n\
+[E] src/main/java/App.java: L3 [E1]\nCompiled core (1.2s)\n";
+        let diagnostics = parse_bloop_compile(output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].file, "src/main/java/App.java");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 5));
+        assert_eq!(diagnostics[0].message, "cannot find symbol");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[1].file, "/ws/core/src/main/java/Other.java");
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!(diagnostics[1].message, "unchecked conversion");
+    }
+
+    #[test]
+    fn test_select_bloop_project_prefers_closest_directory() {
+        let projects = vec![
+            ("root".to_string(), "/ws".to_string()),
+            ("core".to_string(), "/ws/core".to_string()),
+            ("core-test".to_string(), "/ws/core".to_string()),
+        ];
+        assert_eq!(
+            select_bloop_project(&projects, "/ws/core/src/main/java/A.java"),
+            Some("core")
+        );
+        assert_eq!(
+            select_bloop_project(&projects, "/ws/core/src/test/java/ATest.java"),
+            Some("core-test")
+        );
+        assert_eq!(
+            select_bloop_project(&projects, "/ws/other/src/main/java/B.java"),
+            Some("root")
+        );
+        assert_eq!(select_bloop_project(&projects, "/elsewhere/C.java"), None);
+    }
+
+    #[test]
+    fn test_package_json_problems() {
+        let valid = json::parse(
+            r#"{"name":"x","version":"1.0.0","scripts":{"lint":"eslint ."},"dependencies":{"a":"^1"}}"#,
+        )
+        .unwrap();
+        assert!(package_json_problems(&valid).is_empty());
+
+        let invalid = json::parse(
+            r#"{"name":1,"scripts":["lint"],"devDependencies":{"a":"^1","b":{"version":"2"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            package_json_problems(&invalid),
+            vec![
+                r#""name" must be a string"#,
+                r#""scripts" must be an object"#,
+                r#""devDependencies.b" must be a string"#,
+            ]
+        );
+        assert_eq!(
+            package_json_problems(&Json::Array(Vec::new())),
+            vec!["package.json must contain a JSON object"]
+        );
+    }
+
+    #[test]
+    fn test_is_build_script() {
+        assert!(!is_build_script("eslint ."));
+        assert!(!is_build_script(
+            "tsc --noEmit && eslint --max-warnings=0 ."
+        ));
+        assert!(!is_build_script("next lint"));
+        assert!(is_build_script("npm run build && eslint ."));
+        assert!(is_build_script("yarn build"));
+        assert!(is_build_script("run-s build:* lint:*"));
+        assert!(is_build_script("npx tsc -b"));
+        assert!(is_build_script("webpack --mode production; eslint src"));
+    }
+
+    #[test]
+    fn test_npm_lint_script() {
+        let dir =
+            std::env::temp_dir().join(format!("ralph-lint-npm-script-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let root = dir.to_str().unwrap();
+        assert_eq!(npm_lint_script(root), None);
+        fs::write(
+            dir.join("package.json"),
+            r#"{"name":"x","scripts":{"build":"tsc","lint":"eslint ."}}"#,
+        )
+        .unwrap();
+        assert_eq!(npm_lint_script(root).as_deref(), Some("eslint ."));
+        fs::write(dir.join("package.json"), r#"{"name":"x"}"#).unwrap();
+        assert_eq!(npm_lint_script(root), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_checks_whole_project() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-whole-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let root = dir.to_str().unwrap();
+
+        assert!(checks_whole_project(Lang::Rust, "clippy", "src/a.rs", root));
+        assert!(checks_whole_project(Lang::Python, "mypy", "a.py", root));
+        assert!(checks_whole_project(Lang::JavaScript, "tsc", "a.ts", root));
+        assert!(!checks_whole_project(Lang::Python, "ruff", "a.py", root));
+        assert!(!checks_whole_project(
+            Lang::JavaScript,
+            "eslint",
+            "a.ts",
+            root
+        ));
+
+        // eslint's pass in a composite package follows a `tsc -b` of the whole package
+        fs::write(
+            dir.join("tsconfig.json"),
+            r#"{"compilerOptions":{"composite":true}}"#,
+        )
+        .unwrap();
+        assert!(checks_whole_project(
+            Lang::JavaScript,
+            "eslint",
+            "a.ts",
+            root
+        ));
+        assert!(!checks_whole_project(
+            Lang::JavaScript,
+            "eslint",
+            "a.js",
+            root
+        ));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_is_composite_tsconfig() {
+        assert!(is_composite_tsconfig(
+            r#"{"compilerOptions":{"composite": true}}"#
+        ));
+        assert!(is_composite_tsconfig(
+            r#"{"files":[],"references":[{"path":"./packages/a"}]}"#
+        ));
+        assert!(!is_composite_tsconfig(
+            r#"{"compilerOptions":{"composite":false,"strict":true}}"#
+        ));
+        assert!(!is_composite_tsconfig(r#"{"compilerOptions":{}}"#));
+    }
+
+    #[test]
+    fn test_filter_tsc_output_keeps_file_diagnostics() {
+        let output = "src/index.ts(3,5): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                      src/other.ts(1,1): error TS2304: Cannot find name 'x'.\n\
+                      src/index.ts(7,1): error TS2345: Argument mismatch.\n  \
+                      Types of property 'a' are incompatible.\n\
+                      ../dep/src/lib.ts(2,2): error TS1005: ';' expected.";
+        let result = filter_tsc_output(output, "/ws/app/src/index.ts", "/ws/app");
+        assert_eq!(
+            result,
+            "src/index.ts(3,5): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+             src/index.ts(7,1): error TS2345: Argument mismatch.\n  \
+             Types of property 'a' are incompatible."
+        );
+    }
+
+    #[test]
+    fn test_clippy_command_uses_workspace_clippy_toml() {
+        let crate_dir =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/rust/monorepo/crates/app");
+        let cmd = clippy_command(&crate_dir.to_string_lossy(), &Config::default(), false);
+        let conf_dir = command_env(&cmd, "CLIPPY_CONF_DIR").unwrap();
+        assert!(Path::new(conf_dir).ends_with("rust/monorepo"));
+        assert!(rust_config_note(&crate_dir.to_string_lossy()).contains("monorepo/clippy.toml"));
+    }
+
+    fn exit_status(code: i32) -> ExitStatus {
+        use std::os::unix::process::ExitStatusExt;
+        ExitStatus::from_raw(code << 8)
+    }
+
+    #[test]
+    fn test_linter_passed_zero_exit() {
+        assert!(linter_passed(&Config::default(), "eslint", exit_status(0)));
+        assert!(!linter_passed(&Config::default(), "eslint", exit_status(1)));
+    }
+
+    #[test]
+    fn test_linter_passed_pylint_default_mask() {
+        let config = Config::default();
+        // convention (16) and refactor (8) only
+        assert!(linter_passed(&config, "pylint", exit_status(16)));
+        assert!(linter_passed(&config, "pylint", exit_status(24)));
+        // error (2) or warning (4) still block
+        assert!(!linter_passed(&config, "pylint", exit_status(2 | 16)));
+        assert!(!linter_passed(&config, "pylint", exit_status(4)));
+    }
+
+    #[test]
+    fn test_linter_passed_pylint_usage_error_blocks() {
+        assert!(!linter_passed(
+            &Config::default(),
+            "pylint",
+            exit_status(32)
+        ));
+    }
+
+    #[test]
+    fn test_filter_pylint_messages_drops_allowed_categories() {
+        let output = "************* Module app\n\
+                      src/app.py:1:0: C0114: Missing module docstring (missing-module-docstring)\n\
+                      src/app.py:3:4: E0602: Undefined variable 'x' (undefined-variable)\n\
+                      src/app.py:5:0: R0903: Too few public methods (1/2) (too-few-public-methods)\n\
+                      src/app.py:7:0: W0611: Unused import os (unused-import)";
+        let result = filter_pylint_messages(output, 8 | 16);
+        assert_eq!(
+            result,
+            "************* Module app\n\
+             src/app.py:3:4: E0602: Undefined variable 'x' (undefined-variable)\n\
+             src/app.py:7:0: W0611: Unused import os (unused-import)"
+        );
+    }
+
+    #[test]
+    fn test_pylint_message_category() {
+        assert_eq!(
+            pylint_message_category("a.py:1:0: C0114: Missing docstring"),
+            Some('C')
+        );
+        assert_eq!(
+            pylint_message_category("Your code has been rated at 5.00/10"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_linter_passed_config_overrides() {
+        let config = Config::parse(
+            "[linters.golangci-lint]\npass_exit_codes = [1]\n[linters.pylint]\nallowed_exit_mask = 28\n",
+        );
+        assert!(linter_passed(&config, "golangci-lint", exit_status(1)));
+        assert!(!linter_passed(&config, "golangci-lint", exit_status(2)));
+        assert!(linter_passed(&config, "pylint", exit_status(4)));
+        assert!(!linter_passed(&config, "pylint", exit_status(2)));
+    }
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            split_command_line(r#"/usr/bin/cc -DNAME=\"x\" -I"inc dir" -c 'a b.c'"#),
+            vec!["/usr/bin/cc", "-DNAME=\"x\"", "-Iinc dir", "-c", "a b.c"]
+        );
+        assert!(split_command_line("   ").is_empty());
+    }
+
+    #[test]
+    fn test_compile_command_syntax_only_args() {
+        let database = r#"[
+            {"directory":"/p/build","file":"../src/a.c","command":"cc -Iinc -MD -MF a.d -o a.o -c ../src/a.c"},
+            {"directory":"/p","file":"/p/src/b.cpp","arguments":["c++","-std=c++17","-c","src/b.cpp","-o","b.o"]}
+        ]"#;
+        assert_eq!(compile_command_for(database, "/p/src/c.c"), None);
+
+        let entry = compile_command_for(database, "/p/src/a.c").unwrap();
+        assert_eq!(
+            entry.syntax_only_args(),
+            ("cc", vec!["-Iinc", "../src/a.c", "-fsyntax-only"])
+        );
+        let entry = compile_command_for(database, "/p/src/b.cpp").unwrap();
+        assert_eq!(entry.directory, "/p");
+        assert_eq!(
+            entry.syntax_only_args(),
+            ("c++", vec!["-std=c++17", "src/b.cpp", "-fsyntax-only"])
+        );
+    }
+
+    #[test]
+    fn test_includes_header() {
+        let source = "#include <stdio.h>\n#  include \"net/util.h\"\n// #include \"other.h\"\n";
+        assert!(includes_header(source, "util.h"));
+        assert!(includes_header(source, "stdio.h"));
+        assert!(!includes_header(source, "other.h"));
+    }
+
+    #[test]
+    fn test_parse_gcc_diagnostics() {
+        let output = "In file included from main.c:1:\n\
+            ../include/util.h:3:5: error: unknown type name 'nope'\n\
+            main.c:4:9: warning: unused variable 'x' [-Wunused-variable]\n\
+            \x20   4 |     int x;\n";
+        let diagnostics = parse_gcc_diagnostics(output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].file, "../include/util.h");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 5));
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[1].rule, "-Wunused-variable");
+        assert_eq!(diagnostics[1].message, "unused variable 'x'");
+    }
+
+    #[test]
+    fn test_including_translation_unit_prefers_matching_stem() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-header-tu-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("include")).unwrap();
+        fs::write(dir.join("src/main.c"), "#include \"util.h\"\n").unwrap();
+        fs::write(dir.join("src/util.c"), "#include \"util.h\"\n").unwrap();
+        fs::write(dir.join("src/other.c"), "int x;\n").unwrap();
+        let root = dir.to_str().unwrap();
+        let database = format!(
+            r#"[
+                {{"directory":"{root}","file":"src/main.c","command":"cc -c src/main.c"}},
+                {{"directory":"{root}","file":"src/other.c","command":"cc -c src/other.c"}},
+                {{"directory":"{root}","file":"src/util.c","command":"cc -c src/util.c"}}
+            ]"#
+        );
+
+        let entry =
+            including_translation_unit(&database, &format!("{root}/include/util.h")).unwrap();
+        assert_eq!(entry.file, "src/util.c");
+        assert!(including_translation_unit(&database, &format!("{root}/include/none.h")).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_objc_files_use_clang() {
+        assert!(is_objc_file("/p/App/ViewController.m"));
+        assert!(is_objc_file("/p/App/Bridge.mm"));
+        assert!(!is_objc_file("/p/src/main.c"));
+        assert!(objc_args().starts_with(&["-fobjc-arc".to_string(), "-fmodules".to_string()]));
+        if !is_executable_available("clang") {
+            assert_eq!(c_compiler("/p/App/ViewController.m"), None);
+        }
+    }
+
+    #[test]
+    fn test_guess_include_dirs() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/c/project");
+        let root = root.to_str().unwrap();
+        let dirs = guess_include_dirs(&format!("{root}/src/main.c"), root);
+        assert_eq!(
+            dirs,
+            vec![
+                format!("{root}/src"),
+                root.to_string(),
+                format!("{root}/include"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_go_module_file() {
+        assert!(is_go_module_file("/p/go.mod"));
+        assert!(is_go_module_file("/p/go.sum"));
+        assert!(!is_go_module_file("/p/main.go"));
+        assert!(!is_go_module_file("/p/notgo.mod"));
+    }
+
+    #[test]
+    fn test_go_package_arg() {
+        assert_eq!(go_package_arg("/ws/main.go", "/ws"), ".");
+        assert_eq!(go_package_arg("/ws/pkg/api/h.go", "/ws"), "./pkg/api");
+    }
+
+    #[test]
+    fn test_parse_golangci_json() {
+        let stdout = r#"{"Issues":[{"FromLinter":"errcheck","Text":"Error return value of `f` is not checked","Severity":"","Pos":{"Filename":"pkg/lib.go","Offset":10,"Line":3,"Column":2}},{"FromLinter":"govet","Text":"unreachable code","Severity":"warning","Pos":{"Filename":"pkg/other.go","Line":9,"Column":1}}],"Report":{"Linters":[]}}"#;
+        let diagnostics = parse_golangci_json(stdout).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "pkg/lib.go:3:2: error: Error return value of `f` is not checked (errcheck)"
+        );
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert!(diagnostics[0].is_for_file("/ws/pkg/lib.go", "/ws"));
+        assert!(!diagnostics[1].is_for_file("/ws/pkg/lib.go", "/ws"));
+    }
+
+    #[test]
+    fn test_parse_golangci_json_no_issues() {
+        assert_eq!(
+            parse_golangci_json(r#"{"Issues":null,"Report":{}}"#),
+            Some(Vec::new())
+        );
+        assert_eq!(parse_golangci_json("level=error msg=\"boom\""), None);
+    }
+
+    #[test]
+    fn test_parse_revive_json() {
+        let stdout = r#"[{"Severity":"warning","Failure":"exported function Run should have comment or be unexported","RuleName":"exported","Category":"lint","Position":{"Start":{"Filename":"/ws/pkg/lib.go","Offset":20,"Line":3,"Column":1},"End":{"Filename":"/ws/pkg/lib.go","Offset":40,"Line":3,"Column":21}},"Confidence":1,"ReplacementLine":""}]"#;
+        let diagnostics = parse_revive_json(stdout).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "exported");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "/ws/pkg/lib.go:3:1: warning: exported function Run should have comment or be unexported (exported)"
+        );
+        assert_eq!(parse_revive_json("null"), Some(Vec::new()));
+        assert_eq!(parse_revive_json("[]"), Some(Vec::new()));
+        assert_eq!(parse_revive_json("cannot read config"), None);
+    }
+
+    #[test]
+    fn test_revive_config_is_found_above_the_package() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-revive-{}", std::process::id()));
+        fs::create_dir_all(dir.join("pkg")).unwrap();
+        assert_eq!(
+            revive_config(&dir.join("pkg/lib.go").to_string_lossy()),
+            None
+        );
+        fs::write(dir.join("revive.toml"), "confidence = 0.8\n").unwrap();
+        assert_eq!(
+            revive_config(&dir.join("pkg/lib.go").to_string_lossy()),
+            Some(dir.join("revive.toml"))
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_staticcheck_json() {
+        let stdout = "{\"code\":\"SA4006\",\"severity\":\"error\",\"location\":{\"file\":\"/ws/pkg/lib.go\",\"line\":5,\"column\":2},\"end\":{\"file\":\"/ws/pkg/lib.go\",\"line\":5,\"column\":3},\"message\":\"this value of x is never used\"}\n{\"code\":\"U1000\",\"severity\":\"warning\",\"location\":{\"file\":\"/ws/pkg/other.go\",\"line\":9,\"column\":6},\"message\":\"func f is unused\"}\n";
+        let diagnostics = parse_staticcheck_json(stdout).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "/ws/pkg/lib.go:5:2: error: this value of x is never used (SA4006)"
+        );
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert!(!diagnostics[1].is_for_file("/ws/pkg/lib.go", "/ws"));
+        assert_eq!(parse_staticcheck_json(""), Some(Vec::new()));
+        assert_eq!(parse_staticcheck_json("-: cannot load package"), None);
+    }
+
+    #[test]
+    fn test_parse_go_vet_json() {
+        let stderr = "# example.com/pkg\n{\n\t\"example.com/pkg\": {\n\t\t\"printf\": [\n\t\t\t{\n\t\t\t\t\"posn\": \"/ws/pkg/lib.go:10:2\",\n\t\t\t\t\"message\": \"fmt.Printf format %d has arg s of wrong type string\"\n\t\t\t}\n\t\t]\n\t}\n}\n# example.com/pkg [example.com/pkg.test]\n{}\n";
+        let diagnostics = parse_go_vet_json(stderr).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "/ws/pkg/lib.go:10:2: error: fmt.Printf format %d has arg s of wrong type string (printf)"
+        );
+        assert_eq!(parse_go_vet_json(""), Some(Vec::new()));
+        assert_eq!(
+            parse_go_vet_json("# example.com/pkg\nvet: pkg/lib.go:3:1: expected declaration"),
+            None
+        );
+    }
+
+    const RUFF_JSON: &str = r#"[
+        {"cell":null,"code":"F401","end_location":{"column":10,"row":1},"filename":"/ws/app.py","fix":{"applicability":"safe","edits":[],"message":"Remove unused import: `os`"},"location":{"column":8,"row":1},"message":"`os` imported but unused","noqa_row":1,"url":"https://docs.astral.sh/ruff/rules/unused-import"},
+        {"cell":null,"code":"E711","end_location":{"column":12,"row":4},"filename":"/ws/app.py","fix":null,"location":{"column":8,"row":4},"message":"Comparison to `None` should be `cond is None`","noqa_row":4,"url":"https://docs.astral.sh/ruff/rules/none-comparison"}
+    ]"#;
+
+    #[test]
+    fn test_parse_ruff_json() {
+        let diagnostics = parse_ruff_json(RUFF_JSON).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, "F401");
+        assert_eq!(
+            diagnostics[0].url.as_deref(),
+            Some("https://docs.astral.sh/ruff/rules/unused-import")
+        );
+        assert_eq!(
+            diagnostics[0].fix.as_deref(),
+            Some("Remove unused import: `os`")
+        );
+        assert_eq!(diagnostics[1].fix, None);
+        assert_eq!(parse_ruff_json("[]"), Some(Vec::new()));
+        assert_eq!(parse_ruff_json("error: invalid config"), None);
+    }
+
+    #[test]
+    fn test_lenient_rules_from_config() {
+        let config = Config::parse(
+            "[lenient.python]\nallow = [\"F401\"]\n[lenient.rust]\nonly = [\"clippy::unwrap_used\"]\n",
+        );
+        let python = LenientRules::from_config(&config, "python");
+        assert!(!python.keeps("F401", &RUFF_LENIENT_CODES));
+        assert!(python.keeps("F841", &RUFF_LENIENT_CODES));
+        assert!(!LenientRules::default().keeps("F841", &RUFF_LENIENT_CODES));
+
+        let rust = LenientRules::from_config(&config, "rust");
+        assert!(rust.keeps("clippy::unwrap_used", &[]));
+        assert!(!rust.keeps("dead_code", &[]));
+        assert_eq!(
+            rust_lenient_args(&rust),
+            [
+                "-A",
+                "warnings",
+                "-A",
+                "clippy::all",
+                "-D",
+                "clippy::unwrap_used"
+            ]
+        );
+        assert_eq!(
+            rust_lenient_args(&LenientRules::default()),
+            [
+                "-A",
+                "unused_variables",
+                "-A",
+                "unused_imports",
+                "-A",
+                "dead_code"
+            ]
+        );
+    }
 
-/// Build a `{"continue":true}` response, including `systemMessage` only in debug mode.
-pub fn continue_result(debug: bool, message: &str) -> String {
-    if debug {
-        format!(
-            r#"{{"continue":true,"systemMessage":"{}"}}"#,
-            escape_json(message)
-        )
-    } else {
-        r#"{"continue":true}"#.to_string()
+    #[test]
+    fn test_js_lenient_args_follow_rules() {
+        let defaults = js_lenient_args("biome", &LenientRules::default());
+        assert!(defaults.contains(&"--skip=correctness/noUnusedImports".to_string()));
+        let allow = LenientRules::Allow(Some(vec!["no-console".to_string()]));
+        assert_eq!(
+            js_lenient_args("eslint", &allow),
+            ["--rule", "no-console: off"]
+        );
+        let only = LenientRules::Only(vec!["no-debugger".to_string()]);
+        assert_eq!(
+            js_lenient_args("oxlint", &only),
+            ["-A", "all", "-D", "no-debugger"]
+        );
+        assert!(js_lenient_args("eslint", &only).is_empty());
     }
-}
 
-fn output_lint_result(
-    linter: &str,
-    file_path: &str,
-    stdout: &str,
-    stderr: &str,
-    success: bool,
-    debug: bool,
-) -> String {
-    if success {
-        continue_result(
-            debug,
-            &format!("[ralph-hook-lint] lint passed for {file_path} using {linter}."),
+    #[test]
+    fn test_keep_only_rules_filters_text_diagnostics() {
+        let output = "/ws/src/a.ts\n  1:7  error  'x' is unused  no-unused-vars\n  2:1  error  Unexpected debugger  no-debugger\n\n2 problems";
+        let only = ["no-debugger".to_string()];
+        let (kept, blocking) = keep_only_rules(output, &only);
+        assert!(blocking);
+        assert!(kept.contains("no-debugger") && !kept.contains("no-unused-vars"));
+        assert!(kept.contains("/ws/src/a.ts"));
+        let (_, blocking) = keep_only_rules(output, &["eqeqeq".to_string()]);
+        assert!(!blocking);
+    }
+
+    #[test]
+    fn test_ruff_json_result_category_policy() {
+        let policy = Policy::from_config(&Config::parse(
+            "[output]\nwarn_categories = [\"correctness\", \"style\"]\n",
+        ));
+        let result = ruff_json_result("/ws/app.py", RUFF_JSON, None, &policy, true).unwrap();
+        assert!(result.starts_with(r#"{"continue":true"#), "{result}");
+
+        let policy = Policy::from_config(&Config::parse(
+            "[output]\nblock_categories = [\"correctness\"]\n",
+        ));
+        let result = ruff_json_result("/ws/app.py", RUFF_JSON, None, &policy, true).unwrap();
+        assert!(result.contains(r#""decision":"block""#));
+        assert!(
+            result.contains("E711"),
+            "non-blocking findings are still listed"
+        );
+    }
+
+    #[test]
+    fn test_ruff_json_result_lenient_filters_rules() {
+        let strict =
+            ruff_json_result("/ws/app.py", RUFF_JSON, None, &Policy::default(), true).unwrap();
+        assert!(strict.contains("F401") && strict.contains("E711"));
+
+        let lenient = ruff_json_result(
+            "/ws/app.py",
+            RUFF_JSON,
+            Some(&LenientRules::default()),
+            &Policy::default(),
+            true,
         )
-    } else {
-        let output = if !stdout.is_empty() && !stderr.is_empty() {
-            format!("{stdout}\n{stderr}")
-        } else if !stdout.is_empty() {
-            stdout.to_string()
-        } else {
-            stderr.to_string()
-        };
+        .unwrap();
+        assert!(!lenient.contains("F401"));
+        assert!(lenient.contains("E711"));
 
-        format!(
-            r#"{{"decision":"block","reason":"[ralph-hook-lint] lint errors in {} using {}:\n\n{}\n\nFix lint errors."}}"#,
-            escape_json(file_path),
-            escape_json(linter),
-            escape_json(output.trim())
+        let only_lenient_rules = r#"[{"code":"F841","filename":"/ws/a.py","location":{"row":1,"column":1},"message":"unused"}]"#;
+        let result = ruff_json_result(
+            "/ws/a.py",
+            only_lenient_rules,
+            Some(&LenientRules::default()),
+            &Policy::default(),
+            false,
         )
+        .unwrap();
+        assert_eq!(result, r#"{"continue":true}"#);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_parse_biome_json_span_positions() {
+        let stdout = r#"{"summary":{"errors":1,"warnings":1},"diagnostics":[
+            {"category":"lint/correctness/noUnusedVariables","severity":"error","description":"This variable is unused.","location":{"path":{"file":"src/index.ts"},"span":[16,17],"sourceCode":"const a = 1;\nlet b = 2;\n"}},
+            {"category":"lint/style/useConst","severity":"warning","description":"Use const.","location":{"path":{"file":"src/index.ts"},"span":[13,16],"sourceCode":"const a = 1;\nlet b = 2;\n"}}
+        ],"command":"lint"}"#;
+        let diagnostics = parse_biome_json(stdout, "/ws/src/index.ts").unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "src/index.ts:2:4: error: This variable is unused. (lint/correctness/noUnusedVariables)"
+        );
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!((diagnostics[1].line, diagnostics[1].column), (2, 1));
+    }
 
     #[test]
-    fn test_escape_json_simple_string() {
-        assert_eq!(escape_json("hello"), "hello");
+    fn test_parse_biome_json_start_positions_and_notices() {
+        let stdout = "notice: the JSON reporter is experimental\n{\"diagnostics\":[{\"category\":\"lint/suspicious/noDebugger\",\"severity\":\"error\",\"description\":\"Unexpected debugger.\",\"location\":{\"path\":\"src/a.js\",\"start\":{\"line\":4,\"column\":3}}}]}";
+        let diagnostics = parse_biome_json(stdout, "/ws/src/a.js").unwrap();
+        assert_eq!(diagnostics[0].file, "src/a.js");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (4, 3));
+        assert_eq!(parse_biome_json("no json here", "/ws/a.js"), None);
     }
 
     #[test]
-    fn test_escape_json_quotes() {
-        assert_eq!(escape_json(r#"say "hello""#), r#"say \"hello\""#);
+    fn test_line_column_at() {
+        assert_eq!(line_column_at("ab\ncd", 0), (1, 1));
+        assert_eq!(line_column_at("ab\ncd", 4), (2, 2));
+        assert_eq!(line_column_at("ab", 99), (1, 3));
     }
 
     #[test]
-    fn test_escape_json_backslash() {
-        assert_eq!(escape_json(r"path\to\file"), r"path\\to\\file");
+    fn test_parse_pmd_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<pmd xmlns="http://pmd.sourceforge.net/report/2.0.0" version="7.0.0">
+<file name="/ws/src/main/java/App.java">
+<violation beginline="3" endline="3" begincolumn="20" endcolumn="21" rule="UnusedPrivateField" ruleset="Best Practices" priority="3" externalInfoUrl="https://docs.pmd-code.org/rules/unusedprivatefield">
+Avoid unused private fields such as 'x'.
+</violation>
+</file>
+<file name="/ws/src/main/java/Other.java">
+<violation beginline="9" begincolumn="1" rule="EmptyCatchBlock" priority="1">
+Avoid empty catch blocks
+</violation>
+</file>
+</pmd>"#;
+        let diagnostics = parse_pmd_xml(xml);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "/ws/src/main/java/App.java:3:20: warning: Avoid unused private fields such as 'x'. (UnusedPrivateField, priority 3)"
+        );
+        assert_eq!(diagnostics[1].severity, Severity::Error);
     }
 
     #[test]
-    fn test_escape_json_newlines() {
-        assert_eq!(escape_json("line1\nline2"), r"line1\nline2");
+    fn test_parse_spotbugs_xml_prefers_primary_source_line() {
+        let xml = r#"<BugCollection>
+<BugInstance type="NP_NULL_ON_SOME_PATH" priority="1" category="CORRECTNESS">
+<ShortMessage>Possible null pointer dereference</ShortMessage>
+<LongMessage>Possible null pointer dereference of s in App.run()</LongMessage>
+<Class classname="com.x.App"><SourceLine classname="com.x.App" start="1" end="20" sourcepath="com/x/App.java"/></Class>
+<SourceLine classname="com.x.App" start="12" end="12" sourcepath="com/x/App.java" primary="true"/>
+</BugInstance>
+<BugInstance type="DM_DEFAULT_ENCODING" priority="2">
+<LongMessage>Found reliance on default encoding</LongMessage>
+<SourceLine classname="com.x.Util" start="4" sourcepath="com/x/Util.java"/>
+</BugInstance>
+</BugCollection>"#;
+        let diagnostics = parse_spotbugs_xml(xml);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "com/x/App.java:12:0: error: Possible null pointer dereference of s in App.run() (NP_NULL_ON_SOME_PATH, priority 1)"
+        );
+        assert!(Path::new("/ws/src/main/java/com/x/App.java").ends_with(&diagnostics[0].file));
+        assert_eq!(diagnostics[1].line, 4);
     }
 
     #[test]
-    fn test_escape_json_tabs() {
-        assert_eq!(escape_json("col1\tcol2"), r"col1\tcol2");
+    fn test_java_report_result_scopes_to_file() {
+        let root = std::env::temp_dir().join(format!("ralph-lint-pmd-{}", std::process::id()));
+        fs::create_dir_all(root.join("target")).unwrap();
+        let started = SystemTime::now();
+        fs::write(
+            root.join("target/pmd.xml"),
+            r#"<pmd><file name="/ws/src/Other.java"><violation beginline="1" rule="R" priority="3">bad</violation></file></pmd>"#,
+        )
+        .unwrap();
+        let root_str = root.to_string_lossy();
+        let run = JavaRun {
+            linter: "mvn pmd:check".to_string(),
+            task: "pmd:check",
+            started,
+            lenient: None,
+        };
+
+        let result = java_report_result(&run, &root_str, "/ws/src/App.java", false);
+        assert_eq!(result.as_deref(), Some(r#"{"continue":true}"#));
+
+        let result = java_report_result(&run, &root_str, "/ws/src/Other.java", false).unwrap();
+        assert!(result.contains(r#""decision":"block""#) && result.contains("bad (R, priority 3)"));
+
+        // Stale reports from earlier runs are ignored
+        let later = JavaRun {
+            started: started + Duration::from_secs(60),
+            ..run
+        };
+        assert!(java_report_result(&later, &root_str, "/ws/src/Other.java", false).is_none());
+
+        let _ = fs::remove_dir_all(&root);
     }
 
     #[test]
-    fn test_escape_json_carriage_return() {
-        assert_eq!(escape_json("line1\r\nline2"), r"line1\r\nline2");
+    fn test_java_report_result_drops_lenient_rules() {
+        let root =
+            std::env::temp_dir().join(format!("ralph-lint-pmd-lenient-{}", std::process::id()));
+        fs::create_dir_all(root.join("target")).unwrap();
+        let started = SystemTime::now();
+        fs::write(
+            root.join("target/pmd.xml"),
+            r#"<pmd><file name="/ws/src/App.java"><violation beginline="1" rule="UnusedLocalVariable" priority="3">unused x</violation><violation beginline="2" rule="EmptyCatchBlock" priority="3">empty catch</violation></file></pmd>"#,
+        )
+        .unwrap();
+        let root_str = root.to_string_lossy();
+        let run = |lenient| JavaRun {
+            linter: "mvn pmd:check".to_string(),
+            task: "pmd:check",
+            started,
+            lenient,
+        };
+
+        let strict = java_report_result(&run(None), &root_str, "/ws/src/App.java", false).unwrap();
+        assert!(strict.contains("unused x") && strict.contains("empty catch"));
+
+        let lenient = run(Some(LenientRules::default()));
+        let result = java_report_result(&lenient, &root_str, "/ws/src/App.java", false).unwrap();
+        assert!(!result.contains("unused x") && result.contains("empty catch"));
+
+        let only = run(Some(LenientRules::Only(vec![
+            "UnusedLocalVariable".to_string(),
+        ])));
+        let result = java_report_result(&only, &root_str, "/ws/src/App.java", false).unwrap();
+        assert!(result.contains("unused x") && !result.contains("empty catch"));
+
+        let _ = fs::remove_dir_all(&root);
     }
 
     #[test]
-    fn test_escape_json_mixed() {
+    fn test_clippy_command_default_has_no_env() {
+        let cmd = clippy_command("/project", &Config::default(), false);
+        assert_eq!(cmd.get_envs().count(), 0);
+        assert!(!cmd.get_args().any(|a| a == "unused_variables"));
+    }
+
+    #[test]
+    fn test_clippy_command_applies_target_dir_and_env() {
+        let config = Config::parse(
+            "[rust]\ntarget_dir = \"target/ralph-hook\"\n[rust.env]\nCARGO_INCREMENTAL = \"1\"\n",
+        );
+        let cmd = clippy_command("/project", &config, true);
         assert_eq!(
-            escape_json("Error: \"file\\not\\found\"\n"),
-            r#"Error: \"file\\not\\found\"\n"#
+            command_env(&cmd, "CARGO_TARGET_DIR"),
+            Some("/project/target/ralph-hook".as_ref())
         );
+        assert_eq!(command_env(&cmd, "CARGO_INCREMENTAL"), Some("1".as_ref()));
+        assert!(cmd.get_args().any(|a| a == "unused_variables"));
     }
 
     #[test]
-    fn test_output_lint_result_success_debug() {
-        let result = output_lint_result("eslint", "src/app.js", "", "", true, true);
+    fn test_relative_target_dir_is_shared_across_workspace_members() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/rust/monorepo");
+        let member = root.join("crates/app").to_string_lossy().to_string();
+        let config = Config::parse("[rust]\ntarget_dir = \"target/ralph-hook\"\n");
         assert_eq!(
-            result,
-            r#"{"continue":true,"systemMessage":"[ralph-hook-lint] lint passed for src/app.js using eslint."}"#
+            resolve_target_dir(&member, &config, "target/ralph-hook"),
+            root.join("target/ralph-hook")
+        );
+
+        let mut from_file = Config::parse("[rust]\ntarget_dir = \"target/ralph-hook\"\n");
+        from_file.source = Some(PathBuf::from("/ws").join(crate::config::CONFIG_FILE));
+        assert_eq!(
+            resolve_target_dir(&member, &from_file, "target/ralph-hook"),
+            Path::new("/ws/target/ralph-hook")
+        );
+        assert_eq!(
+            resolve_target_dir(&member, &from_file, "/cache/target"),
+            Path::new("/cache/target")
         );
     }
 
     #[test]
-    fn test_output_lint_result_success_no_debug() {
-        let result = output_lint_result("eslint", "src/app.js", "", "", true, false);
-        assert_eq!(result, r#"{"continue":true}"#);
+    fn test_clippy_command_sets_available_rustc_wrapper() {
+        let config = Config::parse("[rust]\nrustc_wrapper = \"/bin/sh\"\n");
+        let cmd = clippy_command("/project", &config, false);
+        assert_eq!(command_env(&cmd, "RUSTC_WRAPPER"), Some("/bin/sh".as_ref()));
     }
 
     #[test]
-    fn test_output_lint_result_failure_stdout_only() {
-        let result = output_lint_result("eslint", "src/app.js", "error on line 1", "", false, true);
+    fn test_parse_clippy_json_uses_primary_span() {
+        let stdout = [
+            r#"{"reason":"compiler-artifact","target":{"name":"a"}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"unused variable: `x`","code":{"code":"unused_variables"},"spans":[{"file_name":"crates/a/src/lib.rs","line_start":2,"column_start":9,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"unused variable: `x`","code":{"code":"unused_variables"},"spans":[{"file_name":"crates/a/src/lib.rs","line_start":2,"column_start":9,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"aborting due to 1 previous error","code":null,"spans":[]}}"#,
+            r#"{"reason":"build-finished","success":false}"#,
+        ]
+        .join("\n");
+        let diagnostics = parse_clippy_json(&stdout);
+        assert_eq!(diagnostics.len(), 1);
         assert_eq!(
-            result,
-            r#"{"decision":"block","reason":"[ralph-hook-lint] lint errors in src/app.js using eslint:\n\nerror on line 1\n\nFix lint errors."}"#
+            diagnostics[0].to_string(),
+            "crates/a/src/lib.rs:2:9: error: unused variable: `x` (unused_variables)"
         );
+        assert!(diagnostics[0].is_for_file("/ws/crates/a/src/lib.rs", "/ws"));
     }
 
     #[test]
-    fn test_output_lint_result_failure_stderr_only() {
-        let result = output_lint_result("eslint", "src/app.js", "", "error on line 2", false, true);
+    fn test_parse_eslint_json_reports_every_file() {
+        let stdout = r#"[
+            {"filePath":"/p/a.js","messages":[{"ruleId":"no-unused-vars","severity":2,"message":"'x' is unused","line":1,"column":7}]},
+            {"filePath":"/p/b.js","messages":[{"ruleId":"eqeqeq","severity":1,"message":"Expected '==='","line":2,"column":3},{"ruleId":null,"fatal":true,"severity":2,"message":"Parsing error","line":4,"column":1}]},
+            {"filePath":"/p/c.js","messages":[]}
+        ]"#;
+        let diagnostics = parse_eslint_json(stdout).unwrap();
+        assert_eq!(diagnostics.len(), 3);
         assert_eq!(
-            result,
-            r#"{"decision":"block","reason":"[ralph-hook-lint] lint errors in src/app.js using eslint:\n\nerror on line 2\n\nFix lint errors."}"#
+            diagnostics[0].to_string(),
+            "/p/a.js:1:7: error: 'x' is unused (no-unused-vars)"
         );
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!(diagnostics[2].rule, "");
+        assert!(parse_eslint_json("Oops! Something went wrong!").is_none());
     }
 
     #[test]
-    fn test_output_lint_result_failure_both() {
-        let result = output_lint_result(
-            "eslint",
-            "src/app.js",
-            "stdout err",
-            "stderr err",
-            false,
-            true,
+    fn test_combine_results_joins_blocking_reasons() {
+        let files = vec!["/p/a.py".to_string(), "/p/b.py".to_string()];
+        let pass = output_lint_result("ruff", "/p/a.py", "", "", true, false);
+        let block = output_lint_result("ruff", "/p/b.py", "b.py:1:1: error: x", "", false, false);
+        assert_eq!(
+            combine_results(&[pass.clone(), pass.clone()], &files, "ruff", true),
+            r#"{"continue":true,"systemMessage":"[ralph-hook-lint] lint passed for 2 files using ruff."}"#
         );
+        let combined = combine_results(&[block.clone(), pass, block], &files, "ruff", false);
+        assert!(combined.starts_with(
+            r#"{"decision":"block","reason":"[ralph-hook-lint] lint errors in /p/b.py"#
+        ));
+        assert_eq!(combined.matches("\\n\\n---\\n\\n").count(), 1);
+    }
+
+    #[test]
+    fn test_clippy_command_skips_missing_rustc_wrapper() {
+        let config = Config::parse("[rust]\nrustc_wrapper = \"/nonexistent/sccache\"\n");
+        let cmd = clippy_command("/project", &config, false);
+        assert_eq!(command_env(&cmd, "RUSTC_WRAPPER"), None);
+    }
+
+    #[test]
+    fn test_parse_phpstan_json() {
+        let stdout = r#"{"totals":{"errors":0,"file_errors":2},"files":{"/ws/src/a.php":{"errors":2,"messages":[
+            {"message":"Undefined variable: $x","line":4,"ignorable":true,"identifier":"variable.undefined"},
+            {"message":"Unused variable $y","line":6,"ignorable":true}
+        ]}},"errors":[]}"#;
+        let diagnostics = parse_php_files_json(stdout).unwrap();
+        assert_eq!(diagnostics.len(), 2);
         assert_eq!(
-            result,
-            r#"{"decision":"block","reason":"[ralph-hook-lint] lint errors in src/app.js using eslint:\n\nstdout err\nstderr err\n\nFix lint errors."}"#
+            diagnostics[0].to_string(),
+            "/ws/src/a.php:4:0: error: Undefined variable: $x (variable.undefined)"
         );
+        assert_eq!(diagnostics[1].rule, "");
+
+        let clean = r#"{"totals":{"errors":0,"file_errors":0},"files":[],"errors":[]}"#;
+        assert_eq!(parse_php_files_json(clean), Some(Vec::new()));
+        let broken = r#"{"totals":{"errors":1,"file_errors":0},"files":[],"errors":["Invalid configuration"]}"#;
+        assert_eq!(parse_php_files_json(broken), None);
+        assert_eq!(parse_php_files_json("PHP Fatal error"), None);
     }
 
     #[test]
-    fn test_output_lint_result_failure_no_debug_still_blocks() {
-        let result =
-            output_lint_result("eslint", "src/app.js", "error on line 1", "", false, false);
+    fn test_parse_phpcs_json() {
+        let stdout = r#"{"totals":{"errors":0,"warnings":1,"fixable":1},"files":{"/ws/a.php":{"errors":0,"warnings":1,"messages":[
+            {"message":"Line exceeds 120 characters","source":"Generic.Files.LineLength.TooLong","severity":5,"fixable":false,"type":"WARNING","line":3,"column":121}
+        ]}}}"#;
+        let diagnostics = parse_php_files_json(stdout).unwrap();
+        assert_eq!(diagnostics[0].rule, "Generic.Files.LineLength.TooLong");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].column, 121);
+    }
+
+    #[test]
+    fn test_parse_psalm_json() {
+        let stdout = r#"[{"severity":"error","line_from":5,"line_to":5,"type":"UndefinedVariable","message":"Cannot find referenced variable $x","file_name":"src/a.php","file_path":"/ws/src/a.php","column_from":10,"column_to":12,"link":"https://psalm.dev/024"},
+            {"severity":"info","line_from":2,"type":"UnusedVariable","message":"$y is never referenced","file_path":"/ws/src/a.php","column_from":1}]"#;
+        let diagnostics = parse_psalm_json(stdout).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].line, 5);
+        assert_eq!(diagnostics[0].url.as_deref(), Some("https://psalm.dev/024"));
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!(parse_psalm_json("[]"), Some(Vec::new()));
+        assert_eq!(parse_psalm_json("Psalm was unable to find a config"), None);
+    }
+
+    #[test]
+    fn test_parse_msbuild_diagnostics() {
+        let output = "  Determining projects to restore...\n\
+/ws/Services/Greeter.cs(12,5): warning CS0168: The variable 'x' is declared but never used [/ws/App.csproj]\n\
+Services/Greeter.cs(3,1,3,14): error IDE0005: Using directive is unnecessary. [/ws/App.csproj]\n\
+/ws/Services/Greeter.cs(12,5): warning CS0168: The variable 'x' is declared but never used [/ws/App.csproj]\n\
+CSC : error CS5001: Program does not contain a static 'Main' method [/ws/App.csproj]\n";
+        let diagnostics = parse_msbuild_diagnostics(output);
+        assert_eq!(diagnostics.len(), 2);
         assert_eq!(
-            result,
-            r#"{"decision":"block","reason":"[ralph-hook-lint] lint errors in src/app.js using eslint:\n\nerror on line 1\n\nFix lint errors."}"#
+            diagnostics[0].to_string(),
+            "/ws/Services/Greeter.cs:12:5: warning: The variable 'x' is declared but never used (CS0168)"
         );
+        assert_eq!(diagnostics[1].file, "Services/Greeter.cs");
+        assert_eq!(diagnostics[1].rule, "IDE0005");
+        assert_eq!(diagnostics[1].message, "Using directive is unnecessary.");
+        assert!(diagnostics[1].is_for_file("/ws/Services/Greeter.cs", "/ws"));
     }
 
     #[test]
-    fn test_output_lint_result_escapes_special_chars() {
-        let result = output_lint_result(
-            "eslint",
-            "src/app.js",
-            "error: \"unexpected\"\n",
-            "",
-            false,
-            true,
+    fn test_parse_swiftlint_json() {
+        let stdout = r#"[
+            {"character":5,"file":"/ws/Sources/App/main.swift","line":3,"reason":"Lines should not have trailing whitespace","rule_id":"trailing_whitespace","severity":"Warning","type":"Trailing Whitespace"},
+            {"character":null,"file":"/ws/Sources/App/main.swift","line":9,"reason":"Force casts should be avoided","rule_id":"force_cast","severity":"Error","type":"Force Cast"}
+        ]"#;
+        let diagnostics = parse_swiftlint_json(stdout).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "/ws/Sources/App/main.swift:3:5: warning: Lines should not have trailing whitespace (trailing_whitespace)"
         );
-        assert!(result.contains(r#"\"unexpected\""#));
-        assert!(result.contains(r"\n"));
+        assert_eq!(diagnostics[1].column, 0);
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+        assert_eq!(parse_swiftlint_json("[]"), Some(Vec::new()));
+        assert_eq!(parse_swiftlint_json("error: could not read config"), None);
     }
 
     #[test]
-    fn test_continue_result_debug() {
-        let result = continue_result(true, "[ralph-hook-lint] some message");
+    fn test_shellcheck_gcc_output() {
+        let output = "install.sh:3:6: warning: foo is referenced but not assigned. [SC2154]\n\
+install.sh:5:1: error: Couldn't find 'fi' for this 'if'. [SC1046]\n\
+install.sh:7:8: note: Double quote to prevent globbing and word splitting. [SC2086]\n";
+        let diagnostics = parse_gcc_diagnostics(output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, "SC2154");
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+        assert_eq!(diagnostics[1].message, "Couldn't find 'fi' for this 'if'.");
+        assert!(is_zsh_script("/ws/prompt.zsh"));
+        assert!(!is_zsh_script("/ws/install.sh"));
+    }
+
+    #[test]
+    fn test_js_exec_dir_strategies() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-js-exec-{}", std::process::id()));
+        let package = dir.join("repo/packages/web");
+        fs::create_dir_all(&package).unwrap();
+        fs::create_dir_all(dir.join("repo/.git")).unwrap();
+        fs::write(dir.join("repo/pnpm-workspace.yaml"), "packages: []\n").unwrap();
+        let package = package.to_string_lossy();
+        let exec_dir = |setting: &str| {
+            js_exec_dir(
+                &package,
+                &Config::parse(&format!("[js]\nrun_from = \"{setting}\"\n")),
+            )
+        };
+        assert_eq!(exec_dir("package"), package);
+        assert_eq!(exec_dir("workspace"), dir.join("repo").to_string_lossy());
+        assert_eq!(exec_dir("root"), dir.join("repo").to_string_lossy());
+        assert_eq!(js_exec_dir(&package, &Config::default()), package);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_astro_check_output() {
+        let output = "src/pages/index.astro:3:7 - error ts(2322): Type 'number' is not assignable to type 'string'.\n\n3 const title: string = 1;\n        ~~~~~\n\nsrc/layouts/Base.astro:1:1 - hint ts(6133): 'x' is declared but its value is never read.\n";
+        let diagnostics = parse_astro_check(output);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file, "src/pages/index.astro");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 7));
+        assert_eq!(diagnostics[0].rule, "ts(2322)");
         assert_eq!(
-            result,
-            r#"{"continue":true,"systemMessage":"[ralph-hook-lint] some message"}"#
+            template_language("/ws/src/pages/index.astro"),
+            Some("astro")
         );
+        assert_eq!(template_language("/ws/views/home.ejs"), Some("ejs"));
+        assert_eq!(template_language("/ws/src/App.vue"), Some("vue"));
+        assert_eq!(template_language("/ws/src/app.ts"), None);
     }
 
     #[test]
-    fn test_continue_result_no_debug() {
-        let result = continue_result(false, "[ralph-hook-lint] some message");
-        assert_eq!(result, r#"{"continue":true}"#);
+    fn test_psscriptanalyzer_json() {
+        let output = r#"[{"RuleName":"PSAvoidUsingComputerNameHardcoded","Line":3,"Column":17,"Message":"The ComputerName parameter of a cmdlet 'Invoke-Command' is hardcoded.","ScriptPath":"/ws/build.ps1"}]"#;
+        let diagnostics = parse_psscriptanalyzer_json(output).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file, "/ws/build.ps1");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 17));
+        assert_eq!(diagnostics[0].rule, "PSAvoidUsingComputerNameHardcoded");
+        assert_eq!(parse_psscriptanalyzer_json("[]\n"), Some(Vec::new()));
+        assert_eq!(
+            parse_psscriptanalyzer_json("Invoke-ScriptAnalyzer: error"),
+            None
+        );
     }
 
     #[test]
-    fn test_filter_clippy_output_matches_relative_path() {
-        let stderr = "warning: unused variable\n  --> src/main.rs:10:5\nerror: something else";
-        let result = filter_clippy_output_multi("", stderr, &["/project/src/main.rs"], "/project");
-        assert!(result.contains("src/main.rs:10:5"));
-        assert!(!result.contains("unused variable"));
+    fn test_sbt_tasks_and_diagnostics() {
+        let tasks = "This is a list of tasks defined for the current project.\n\n  compile      Compiles sources.\n  scalafix     Run scalafix rule(s) in this project.\n\nMore tasks may be viewed\n";
+        assert_eq!(parse_sbt_tasks(tasks), vec!["compile", "scalafix"]);
+
+        let output = "[info] welcome to sbt\n\
+[error] /ws/src/main/scala/App.scala:3:1: error: [RemoveUnused] Unused import\n\
+[warn] /ws/src/main/scala/App.scala:5: Magic Number\n\
+[error] --- /ws/src/main/scala/Other.scala\n\
+[error] (Compile / scalafix) scalafix.sbt.ScalafixFailed: LinterError\n";
+        let diagnostics = parse_sbt_diagnostics(output);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].rule, "RemoveUnused");
+        assert_eq!(diagnostics[0].message, "Unused import");
+        assert_eq!((diagnostics[1].line, diagnostics[1].column), (5, 1));
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!(diagnostics[1].message, "Magic Number");
+        assert_eq!(diagnostics[2].file, "/ws/src/main/scala/Other.scala");
+        assert_eq!(diagnostics[2].rule, "scalafix");
     }
 
     #[test]
-    fn test_filter_clippy_output_matches_filename_fallback() {
-        let stderr = "warning: unused in main.rs\n  --> other/main.rs:5:1";
-        let result = filter_clippy_output_multi("", stderr, &["/project/src/main.rs"], "/project");
-        assert!(result.contains("main.rs"));
+    fn test_tflint_and_conftest_output() {
+        let tflint = "main.tf:1:1: Warning - Missing version constraint for provider \"aws\" in \"required_providers\" (terraform_required_providers)\n\
+main.tf:7:3: Error - Unsupported argument; An argument named \"acl_x\" is not expected here.\n\
+\n2 issue(s) found:\n";
+        let diagnostics = parse_tflint_compact(tflint);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, "terraform_required_providers");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[1].rule, "tflint");
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+
+        let conftest = r#"[{"filename":"main.tf","namespace":"s3","successes":2,"failures":[{"msg":"S3 bucket 'logs' is public","metadata":{"query":"data.s3.deny"}}],"warnings":[{"msg":"S3 bucket 'logs' has no versioning"}]}]"#;
+        let diagnostics = parse_conftest_json(conftest).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, "s3");
+        assert_eq!(diagnostics[0].message, "S3 bucket 'logs' is public");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!(parse_conftest_json("Error: loading policies"), None);
     }
 
     #[test]
-    fn test_filter_clippy_output_empty_when_no_match() {
-        let stderr = "warning: in other.rs:10:5";
-        let result = filter_clippy_output_multi("", stderr, &["/project/src/main.rs"], "/project");
-        assert!(result.is_empty() || !result.contains("other.rs"));
+    fn test_solhint_and_solc_output() {
+        let solhint = "contracts/Token.sol:12:5: Variable \"x\" is unused [Warning/no-unused-vars]\n\
+contracts/Token.sol:3:1: Compiler version ^0.7.0 does not satisfy the ^0.8.0 semver requirement [Error/compiler-version]\n\
+\n2 problems\n";
+        let diagnostics = parse_solhint_unix(solhint);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, "no-unused-vars");
+        assert_eq!(diagnostics[0].message, "Variable \"x\" is unused");
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+
+        let forge = "Compiling 1 files with Solc 0.8.24\nError: Compiler run failed:\n\
+Error (7576): Undeclared identifier.\n  --> src/Counter.sol:10:9:\n   |\n10 |         foo();\n   |         ^^^\n\
+Warning (2072): Unused local variable.\n --> src/Counter.sol:8:9:\n";
+        let diagnostics = parse_solc_diagnostics(forge);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].file, "src/Counter.sol");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (10, 9));
+        assert_eq!(diagnostics[0].rule, "7576");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[1].rule, "2072");
     }
 
     #[test]
-    fn test_filter_clippy_output_multi_matches_multiple_files() {
-        let stderr = "  --> src/main.rs:10:5\n  --> src/lib.rs:20:3\n  --> src/other.rs:1:1";
-        let result = filter_clippy_output_multi(
-            "",
-            stderr,
-            &["/project/src/main.rs", "/project/src/lib.rs"],
-            "/project",
+    fn test_statix_errfmt() {
+        let output = "flake.nix>12:5:W:20:Assignment instead of inherit\n\
+flake.nix>3:1:E:0:Syntax error: unexpected token\n\
+[statix] 2 issues\n";
+        let diagnostics = parse_statix_errfmt(output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].file, "flake.nix");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (12, 5));
+        assert_eq!(diagnostics[0].rule, "W20");
+        assert_eq!(diagnostics[0].message, "Assignment instead of inherit");
+        assert_eq!(diagnostics[1].rule, "E00");
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_selene_and_luacheck_output() {
+        let selene = "init.lua:3:7: warning[unused_variable]: x is defined, but never used\n\
+init.lua:9:1: error[parse_error]: unexpected token `end`\n\
+Results:\n0 errors\n1 warnings\n";
+        let diagnostics = parse_selene_quiet(selene);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, "unused_variable");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 7));
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+        assert_eq!(diagnostics[1].message, "unexpected token `end`");
+
+        let luacheck = "init.lua:3:7: (W211) unused variable 'x'\n\
+init.lua:9:1: (E011) expected expression near 'end'\n\
+\n\
+Total: 1 warning / 1 error in 1 file\n";
+        let diagnostics = parse_luacheck_plain(luacheck);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, "W211");
+        assert_eq!(diagnostics[0].message, "unused variable 'x'");
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_zig_error_rules() {
+        let output = "src/main.zig:3:11: error: unused local constant\n\
+src/main.zig:5:8: error: unused function parameter\n\
+src/main.zig:9:1: error: expected ';' after statement\n";
+        let rules: Vec<&str> = parse_gcc_diagnostics(output)
+            .iter()
+            .map(|d| zig_error_rule(&d.message))
+            .collect();
+        assert_eq!(rules, ["unused-variable", "unused-parameter", ""]);
+    }
+
+    #[test]
+    fn test_hlint_json_output() {
+        let output = r#"[{"module":["Main"],"decl":["main"],"severity":"Suggestion","hint":"Use map","file":"src/Main.hs","startLine":4,"startColumn":8,"endLine":4,"endColumn":20,"from":"foldr (\\x acc -> f x : acc) []","to":"map f","note":[],"refactorings":"[]"},
+{"module":[],"decl":[],"severity":"Ignore","hint":"Eta reduce","file":"src/Main.hs","startLine":6,"startColumn":1,"endLine":6,"endColumn":9,"from":"","to":null,"note":[],"refactorings":"[]"}]"#;
+        let diagnostics = parse_hlint_json(output).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "Use map");
+        assert_eq!(diagnostics[0].message, "Use map. Why not: map f");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (4, 8));
+        assert!(parse_hlint_json("").is_none());
+    }
+
+    #[test]
+    fn test_ghc_output() {
+        let output = "[1 of 1] Compiling Main ( src/Main.hs, nothing )\n\
+\n\
+src/Main.hs:3:1: warning: [GHC-40910] [-Wunused-top-binds]\n\
+    Defined but not used: \u{2018}helper\u{2019}\n\
+  |\n\
+3 | helper = 1\n\
+  | ^^^^^^\n\
+\n\
+src/Main.hs:(6,8)-(7,12): error: [GHC-88464]\n\
+    Variable not in scope: frobnicate\n\
+      :: Int -> Int\n\
+  |\n\
+src/Main.hs:9:1-4: warning: [-Wunused-imports, Werror=unused-imports] The import of Data.List is redundant\n";
+        let diagnostics = parse_ghc_output(output);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].rule, "-Wunused-top-binds");
+        assert_eq!(
+            diagnostics[0].message,
+            "Defined but not used: \u{2018}helper\u{2019}"
+        );
+        assert_eq!(diagnostics[1].rule, "GHC-88464");
+        assert_eq!((diagnostics[1].line, diagnostics[1].column), (6, 8));
+        assert_eq!(
+            diagnostics[1].message,
+            "Variable not in scope: frobnicate :: Int -> Int"
+        );
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+        assert_eq!(diagnostics[2].rule, "-Wunused-imports");
+        assert_eq!(
+            diagnostics[2].message,
+            "The import of Data.List is redundant"
         );
-        assert!(result.contains("src/main.rs:10:5"));
-        assert!(result.contains("src/lib.rs:20:3"));
-        assert!(!result.contains("src/other.rs"));
     }
 
     #[test]
-    fn test_filter_clippy_workspace_no_cross_crate_leak() {
-        // Simulate a workspace where clippy reports errors from two crates.
-        // The filter for crate "app" should NOT match errors from "core" via
-        // the relative path, even though both have "lib.rs".
-        let stderr = "  --> src/lib.rs:10:5\n  --> /ws/crates/core/src/lib.rs:20:3";
-        let result = filter_clippy_output_multi(
-            "",
-            stderr,
-            &["/ws/crates/app/src/lib.rs"],
-            "/ws/crates/app",
+    fn test_credo_flycheck_output() {
+        let output = "lib/app.ex:1:11: R: Modules should have a @moduledoc tag.\n\
+lib/app.ex:7: W: There should be no calls to `IO.inspect/1`.\n\
+Analysis took 0.1 seconds\n";
+        let diagnostics = parse_credo_flycheck(output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            (
+                diagnostics[0].rule.as_str(),
+                diagnostics[0].line,
+                diagnostics[0].column
+            ),
+            ("readability", 1, 11)
         );
-        // "src/lib.rs:10:5" matches via relative path (correct — app's own file)
-        assert!(result.contains("src/lib.rs:10:5"));
-        // The absolute path "/ws/crates/core/src/lib.rs:20:3" should NOT match
-        // via relative path, but WILL match via the filename fallback "lib.rs".
-        // This is a known limitation of the filename fallback.
+        assert_eq!(diagnostics[1].column, 0);
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_elixir_compiler_output() {
+        let modern = "Compiling 2 files (.ex)\n\
+    warning: variable \"x\" is unused (if the variable is not meant to be used, prefix it with an underscore)\n\
+    │\n\
+  3 │     x = 1\n\
+    │     ~\n\
+    │\n\
+    └─ lib/app.ex:3:5: App.run/0\n\
+\n\
+    error: undefined variable \"y\"\n\
+    │\n\
+  4 │     y\n\
+    └─ lib/app.ex:4:5: App.run/0\n\
+\n\
+== Compilation error in file lib/app.ex ==\n\
+** (CompileError) lib/app.ex: cannot compile module App (errors have been logged)\n";
+        let diagnostics = parse_elixir_compiler(modern);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, "unused-variable");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 5));
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+        assert_eq!(diagnostics[1].message, "undefined variable \"y\"");
+
+        let legacy = "warning: unused alias Repo\n  lib/app.ex:2\n\n\
+** (CompileError) lib/app.ex:9: undefined function go/0\n";
+        let diagnostics = parse_elixir_compiler(legacy);
+        assert_eq!(diagnostics[0].rule, "unused-alias");
+        assert_eq!(diagnostics[0].line, 2);
+        assert_eq!(diagnostics[1].message, "undefined function go/0");
+
+        let syntax = "** (SyntaxError) invalid syntax found on lib/app.ex:3:5:\n";
+        let diagnostics = parse_elixir_compiler(syntax);
+        assert_eq!(
+            (diagnostics[0].line, diagnostics[0].message.as_str()),
+            (3, "SyntaxError: invalid syntax found on")
+        );
+    }
+
+    #[test]
+    fn test_markdownlint_output() {
+        let output = "markdownlint-cli2 v0.17.2 (markdownlint v0.37.4)\n\
+Finding: docs/intro.md\n\
+docs/intro.md:3 MD022/blanks-around-headings Headings should be surrounded by blank lines [Expected: 1; Actual: 0; Below]\n\
+docs/intro.md:9:81 error MD013/line-length Line length [Expected: 80; Actual: 120]\n\
+docs/intro.md:12:1 warning MD052/reference-links-images Reference links and images should use a label that is defined [Missing link or image reference definition: \"api\"]\n\
+Summary: 3 error(s)\n";
+        let diagnostics = parse_markdownlint(output);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            (
+                diagnostics[0].rule.as_str(),
+                diagnostics[0].line,
+                diagnostics[0].column
+            ),
+            ("MD022", 3, 0)
+        );
+        assert!(
+            diagnostics[0]
+                .message
+                .starts_with("blanks-around-headings: Headings")
+        );
+        assert_eq!(diagnostics[1].column, 81);
+        assert_eq!(diagnostics[2].severity, Severity::Warning);
     }
 }
@@ -1,171 +1,1090 @@
+mod bash;
+mod bench;
+mod capabilities;
+mod category;
+mod cli;
+mod codeblock;
 mod collect;
+mod config;
+mod content;
+mod daemon;
+mod diagnostic;
+mod encoding;
 mod extract;
+mod gitstatus;
+mod json;
 mod lint;
+mod network;
+mod origin;
+mod overlay;
+mod pipeline;
+mod policy;
+mod procgroup;
 mod project;
+mod quarantine;
+mod ratelimit;
+mod response;
+mod sandbox;
+mod schedule;
+mod schema;
+mod selftest;
+mod serve;
+mod skip;
+mod tier;
+mod verdict;
+mod watch;
+mod wsl;
+mod xml;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::env;
+use std::fmt::Write as _;
 use std::io::{self, Read};
+use std::time::Duration;
 
-use extract::{extract_file_path, extract_session_id};
+use cli::{Command, Mode};
+use config::Config;
+use extract::{extract_file_path, extract_hook_event_name, extract_session_id, extract_tool_name};
 use lint::{
-    continue_result, escape_json, run_go_lint, run_java_lint, run_js_lint, run_python_lint,
-    run_rust_lint, run_rust_lint_multi,
+    continue_result, escape_json, run_c_lint, run_csharp_lint, run_elixir_lint, run_go_lint,
+    run_haskell_lint, run_java_lint, run_js_lint, run_js_lint_multi, run_kotlin_lint, run_lua_lint,
+    run_markdown_lint, run_nix_lint, run_php_lint, run_powershell_lint, run_python_lint,
+    run_python_lint_multi, run_rust_lint, run_rust_lint_multi, run_rust_workspace_lint,
+    run_scala_lint, run_shell_lint, run_solidity_lint, run_standalone_lint, run_swift_lint,
+    run_terraform_lint, run_zig_lint,
 };
-use project::{Lang, find_project_root};
+use project::{Lang, find_cargo_workspace_root, find_project_root, find_repo_root};
+use quarantine::Quarantined;
+use response::{BUDGET_NOTE_PREFIX, OutputPolicy, SUMMARY_NOTE_PREFIX};
+use schedule::{Job, JobError};
+use schema::Schema;
+use skip::SkipReason;
+
+/// Default `[hooks] dedup_window_ms`: long enough to catch an immediate retry of the same event
+const DEFAULT_DEDUP_WINDOW_MS: i64 = 2000;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let args: Vec<String> = env::args().skip(1).collect();
+    let cli = match cli::parse(&args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            // Exit 1, not 2: Claude Code treats exit code 2 as a blocking error
+            eprintln!("ralph-hook-lint: {e}\n\n{}", cli::USAGE);
+            std::process::exit(1);
+        }
+    };
+    let (debug, lenient) = (cli.debug, cli.lenient);
 
-    // Handle --version flag
-    if args.iter().any(|a| a == "--version" || a == "-V") {
-        println!("{}", env!("CARGO_PKG_VERSION"));
-        return;
+    // Output policy and linter process settings come from the config for the agent's working directory
+    let cwd_config = env::current_dir()
+        .map(|dir| Config::load(&dir.to_string_lossy()))
+        .unwrap_or_default();
+    let policy = OutputPolicy::from_config(&cwd_config);
+    procgroup::configure(procgroup::Settings::from_config(&cwd_config));
+    if let Some(max) = cli.max_parallel {
+        schedule::configure_max_parallel(max);
+    }
+    if cli.collect_from_git {
+        collect::configure_from_git();
     }
 
-    let debug = args.iter().any(|a| a == "--debug");
-    let lenient = args.iter().any(|a| a == "--lenient");
-    let collect_mode = args.iter().any(|a| a == "--collect");
-    let lint_collected_mode = args.iter().any(|a| a == "--lint-collected");
+    let result = match &cli.command {
+        Command::Version => {
+            println!("{}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+        Command::Replay { log, mode } => run_replay(log, *mode, policy, debug, lenient),
+        Command::Doctor => {
+            let lint =
+                |payload: &str| respond(payload, Mode::Lint, OutputPolicy::default(), true, false);
+            selftest::run(lint).map(|all_ok| {
+                if !all_ok {
+                    std::process::exit(1);
+                }
+            })
+        }
+        Command::Bench { target } => bench::run(target, |payload| {
+            respond(payload, Mode::Lint, policy, true, lenient)
+        }),
+        Command::Watch { dir } => watch::run(dir, |payload| {
+            respond(payload, Mode::Lint, policy, true, lenient)
+        }),
+        Command::Daemon { action, dir } => daemon::run(*action, dir),
+        Command::Session { action, session_id } => overlay::run(action, session_id),
+        Command::Serve => serve::run(|payload, lenient_request| {
+            respond(
+                payload,
+                Mode::Lint,
+                policy,
+                true,
+                lenient || lenient_request,
+            )
+        }),
+        Command::Init => run_init(),
+        Command::Capabilities { json } => capabilities::run(*json),
+        Command::LintBackground {
+            session_id,
+            file_path,
+        } => run_background_lint(session_id, file_path, lenient),
+        Command::Hook(mode) => {
+            let mut input = String::new();
+            let output = match read_input(cli.input.as_deref(), &mut input) {
+                Ok(()) => respond(&input, *mode, policy, debug, lenient),
+                Err(e) => policy.error_response(None, &e.to_string(), debug),
+            };
+            println!("{output}");
+            Ok(())
+        }
+    };
 
-    let result = if collect_mode {
-        run_collect(debug)
-    } else if lint_collected_mode {
-        run_lint_collected(debug, lenient)
+    if let Err(e) = result {
+        eprintln!("ralph-hook-lint: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// Handle one hook payload and build the response for its schema and the output policy.
+fn respond(input: &str, mode: Mode, policy: OutputPolicy, debug: bool, lenient: bool) -> String {
+    let verbose = policy.verbose(debug);
+    if extract_hook_event_name(input).as_deref() == Some("SessionStart") {
+        return run_session_start(input, verbose);
+    }
+    let mode = resolve_mode(mode, input);
+    if let Some(session_id) = extract_session_id(input).filter(|sid| !sid.is_empty()) {
+        origin::set_session(&session_id);
+        if mode != Mode::LintCollected {
+            let _ = collect::record_baseline(&session_id, &payload_cwd(input));
+        }
+        // Only PreToolUse sees a file before the edit, so it decides whether the file is new
+        if extract_hook_event_name(input).as_deref() == Some("PreToolUse") {
+            for file_path in payload_files(input) {
+                let _ = origin::remember(&session_id, &file_path);
+            }
+        }
+    }
+    if mode != Mode::LintCollected {
+        if let Some(skipped) = filtered_event(input, verbose) {
+            return skipped;
+        }
+    }
+    if matches!(mode, Mode::Lint | Mode::Collect) {
+        if let Some(skipped) = duplicate_event(input, mode, verbose) {
+            return skipped;
+        }
+    }
+    let result = if let Err(e) = policy::current() {
+        Err(e.into())
+    } else if mode != Mode::LintCollected && extract_tool_name(input).as_deref() == Some("Bash") {
+        run_bash(input, verbose, lenient)
     } else {
-        run(debug, lenient)
+        match mode {
+            Mode::Collect => run_collect(input, verbose, lenient),
+            Mode::LintCollected => run_lint_collected(input, verbose, lenient),
+            Mode::Content => run_content(input, verbose, lenient),
+            Mode::Lint | Mode::Auto => run(input, verbose, lenient),
+        }
     };
-
+    let session_id = extract_session_id(input).filter(|sid| !sid.is_empty());
     match result {
-        Ok(output) => println!("{output}"),
-        Err(e) => println!(
-            "{}",
-            continue_result(debug, &format!("[ralph-hook-lint] lint hook error: {e}"))
+        Ok(output) => {
+            OutputPolicy::record_success(session_id.as_deref());
+            let output = match sandbox::take_missing_wrapper_note() {
+                Some(note) => tier::annotate(&output, &note),
+                None => output,
+            };
+            Schema::detect(input).adapt(policy.apply(output, debug))
+        }
+        Err(e) if Quarantined::find(e.as_ref()).is_some() => {
+            // Always reported, so the broken linter gets noticed
+            let note = format!(
+                r#"{{"continue":true,"systemMessage":"{}"}}"#,
+                escape_json(&e.to_string())
+            );
+            Schema::detect(input).adapt(policy.apply(note, debug))
+        }
+        Err(e) => Schema::detect(input).adapt(policy.error_response(
+            session_id.as_deref(),
+            &e.to_string(),
+            debug,
+        )),
+    }
+}
+
+/// Pick the behavior for `Mode::Auto` from the payload's `hook_event_name`, so one hook entry
+/// without flags can serve every hook point. `PostToolUse` lints immediately unless the config
+/// sets `[hooks] post_tool_use = "collect"`; payloads without an event name are linted.
+fn resolve_mode(mode: Mode, input: &str) -> Mode {
+    if mode != Mode::Auto {
+        return mode;
+    }
+    match extract_hook_event_name(input).as_deref() {
+        Some("Stop" | "SubagentStop") => Mode::LintCollected,
+        Some("PreToolUse") => Mode::Content,
+        Some("PostToolUse") => {
+            let config = env::current_dir()
+                .map(|dir| Config::load(&dir.to_string_lossy()))
+                .unwrap_or_default();
+            if config.get_str("hooks.post_tool_use") == Some("collect") {
+                Mode::Collect
+            } else {
+                Mode::Lint
+            }
+        }
+        _ => Mode::Lint,
+    }
+}
+
+/// A skip response if the `[hooks]` rules in the config nearest each edited file exclude this
+/// event's tool or every file.
+fn filtered_event(input: &str, debug: bool) -> Option<String> {
+    let file_paths = payload_files(input);
+    if file_paths.is_empty() || !accepted_files(input).is_empty() {
+        return None;
+    }
+    Some(SkipReason::Excluded.result(
+        debug,
+        &format!(
+            "skipping {}: excluded by [hooks] rules or the managed policy.",
+            file_paths.join(", ")
         ),
+    ))
+}
+
+/// A skip response if this event repeats one the session saw moments ago (same tool, files,
+/// and contents), as when an agent retries a `PostToolUse` event. The window is
+/// `[hooks] dedup_window_ms` from the config nearest each file; 0 turns deduplication off.
+fn duplicate_event(input: &str, mode: Mode, debug: bool) -> Option<String> {
+    let session_id = extract_session_id(input).filter(|sid| !sid.is_empty())?;
+    let event = format!("{mode:?} {}", extract_tool_name(input).unwrap_or_default());
+    let file_paths = payload_files(input);
+    let duplicates: Vec<bool> = file_paths
+        .iter()
+        .map(|fp| {
+            let window_ms = std::path::Path::new(fp).parent().map_or(0, |dir| {
+                Config::load(&dir.to_string_lossy())
+                    .get_int("hooks.dedup_window_ms")
+                    .unwrap_or(DEFAULT_DEDUP_WINDOW_MS)
+            });
+            let window = Duration::from_millis(u64::try_from(window_ms).unwrap_or(0));
+            !window.is_zero() && collect::is_duplicate_event(&session_id, &event, fp, window)
+        })
+        .collect();
+    if duplicates.is_empty() || duplicates.contains(&false) {
+        return None;
     }
+    Some(SkipReason::Debounced.result(
+        debug,
+        &format!(
+            "skipping {}: duplicate of an event moments ago.",
+            file_paths.join(", ")
+        ),
+    ))
 }
 
-/// Collect mode: record the file path from stdin into the session temp file, return immediately.
-fn run_collect(debug: bool) -> Result<String, Box<dyn std::error::Error>> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
+/// The size in KiB of `file_path`, if it is over `[hooks] max_file_kb` from the config nearest
+/// the file. Without that setting, files of any size are linted.
+fn oversized(file_path: &str) -> Option<u64> {
+    let path = std::path::Path::new(file_path);
+    let limit = Config::load(&path.parent()?.to_string_lossy())
+        .get_int("hooks.max_file_kb")
+        .and_then(|kb| u64::try_from(kb).ok())
+        .filter(|kb| *kb > 0)?;
+    let size = std::fs::metadata(path).ok()?.len().div_ceil(1024);
+    (size > limit).then_some(size)
+}
+
+/// Every file in the payload, translated across a WSL boundary if needed.
+fn payload_files(input: &str) -> Vec<String> {
+    extract::extract_file_paths(input)
+        .iter()
+        .map(|fp| wsl::translate(fp))
+        .collect()
+}
+
+/// The agent's working directory from the payload's `cwd`, else this process's.
+fn payload_cwd(input: &str) -> std::path::PathBuf {
+    extract::extract_cwd(input)
+        .map(|dir| std::path::PathBuf::from(wsl::translate(&dir)))
+        .or_else(|| env::current_dir().ok())
+        .unwrap_or_default()
+}
+
+/// The payload's files that the `[hooks]` rules in the config nearest each file accept.
+fn accepted_files(input: &str) -> Vec<String> {
+    let tool_name = extract_tool_name(input);
+    payload_files(input)
+        .into_iter()
+        .filter(|fp| {
+            std::path::Path::new(fp).parent().is_some_and(|dir| {
+                Config::load(&dir.to_string_lossy()).accepts_event(tool_name.as_deref(), fp)
+            })
+        })
+        .collect()
+}
 
-    let session_id = match extract_session_id(&input) {
+/// Replay mode: re-run each hook payload in a JSONL log (one payload per line, as accepted by
+/// `--input`) against the current code and print the response for each. `Stop` and
+/// `SubagentStop` events run as `--lint-collected`; other events use the mode from the flags,
+/// or the one their event name selects when no mode was given.
+fn run_replay(
+    log: &str,
+    mode: Mode,
+    policy: OutputPolicy,
+    debug: bool,
+    lenient: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let contents =
+        encoding::decode(&std::fs::read(log).map_err(|e| format!("cannot read {log}: {e}"))?);
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let event = extract_hook_event_name(line).unwrap_or_default();
+        let event_mode = match event.as_str() {
+            "Stop" | "SubagentStop" => Mode::LintCollected,
+            _ => mode,
+        };
+        // Print the event number, name, and file, omitting whatever the payload lacks
+        let header = [
+            format!("#{}", index + 1),
+            event,
+            extract_file_path(line).unwrap_or_default(),
+        ];
+        println!(
+            "{}",
+            header
+                .iter()
+                .filter(|s| !s.is_empty())
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        println!("{}", respond(line, event_mode, policy, debug, lenient));
+    }
+    Ok(())
+}
+
+/// Read the hook payload from `--input <file>` if given, otherwise from stdin.
+fn read_input(path: Option<&str>, input: &mut String) -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = if let Some(path) = path {
+        std::fs::read(path).map_err(|e| format!("cannot read --input {path}: {e}"))?
+    } else {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        bytes
+    };
+    // Payloads relayed through Windows tooling may carry a BOM or be UTF-16
+    *input = encoding::decode(&bytes);
+    Ok(())
+}
+
+/// Init mode: write a commented starter config to the current directory.
+fn run_init() -> Result<(), Box<dyn std::error::Error>> {
+    let path = env::current_dir()?.join(config::CONFIG_FILE);
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()).into());
+    }
+    std::fs::write(&path, config::STARTER_CONFIG)?;
+    println!("created {}", path.display());
+    Ok(())
+}
+
+/// Collect mode: record the file path from stdin into the session temp file, return immediately.
+fn run_collect(
+    input: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let session_id = match extract_session_id(input) {
         Some(sid) if !sid.is_empty() => sid,
         _ => {
-            return Ok(continue_result(
-                debug,
-                "[ralph-hook-lint] no session_id, skipping collect.",
-            ));
+            return Ok(SkipReason::NoSession.result(debug, "no session_id, skipping collect."));
         }
     };
 
-    let file_path = match extract_file_path(&input) {
-        Some(fp) if !fp.is_empty() => fp,
+    let file_paths = accepted_files(input);
+    if file_paths.is_empty() {
+        return Ok(SkipReason::NoFilePath.result(debug, "no file_path provided, skipping collect."));
+    }
+
+    let agent_id = extract::extract_agent_id(input);
+    let messages = file_paths
+        .iter()
+        .map(|file_path| collect_file(&session_id, agent_id.as_deref(), file_path, lenient))
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok(continue_result(debug, &messages.join("\n")))
+}
+
+/// Record one file for the session's `lint-collected` and start any early work on it.
+fn collect_file(
+    session_id: &str,
+    agent_id: Option<&str>,
+    file_path: &str,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    collect::record_path(session_id, file_path)?;
+    if let Some(agent_id) = agent_id {
+        collect::record_agent_path(session_id, agent_id, file_path)?;
+    }
+
+    // Lint the file now in the background so Stop only has to gather the result
+    let background = std::path::Path::new(&file_path)
+        .parent()
+        .is_some_and(|dir| {
+            Config::load(&dir.to_string_lossy()).get_bool("lint_collected.background") == Some(true)
+        })
+        && start_background_lint(session_id, file_path, lenient).is_ok();
+
+    // Compile dependencies ahead of the Stop hook's clippy run
+    let warming = !background
+        && project::detect_lang(file_path) == Some(Lang::Rust)
+        && find_project_root(file_path)
+            .is_some_and(|p| !p.standalone && lint::warm_rust_build(&p.root));
+
+    let note = if background {
+        "; started background lint"
+    } else if warming {
+        "; started cargo check to warm the build"
+    } else {
+        ""
+    };
+    Ok(format!(
+        "[ralph-hook-lint] collected {file_path} for deferred lint{note}."
+    ))
+}
+
+/// `SessionStart`: record the session's "before" state. A new session (`startup`, `clear`)
+/// starts from an empty store, dropping anything left under its id; a `resume` or `compact`
+/// keeps what the session recorded so far. Either way the git baseline (`HEAD` and the dirty
+/// files) is recorded if the session has none, so `--collect-from-git` and new-file detection
+/// don't depend on which event arrives first.
+fn run_session_start(input: &str, debug: bool) -> String {
+    let session_id = match extract_session_id(input) {
+        Some(sid) if !sid.is_empty() => sid,
+        _ => return SkipReason::NoSession.result(debug, "no session_id, skipping session start."),
+    };
+    let resumed = matches!(
+        extract::extract_session_source(input).as_deref(),
+        Some("resume" | "compact")
+    );
+    if !resumed {
+        collect::reset_session(&session_id);
+        let _ = std::fs::remove_file(origin::snapshot_path(&session_id));
+    }
+    let cwd = payload_cwd(input);
+    if let Err(e) = collect::record_baseline(&session_id, &cwd) {
+        return continue_result(debug, &format!("[ralph-hook-lint] lint hook error: {e}"));
+    }
+    let state = if collect::baseline_path(&session_id).exists() {
+        "recorded git baseline"
+    } else {
+        "no git repository, so no baseline"
+    };
+    continue_result(
+        debug,
+        &format!(
+            "[ralph-hook-lint] session {}: {state} for {}.",
+            if resumed { "resumed" } else { "started" },
+            cwd.display()
+        ),
+    )
+}
+
+/// `Bash` events carry a command rather than a file path. With `[bash] collect` set in the
+/// config for the command's `cwd`, the files the command edited are collected for the Stop
+/// hook's `lint-collected`, whatever the mode: `"command"` reads them from the command line,
+/// `"git"` diffs `git status` against a snapshot taken at `PreToolUse`.
+fn run_bash(input: &str, debug: bool, lenient: bool) -> Result<String, Box<dyn std::error::Error>> {
+    let cwd = payload_cwd(input);
+    let config = Config::load(&cwd.to_string_lossy());
+    let Some(strategy) = config
+        .get_str("bash.collect")
+        .filter(|s| matches!(*s, "command" | "git"))
+    else {
+        return Ok(SkipReason::NoFilePath.result(
+            debug,
+            "no file_path in Bash event, and [bash] collect is not set.",
+        ));
+    };
+    let session_id = match extract_session_id(input) {
+        Some(sid) if !sid.is_empty() => sid,
         _ => {
-            return Ok(continue_result(
-                debug,
-                "[ralph-hook-lint] no file_path provided, skipping collect.",
-            ));
+            return Ok(SkipReason::NoSession.result(debug, "no session_id, skipping Bash collect."));
         }
     };
 
-    collect::record_path(&session_id, &file_path)?;
+    let git = strategy == "git";
+    if extract_hook_event_name(input).as_deref() == Some("PreToolUse") {
+        if git {
+            bash::record_snapshot(&session_id, &cwd)?;
+        }
+        return Ok(continue_result(
+            debug,
+            "[ralph-hook-lint] recorded git status before Bash.",
+        ));
+    }
+    let command = json::parse(input)
+        .and_then(|j| {
+            j.get("tool_input")
+                .and_then(|t| t.get("command"))
+                .and_then(json::Json::as_str)
+                .map(str::to_string)
+        })
+        .unwrap_or_default();
+    let changed = git
+        .then(|| bash::changed_since_snapshot(&session_id))
+        .flatten()
+        .unwrap_or_else(|| bash::modified_files(&command, &cwd));
 
-    Ok(continue_result(
-        debug,
-        &format!("[ralph-hook-lint] collected {file_path} for deferred lint."),
-    ))
+    let file_paths: Vec<String> = changed
+        .iter()
+        .filter(|file| file.is_file())
+        .map(|file| file.to_string_lossy().to_string())
+        .filter(|fp| {
+            project::detect_lang(fp).is_some()
+                && std::path::Path::new(fp).parent().is_some_and(|dir| {
+                    Config::load(&dir.to_string_lossy()).accepts_event(Some("Bash"), fp)
+                })
+        })
+        .collect();
+    if file_paths.is_empty() {
+        return Ok(SkipReason::NoFilePath.result(debug, "no lintable files edited by Bash."));
+    }
+    let agent_id = extract::extract_agent_id(input);
+    let messages = file_paths
+        .iter()
+        .map(|file_path| collect_file(&session_id, agent_id.as_deref(), file_path, lenient))
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok(continue_result(debug, &messages.join("\n")))
 }
 
-/// Lint-collected mode: read all collected paths, lint each, aggregate errors.
-fn run_lint_collected(debug: bool, lenient: bool) -> Result<String, Box<dyn std::error::Error>> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
+/// Start `lint-background` for `file_path` as a detached process that outlives this hook.
+fn start_background_lint(session_id: &str, file_path: &str, lenient: bool) -> io::Result<()> {
+    let mut command = std::process::Command::new(env::current_exe()?);
+    command.args(["lint-background", session_id, file_path]);
+    if lenient {
+        command.arg("--lenient");
+    }
+    procgroup::GroupOutput::spawn_detached(&mut command)
+}
+
+/// Background lint: lint one collected file and store the response, keyed by the contents
+/// that were linted, for the session's `lint-collected` to pick up.
+fn run_background_lint(
+    session_id: &str,
+    file_path: &str,
+    lenient: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    origin::set_session(session_id);
+    let Some(hash) = collect::content_hash(file_path) else {
+        return Ok(());
+    };
+    let Some(project) = find_project_root(file_path) else {
+        return Ok(());
+    };
+    let lenient = file_lenient(file_path, &project.root, lenient);
+    let output = lint_file(file_path, &project, false, lenient)?;
+    collect::record_result(session_id, file_path, hash, &output)
+}
+
+/// With `--collect-from-git`, files git reports as changed since the session's baseline that
+/// weren't collected, such as those written by scripts.
+fn git_changed_files(session_id: &str, collected: &[String]) -> Vec<String> {
+    if !collect::from_git() {
+        return Vec::new();
+    }
+    collect::changed_since_baseline(session_id)
+        .into_iter()
+        .filter(|fp| {
+            !collected.contains(fp)
+                && project::detect_lang(fp).is_some()
+                && std::path::Path::new(fp)
+                    .parent()
+                    .is_some_and(|dir| Config::load(&dir.to_string_lossy()).accepts_event(None, fp))
+        })
+        .collect()
+}
+
+/// The subagent a `SubagentStop` event is for, from its `agent_id`. Its `lint-collected` covers
+/// only the files that subagent's tool calls collected.
+fn subagent_scope(input: &str) -> Option<String> {
+    (extract_hook_event_name(input).as_deref() == Some("SubagentStop"))
+        .then(|| extract::extract_agent_id(input))
+        .flatten()
+        .filter(|id| !id.is_empty())
+}
 
-    let session_id = match extract_session_id(&input) {
+/// Why `lint-collected` has nothing to lint.
+const fn nothing_to_lint(none_collected: bool, none_changed: bool) -> (SkipReason, &'static str) {
+    if none_collected {
+        (SkipReason::NoFilePath, "no files collected")
+    } else if none_changed {
+        (
+            SkipReason::Debounced,
+            "collected files passed lint and are unchanged",
+        )
+    } else {
+        (
+            SkipReason::Excluded,
+            "collected files are gone, ignored, too large, or temp-directory scratch",
+        )
+    }
+}
+
+/// Lint-collected mode: read all collected paths, lint each, aggregate errors.
+fn run_lint_collected(
+    input: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let session_id = match extract_session_id(input) {
         Some(sid) if !sid.is_empty() => sid,
         _ => {
-            return Ok(continue_result(
-                debug,
-                "[ralph-hook-lint] no session_id, skipping lint-collected.",
-            ));
+            return Ok(
+                SkipReason::NoSession.result(debug, "no session_id, skipping lint-collected.")
+            );
         }
     };
 
-    let paths = collect::read_and_cleanup(&session_id)?;
+    let agent_id = subagent_scope(input);
+    let collected = if let Some(agent_id) = &agent_id {
+        collect::read_and_cleanup_agent(&session_id, agent_id)?
+    } else {
+        let mut collected = collect::read_and_cleanup(&session_id)?;
+        collected.extend(git_changed_files(&session_id, &collected));
+        collected
+    };
+    // Other subagents' background results are still pending
+    let clear_results = || {
+        if agent_id.is_none() {
+            collect::clear_results(&session_id);
+        }
+    };
+
+    // Skip files that already passed an immediate (PostToolUse) lint and are unchanged since,
+    // and files that are gone, ignored by git, too large, or uncommitted temp-directory scratch.
+    let changed: Vec<&String> = collected
+        .iter()
+        .filter(|fp| !collect::is_unchanged_clean(&session_id, fp))
+        .collect();
+    let paths: Vec<String> = changed
+        .iter()
+        .filter(|fp| {
+            let lint_temp_files = std::path::Path::new(fp).parent().is_some_and(|dir| {
+                Config::load(&dir.to_string_lossy()).get_bool("lint_collected.lint_temp_files")
+                    == Some(true)
+            });
+            collect::drop_reason(fp, lint_temp_files).is_none() && oversized(fp).is_none()
+        })
+        .map(|fp| (*fp).clone())
+        .collect();
 
     if paths.is_empty() {
-        return Ok(continue_result(
-            debug,
-            "[ralph-hook-lint] no files collected, skipping lint.",
+        clear_results();
+        let (reason, detail) = nothing_to_lint(collected.is_empty(), changed.is_empty());
+        return Ok(reason.result(debug, &format!("{detail}, skipping lint.")));
+    }
+
+    // Files a background lint already checked in their current state need no job
+    let background: Vec<(String, String)> = paths
+        .iter()
+        .filter_map(|fp| Some((fp.clone(), collect::take_result(&session_id, fp)?)))
+        .collect();
+    clear_results();
+    let pending: Vec<String> = paths
+        .iter()
+        .filter(|fp| !background.iter().any(|(done, _)| done == *fp))
+        .cloned()
+        .collect();
+
+    let (jobs, downgraded) = lint_jobs(&pending, debug, lenient);
+
+    let budget = lint_collected_budget();
+    let parallelism = env::current_dir().map_or(1, |dir| {
+        schedule::max_parallel(&Config::load(&dir.to_string_lossy()))
+    });
+    let outcome = schedule::run_with_budget(jobs, budget, parallelism);
+
+    let frozen: Vec<String> = downgraded.keys().cloned().collect();
+    let downgrade_notes: Vec<String> = downgraded.values().map(ToString::to_string).collect();
+    schedule::record_durations(&outcome.completed, &frozen);
+    schedule::record_accounting(&outcome.completed, parallelism);
+
+    let results = settle_verdicts(
+        &session_id,
+        &paths,
+        background,
+        outcome.completed,
+        &outcome.skipped,
+        debug,
+    );
+    let (errors, mut notes) = errors_by_repo(&paths, results);
+    notes.extend(downgrade_notes);
+
+    if !outcome.skipped.is_empty() {
+        notes.push(format!(
+            "{BUDGET_NOTE_PREFIX} of {}s reached; not linted: {}",
+            budget.as_secs(),
+            outcome.skipped.join(", ")
         ));
     }
 
+    let unchanged = collected.len() - changed.len();
+    Ok(collected_response(
+        errors, notes, &paths, unchanged, debug, lenient,
+    ))
+}
+
+/// The `--lint-collected` response: lint errors block, followed by any notes; notes alone, or
+/// the summary of a clean run, are reported even without `--debug`.
+fn collected_response(
+    mut errors: Vec<String>,
+    mut notes: Vec<String>,
+    paths: &[String],
+    unchanged: usize,
+    debug: bool,
+    lenient: bool,
+) -> String {
+    if errors.is_empty() && notes.is_empty() {
+        notes.extend(clean_summary(paths, unchanged, lenient));
+    }
+    if !errors.is_empty() {
+        errors.append(&mut notes);
+        let combined = errors.join("\n\n---\n\n");
+        format!(
+            r#"{{"decision":"block","reason":"{}"}}"#,
+            escape_json(&combined)
+        )
+    } else if !notes.is_empty() {
+        // Always reported: the agent should know some files went unchecked
+        format!(
+            r#"{{"continue":true,"systemMessage":"{}"}}"#,
+            escape_json(&notes.join("\n"))
+        )
+    } else {
+        continue_result(
+            debug,
+            &format!(
+                "[ralph-hook-lint] all {} collected file(s) passed lint.",
+                paths.len()
+            ),
+        )
+    }
+}
+
+/// Lint failures grouped per repository, since one session can touch several unrelated
+/// repos, and notes about quarantined linters.
+fn errors_by_repo(
+    paths: &[String],
+    mut results: Vec<(String, Result<String, JobError>)>,
+) -> (Vec<String>, Vec<String>) {
+    // Jobs finish in any order; report them by label so reruns give the same reason
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    let repo_of = |path: &str| find_repo_root(path).unwrap_or_default();
+    let repos: BTreeSet<String> = paths.iter().map(|p| repo_of(p)).collect();
+    let mut repo_errors: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut notes: Vec<String> = Vec::new();
+    for (label, result) in results {
+        if let Err(e) = &result {
+            if e.quarantined {
+                notes.push(e.message.clone());
+                continue;
+            }
+        }
+        let errors = repo_errors.entry(repo_of(&label)).or_default();
+        collect_lint_errors(result.map_err(|e| e.message.into()), &label, errors);
+    }
     let mut errors: Vec<String> = Vec::new();
-    // Group Rust files by project root so clippy runs once and filters for all files.
-    let mut rust_projects: HashMap<String, Vec<String>> = HashMap::new();
+    for (repo, mut found) in repo_errors {
+        if found.is_empty() {
+            continue;
+        }
+        if repos.len() > 1 && !repo.is_empty() {
+            found[0] = format!("[ralph-hook-lint] repository {repo}:\n\n{}", found[0]);
+        }
+        errors.append(&mut found);
+    }
+    (errors, notes)
+}
+
+/// Labeled lint results from background lints and completed jobs, rewritten by the verdict
+/// history. Collected files that neither blocked, errored, nor were skipped are recorded clean.
+fn settle_verdicts(
+    session_id: &str,
+    paths: &[String],
+    background: Vec<(String, String)>,
+    completed: Vec<schedule::Completed>,
+    skipped: &[String],
+    debug: bool,
+) -> Vec<(String, Result<String, JobError>)> {
+    let mut unsettled: Vec<String> = skipped.to_vec();
+    let mut results: Vec<(String, Result<String, JobError>)> = background
+        .into_iter()
+        .map(|(file_path, output)| (file_path, Ok(output)))
+        .chain(completed.into_iter().map(|job| (job.label, job.result)))
+        .collect();
+    for (label, result) in &mut results {
+        match result {
+            Ok(output) => {
+                let (rewritten, mut blocked) =
+                    verdict::apply(session_id, std::mem::take(output), debug);
+                *output = rewritten;
+                unsettled.append(&mut blocked);
+            }
+            Err(_) => unsettled.push(label.clone()),
+        }
+    }
+    for file_path in paths {
+        if !is_unsettled(file_path, &unsettled) {
+            let _ = collect::record_verdict(session_id, file_path, &[]);
+        }
+    }
+    results
+}
+
+/// Whether `file_path` is one of the `unsettled` files, or in one of their project
+/// directories. Paths are compared by component, so `/ws/app` doesn't cover `/ws/app2`.
+fn is_unsettled(file_path: &str, unsettled: &[String]) -> bool {
+    unsettled
+        .iter()
+        .any(|label| std::path::Path::new(file_path).starts_with(label))
+}
+
+/// A Rust crate root and the collected files in it.
+type CrateFiles = (String, Vec<String>);
+
+/// Build one lint job per collected file, grouping Rust files per project for a single clippy
+/// run and linting each Java project once. Also returns the projects that run the fast tier.
+fn lint_jobs(
+    paths: &[String],
+    debug: bool,
+    lenient: bool,
+) -> (Vec<Job>, BTreeMap<String, tier::Downgrade>) {
+    let durations = schedule::load_durations();
+    let mut jobs: Vec<Job> = Vec::new();
+    let mut tiers: BTreeMap<String, Option<tier::Downgrade>> = BTreeMap::new();
+    // Group Rust files by project root (and strictness) so clippy runs once and filters for all files.
+    let mut rust_projects: HashMap<(String, bool), Vec<String>> = HashMap::new();
+    // Group JavaScript and Python files the same way, so eslint/ruff check them in one run.
+    let mut script_projects: BTreeMap<(String, bool, bool), Vec<String>> = BTreeMap::new();
     // Track Java projects already linted to avoid redundant maven/gradle runs.
     let mut java_projects: HashSet<String> = HashSet::new();
 
-    for file_path in &paths {
+    for file_path in paths {
         let Some(project) = find_project_root(file_path) else {
             continue;
         };
 
-        match project.lang {
+        let lenient = file_lenient(file_path, &project.root, lenient);
+        let (file, root, lang) = (file_path.clone(), project.root, project.lang);
+        if !project.standalone {
+            tiers
+                .entry(root.clone())
+                .or_insert_with(|| tier::downgrade(&root, lang));
+        }
+
+        if project.standalone {
+            let cost = expected_cost(&durations, &file, lang);
+            jobs.push(Job::new(file_path.clone(), file.clone(), cost, move || {
+                run_standalone_lint(&file, lang, &root, debug, lenient).map_err(JobError::from)
+            }));
+            continue;
+        }
+
+        match lang {
             Lang::Rust => {
-                rust_projects
-                    .entry(project.root)
+                rust_projects.entry((root, lenient)).or_default().push(file);
+            }
+            Lang::JavaScript | Lang::Python => {
+                let is_js = lang == Lang::JavaScript;
+                script_projects
+                    .entry((root, lenient, is_js))
                     .or_default()
-                    .push(file_path.clone());
+                    .push(file);
             }
             Lang::Java => {
-                if !java_projects.insert(project.root.clone()) {
+                if !java_projects.insert(root.clone()) {
                     continue;
                 }
-                collect_lint_errors(
-                    run_java_lint(file_path, &project.root, debug, lenient),
-                    file_path,
-                    &mut errors,
-                );
+                let cost = expected_cost(&durations, &root, lang);
+                jobs.push(Job::new(file_path.clone(), root.clone(), cost, move || {
+                    run_java_lint(&file, &root, debug, lenient).map_err(JobError::from)
+                }));
             }
             _ => {
-                let result = match project.lang {
-                    Lang::JavaScript => run_js_lint(file_path, &project.root, debug, lenient),
-                    Lang::Python => run_python_lint(file_path, &project.root, debug, lenient),
-                    Lang::Go => run_go_lint(file_path, &project.root, debug, lenient),
-                    _ => unreachable!(),
-                };
-                collect_lint_errors(result, file_path, &mut errors);
+                let cost = expected_cost(&durations, &root, lang);
+                jobs.push(Job::new(file_path.clone(), root.clone(), cost, move || {
+                    let result = match lang {
+                        Lang::Go => run_go_lint(&file, &root, debug, lenient),
+                        Lang::C => run_c_lint(&file, &root, debug, lenient),
+                        Lang::Kotlin => run_kotlin_lint(&file, &root, debug, lenient),
+                        Lang::Php => run_php_lint(&file, &root, debug, lenient),
+                        Lang::CSharp => run_csharp_lint(&file, &root, debug, lenient),
+                        Lang::Swift => run_swift_lint(&file, &root, debug, lenient),
+                        Lang::Shell => run_shell_lint(&file, &root, debug, lenient),
+                        Lang::PowerShell => run_powershell_lint(&file, &root, debug, lenient),
+                        Lang::Markdown => run_markdown_lint(&file, &root, debug, lenient)
+                            .and_then(|output| lint_code_blocks(&file, &root, output, debug)),
+                        Lang::Elixir => run_elixir_lint(&file, &root, debug, lenient),
+                        Lang::Haskell => run_haskell_lint(&file, &root, debug, lenient),
+                        Lang::Zig => run_zig_lint(&file, &root, debug, lenient),
+                        Lang::Lua => run_lua_lint(&file, &root, debug, lenient),
+                        Lang::Nix => run_nix_lint(&file, &root, debug, lenient),
+                        Lang::Solidity => run_solidity_lint(&file, &root, debug, lenient),
+                        Lang::Terraform => run_terraform_lint(&file, &root, debug, lenient),
+                        Lang::Scala => run_scala_lint(&file, &root, debug, lenient),
+                        _ => unreachable!(),
+                    };
+                    result.map_err(JobError::from)
+                }));
             }
         }
     }
 
-    // Run clippy once per Rust project, filtering output for all collected files.
-    for (root, files) in &rust_projects {
-        collect_lint_errors(
-            run_rust_lint_multi(files, root, debug, lenient),
-            &root.clone(),
-            &mut errors,
-        );
+    for ((root, lenient, is_js), files) in script_projects {
+        let lang = if is_js {
+            Lang::JavaScript
+        } else {
+            Lang::Python
+        };
+        let cost = expected_cost(&durations, &root, lang);
+        let label = if files.len() == 1 {
+            files[0].clone()
+        } else {
+            root.clone()
+        };
+        jobs.push(Job::new(label, root.clone(), cost, move || {
+            let result = if is_js {
+                run_js_lint_multi(&files, &root, debug, lenient)
+            } else {
+                run_python_lint_multi(&files, &root, debug, lenient)
+            };
+            result.map_err(JobError::from)
+        }));
     }
 
-    if errors.is_empty() {
-        Ok(continue_result(
-            debug,
-            &format!(
-                "[ralph-hook-lint] all {} collected file(s) passed lint.",
-                paths.len()
-            ),
-        ))
-    } else {
-        let combined = errors.join("\n\n---\n\n");
-        Ok(format!(
-            r#"{{"decision":"block","reason":"{}"}}"#,
-            escape_json(&combined)
-        ))
+    jobs.extend(rust_jobs(rust_projects, &durations, debug));
+    let downgraded = tiers
+        .into_iter()
+        .filter_map(|(root, downgrade)| Some((root, downgrade?)))
+        .collect();
+    (jobs, downgraded)
+}
+
+/// Clippy jobs for the collected Rust files, grouped by crate root and strictness. Crates in
+/// the same Cargo workspace share one clippy run from the workspace root, unless a manifest
+/// edit needs the per-crate `cargo check`.
+fn rust_jobs(
+    rust_projects: HashMap<(String, bool), Vec<String>>,
+    durations: &HashMap<String, Duration>,
+    debug: bool,
+) -> Vec<Job> {
+    let mut jobs: Vec<Job> = Vec::new();
+    let mut workspaces: BTreeMap<(String, bool), Vec<CrateFiles>> = BTreeMap::new();
+    for ((root, lenient), files) in rust_projects {
+        let workspace = find_cargo_workspace_root(&root)
+            .filter(|_| !files.iter().any(|f| lint::is_cargo_manifest(f)))
+            .unwrap_or_else(|| root.clone());
+        workspaces
+            .entry((workspace, lenient))
+            .or_default()
+            .push((root, files));
+    }
+    for ((workspace, lenient), crates) in workspaces {
+        if crates.len() > 1 {
+            let (roots, files): (Vec<String>, Vec<Vec<String>>) = crates.into_iter().unzip();
+            let files: Vec<String> = files.into_iter().flatten().collect();
+            let cost = expected_cost(durations, &workspace, Lang::Rust);
+            jobs.push(Job::new(
+                workspace.clone(),
+                workspace.clone(),
+                cost,
+                move || {
+                    run_rust_workspace_lint(&files, &roots, &workspace, debug, lenient)
+                        .map_err(JobError::from)
+                },
+            ));
+            continue;
+        }
+        // Run clippy once per Rust project, filtering output for all collected files.
+        for (root, files) in crates {
+            let cost = expected_cost(durations, &root, Lang::Rust);
+            jobs.push(Job::new(root.clone(), root.clone(), cost, move || {
+                run_rust_lint_multi(&files, &root, debug, lenient).map_err(JobError::from)
+            }));
+        }
+    }
+
+    jobs
+}
+
+/// Wall-clock budget for `--lint-collected`, from `lint_collected.budget_secs` in the config for
+/// the agent's working directory.
+fn lint_collected_budget() -> Duration {
+    env::current_dir()
+        .ok()
+        .and_then(|dir| Config::load(&dir.to_string_lossy()).get_int("lint_collected.budget_secs"))
+        .and_then(|secs| u64::try_from(secs).ok())
+        .map_or(schedule::DEFAULT_BUDGET, Duration::from_secs)
+}
+
+/// Whether a clean `--lint-collected` run reports what it verified, from
+/// `lint_collected.summary` in the config for the agent's working directory.
+fn lint_collected_summary() -> bool {
+    env::current_dir().is_ok_and(|dir| {
+        Config::load(&dir.to_string_lossy()).get_bool("lint_collected.summary") == Some(true)
+    })
+}
+
+/// A record of a clean `--lint-collected` run when `lint_collected.summary` asks for one, such as `[ralph-hook-lint] verified: linted 14
+/// file(s) across 3 project(s): clean; 2 unchanged since they passed; lenient profile.`
+fn clean_summary(paths: &[String], unchanged: usize, lenient: bool) -> Option<String> {
+    if !lint_collected_summary() {
+        return None;
     }
+    let projects: BTreeSet<String> = paths
+        .iter()
+        .map(|p| find_project_root(p).map_or_else(|| p.clone(), |project| project.root))
+        .collect();
+    let mut summary = format!(
+        "{SUMMARY_NOTE_PREFIX} linted {} file(s) across {} project(s): clean",
+        paths.len(),
+        projects.len()
+    );
+    if unchanged > 0 {
+        let _ = write!(summary, "; {unchanged} unchanged since they passed");
+    }
+    if lenient {
+        summary.push_str("; lenient profile, so rules it ignores were not checked");
+    }
+    summary.push('.');
+    Some(summary)
+}
+
+/// Expected lint duration: the last recorded run for `key`, or a rough per-language guess.
+fn expected_cost(durations: &HashMap<String, Duration>, key: &str, lang: Lang) -> Duration {
+    durations.get(key).copied().unwrap_or_else(|| {
+        Duration::from_secs(match lang {
+            Lang::Java | Lang::Scala => 60,
+            Lang::Rust | Lang::CSharp => 30,
+            Lang::Go | Lang::Elixir | Lang::Solidity => 10,
+            Lang::JavaScript
+            | Lang::C
+            | Lang::Kotlin
+            | Lang::Php
+            | Lang::Swift
+            | Lang::Haskell
+            | Lang::PowerShell
+            | Lang::Terraform => 5,
+            Lang::Python | Lang::Shell | Lang::Markdown | Lang::Zig | Lang::Lua | Lang::Nix => 1,
+        })
+    })
 }
 
 /// Push the reason from a block result into the errors vec, or ignore continues.
@@ -194,39 +1113,303 @@ fn extract_reason(json: &str) -> Option<String> {
     extract::extract_reason_field(json)
 }
 
-fn run(debug: bool, lenient: bool) -> Result<String, Box<dyn std::error::Error>> {
-    // Read input from stdin
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
+/// Apply a `[paths]` rule's `lenient`/`strict` setting, falling back to the `--lenient` flag.
+fn file_lenient(file_path: &str, project_root: &str, lenient: bool) -> bool {
+    Config::load(project_root)
+        .path_profile(file_path)
+        .lenient
+        .unwrap_or(lenient)
+}
+
+/// Run the pipeline for `project.lang` on one file, noting any fast-tier downgrade.
+fn lint_file(
+    file_path: &str,
+    project: &project::ProjectInfo,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let output = run_pipeline(file_path, project, debug, lenient)?;
+    Ok(annotate_tier(project, output))
+}
+
+/// Run the pipeline for `project.lang` on one file.
+fn run_pipeline(
+    file_path: &str,
+    project: &project::ProjectInfo,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    match project.lang {
+        _ if project.standalone => {
+            run_standalone_lint(file_path, project.lang, &project.root, debug, lenient)
+        }
+        Lang::JavaScript => run_js_lint(file_path, &project.root, debug, lenient),
+        Lang::Rust => run_rust_lint(file_path, &project.root, debug, lenient),
+        Lang::Python => run_python_lint(file_path, &project.root, debug, lenient),
+        Lang::Java => run_java_lint(file_path, &project.root, debug, lenient),
+        Lang::Go => run_go_lint(file_path, &project.root, debug, lenient),
+        Lang::C => run_c_lint(file_path, &project.root, debug, lenient),
+        Lang::Kotlin => run_kotlin_lint(file_path, &project.root, debug, lenient),
+        Lang::Php => run_php_lint(file_path, &project.root, debug, lenient),
+        Lang::CSharp => run_csharp_lint(file_path, &project.root, debug, lenient),
+        Lang::Swift => run_swift_lint(file_path, &project.root, debug, lenient),
+        Lang::Shell => run_shell_lint(file_path, &project.root, debug, lenient),
+        Lang::PowerShell => run_powershell_lint(file_path, &project.root, debug, lenient),
+        Lang::Markdown => run_markdown_lint(file_path, &project.root, debug, lenient)
+            .and_then(|output| lint_code_blocks(file_path, &project.root, output, debug)),
+        Lang::Elixir => run_elixir_lint(file_path, &project.root, debug, lenient),
+        Lang::Haskell => run_haskell_lint(file_path, &project.root, debug, lenient),
+        Lang::Zig => run_zig_lint(file_path, &project.root, debug, lenient),
+        Lang::Lua => run_lua_lint(file_path, &project.root, debug, lenient),
+        Lang::Nix => run_nix_lint(file_path, &project.root, debug, lenient),
+        Lang::Solidity => run_solidity_lint(file_path, &project.root, debug, lenient),
+        Lang::Terraform => run_terraform_lint(file_path, &project.root, debug, lenient),
+        Lang::Scala => run_scala_lint(file_path, &project.root, debug, lenient),
+    }
+}
+
+/// Note in `output` that slow linters were skipped, if `project` is on the fast tier.
+fn annotate_tier(project: &project::ProjectInfo, output: String) -> String {
+    match tier::downgrade(&project.root, project.lang).filter(|_| !project.standalone) {
+        Some(downgrade) => tier::annotate(&output, &downgrade),
+        None => output,
+    }
+}
 
-    // Extract file_path from tool_input.file_path using simple string search
-    let file_path = extract_file_path(&input);
+/// With `[markdown] code_blocks = true`, lint the fenced code blocks of a Markdown file in
+/// Rust, Python, JavaScript/TypeScript, and shell, each staged next to it, and add their
+/// findings to markdownlint's `output`. Blocks are examples, so they are linted leniently, and
+/// Rust blocks are compiled on their own like doctests rather than as part of the crate.
+fn lint_code_blocks(
+    file_path: &str,
+    project_root: &str,
+    output: String,
+    debug: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let config = Config::load(project_root);
+    if !config.get_bool("markdown.code_blocks").unwrap_or(false) {
+        return Ok(output);
+    }
+    let Ok(markdown) = std::fs::read_to_string(file_path) else {
+        return Ok(output);
+    };
+    let mut errors = Vec::new();
+    collect_lint_errors(Ok(output.clone()), file_path, &mut errors);
+    for block in codeblock::extract(&markdown) {
+        let staged = content::StagedFile::write_at(block.staged_path(file_path), &block.code)?;
+        let staged_path = staged.path.to_string_lossy().to_string();
+        let project = if block.lang == Lang::Rust {
+            Some(project::ProjectInfo {
+                root: project_root.to_string(),
+                lang: block.lang,
+                standalone: true,
+            })
+        } else {
+            find_project_root(&staged_path).filter(|p| p.lang == block.lang)
+        };
+        let Some(project) = project else {
+            continue;
+        };
+        let label = block.label(file_path);
+        let result = lint_file(&staged_path, &project, debug, true)
+            .map(|output| staged.unstage(&output, &label));
+        collect_lint_errors(result, &label, &mut errors);
+    }
+    if errors.is_empty() {
+        return Ok(output);
+    }
+    Ok(format!(
+        r#"{{"decision":"block","reason":"{}"}}"#,
+        escape_json(&errors.join("\n\n---\n\n"))
+    ))
+}
 
-    let file_path = match file_path {
-        Some(fp) if !fp.is_empty() => fp,
+/// Content mode (`PreToolUse`): lint what a `Write` or `Edit` would leave in the file before it
+/// is written. The proposed contents are staged next to the file so the project's linter config
+/// applies. Rust, Java, Go, and C# are skipped: their linters check the whole crate, module,
+/// package, or project, where a staged sibling would clash with the real file.
+fn run_content(
+    input: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let file_path = match extract_file_path(input) {
+        Some(fp) if !fp.is_empty() => wsl::translate(&fp),
         _ => {
-            return Ok(continue_result(
-                debug,
-                "[ralph-hook-lint] no file_path provided, skipping lint hook.",
-            ));
+            return Ok(SkipReason::NoFilePath
+                .result(debug, "no file_path provided, skipping content lint."));
         }
     };
+    let skip = |reason: SkipReason, detail: &str| {
+        Ok(reason.result(
+            debug,
+            &format!("skipping content lint for {file_path}: {detail}."),
+        ))
+    };
+
+    let Some(lang) = project::detect_lang(&file_path) else {
+        return skip(SkipReason::UnsupportedType, "unsupported file type");
+    };
+    if matches!(
+        lang,
+        Lang::Rust
+            | Lang::Java
+            | Lang::Go
+            | Lang::CSharp
+            | Lang::Elixir
+            | Lang::Scala
+            | Lang::Terraform
+    ) {
+        return skip(
+            SkipReason::UnsupportedType,
+            "its linter checks the whole project, not single contents",
+        );
+    }
+    let current = std::fs::read_to_string(&file_path).ok();
+    let Some(contents) = content::proposed_content(input, current.as_deref()) else {
+        return skip(
+            SkipReason::NoFilePath,
+            "no Write or Edit contents in the payload",
+        );
+    };
+    // Look up the project from the staged copy: the real file may not exist yet
+    let staged = content::StagedFile::write(&file_path, &contents)?;
+    let staged_path = staged.path.to_string_lossy().to_string();
+    let Some(project) = find_project_root(&staged_path).filter(|p| p.lang == lang) else {
+        return skip(SkipReason::NoProjectRoot, "no project found");
+    };
+
+    let lenient = file_lenient(&file_path, &project.root, lenient);
+    let output = lint_file(&staged_path, &project, debug, lenient)?;
+    Ok(staged.unstage(&output, &file_path))
+}
+
+fn run(input: &str, debug: bool, lenient: bool) -> Result<String, Box<dyn std::error::Error>> {
+    // Payloads from the other side of a WSL boundary carry paths this process can't open as-is
+    let file_paths = accepted_files(input);
+    match file_paths.as_slice() {
+        [] => {
+            Ok(SkipReason::NoFilePath.result(debug, "no file_path provided, skipping lint hook."))
+        }
+        [file_path] => run_file(input, file_path, debug, lenient),
+        _ => run_files(input, &file_paths, debug, lenient),
+    }
+}
+
+/// Lint every file of a batch-writing tool's payload and report them in one response.
+fn run_files(
+    input: &str,
+    file_paths: &[String],
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut errors = Vec::new();
+    let mut notes = Vec::new();
+    for file_path in file_paths {
+        match run_file(input, file_path, debug, lenient) {
+            Err(e) if Quarantined::find(e.as_ref()).is_some() => {
+                notes.push(e.to_string());
+            }
+            result => collect_lint_errors(result, file_path, &mut errors),
+        }
+    }
+    if !errors.is_empty() {
+        errors.append(&mut notes);
+        return Ok(format!(
+            r#"{{"decision":"block","reason":"{}"}}"#,
+            escape_json(&errors.join("\n\n---\n\n"))
+        ));
+    }
+    if !notes.is_empty() {
+        return Err(notes.join("\n").into());
+    }
+    Ok(continue_result(
+        debug,
+        &format!(
+            "[ralph-hook-lint] all {} file(s) in the payload passed lint.",
+            file_paths.len()
+        ),
+    ))
+}
+
+/// Lint one file of the payload and remember the verdict for the session.
+fn run_file(
+    input: &str,
+    file_path: &str,
+    debug: bool,
+    lenient: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let file_path = file_path.to_string();
 
     // Find the nearest project root (also validates file type)
     let Some(project) = find_project_root(&file_path) else {
-        return Ok(continue_result(
+        let (reason, detail) = if project::detect_lang(&file_path).is_some() {
+            (SkipReason::NoProjectRoot, "no project found")
+        } else {
+            (SkipReason::UnsupportedType, "unsupported file type")
+        };
+        return Ok(reason.result(debug, &format!("skipping lint for {file_path}: {detail}.")));
+    };
+
+    if let Some(size) = oversized(&file_path) {
+        return Ok(SkipReason::TooLarge.result(
             debug,
-            &format!(
-                "[ralph-hook-lint] skipping lint: unsupported file type or no project found for {file_path}."
-            ),
+            &format!("skipping lint for {file_path}: {size} KiB is over [hooks] max_file_kb."),
         ));
+    }
+
+    let lenient = file_lenient(&file_path, &project.root, lenient);
+    let session_id = extract_session_id(input).filter(|sid| !sid.is_empty());
+    let rate_limit = session_id
+        .as_ref()
+        .and_then(|_| ratelimit::for_project(&project));
+    if let (Some(session_id), Some(limit)) = (&session_id, &rate_limit) {
+        if let Some(output) = limit.check(session_id, &project.root, &file_path, debug) {
+            return Ok(output);
+        }
+    }
+    // Lint verbosely to learn which linter passed, then quiet the response unless debugging.
+    let output = run_pipeline(&file_path, &project, true, lenient)?;
+    let passed = response::passing_linter(&output);
+    let output = if debug || output.contains(r#""decision":"block"#) {
+        output
+    } else {
+        continue_result(false, "")
     };
+    let output = annotate_tier(&project, output);
 
-    match project.lang {
-        Lang::JavaScript => run_js_lint(&file_path, &project.root, debug, lenient),
-        Lang::Rust => run_rust_lint(&file_path, &project.root, debug, lenient),
-        Lang::Python => run_python_lint(&file_path, &project.root, debug, lenient),
-        Lang::Java => run_java_lint(&file_path, &project.root, debug, lenient),
-        Lang::Go => run_go_lint(&file_path, &project.root, debug, lenient),
+    let Some(session_id) = session_id else {
+        return Ok(output);
+    };
+    if rate_limit.is_some() {
+        let _ = ratelimit::record(&session_id, &project.root, &file_path, &output);
+    }
+    let (output, blocked) = verdict::apply(&session_id, output, debug);
+    if blocked.is_empty() && !output.contains(r#""decision":"block"#) {
+        let _ = collect::record_verdict(&session_id, &file_path, &[]);
+        // Remember files a linter passed so a later --lint-collected run can skip them if
+        // unchanged, unless the pass also depended on the rest of the project.
+        if passed.is_some_and(|linter| {
+            !lint::checks_whole_project(project.lang, &linter, &file_path, &project.root)
+        }) {
+            let _ = collect::record_clean(&session_id, &file_path);
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsettled_labels_cover_files_by_path_component() {
+        let unsettled = ["/ws/app".to_string(), "/ws/lib/a.rs".to_string()];
+        assert!(is_unsettled("/ws/app/src/x.rs", &unsettled));
+        assert!(is_unsettled("/ws/lib/a.rs", &unsettled));
+        assert!(!is_unsettled("/ws/app2/src/x.rs", &unsettled));
+        assert!(!is_unsettled("/ws/apple.rs", &unsettled));
+        assert!(!is_unsettled("/ws/lib/a.rs.bak", &unsettled));
     }
 }
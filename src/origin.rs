@@ -0,0 +1,152 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// Whether an edited file was created during the session or existed before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    New,
+    Modified,
+}
+
+static SESSION: OnceLock<String> = OnceLock::new();
+
+/// Set the session whose snapshot `of` consults. Called once per hook invocation.
+pub fn set_session(session_id: &str) {
+    let _ = SESSION.set(session_id.to_string());
+}
+
+/// The session set with `set_session`, if any.
+pub fn session() -> Option<&'static str> {
+    SESSION.get().map(String::as_str)
+}
+
+/// Snapshot file path for a session: `<temp_dir>/ralph-lint-<session_id>-origin.txt`
+pub fn snapshot_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-origin.txt"))
+}
+
+/// Record whether `file_path` exists before the session's first edit to it (from a
+/// `PreToolUse` event). Later calls for the same file keep the first answer.
+pub fn remember(session_id: &str, file_path: &str) -> std::io::Result<()> {
+    if snapshot_lookup(session_id, file_path).is_some() {
+        return Ok(());
+    }
+    let origin = if Path::new(file_path).exists() {
+        "modified"
+    } else {
+        "new"
+    };
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(snapshot_path(session_id))?;
+    writeln!(file, "{origin}\t{file_path}")
+}
+
+fn snapshot_lookup(session_id: &str, file_path: &str) -> Option<Origin> {
+    let contents = fs::read_to_string(snapshot_path(session_id)).ok()?;
+    contents
+        .lines()
+        .find_map(|line| match line.split_once('\t') {
+            Some(("new", path)) if path == file_path => Some(Origin::New),
+            Some(("modified", path)) if path == file_path => Some(Origin::Modified),
+            _ => None,
+        })
+}
+
+/// Where `file_path` came from: the session snapshot if a `PreToolUse` event recorded it,
+/// otherwise git (untracked or newly added files are new), except that files already dirty
+/// at the session's git baseline predate it. `None` outside a git repository when no
+/// snapshot covers the file.
+pub fn of(file_path: &str) -> Option<Origin> {
+    if let Some(session_id) = SESSION.get() {
+        if let Some(origin) = snapshot_lookup(session_id, file_path) {
+            return Some(origin);
+        }
+        if crate::collect::dirty_at_baseline(session_id, file_path) {
+            return Some(Origin::Modified);
+        }
+    }
+    from_git(file_path)
+}
+
+/// Where `file_path` came from according to git alone. `None` outside a git repository.
+pub fn from_git(file_path: &str) -> Option<Origin> {
+    let repo = crate::project::find_repo_root(file_path)?;
+    let relative = Path::new(file_path).strip_prefix(&repo).ok()?;
+    // The whole repository's status, since a rename is only reported with both of its paths
+    let output = Command::new("git")
+        .args(["status", "--porcelain", "-z"])
+        .current_dir(&repo)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(porcelain_origin(
+        &crate::encoding::decode(&output.stdout),
+        &relative.to_string_lossy(),
+    ))
+}
+
+/// Origin of `relative` from `git status --porcelain -z` output. `??` (untracked) and `A`
+/// (added to the index) mean new; renames and copies, other changes, and a clean tracked file
+/// with no entry mean modified.
+fn porcelain_origin(output: &str, relative: &str) -> Origin {
+    let mut entries = output.split('\0');
+    while let Some(entry) = entries.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        let (status, path) = entry.split_at(3);
+        if status.starts_with(['R', 'C']) {
+            // Followed by the original path
+            entries.next();
+        }
+        if path == relative {
+            return if status.starts_with("??") || status.starts_with('A') {
+                Origin::New
+            } else {
+                Origin::Modified
+            };
+        }
+    }
+    Origin::Modified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn porcelain_status_maps_to_origin() {
+        let output =
+            "?? src/new.ts\0AM src/added.ts\0 M src/old.ts\0RM src/moved.ts\0src/orig.ts\0";
+        assert_eq!(porcelain_origin(output, "src/new.ts"), Origin::New);
+        assert_eq!(porcelain_origin(output, "src/added.ts"), Origin::New);
+        assert_eq!(porcelain_origin(output, "src/old.ts"), Origin::Modified);
+        assert_eq!(porcelain_origin(output, "src/moved.ts"), Origin::Modified);
+        assert_eq!(porcelain_origin(output, "src/clean.ts"), Origin::Modified);
+        assert_eq!(porcelain_origin("", "a.ts"), Origin::Modified);
+    }
+
+    #[test]
+    fn snapshot_keeps_first_answer() {
+        let sid = format!("test-origin-{}", std::process::id());
+        let _ = fs::remove_file(snapshot_path(&sid));
+        let dir = std::env::temp_dir().join(format!("ralph-lint-origin-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("a.py");
+        let file = file.to_str().unwrap();
+
+        remember(&sid, file).unwrap();
+        fs::write(file, "x = 1\n").unwrap();
+        remember(&sid, file).unwrap();
+        assert_eq!(snapshot_lookup(&sid, file), Some(Origin::New));
+        assert_eq!(snapshot_lookup(&sid, "/other.py"), None);
+
+        let _ = fs::remove_file(snapshot_path(&sid));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
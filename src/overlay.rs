@@ -0,0 +1,137 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::cli::SessionAction;
+use crate::collect;
+use crate::origin;
+
+/// Linters a session can add rules to: clippy denies each lint (`-D`), ruff selects each rule
+/// on top of the project's own selection (`--extend-select`).
+const LINTERS: [&str; 2] = ["clippy", "ruff"];
+
+/// Overlay file path for a session: `<temp_dir>/ralph-lint-<session_id>-overlay.txt`, one
+/// `<linter>\t<rule>` line per added rule.
+pub fn overlay_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-overlay.txt"))
+}
+
+/// The rules added to a session, in the order they were added.
+fn entries(session_id: &str) -> Vec<(String, String)> {
+    fs::read_to_string(overlay_path(session_id))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(linter, rule)| (linter.to_string(), rule.to_string()))
+        .collect()
+}
+
+fn write_entries(session_id: &str, entries: &[(String, String)]) -> std::io::Result<()> {
+    let path = overlay_path(session_id);
+    if entries.is_empty() {
+        return match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    let mut file = fs::File::create(path)?;
+    for (linter, rule) in entries {
+        writeln!(file, "{linter}\t{rule}")?;
+    }
+    Ok(())
+}
+
+/// Extra arguments for `linter` from the current session's overlay (see
+/// [`origin::session`]); empty outside a session or without rules for it.
+pub fn args(linter: &str) -> Vec<String> {
+    let Some(session_id) = origin::session() else {
+        return Vec::new();
+    };
+    let rules: Vec<String> = entries(session_id)
+        .into_iter()
+        .filter(|(l, _)| l == linter)
+        .map(|(_, rule)| rule)
+        .collect();
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    match linter {
+        "clippy" => rules
+            .into_iter()
+            .flat_map(|rule| ["-D".to_string(), rule])
+            .collect(),
+        _ => vec![format!("--extend-select={}", rules.join(","))],
+    }
+}
+
+/// `ralph-hook-lint session`: add, remove, list, or clear a session's extra rules. Changing them
+/// forgets which files already passed and which events were seen, so the next lint checks
+/// files against the new rules even when they haven't changed.
+pub fn run(action: &SessionAction, session_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut current = entries(session_id);
+    match action {
+        SessionAction::Add { linter, rule } | SessionAction::Remove { linter, rule } => {
+            if !LINTERS.contains(&linter.as_str()) {
+                return Err(format!(
+                    "unknown linter '{linter}'; session rules apply to {}",
+                    LINTERS.join(" and ")
+                )
+                .into());
+            }
+            let entry = (linter.clone(), rule.clone());
+            if matches!(action, SessionAction::Add { .. }) {
+                if !current.contains(&entry) {
+                    current.push(entry);
+                }
+            } else {
+                current.retain(|e| *e != entry);
+            }
+        }
+        SessionAction::Clear => current.clear(),
+        SessionAction::List => {
+            for (linter, rule) in &current {
+                println!("{linter}\t{rule}");
+            }
+            return Ok(());
+        }
+    }
+    write_entries(session_id, &current)?;
+    let _ = fs::remove_file(collect::clean_path(session_id));
+    let _ = fs::remove_file(collect::events_path(session_id));
+    collect::clear_results(session_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_are_added_once_and_removed() {
+        let session_id = format!("overlay-test-{}", std::process::id());
+        let add = |linter: &str, rule: &str| SessionAction::Add {
+            linter: linter.to_string(),
+            rule: rule.to_string(),
+        };
+        run(&add("clippy", "clippy::unwrap_used"), &session_id).unwrap();
+        run(&add("clippy", "clippy::unwrap_used"), &session_id).unwrap();
+        run(&add("ruff", "S"), &session_id).unwrap();
+        assert_eq!(
+            entries(&session_id),
+            [
+                ("clippy".to_string(), "clippy::unwrap_used".to_string()),
+                ("ruff".to_string(), "S".to_string())
+            ]
+        );
+        assert!(run(&add("eslint", "no-console"), &session_id).is_err());
+
+        let remove = SessionAction::Remove {
+            linter: "ruff".to_string(),
+            rule: "S".to_string(),
+        };
+        run(&remove, &session_id).unwrap();
+        assert_eq!(entries(&session_id).len(), 1);
+        run(&SessionAction::Clear, &session_id).unwrap();
+        assert!(!overlay_path(&session_id).exists());
+    }
+}
@@ -0,0 +1,197 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::path_matches;
+use crate::json::{self, Json};
+
+/// A lint pipeline the project declares itself, preferred over guessing a linter when
+/// `[js] project_pipeline = true`.
+#[derive(Debug)]
+pub struct Pipeline {
+    /// Name shown in the hook's messages
+    pub label: &'static str,
+    /// Commands to run in order, each already given the edited file
+    pub commands: Vec<Command>,
+}
+
+/// Find the project's own pipeline for `file_path`: a `lint:file` script, or the
+/// lint-staged commands whose glob matches the file.
+pub fn find(project_root: &str, file_path: &str) -> Option<Pipeline> {
+    let package = fs::read_to_string(Path::new(project_root).join("package.json"))
+        .ok()
+        .and_then(|text| json::parse(&text));
+
+    if package
+        .as_ref()
+        .and_then(|p| p.get("scripts")?.get("lint:file"))
+        .is_some()
+    {
+        let mut command = Command::new("npm");
+        command
+            .args(["run", "lint:file", "--", file_path])
+            .current_dir(project_root);
+        return Some(Pipeline {
+            label: "npm run lint:file",
+            commands: vec![command],
+        });
+    }
+
+    let config = package
+        .as_ref()
+        .and_then(|p| p.get("lint-staged").cloned())
+        .or_else(|| {
+            [".lintstagedrc", ".lintstagedrc.json"]
+                .iter()
+                .find_map(|name| fs::read_to_string(Path::new(project_root).join(name)).ok())
+                .and_then(|text| json::parse(&text))
+        })?;
+    let relative = file_path
+        .strip_prefix(project_root)
+        .unwrap_or(file_path)
+        .trim_start_matches('/');
+    let commands: Vec<Command> = lint_staged_commands(&config, relative)
+        .iter()
+        .map(|script| shell_command(project_root, script, file_path))
+        .collect();
+    if commands.is_empty() {
+        return None;
+    }
+    Some(Pipeline {
+        label: "lint-staged",
+        commands,
+    })
+}
+
+/// Commands from a lint-staged config for the file at `relative` (relative to the project),
+/// made read-only: `--fix` is dropped and commands that rewrite or stage files are skipped.
+fn lint_staged_commands(config: &Json, relative: &str) -> Vec<String> {
+    let Json::Object(entries) = config else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter(|(pattern, _)| {
+            expand_braces(pattern)
+                .iter()
+                .any(|p| path_matches(p, relative))
+        })
+        .flat_map(|(_, commands)| match commands {
+            Json::String(command) => vec![command.as_str()],
+            Json::Array(items) => items.iter().filter_map(Json::as_str).collect(),
+            _ => Vec::new(),
+        })
+        .filter_map(read_only_command)
+        .collect()
+}
+
+fn read_only_command(command: &str) -> Option<String> {
+    let words: Vec<&str> = command.split_whitespace().collect();
+    if words.iter().any(|w| *w == "--write" || *w == "-w") || words.starts_with(&["git", "add"]) {
+        return None;
+    }
+    let kept: Vec<&str> = words.into_iter().filter(|w| *w != "--fix").collect();
+    (!kept.is_empty()).then(|| kept.join(" "))
+}
+
+/// Expand `{a,b}` alternatives, which lint-staged globs commonly use (`*.{js,ts}`).
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+    let Some(close) = pattern[open..].find('}').map(|i| open + i) else {
+        return vec![pattern.to_string()];
+    };
+    let (prefix, suffix) = (&pattern[..open], &pattern[close + 1..]);
+    pattern[open + 1..close]
+        .split(',')
+        .flat_map(|alt| expand_braces(&format!("{prefix}{alt}{suffix}")))
+        .collect()
+}
+
+/// Run `script` with the file appended, resolving binaries from `node_modules/.bin` as
+/// lint-staged does.
+fn shell_command(project_root: &str, script: &str, file_path: &str) -> Command {
+    let bin = Path::new(project_root).join("node_modules/.bin");
+    let path = std::env::var("PATH").unwrap_or_default();
+    let mut command = Command::new("sh");
+    command
+        .args(["-c", &format!("{script} \"$@\""), "sh", file_path])
+        .env("PATH", format!("{}:{path}", bin.display()))
+        .current_dir(project_root);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &Command) -> Vec<String> {
+        command
+            .get_args()
+            .map(|a| a.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn expand_braces_alternatives() {
+        assert_eq!(expand_braces("*.{js,ts}"), vec!["*.js", "*.ts"]);
+        assert_eq!(expand_braces("src/*.js"), vec!["src/*.js"]);
+        assert_eq!(
+            expand_braces("{a,b}/*.{x,y}"),
+            vec!["a/*.x", "a/*.y", "b/*.x", "b/*.y"]
+        );
+    }
+
+    #[test]
+    fn lint_staged_commands_match_and_are_read_only() {
+        let config = json::parse(
+            r#"{
+                "*.{js,ts}": ["eslint --fix", "prettier --write"],
+                "src/**/*.ts": "tsc-files --noEmit",
+                "*.md": "markdownlint",
+                "*.css": ["stylelint --fix", "git add"]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            lint_staged_commands(&config, "src/app/main.ts"),
+            vec!["eslint", "tsc-files --noEmit"]
+        );
+        assert_eq!(lint_staged_commands(&config, "lib/a.js"), vec!["eslint"]);
+        assert_eq!(lint_staged_commands(&config, "a.css"), vec!["stylelint"]);
+        assert!(lint_staged_commands(&config, "a.py").is_empty());
+    }
+
+    #[test]
+    fn find_prefers_lint_file_script_then_lint_staged() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-pipeline-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let root = dir.to_str().unwrap();
+        let file = format!("{root}/src/index.js");
+
+        fs::write(dir.join("package.json"), r#"{"name":"x"}"#).unwrap();
+        assert!(find(root, &file).is_none());
+
+        fs::write(dir.join(".lintstagedrc"), r#"{"*.js":"eslint --fix"}"#).unwrap();
+        let pipeline = find(root, &file).unwrap();
+        assert_eq!(pipeline.label, "lint-staged");
+        assert_eq!(
+            args(&pipeline.commands[0]),
+            vec!["-c", "eslint \"$@\"", "sh", file.as_str()]
+        );
+
+        fs::write(
+            dir.join("package.json"),
+            r#"{"name":"x","scripts":{"lint:file":"eslint"}}"#,
+        )
+        .unwrap();
+        let pipeline = find(root, &file).unwrap();
+        assert_eq!(pipeline.label, "npm run lint:file");
+        assert_eq!(
+            args(&pipeline.commands[0]),
+            vec!["run", "lint:file", "--", file.as_str()]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
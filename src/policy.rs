@@ -0,0 +1,376 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::config::Config;
+
+/// Path or URL of the managed policy, overriding [`DEFAULT_PATH`]
+pub const PATH_ENV: &str = "RALPH_HOOK_LINT_POLICY";
+/// Expected SHA-256 of the policy file, as 64 hex digits
+pub const SHA256_ENV: &str = "RALPH_HOOK_LINT_POLICY_SHA256";
+/// Where a fleet's configuration management drops the policy
+const DEFAULT_PATH: &str = "/etc/ralph-hook-lint/policy.toml";
+
+/// Where the managed policy comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    File(PathBuf),
+    /// Fetched by `daemon start` into [`cache_path`]; lints read the cached copy, which must
+    /// match [`SHA256_ENV`]
+    Url(String),
+}
+
+impl Source {
+    fn from_env() -> Option<Self> {
+        match std::env::var(PATH_ENV) {
+            Ok(value) if value.starts_with("https://") || value.starts_with("http://") => {
+                Some(Self::Url(value))
+            }
+            Ok(value) if !value.is_empty() => Some(Self::File(PathBuf::from(value))),
+            _ => Some(PathBuf::from(DEFAULT_PATH))
+                .filter(|path| path.exists())
+                .map(Self::File),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::File(path) => path.display().to_string(),
+            Self::Url(url) => url.clone(),
+        }
+    }
+}
+
+/// The per-user directory holding policies fetched from a URL:
+/// `$XDG_CACHE_HOME/ralph-hook-lint`, or `~/.cache/ralph-hook-lint`.
+fn cache_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(std::env::var_os("HOME")?).join(".cache")))
+        .map(|dir| dir.join("ralph-hook-lint"))
+}
+
+/// The cached copy of a policy fetched from `url`:
+/// `<cache_dir>/policy-<hash of the URL>.toml`.
+fn cache_path(url: &str) -> Result<PathBuf, String> {
+    let dir = cache_dir().ok_or("no cache directory: neither XDG_CACHE_HOME nor HOME is set")?;
+    Ok(dir.join(format!("policy-{}.toml", &sha256_hex(url.as_bytes())[..16])))
+}
+
+/// Create the cache directory readable only by the current user.
+#[cfg(unix)]
+fn create_cache_dir(dir: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)?;
+    fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+}
+
+#[cfg(not(unix))]
+fn create_cache_dir(dir: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dir)
+}
+
+/// Refuse a cached policy another user could have planted or replaced: it and its directory
+/// must be owned by the current user and not writable by group or others.
+#[cfg(unix)]
+fn check_owner(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::MetadataExt;
+    let uid = current_uid().ok_or("cannot determine the current user")?;
+    for path in [path, path.parent().unwrap_or(path)] {
+        let metadata =
+            fs::metadata(path).map_err(|e| format!("cannot stat {}: {e}", path.display()))?;
+        if metadata.uid() != uid {
+            return Err(format!(
+                "{} is not owned by the current user",
+                path.display()
+            ));
+        }
+        if metadata.mode() & 0o022 != 0 {
+            return Err(format!("{} is writable by other users", path.display()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_owner(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+/// The real user ID: the owner of `/proc/self` on Linux, else `id -u`.
+#[cfg(unix)]
+fn current_uid() -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    if let Ok(metadata) = fs::metadata("/proc/self") {
+        return Some(metadata.uid());
+    }
+    let output = Command::new("id").arg("-u").output().ok()?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Check `text` against the expected checksum from [`SHA256_ENV`]. The checksum is optional
+/// for a policy file but required for one served from a URL.
+fn verify(source: &Source, text: &str) -> Result<(), String> {
+    let Ok(expected) = std::env::var(SHA256_ENV) else {
+        return match source {
+            Source::File(_) => Ok(()),
+            Source::Url(_) => Err(format!("a policy served from a URL needs {SHA256_ENV}")),
+        };
+    };
+    let actual = sha256_hex(text.as_bytes());
+    if actual.eq_ignore_ascii_case(expected.trim()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "checksum mismatch: expected {}, got {actual}",
+            expected.trim()
+        ))
+    }
+}
+
+fn load(source: &Source) -> Result<Config, String> {
+    let path = match source {
+        Source::File(path) => path.clone(),
+        Source::Url(url) => cache_path(url)?,
+    };
+    let text = fs::read_to_string(&path).map_err(|e| match source {
+        Source::File(_) => format!("cannot read it: {e}"),
+        Source::Url(_) => "not fetched yet; run `ralph-hook-lint daemon start`".to_string(),
+    })?;
+    if let Source::Url(_) = source {
+        check_owner(&path)?;
+    }
+    verify(source, &text)?;
+    Ok(Config::parse(&text))
+}
+
+/// The managed policy, loaded once per process: `Ok(None)` when none is configured, and an
+/// error naming the policy when it is configured but can't be read or fails its checksum.
+pub fn current() -> Result<Option<&'static Config>, &'static str> {
+    static POLICY: OnceLock<Result<Option<Config>, String>> = OnceLock::new();
+    POLICY
+        .get_or_init(|| {
+            let Some(source) = Source::from_env() else {
+                return Ok(None);
+            };
+            load(&source)
+                .map(Some)
+                .map_err(|e| format!("managed policy {} rejected: {e}", source.describe()))
+        })
+        .as_ref()
+        .map(Option::as_ref)
+        .map_err(String::as_str)
+}
+
+/// A line describing the managed policy for `capabilities`, or `None` without one.
+pub fn status() -> Option<String> {
+    let source = Source::from_env()?;
+    Some(match current() {
+        Ok(_) => format!("{} (active)", source.describe()),
+        Err(e) => e.to_string(),
+    })
+}
+
+/// Fetch a policy served from a URL into the cache, verifying it before it replaces the
+/// cached copy, for `daemon start`. Returns a line to print, or `None` without a policy.
+pub fn fetch() -> Result<Option<String>, String> {
+    let Some(source) = Source::from_env() else {
+        return Ok(None);
+    };
+    let Source::Url(url) = &source else {
+        return load(&source)
+            .map(|_| Some(format!("using {}", source.describe())))
+            .map_err(|e| format!("managed policy {} rejected: {e}", source.describe()));
+    };
+    let rejected = |e: String| format!("managed policy {url} rejected: {e}");
+    if std::env::var_os(SHA256_ENV).is_none() {
+        return Err(rejected(format!(
+            "a policy served from a URL needs {SHA256_ENV}"
+        )));
+    }
+    let cache = cache_path(url).map_err(rejected)?;
+    if let Some(dir) = cache.parent() {
+        create_cache_dir(dir).map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+        check_owner(dir).map_err(rejected)?;
+    }
+    let partial = cache.with_extension("toml.part");
+    let output = Command::new("curl")
+        .args([
+            "--fail",
+            "--silent",
+            "--show-error",
+            "--location",
+            "--max-time",
+            "30",
+            "--output",
+        ])
+        .arg(&partial)
+        .arg(url)
+        .output()
+        .map_err(|e| format!("cannot run curl to fetch {url}: {e}"))?;
+    if !output.status.success() {
+        let _ = fs::remove_file(&partial);
+        return Err(format!(
+            "cannot fetch {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let text = fs::read_to_string(&partial).map_err(|e| format!("cannot read {url}: {e}"))?;
+    if let Err(e) = verify(&source, &text) {
+        let _ = fs::remove_file(&partial);
+        return Err(rejected(e));
+    }
+    fs::rename(&partial, &cache).map_err(|e| format!("cannot cache {url}: {e}"))?;
+    Ok(Some(format!("fetched {url} into {}", cache.display())))
+}
+
+/// SHA-256 round constants
+#[rustfmt::skip]
+const SHA256_K: [u32; 64] = [
+    0x428a_2f98, 0x7137_4491, 0xb5c0_fbcf, 0xe9b5_dba5, 0x3956_c25b, 0x59f1_11f1, 0x923f_82a4, 0xab1c_5ed5,
+    0xd807_aa98, 0x1283_5b01, 0x2431_85be, 0x550c_7dc3, 0x72be_5d74, 0x80de_b1fe, 0x9bdc_06a7, 0xc19b_f174,
+    0xe49b_69c1, 0xefbe_4786, 0x0fc1_9dc6, 0x240c_a1cc, 0x2de9_2c6f, 0x4a74_84aa, 0x5cb0_a9dc, 0x76f9_88da,
+    0x983e_5152, 0xa831_c66d, 0xb003_27c8, 0xbf59_7fc7, 0xc6e0_0bf3, 0xd5a7_9147, 0x06ca_6351, 0x1429_2967,
+    0x27b7_0a85, 0x2e1b_2138, 0x4d2c_6dfc, 0x5338_0d13, 0x650a_7354, 0x766a_0abb, 0x81c2_c92e, 0x9272_2c85,
+    0xa2bf_e8a1, 0xa81a_664b, 0xc24b_8b70, 0xc76c_51a3, 0xd192_e819, 0xd699_0624, 0xf40e_3585, 0x106a_a070,
+    0x19a4_c116, 0x1e37_6c08, 0x2748_774c, 0x34b0_bcb5, 0x391c_0cb3, 0x4ed8_aa4a, 0x5b9c_ca4f, 0x682e_6ff3,
+    0x748f_82ee, 0x78a5_636f, 0x84c8_7814, 0x8cc7_0208, 0x90be_fffa, 0xa450_6ceb, 0xbef9_a3f7, 0xc671_78f2,
+];
+
+/// SHA-256 initial hash value
+#[rustfmt::skip]
+const SHA256_INIT: [u32; 8] = [
+    0x6a09_e667, 0xbb67_ae85, 0x3c6e_f372, 0xa54f_f53a, 0x510e_527f, 0x9b05_688c, 0x1f83_d9ab, 0x5be0_cd19,
+];
+
+/// SHA-256 of `data` as lowercase hex, as printed by `sha256sum`.
+fn sha256_hex(data: &[u8]) -> String {
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_be_bytes());
+
+    let mut state = SHA256_INIT;
+    for block in message.chunks_exact(64) {
+        sha256_compress(&mut state, block);
+    }
+    state.iter().fold(String::new(), |mut hex, word| {
+        let _ = write!(hex, "{word:08x}");
+        hex
+    })
+}
+
+fn sha256_compress(state: &mut [u32; 8], block: &[u8]) {
+    let mut words = [0u32; 64];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    for i in 16..64 {
+        let (w15, w2) = (words[i - 15], words[i - 2]);
+        let s0 = w15.rotate_right(7) ^ w15.rotate_right(18) ^ (w15 >> 3);
+        let s1 = w2.rotate_right(17) ^ w2.rotate_right(19) ^ (w2 >> 10);
+        words[i] = words[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(words[i - 7])
+            .wrapping_add(s1);
+    }
+    // The working variables a..h
+    let mut v = *state;
+    for (k, word) in SHA256_K.iter().zip(words) {
+        let s1 = v[4].rotate_right(6) ^ v[4].rotate_right(11) ^ v[4].rotate_right(25);
+        let choice = (v[4] & v[5]) ^ (!v[4] & v[6]);
+        let t1 = v[7]
+            .wrapping_add(s1)
+            .wrapping_add(choice)
+            .wrapping_add(*k)
+            .wrapping_add(word);
+        let s0 = v[0].rotate_right(2) ^ v[0].rotate_right(13) ^ v[0].rotate_right(22);
+        let majority = (v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]);
+        let t2 = s0.wrapping_add(majority);
+        v = [
+            t1.wrapping_add(t2),
+            v[0],
+            v[1],
+            v[2],
+            v[3].wrapping_add(t1),
+            v[4],
+            v[5],
+            v[6],
+        ];
+    }
+    for (word, value) in state.iter_mut().zip(v) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_matches_known_digests() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // Two blocks once padded
+        assert_eq!(
+            sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn url_policies_are_cached_per_url() {
+        let Ok(a) = cache_path("https://example.com/a.toml") else {
+            return;
+        };
+        assert_ne!(Ok(a.clone()), cache_path("https://example.com/b.toml"));
+        assert!(!a.starts_with(std::env::temp_dir()));
+        assert!(a.parent().unwrap().ends_with("ralph-hook-lint"));
+    }
+
+    #[test]
+    fn url_policies_require_a_checksum() {
+        let url = Source::Url("https://example.com/policy.toml".to_string());
+        if std::env::var_os(SHA256_ENV).is_none() {
+            assert!(verify(&url, "").unwrap_err().contains(SHA256_ENV));
+            assert!(verify(&Source::File(PathBuf::from("policy.toml")), "").is_ok());
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cached_policies_writable_by_others_are_refused() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir =
+            std::env::temp_dir().join(format!("ralph-lint-policy-owner-{}", std::process::id()));
+        create_cache_dir(&dir).unwrap();
+        let file = dir.join("policy.toml");
+        fs::write(&file, "").unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(check_owner(&file), Ok(()));
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o666)).unwrap();
+        assert!(
+            check_owner(&file)
+                .unwrap_err()
+                .contains("writable by other users")
+        );
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o600)).unwrap();
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(check_owner(&file).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
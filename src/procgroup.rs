@@ -0,0 +1,597 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::mpsc;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::network::Network;
+use crate::quarantine;
+use crate::sandbox::Limits;
+
+/// Scheduling priority for linter process groups, from the `[process]` config section.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Priority {
+    /// Niceness (0-19) applied with `renice`
+    pub nice: Option<i64>,
+    /// I/O scheduling class applied with `ionice` (Linux only)
+    pub io_class: Option<IoClass>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoClass {
+    /// Only gets disk time when nothing else wants it
+    Idle,
+    /// Lowest best-effort priority
+    BestEffort,
+}
+
+impl Priority {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            nice: config.get_int("process.nice").map(|n| n.clamp(0, 19)),
+            io_class: match config.get_str("process.io_class") {
+                Some("idle") => Some(IoClass::Idle),
+                Some("best-effort") => Some(IoClass::BestEffort),
+                _ => None,
+            },
+        }
+    }
+
+    /// Commands that lower process group `group` to this priority.
+    fn commands(self, group: u32) -> Vec<Command> {
+        let mut commands = Vec::new();
+        if let Some(nice) = self.nice {
+            let mut renice = Command::new("renice");
+            renice.args(["-n", &nice.to_string(), "-g", &group.to_string()]);
+            commands.push(renice);
+        }
+        if let Some(class) = self.io_class {
+            let mut ionice = Command::new("ionice");
+            match class {
+                IoClass::Idle => ionice.args(["-c", "3"]),
+                IoClass::BestEffort => ionice.args(["-c", "2", "-n", "7"]),
+            };
+            ionice.args(["-P", &group.to_string()]);
+            commands.push(ionice);
+        }
+        commands
+    }
+}
+
+/// Priority and containment for every linter started with [`GroupOutput::output_grouped`].
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    pub priority: Priority,
+    pub limits: Limits,
+    pub network: Network,
+    /// Failures after which a linter is quarantined for the session; 0 disables quarantine
+    pub quarantine_after: usize,
+}
+
+impl Settings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            priority: Priority::from_config(config),
+            limits: Limits::from_config(config),
+            network: Network::from_config(config),
+            quarantine_after: config
+                .get_int("process.quarantine_after")
+                .and_then(|n| usize::try_from(n).ok())
+                .unwrap_or(quarantine::DEFAULT_AFTER),
+        }
+    }
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Settings from project configs, keyed by config file, so each repo in a multi-root session
+/// gets its own `[process]`/`[sandbox]` rules.
+static PROJECT_SETTINGS: OnceLock<Mutex<HashMap<PathBuf, Settings>>> = OnceLock::new();
+
+/// Set the process settings once, before any linter runs. These apply to commands whose
+/// working directory has no config file of its own.
+pub fn configure(settings: Settings) {
+    let _ = SETTINGS.set(settings);
+}
+
+/// Settings for a command run in `dir`: from the nearest config at or above it, falling back
+/// to the configured defaults.
+fn settings_for(dir: Option<&std::path::Path>) -> Option<Settings> {
+    let fallback = || SETTINGS.get().cloned();
+    let Some(dir) = dir else {
+        return fallback();
+    };
+    let config = Config::load(&dir.to_string_lossy());
+    let Some(source) = config.source.clone() else {
+        return fallback();
+    };
+    let cache = PROJECT_SETTINGS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cache = cache
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    Some(
+        cache
+            .entry(source)
+            .or_insert_with(|| Settings::from_config(&config))
+            .clone(),
+    )
+}
+
+thread_local! {
+    /// CPU time of the linters this thread waited for, for per-job accounting
+    static CPU_TIME: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+    /// Identity of the linter this thread started last, see [`linter_identity`]
+    static LAST_LINTER: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Identity of the linter this thread started last, so a failure judged from its exit code is
+/// counted against the same quarantine key as crashes and timeouts.
+pub fn last_linter() -> Option<String> {
+    LAST_LINTER.with(|last| last.borrow().clone())
+}
+
+/// CPU time used by the linters this thread ran since the last call. Zero where `/proc` isn't
+/// available.
+pub fn take_cpu_time() -> Duration {
+    CPU_TIME.with(|cpu| cpu.replace(Duration::ZERO))
+}
+
+fn add_cpu_time(used: Duration) {
+    CPU_TIME.with(|cpu| cpu.set(cpu.get() + used));
+}
+
+/// Run linters in their own process group so they can be cleaned up if the hook is killed.
+pub trait GroupOutput {
+    /// Like [`Command::output`], but the child leads a new process group, and a watchdog kills
+    /// that group if this process exits before the child does (e.g. the agent's hook timeout).
+    /// Otherwise orphaned gradle/cargo builds keep running and holding locks.
+    fn output_grouped(&mut self) -> io::Result<Output>;
+
+    /// [`GroupOutput::output_grouped`] that kills the whole group and returns `None` if the
+    /// command is still running after `timeout`.
+    fn output_grouped_timeout(&mut self, timeout: Duration) -> io::Result<Option<Output>>;
+
+    /// Start the command in its own process group with output discarded, and don't wait for
+    /// it. No watchdog is attached, so it outlives the hook; for warm-up work only.
+    fn spawn_detached(&mut self) -> io::Result<()>;
+}
+
+impl GroupOutput for Command {
+    fn output_grouped(&mut self) -> io::Result<Output> {
+        let (output, cpu) = wait_accounted(spawn_grouped(self)?)?;
+        add_cpu_time(cpu);
+        if output.status.code().is_none() {
+            // Killed by a signal: a crash or an out-of-memory kill, not lint findings
+            quarantine::record_failure(&linter_identity(self));
+        }
+        Ok(output)
+    }
+
+    fn output_grouped_timeout(&mut self, timeout: Duration) -> io::Result<Option<Output>> {
+        let child = spawn_grouped(self)?;
+        let group = child.id();
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(wait_accounted(child));
+        });
+        receiver.recv_timeout(timeout).map_or_else(
+            |_| {
+                kill_group(group);
+                quarantine::record_failure(&linter_identity(self));
+                Ok(None)
+            },
+            |output| {
+                output.map(|(output, cpu)| {
+                    add_cpu_time(cpu);
+                    Some(output)
+                })
+            },
+        )
+    }
+
+    fn spawn_detached(&mut self) -> io::Result<()> {
+        spawn_in_group(self, false).map(drop)
+    }
+}
+
+/// Like [`Child::wait_with_output`], also returning the CPU time the child and the
+/// descendants it waited for used. That is read from `/proc` between the child's exit and
+/// reaping it, the only moment it covers the whole run.
+fn wait_accounted(mut child: Child) -> io::Result<(Output, Duration)> {
+    let stderr = child.stderr.take();
+    let stderr = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_end(&mut buf);
+        }
+        buf
+    });
+    let mut stdout = Vec::new();
+    if let Some(mut pipe) = child.stdout.take() {
+        pipe.read_to_end(&mut stdout)?;
+    }
+    let stderr = stderr.join().unwrap_or_default();
+    let cpu = exited_cpu_time(child.id());
+    let status = child.wait()?;
+    Ok((
+        Output {
+            status,
+            stdout,
+            stderr,
+        },
+        cpu,
+    ))
+}
+
+/// Wait until process `pid` is a zombie and return its CPU time, including the children it
+/// reaped. Zero if `/proc` can't tell.
+fn exited_cpu_time(pid: u32) -> Duration {
+    loop {
+        let Some((state, ticks)) = fs::read_to_string(format!("/proc/{pid}/stat"))
+            .ok()
+            .and_then(|stat| parse_stat(&stat))
+        else {
+            return Duration::ZERO;
+        };
+        if state == 'Z' {
+            // Clock ticks are 1/100 s (USER_HZ) on every mainstream Linux build
+            return Duration::from_millis(ticks * 10);
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// The state and total `utime + stime + cutime + cstime` ticks from a `/proc/<pid>/stat` line.
+fn parse_stat(stat: &str) -> Option<(char, u64)> {
+    // The command name is parenthesized and may contain spaces, so fields start after the last `)`
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let state = fields.first()?.chars().next()?;
+    let ticks = fields
+        .get(11..15)?
+        .iter()
+        .map(|field| field.parse::<u64>().ok())
+        .sum::<Option<u64>>()?;
+    Some((state, ticks))
+}
+
+/// Start a linter attached to this process, unless it failed too often this session.
+fn spawn_grouped(command: &mut Command) -> io::Result<Child> {
+    let linter = linter_identity(command);
+    LAST_LINTER.with(|last| last.replace(Some(linter.clone())));
+    let after = settings_for(command.get_current_dir())
+        .map_or(quarantine::DEFAULT_AFTER, |s| s.quarantine_after);
+    if let Some(quarantined) = quarantine::check(&linter, after) {
+        return Err(io::Error::other(quarantined));
+    }
+    spawn_in_group(command, true)
+}
+
+/// Programs that run the tool or task named by an argument, such as `cargo clippy`,
+/// `mvn checkstyle:check`, or `sh -c 'eslint "$@"'`
+const LAUNCHERS: [&str; 20] = [
+    "bash", "bunx", "cargo", "dotnet", "go", "gradle", "gradlew", "mix", "mvn", "mvnw", "npm",
+    "npx", "pnpm", "poetry", "python", "python3", "sbt", "sh", "uv", "yarn",
+];
+
+/// The linter a command runs, which quarantine counts failures against: the program's file
+/// name, e.g. `eslint` for `node_modules/.bin/eslint`, followed for launchers by the first
+/// word of the tool or task they run, e.g. `cargo clippy` or `npm lint` for `npm run lint`.
+fn linter_identity(command: &Command) -> String {
+    let program = file_stem(&command.get_program().to_string_lossy());
+    if !LAUNCHERS.contains(&program.as_str()) {
+        return program;
+    }
+    let tool = command
+        .get_args()
+        .map(|arg| arg.to_string_lossy())
+        .find(|arg| {
+            !arg.starts_with(['-', '+']) && !["run", "exec", "x", "dlx"].contains(&arg.as_ref())
+        })
+        .and_then(|arg| arg.split_whitespace().next().map(file_stem));
+    tool.map_or_else(|| program.clone(), |tool| format!("{program} {tool}"))
+}
+
+/// File name of `program` without its extension, e.g. `eslint` for `node_modules/.bin/eslint`.
+fn file_stem(program: &str) -> String {
+    let path = std::path::Path::new(program);
+    path.file_stem()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(unix)]
+fn spawn_in_group(command: &mut Command, attached: bool) -> io::Result<Child> {
+    use std::os::unix::process::CommandExt;
+
+    let settings = settings_for(command.get_current_dir());
+    let settings = settings.as_ref();
+    if let Some(settings) = settings {
+        settings.network.apply(command);
+    }
+    let mut contained = settings.and_then(|s| s.limits.contain(command));
+    let command = contained.as_mut().unwrap_or(command);
+    let output = || {
+        if attached {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        }
+    };
+    let child = command
+        .process_group(0)
+        .stdin(Stdio::null())
+        .stdout(output())
+        .stderr(output())
+        .spawn()?;
+    if attached {
+        // Best effort: without a watchdog the lint still runs, it just isn't cleaned up
+        let _ = spawn_watchdog(std::process::id(), child.id());
+    }
+    // Descendants inherit the group leader's priority, so lowering it early covers the build
+    let priority = settings.map(|s| s.priority).unwrap_or_default();
+    for mut command in priority.commands(child.id()) {
+        let _ = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+    Ok(child)
+}
+
+#[cfg(not(unix))]
+fn spawn_in_group(command: &mut Command, attached: bool) -> io::Result<Child> {
+    let output = || {
+        if attached {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        }
+    };
+    command
+        .stdin(Stdio::null())
+        .stdout(output())
+        .stderr(output())
+        .spawn()
+}
+
+/// Terminate every process in group `group`.
+#[cfg(unix)]
+fn kill_group(group: u32) {
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{group}")])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(not(unix))]
+fn kill_group(group: u32) {
+    let _ = Command::new("taskkill")
+        .args(["/F", "/T", "/PID", &group.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// Start a detached shell that polls until `parent` exits, then terminates process group
+/// `group`. It gives up as soon as the group is gone, so finished lints leave nothing behind.
+/// Polling survives SIGKILL of the hook, which no signal handler could.
+#[cfg(unix)]
+fn spawn_watchdog(parent: u32, group: u32) -> io::Result<()> {
+    use std::os::unix::process::CommandExt;
+
+    let script = format!(
+        "while kill -0 {parent} 2>/dev/null; do kill -0 -{group} 2>/dev/null || exit 0; sleep 1; done; \
+         kill -TERM -{group} 2>/dev/null; sleep 2; kill -KILL -{group} 2>/dev/null"
+    );
+    Command::new("sh")
+        .args(["-c", &script])
+        // Own group, so it outlives a kill aimed at the hook's group
+        .process_group(0)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::CommandExt;
+    use std::time::Instant;
+
+    fn args(command: &Command) -> Vec<String> {
+        std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|a| a.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn launchers_are_identified_by_the_tool_they_run() {
+        let identity = |program: &str, args: &[&str]| {
+            let mut command = Command::new(program);
+            command.args(args);
+            linter_identity(&command)
+        };
+        assert_eq!(
+            identity("/ws/node_modules/.bin/eslint", &["a.js"]),
+            "eslint"
+        );
+        assert_eq!(
+            identity("cargo", &["clippy", "--message-format=json"]),
+            "cargo clippy"
+        );
+        assert_eq!(identity("cargo", &["check"]), "cargo check");
+        assert_eq!(
+            identity("mvn", &["-q", "help:describe"]),
+            "mvn help:describe"
+        );
+        assert_eq!(identity("npm", &["run", "lint", "--", "a.js"]), "npm lint");
+        assert_eq!(
+            identity(
+                "sh",
+                &["-c", "node_modules/.bin/eslint \"$@\"", "sh", "a.js"]
+            ),
+            "sh eslint"
+        );
+        assert_eq!(identity("./gradlew", &[]), "gradlew");
+    }
+
+    #[test]
+    fn priority_from_config() {
+        let config = Config::parse("[process]\nnice = 40\nio_class = \"idle\"");
+        let priority = Priority::from_config(&config);
+        assert_eq!(priority.nice, Some(19));
+        let commands: Vec<Vec<String>> = priority.commands(42).iter().map(args).collect();
+        assert_eq!(
+            commands,
+            vec![
+                vec!["renice", "-n", "19", "-g", "42"],
+                vec!["ionice", "-c", "3", "-P", "42"]
+            ]
+        );
+        assert!(Priority::default().commands(42).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn renice_lowers_group_priority() {
+        // Field 19 of /proc/<pid>/stat is the nice value
+        let mut child = Command::new("sleep")
+            .arg("5")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let priority = Priority {
+            nice: Some(7),
+            io_class: None,
+        };
+        for mut command in priority.commands(child.id()) {
+            command.stdout(Stdio::null()).status().unwrap();
+        }
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", child.id())).unwrap();
+        let nice = stat.rsplit(')').next().unwrap().split_whitespace().nth(16);
+        child.kill().unwrap();
+        let _ = child.wait();
+        assert_eq!(nice, Some("7"));
+    }
+
+    #[test]
+    fn parse_stat_sums_cpu_ticks() {
+        let stat =
+            "4242 (my (odd) cmd) Z 1 4242 4242 0 -1 4194560 100 0 0 0 7 3 20 10 20 0 1 0 5 0 0";
+        assert_eq!(parse_stat(stat), Some(('Z', 40)));
+        assert_eq!(parse_stat("4242 (cmd) R 1"), None);
+    }
+
+    #[test]
+    fn output_grouped_accounts_cpu_time() {
+        take_cpu_time();
+        let output = Command::new("sh")
+            .args([
+                "-c",
+                "i=0; while [ $i -lt 200000 ]; do i=$((i+1)); done; echo done",
+            ])
+            .output_grouped()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "done\n");
+        if std::path::Path::new("/proc/self/stat").exists() {
+            assert!(take_cpu_time() > Duration::ZERO);
+        }
+        assert_eq!(take_cpu_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn output_grouped_captures_output_and_status() {
+        let output = Command::new("sh")
+            .args(["-c", "echo out; echo err >&2; exit 3"])
+            .output_grouped()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "out\n");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "err\n");
+        assert_eq!(output.status.code(), Some(3));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn child_leads_its_own_process_group() {
+        // Field 5 of /proc/<pid>/stat is the process group id
+        let output = Command::new("sh")
+            .args(["-c", "echo $$; cut -d' ' -f5 /proc/$$/stat"])
+            .output_grouped()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines();
+        assert_eq!(lines.next(), lines.next());
+    }
+
+    #[test]
+    fn spawn_detached_returns_without_waiting() {
+        let marker =
+            std::env::temp_dir().join(format!("ralph-lint-detached-{}", std::process::id()));
+        let _ = std::fs::remove_file(&marker);
+        let started = Instant::now();
+        Command::new("sh")
+            .args([
+                "-c",
+                &format!("sleep 0.2; echo done > '{}'", marker.display()),
+            ])
+            .spawn_detached()
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_millis(200));
+        while !marker.exists() && started.elapsed() < Duration::from_secs(10) {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(marker.exists(), "detached command should run to completion");
+        let _ = std::fs::remove_file(&marker);
+    }
+
+    #[test]
+    fn output_grouped_timeout_kills_slow_command() {
+        let started = Instant::now();
+        let output = Command::new("sh")
+            .args(["-c", "sleep 30 & wait"])
+            .output_grouped_timeout(Duration::from_millis(200))
+            .unwrap();
+        assert!(output.is_none());
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        let output = Command::new("echo")
+            .arg("fast")
+            .output_grouped_timeout(Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(output.map(|o| o.stdout), Some(b"fast\n".to_vec()));
+    }
+
+    #[test]
+    fn watchdog_kills_group_when_parent_exits() {
+        let mut parent = Command::new("sleep").arg("30").spawn().unwrap();
+        let mut linter = Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        spawn_watchdog(parent.id(), linter.id()).unwrap();
+
+        parent.kill().unwrap();
+        parent.wait().unwrap();
+
+        let started = Instant::now();
+        while linter.try_wait().unwrap().is_none() {
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "linter group should be killed after its parent exits"
+            );
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
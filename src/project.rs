@@ -1,6 +1,9 @@
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::config::Config;
+
 /// Project information for a detected language/ecosystem
 #[derive(Debug)]
 pub struct ProjectInfo {
@@ -9,6 +12,8 @@ pub struct ProjectInfo {
     /// Detected language/ecosystem (reserved for future use)
     #[allow(dead_code)]
     pub lang: Lang,
+    /// True if no project root was found and the file is checked on its own
+    pub standalone: bool,
 }
 
 /// Supported languages/ecosystems
@@ -19,31 +24,191 @@ pub enum Lang {
     Python,
     Java,
     Go,
+    /// C, C++, and Objective-C, including headers
+    C,
+    /// Kotlin scripts: Gradle Kotlin DSL (`build.gradle.kts`) and `*.main.kts`
+    Kotlin,
+    Php,
+    CSharp,
+    Swift,
+    /// sh, bash, and zsh scripts, by extension or, for files without one, by shebang
+    Shell,
+    /// Markdown and MDX documents
+    Markdown,
+    Elixir,
+    Haskell,
+    Zig,
+    Lua,
+    Scala,
+    /// PowerShell scripts and modules
+    PowerShell,
+    Nix,
+    Solidity,
+    /// Terraform and `OpenTofu` configuration
+    Terraform,
+}
+
+impl Lang {
+    /// The language's name in config keys such as `[rate_limit]`, as listed by `capabilities`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::JavaScript => "javascript",
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::Java => "java",
+            Self::Go => "go",
+            Self::C => "c",
+            Self::Kotlin => "kotlin",
+            Self::Php => "php",
+            Self::CSharp => "csharp",
+            Self::Swift => "swift",
+            Self::Shell => "shell",
+            Self::Markdown => "markdown",
+            Self::Elixir => "elixir",
+            Self::Haskell => "haskell",
+            Self::Zig => "zig",
+            Self::Lua => "lua",
+            Self::Scala => "scala",
+            Self::PowerShell => "powershell",
+            Self::Nix => "nix",
+            Self::Solidity => "solidity",
+            Self::Terraform => "terraform",
+        }
+    }
 }
 
 /// Detect language from file extension
 pub fn detect_lang(file_path: &str) -> Option<Lang> {
-    let js_extensions = [".js", ".jsx", ".ts", ".tsx", ".mjs", ".cjs"];
+    let js_extensions = [
+        ".js", ".jsx", ".ts", ".tsx", ".mjs", ".cjs", ".astro", ".marko", ".ejs", ".vue",
+    ];
     let rust_extensions = [".rs"];
     let python_extensions = [".py", ".pyi"];
     let java_extensions = [".java"];
     let go_extensions = [".go"];
+    let kotlin_extensions = [".kts"];
+    let php_extensions = [".php"];
+    let csharp_extensions = [".cs"];
+    let swift_extensions = [".swift"];
+    let shell_extensions = [".sh", ".bash", ".zsh"];
+    let markdown_extensions = [".md", ".mdx"];
+    let elixir_extensions = [".ex", ".exs"];
+    let haskell_extensions = [".hs"];
+    let zig_extensions = [".zig"];
+    let lua_extensions = [".lua"];
+    let scala_extensions = [".scala"];
+    let powershell_extensions = [".ps1", ".psm1"];
+    let nix_extensions = [".nix"];
+    let solidity_extensions = [".sol"];
+    let terraform_extensions = [".tf", ".tofu"];
+    let c_extensions = [
+        ".c", ".h", ".cc", ".cpp", ".cxx", ".c++", ".hh", ".hpp", ".hxx", ".m", ".mm",
+    ];
 
-    if js_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+    if js_extensions.iter().any(|ext| file_path.ends_with(ext))
+        || file_path.ends_with("/package.json")
+    {
         Some(Lang::JavaScript)
-    } else if rust_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+    } else if rust_extensions.iter().any(|ext| file_path.ends_with(ext))
+        || ["/Cargo.toml", "/Cargo.lock"]
+            .iter()
+            .any(|name| file_path.ends_with(name))
+    {
         Some(Lang::Rust)
     } else if python_extensions.iter().any(|ext| file_path.ends_with(ext)) {
         Some(Lang::Python)
     } else if java_extensions.iter().any(|ext| file_path.ends_with(ext)) {
         Some(Lang::Java)
-    } else if go_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+    } else if go_extensions.iter().any(|ext| file_path.ends_with(ext))
+        || ["/go.mod", "/go.sum"]
+            .iter()
+            .any(|name| file_path.ends_with(name))
+    {
         Some(Lang::Go)
+    } else if c_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+        Some(Lang::C)
+    } else if kotlin_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+        Some(Lang::Kotlin)
+    } else if php_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+        Some(Lang::Php)
+    } else if csharp_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+        Some(Lang::CSharp)
+    } else if swift_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+        Some(Lang::Swift)
+    } else if shell_extensions.iter().any(|ext| file_path.ends_with(ext))
+        || has_shell_shebang(file_path)
+    {
+        Some(Lang::Shell)
+    } else if markdown_extensions
+        .iter()
+        .any(|ext| file_path.ends_with(ext))
+    {
+        Some(Lang::Markdown)
+    } else if elixir_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+        Some(Lang::Elixir)
+    } else if haskell_extensions
+        .iter()
+        .any(|ext| file_path.ends_with(ext))
+    {
+        Some(Lang::Haskell)
+    } else if zig_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+        Some(Lang::Zig)
+    } else if lua_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+        Some(Lang::Lua)
+    } else if scala_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+        Some(Lang::Scala)
+    } else if powershell_extensions
+        .iter()
+        .any(|ext| file_path.ends_with(ext))
+    {
+        Some(Lang::PowerShell)
+    } else if nix_extensions.iter().any(|ext| file_path.ends_with(ext)) {
+        Some(Lang::Nix)
+    } else if solidity_extensions
+        .iter()
+        .any(|ext| file_path.ends_with(ext))
+    {
+        Some(Lang::Solidity)
+    } else if terraform_extensions
+        .iter()
+        .any(|ext| file_path.ends_with(ext))
+    {
+        Some(Lang::Terraform)
     } else {
         None
     }
 }
 
+/// Shells whose scripts are recognized by shebang.
+const SHELL_INTERPRETERS: [&str; 5] = ["sh", "bash", "dash", "ksh", "zsh"];
+
+/// Whether an extensionless file starts with a shell shebang such as `#!/bin/sh` or
+/// `#!/usr/bin/env bash`. Files with an extension are never read.
+fn has_shell_shebang(file_path: &str) -> bool {
+    let path = Path::new(file_path);
+    if path.extension().is_some() {
+        return false;
+    }
+    let Ok(contents) = fs::read(path) else {
+        return false;
+    };
+    let first_line = contents.split(|&b| b == b'\n').next().unwrap_or_default();
+    let Some(command) = String::from_utf8_lossy(first_line)
+        .strip_prefix("#!")
+        .map(str::to_string)
+    else {
+        return false;
+    };
+    let mut words = command.split_whitespace();
+    let interpreter = match words.next() {
+        Some(env) if env.ends_with("/env") => words.find(|w| !w.starts_with('-')),
+        other => other,
+    };
+    interpreter
+        .and_then(|i| i.rsplit('/').next())
+        .is_some_and(|name| SHELL_INTERPRETERS.contains(&name))
+}
+
 /// Find the nearest project root for the given file path.
 /// Returns None if no project root is found or file type is unsupported.
 pub fn find_project_root(file_path: &str) -> Option<ProjectInfo> {
@@ -52,13 +217,61 @@ pub fn find_project_root(file_path: &str) -> Option<ProjectInfo> {
         .parent()
         .map_or_else(|| ".".to_string(), |p| p.to_string_lossy().to_string());
 
-    match lang {
-        Lang::JavaScript => find_npm_root(&file_dir).map(|root| ProjectInfo { root, lang }),
-        Lang::Rust => find_cargo_root(&file_dir).map(|root| ProjectInfo { root, lang }),
-        Lang::Python => find_python_root(&file_dir).map(|root| ProjectInfo { root, lang }),
-        Lang::Java => find_java_root(&file_dir).map(|root| ProjectInfo { root, lang }),
-        Lang::Go => find_go_root(&file_dir).map(|root| ProjectInfo { root, lang }),
+    let root = match lang {
+        Lang::JavaScript => find_npm_root(&file_dir),
+        Lang::Rust => find_cargo_root(&file_dir),
+        Lang::Python => find_python_root(&file_dir),
+        // Build scripts belong to the Gradle project; loose scripts are checked on their own
+        Lang::Java | Lang::Kotlin => find_java_root(&file_dir),
+        Lang::Go => find_go_root(&file_dir),
+        Lang::C => find_c_root(&file_dir),
+        Lang::Php => find_composer_root(&file_dir),
+        Lang::CSharp => find_dotnet_root(&file_dir),
+        Lang::Swift => find_swift_root(&file_dir),
+        // Scripts have no project manifest: lint from the repository or the script's directory
+        Lang::Shell | Lang::PowerShell => {
+            find_repo_root(&file_dir).or_else(|| Some(file_dir.clone()))
+        }
+        // Documents: where markdownlint-cli2 is installed, else as for scripts
+        Lang::Markdown => find_npm_root(&file_dir)
+            .or_else(|| find_repo_root(&file_dir))
+            .or_else(|| Some(file_dir.clone())),
+        Lang::Elixir => find_mix_root(&file_dir),
+        Lang::Haskell => find_haskell_root(&file_dir),
+        Lang::Zig => find_zig_root(&file_dir),
+        // Where the linter config is, else the repository
+        Lang::Lua => find_lua_root(&file_dir).or_else(|| find_repo_root(&file_dir)),
+        Lang::Nix => find_nix_root(&file_dir)
+            .or_else(|| find_repo_root(&file_dir))
+            .or_else(|| Some(file_dir.clone())),
+        Lang::Scala => find_sbt_root(&file_dir),
+        Lang::Solidity => find_solidity_root(&file_dir),
+        // A Terraform module is the directory of its files
+        Lang::Terraform => Some(file_dir.clone()),
+    };
+
+    if let Some(root) = root {
+        return Some(ProjectInfo {
+            root,
+            lang,
+            standalone: false,
+        });
     }
+
+    // Scratch files outside any project get a best-effort single-file check unless disabled.
+    if Path::new(file_path).is_file()
+        && Config::load(&file_dir)
+            .get_bool("standalone.enabled")
+            .unwrap_or(true)
+    {
+        return Some(ProjectInfo {
+            root: file_dir,
+            lang,
+            standalone: true,
+        });
+    }
+
+    None
 }
 
 /// Find the nearest package.json directory using npm prefix
@@ -71,13 +284,32 @@ fn find_npm_root(dir: &str) -> Option<String> {
         .and_then(|o| {
             if o.status.success() {
                 let root = String::from_utf8_lossy(&o.stdout).trim().to_string();
-                if root.is_empty() { None } else { Some(root) }
+                // npm prefix falls back to the cwd when no package.json exists above it
+                if root.is_empty() || !Path::new(&root).join("package.json").exists() {
+                    None
+                } else {
+                    Some(root)
+                }
             } else {
                 None
             }
         })
 }
 
+/// Find the Cargo workspace root containing the crate at `crate_root`: the nearest Cargo.toml
+/// at or above it with a `[workspace]` table.
+pub fn find_cargo_workspace_root(crate_root: &str) -> Option<String> {
+    let mut current = Path::new(crate_root);
+    loop {
+        let is_workspace = fs::read_to_string(current.join("Cargo.toml"))
+            .is_ok_and(|text| text.lines().any(|line| line.trim() == "[workspace]"));
+        if is_workspace {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
 /// Find the nearest Cargo.toml directory by walking up the directory tree
 fn find_cargo_root(dir: &str) -> Option<String> {
     let mut current = Path::new(dir);
@@ -125,6 +357,86 @@ fn find_java_root(dir: &str) -> Option<String> {
     }
 }
 
+/// Find the nearest file named one of `names` at or above `dir`, e.g. a workspace-level
+/// clippy.toml shared by every crate.
+pub fn find_nearest_file(dir: &str, names: &[&str]) -> Option<PathBuf> {
+    let mut current = Path::new(dir);
+    loop {
+        for name in names {
+            let candidate = current.join(name);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the directory containing settings.gradle(.kts) at or above `project_root`.
+/// Gradle multi-project and composite builds must be invoked from there.
+pub fn find_gradle_settings_root(project_root: &str) -> Option<String> {
+    let markers = ["settings.gradle", "settings.gradle.kts"];
+    let mut current = Path::new(project_root);
+    loop {
+        for marker in &markers {
+            if current.join(marker).exists() {
+                return Some(current.to_string_lossy().to_string());
+            }
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the repository containing `path`: the nearest directory with a `.git` directory or
+/// file (worktrees and submodules use a file).
+pub fn find_repo_root(path: &str) -> Option<String> {
+    let mut current = Path::new(path);
+    loop {
+        if current.join(".git").exists() {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the Bloop workspace (a directory containing `.bloop/`) at or above `project_root`.
+pub fn find_bloop_workspace(project_root: &str) -> Option<String> {
+    let mut current = Path::new(project_root);
+    loop {
+        if current.join(".bloop").is_dir() {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the nearest PHP project root by walking up the directory tree
+/// Looks for composer.json
+fn find_composer_root(dir: &str) -> Option<String> {
+    let mut current = Path::new(dir);
+    loop {
+        if current.join("composer.json").exists() {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the nearest Swift project root by walking up the directory tree
+/// Looks for Package.swift or .swiftlint.yml
+fn find_swift_root(dir: &str) -> Option<String> {
+    let markers = ["Package.swift", ".swiftlint.yml"];
+    let mut current = Path::new(dir);
+    loop {
+        for marker in &markers {
+            if current.join(marker).exists() {
+                return Some(current.to_string_lossy().to_string());
+            }
+        }
+        current = current.parent()?;
+    }
+}
+
 /// Find the nearest Go project root by walking up the directory tree
 /// Looks for go.mod
 fn find_go_root(dir: &str) -> Option<String> {
@@ -137,6 +449,174 @@ fn find_go_root(dir: &str) -> Option<String> {
     }
 }
 
+/// Find the nearest Mix project root by walking up the directory tree
+/// Looks for mix.exs; in an umbrella that is the app's own directory
+fn find_mix_root(dir: &str) -> Option<String> {
+    let mut current = Path::new(dir);
+    loop {
+        if current.join("mix.exs").exists() {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the nearest Haskell package by walking up the directory tree
+/// Looks for a `.cabal` file, `package.yaml` (hpack), or `stack.yaml`
+fn find_haskell_root(dir: &str) -> Option<String> {
+    let mut current = Path::new(dir);
+    loop {
+        let has_cabal_file = fs::read_dir(current).is_ok_and(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .any(|entry| entry.path().extension().is_some_and(|ext| ext == "cabal"))
+        });
+        if has_cabal_file
+            || current.join("package.yaml").exists()
+            || current.join("stack.yaml").exists()
+        {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the nearest Zig project root (directory containing build.zig) by walking up the directory tree
+fn find_zig_root(dir: &str) -> Option<String> {
+    let mut current = Path::new(dir);
+    loop {
+        if current.join("build.zig").exists() {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the nearest directory with a Lua linter config (`.luacheckrc` or `selene.toml`)
+fn find_lua_root(dir: &str) -> Option<String> {
+    let mut current = Path::new(dir);
+    loop {
+        if current.join(".luacheckrc").exists() || current.join("selene.toml").exists() {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the nearest directory with a `flake.nix` or `default.nix`, the expression the edited
+/// file is most likely imported from
+fn find_nix_root(dir: &str) -> Option<String> {
+    let mut current = Path::new(dir);
+    loop {
+        if current.join("flake.nix").exists() || current.join("default.nix").exists() {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the nearest Foundry, Hardhat, or Truffle project for a Solidity file
+fn find_solidity_root(dir: &str) -> Option<String> {
+    let markers = [
+        "foundry.toml",
+        "hardhat.config.js",
+        "hardhat.config.ts",
+        "truffle-config.js",
+    ];
+    let mut current = Path::new(dir);
+    loop {
+        if markers.iter().any(|marker| current.join(marker).exists()) {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the sbt build for a Scala file: the outermost directory with a `build.sbt`, since
+/// subprojects' `build.sbt` files are loaded by the root build and sbt runs from there
+fn find_sbt_root(dir: &str) -> Option<String> {
+    let mut current = Some(Path::new(dir));
+    let mut root = None;
+    while let Some(dir) = current {
+        if dir.join("build.sbt").exists() {
+            root = Some(dir.to_string_lossy().to_string());
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+        current = dir.parent();
+    }
+    root
+}
+
+/// Find the nearest C/C++ project root by walking up the directory tree
+/// Looks for `compile_commands.json`, a build file (`CMake`, Make, Meson, Autoconf), or an
+/// Xcode project
+fn find_c_root(dir: &str) -> Option<String> {
+    let markers = [
+        "compile_commands.json",
+        "CMakeLists.txt",
+        "Makefile",
+        "makefile",
+        "GNUmakefile",
+        "meson.build",
+        "configure.ac",
+    ];
+    let mut current = Path::new(dir);
+    loop {
+        for marker in &markers {
+            if current.join(marker).exists() {
+                return Some(current.to_string_lossy().to_string());
+            }
+        }
+        if has_xcode_project(current) {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// Find the nearest .NET project root by walking up the directory tree
+/// Looks for a `.csproj` or `.sln` file
+fn find_dotnet_root(dir: &str) -> Option<String> {
+    let mut current = Path::new(dir);
+    loop {
+        if dotnet_workspace(current).is_some() {
+            return Some(current.to_string_lossy().to_string());
+        }
+        current = current.parent()?;
+    }
+}
+
+/// The project or solution file `dotnet` should load in `dir`: the first `.csproj` by name,
+/// else the first `.sln`.
+pub fn dotnet_workspace(dir: &Path) -> Option<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    ["csproj", "sln"].iter().find_map(|wanted| {
+        files
+            .iter()
+            .find(|path| path.extension().is_some_and(|ext| ext == *wanted))
+            .cloned()
+    })
+}
+
+fn has_xcode_project(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries.filter_map(Result::ok).any(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext == "xcodeproj" || ext == "xcworkspace")
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,6 +640,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_cargo_workspace_root_skips_plain_manifests() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-workspace-{}", std::process::id()));
+        fs::create_dir_all(dir.join("crates/a")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        fs::write(dir.join("crates/a/Cargo.toml"), "[package]\nname = \"a\"\n").unwrap();
+
+        let member = dir.join("crates/a");
+        assert_eq!(
+            find_cargo_workspace_root(&member.to_string_lossy()),
+            Some(dir.to_string_lossy().to_string())
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn find_project_root_nested() {
         let fixture_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -221,19 +720,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_project_root_standalone_rust_file() {
+        let dir =
+            std::env::temp_dir().join(format!("ralph-lint-standalone-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("scratch.rs");
+        std::fs::write(&file_path, "fn main() {}").unwrap();
+
+        let info = find_project_root(&file_path.to_string_lossy()).unwrap();
+        assert_eq!(info.lang, Lang::Rust);
+        assert!(info.standalone);
+        assert_eq!(info.root, dir.to_string_lossy());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn find_project_root_standalone_disabled_by_config() {
+        let dir =
+            std::env::temp_dir().join(format!("ralph-lint-standalone-off-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(crate::config::CONFIG_FILE),
+            "[standalone]\nenabled = false\n",
+        )
+        .unwrap();
+        let file_path = dir.join("scratch.py");
+        std::fs::write(&file_path, "print(1)\n").unwrap();
+
+        assert!(find_project_root(&file_path.to_string_lossy()).is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn find_project_root_missing_rust_file_is_none() {
+        assert!(find_project_root("/tmp/no-cargo/missing.rs").is_none());
+    }
+
     #[test]
     fn detect_lang_js() {
         assert_eq!(detect_lang("/path/to/file.js"), Some(Lang::JavaScript));
         assert_eq!(detect_lang("/path/to/file.ts"), Some(Lang::JavaScript));
         assert_eq!(detect_lang("/path/to/file.tsx"), Some(Lang::JavaScript));
+        assert_eq!(detect_lang("/path/to/Page.astro"), Some(Lang::JavaScript));
+        assert_eq!(detect_lang("/path/to/App.vue"), Some(Lang::JavaScript));
+        assert_eq!(detect_lang("/path/to/view.marko"), Some(Lang::JavaScript));
         assert_eq!(detect_lang("/path/to/file.jsx"), Some(Lang::JavaScript));
         assert_eq!(detect_lang("/path/to/file.mjs"), Some(Lang::JavaScript));
         assert_eq!(detect_lang("/path/to/file.cjs"), Some(Lang::JavaScript));
+        assert_eq!(detect_lang("/path/to/package.json"), Some(Lang::JavaScript));
+        assert_eq!(detect_lang("/path/to/tsconfig.json"), None);
     }
 
     #[test]
     fn detect_lang_rust() {
         assert_eq!(detect_lang("/path/to/file.rs"), Some(Lang::Rust));
+        assert_eq!(detect_lang("/path/to/Cargo.toml"), Some(Lang::Rust));
+        assert_eq!(detect_lang("/path/to/Cargo.lock"), Some(Lang::Rust));
+        assert_eq!(detect_lang("/path/to/pyproject.toml"), None);
     }
 
     #[test]
@@ -245,6 +791,251 @@ mod tests {
     #[test]
     fn detect_lang_go() {
         assert_eq!(detect_lang("/path/to/file.go"), Some(Lang::Go));
+        assert_eq!(detect_lang("/path/to/go.mod"), Some(Lang::Go));
+        assert_eq!(detect_lang("/path/to/go.sum"), Some(Lang::Go));
+    }
+
+    #[test]
+    fn detect_lang_c() {
+        assert_eq!(detect_lang("/path/to/file.c"), Some(Lang::C));
+        assert_eq!(detect_lang("/path/to/file.h"), Some(Lang::C));
+        assert_eq!(detect_lang("/path/to/file.cpp"), Some(Lang::C));
+        assert_eq!(detect_lang("/path/to/file.hpp"), Some(Lang::C));
+        assert_eq!(detect_lang("/path/to/file.m"), Some(Lang::C));
+        assert_eq!(detect_lang("/path/to/file.mm"), Some(Lang::C));
+    }
+
+    #[test]
+    fn detect_lang_kotlin_script() {
+        assert_eq!(detect_lang("/path/to/build.gradle.kts"), Some(Lang::Kotlin));
+        assert_eq!(
+            detect_lang("/path/to/settings.gradle.kts"),
+            Some(Lang::Kotlin)
+        );
+        assert_eq!(detect_lang("/path/to/deploy.main.kts"), Some(Lang::Kotlin));
+    }
+
+    #[test]
+    fn detect_lang_shell_by_extension_or_shebang() {
+        assert_eq!(detect_lang("/path/to/install.sh"), Some(Lang::Shell));
+        assert_eq!(detect_lang("/path/to/.zshrc.zsh"), Some(Lang::Shell));
+
+        let dir = std::env::temp_dir().join(format!("ralph-lint-shebang-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, shebang) in [
+            ("direct", "#!/bin/bash\n"),
+            ("env", "#!/usr/bin/env -S bash -e\n"),
+            ("python", "#!/usr/bin/env python3\n"),
+            ("plain", "echo hi\n"),
+        ] {
+            std::fs::write(dir.join(name), shebang).unwrap();
+        }
+        let lang = |name: &str| detect_lang(&dir.join(name).to_string_lossy());
+        assert_eq!(lang("direct"), Some(Lang::Shell));
+        assert_eq!(lang("env"), Some(Lang::Shell));
+        assert_eq!(lang("python"), None);
+        assert_eq!(lang("plain"), None);
+
+        // No repository above the script: it is its own root, not a standalone file
+        let info = find_project_root(&dir.join("direct").to_string_lossy()).unwrap();
+        assert!(!info.standalone);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn detect_lang_elixir_and_mix_root() {
+        assert_eq!(detect_lang("/ws/lib/app.ex"), Some(Lang::Elixir));
+        assert_eq!(detect_lang("/ws/test/app_test.exs"), Some(Lang::Elixir));
+
+        let dir = std::env::temp_dir().join(format!("ralph-lint-mix-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("apps/web/lib")).unwrap();
+        std::fs::write(dir.join("mix.exs"), "").unwrap();
+        std::fs::write(dir.join("apps/web/mix.exs"), "").unwrap();
+        let file = dir.join("apps/web/lib/web.ex");
+        std::fs::write(&file, "").unwrap();
+        let info = find_project_root(&file.to_string_lossy()).unwrap();
+        assert_eq!(info.root, dir.join("apps/web").to_string_lossy());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn detect_lang_haskell_and_package_root() {
+        assert_eq!(detect_lang("/ws/src/Main.hs"), Some(Lang::Haskell));
+
+        let dir = std::env::temp_dir().join(format!("ralph-lint-cabal-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("core/src/Data")).unwrap();
+        std::fs::write(dir.join("stack.yaml"), "").unwrap();
+        std::fs::write(dir.join("core/core.cabal"), "").unwrap();
+        let file = dir.join("core/src/Data/Tree.hs");
+        std::fs::write(&file, "").unwrap();
+        let info = find_project_root(&file.to_string_lossy()).unwrap();
+        assert_eq!(info.root, dir.join("core").to_string_lossy());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn detect_lang_zig_and_build_root() {
+        assert_eq!(detect_lang("/ws/src/main.zig"), Some(Lang::Zig));
+        // build.zig is itself Zig, and marks its own directory as the root
+        let dir = std::env::temp_dir().join(format!("ralph-lint-zig-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("build.zig"), "").unwrap();
+        let info = find_project_root(&dir.join("src/main.zig").to_string_lossy()).unwrap();
+        assert_eq!(info.root, dir.to_string_lossy());
+        let info = find_project_root(&dir.join("build.zig").to_string_lossy()).unwrap();
+        assert_eq!(info.root, dir.to_string_lossy());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn detect_lang_powershell() {
+        assert_eq!(detect_lang("/ws/build.ps1"), Some(Lang::PowerShell));
+        assert_eq!(detect_lang("/ws/Tools/Tools.psm1"), Some(Lang::PowerShell));
+        assert_eq!(detect_lang("/ws/Tools/Tools.psd1"), None);
+    }
+
+    #[test]
+    fn detect_lang_scala_and_sbt_root() {
+        assert_eq!(
+            detect_lang("/ws/src/main/scala/App.scala"),
+            Some(Lang::Scala)
+        );
+
+        let dir = std::env::temp_dir().join(format!("ralph-lint-sbt-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::create_dir_all(dir.join("core/src/main/scala")).unwrap();
+        std::fs::write(dir.join("build.sbt"), "").unwrap();
+        std::fs::write(dir.join("core/build.sbt"), "").unwrap();
+        let file = dir.join("core/src/main/scala/App.scala");
+        std::fs::write(&file, "").unwrap();
+        // A subproject's build.sbt is part of the root build
+        let info = find_project_root(&file.to_string_lossy()).unwrap();
+        assert_eq!(info.root, dir.to_string_lossy());
+        assert!(!info.standalone);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn detect_lang_terraform_and_module_root() {
+        assert_eq!(detect_lang("/ws/infra/main.tf"), Some(Lang::Terraform));
+        assert_eq!(detect_lang("/ws/infra/main.tofu"), Some(Lang::Terraform));
+        assert_eq!(detect_lang("/ws/infra/prod.tfvars"), None);
+        let info = find_project_root("/ws/infra/s3/main.tf").unwrap();
+        assert_eq!(info.root, "/ws/infra/s3");
+        assert!(!info.standalone);
+    }
+
+    #[test]
+    fn detect_lang_solidity_and_foundry_root() {
+        assert_eq!(detect_lang("/ws/src/Counter.sol"), Some(Lang::Solidity));
+
+        let dir = std::env::temp_dir().join(format!("ralph-lint-sol-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("contracts/tokens")).unwrap();
+        std::fs::write(dir.join("hardhat.config.ts"), "export default {};\n").unwrap();
+        let file = dir.join("contracts/tokens/Token.sol");
+        std::fs::write(&file, "").unwrap();
+        let info = find_project_root(&file.to_string_lossy()).unwrap();
+        assert_eq!(info.root, dir.to_string_lossy());
+        assert!(!info.standalone);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn detect_lang_nix_and_flake_root() {
+        assert_eq!(detect_lang("/ws/flake.nix"), Some(Lang::Nix));
+
+        let dir = std::env::temp_dir().join(format!("ralph-lint-nix-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::create_dir_all(dir.join("modules/services")).unwrap();
+        let file = dir.join("modules/services/web.nix");
+        std::fs::write(&file, "{ }\n").unwrap();
+        let file = file.to_string_lossy();
+        assert_eq!(
+            find_project_root(&file).unwrap().root,
+            dir.to_string_lossy()
+        );
+        std::fs::write(dir.join("modules/default.nix"), "{ }\n").unwrap();
+        assert_eq!(
+            find_project_root(&file).unwrap().root,
+            dir.join("modules").to_string_lossy()
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn detect_lang_lua_and_config_root() {
+        assert_eq!(detect_lang("/ws/init.lua"), Some(Lang::Lua));
+
+        let dir = std::env::temp_dir().join(format!("ralph-lint-lua-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::create_dir_all(dir.join("nvim/lua/plugins")).unwrap();
+        let file = dir.join("nvim/lua/plugins/init.lua");
+        std::fs::write(&file, "").unwrap();
+        let file = file.to_string_lossy();
+        assert_eq!(
+            find_project_root(&file).unwrap().root,
+            dir.to_string_lossy()
+        );
+        std::fs::write(dir.join("nvim/selene.toml"), "std = \"lua51\"\n").unwrap();
+        assert_eq!(
+            find_project_root(&file).unwrap().root,
+            dir.join("nvim").to_string_lossy()
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn detect_lang_markdown() {
+        assert_eq!(detect_lang("/ws/README.md"), Some(Lang::Markdown));
+        assert_eq!(detect_lang("/ws/docs/intro.mdx"), Some(Lang::Markdown));
+        assert_eq!(detect_lang("/ws/notes.txt"), None);
+    }
+
+    #[test]
+    fn find_project_root_for_xcode_project() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-xcode-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("App.xcodeproj")).unwrap();
+        std::fs::create_dir_all(dir.join("App/Views")).unwrap();
+        let file = dir.join("App/Views/ViewController.m");
+        std::fs::write(&file, "").unwrap();
+
+        let info = find_project_root(&file.to_string_lossy()).expect("Expected Xcode project root");
+        assert_eq!(info.lang, Lang::C);
+        assert_eq!(info.root, dir.to_string_lossy());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn find_project_root_for_c_file() {
+        let fixture_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/c/project");
+
+        let info = find_project_root(&fixture_dir.join("src/main.c").to_string_lossy())
+            .expect("Expected to find project root");
+        assert_eq!(info.lang, Lang::C);
+        assert!(!info.standalone);
+        assert!(info.root.ends_with("c/project"), "got: {}", info.root);
+    }
+
+    #[test]
+    fn find_repo_root_walks_up_to_git() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-repo-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::create_dir_all(dir.join("sub/module")).unwrap();
+        std::fs::write(
+            dir.join("sub/module/.git"),
+            "gitdir: ../../.git/modules/m\n",
+        )
+        .unwrap();
+
+        let repo = find_repo_root(&dir.join("src/main.rs").to_string_lossy());
+        assert_eq!(repo.as_deref(), Some(dir.to_string_lossy().as_ref()));
+        let submodule = find_repo_root(&dir.join("sub/module/lib.rs").to_string_lossy());
+        assert_eq!(
+            submodule.as_deref(),
+            Some(dir.join("sub/module").to_string_lossy().as_ref())
+        );
+        let _ = std::fs::remove_dir_all(&dir);
     }
 
     #[test]
@@ -332,6 +1123,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_gradle_settings_root_for_subproject() {
+        let fixture_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/java/gradle-multi");
+
+        let file_path = fixture_dir.join("lib/src/main/java/Lib.java");
+        let info = find_project_root(&file_path.to_string_lossy()).unwrap();
+        assert!(
+            info.root.ends_with("lib"),
+            "Expected lib subproject, got: {}",
+            info.root
+        );
+
+        let settings_root = find_gradle_settings_root(&info.root).unwrap();
+        assert!(
+            settings_root.ends_with("gradle-multi"),
+            "Expected settings root, got: {settings_root}"
+        );
+    }
+
+    #[test]
+    fn find_nearest_file_walks_up_to_workspace() {
+        let fixture_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/rust/monorepo/crates/app");
+
+        let found = find_nearest_file(
+            &fixture_dir.to_string_lossy(),
+            &["clippy.toml", ".clippy.toml"],
+        )
+        .unwrap();
+        assert!(
+            found.ends_with("monorepo/clippy.toml"),
+            "Expected workspace clippy.toml, got: {}",
+            found.display()
+        );
+        assert!(
+            find_nearest_file(&fixture_dir.to_string_lossy(), &["no-such-file.toml"]).is_none()
+        );
+    }
+
     #[test]
     fn find_project_root_for_go_file() {
         let fixture_dir =
@@ -350,6 +1181,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_project_root_for_php_file() {
+        let fixture_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/php/project");
+
+        let info = find_project_root(&fixture_dir.join("src/App.php").to_string_lossy()).unwrap();
+        assert_eq!(info.lang, Lang::Php);
+        assert!(info.root.ends_with("project"), "got: {}", info.root);
+        assert!(!info.standalone);
+    }
+
+    #[test]
+    fn find_project_root_for_csharp_file() {
+        let fixture_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/csharp/project");
+
+        let info =
+            find_project_root(&fixture_dir.join("Services/Greeter.cs").to_string_lossy()).unwrap();
+        assert_eq!(info.lang, Lang::CSharp);
+        assert!(info.root.ends_with("project"), "got: {}", info.root);
+        assert_eq!(
+            dotnet_workspace(&fixture_dir),
+            Some(fixture_dir.join("App.csproj"))
+        );
+    }
+
+    #[test]
+    fn find_project_root_for_swift_file() {
+        let fixture_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/swift/package");
+
+        let info = find_project_root(
+            &fixture_dir
+                .join("Sources/Greeter/Greeter.swift")
+                .to_string_lossy(),
+        )
+        .unwrap();
+        assert_eq!(info.lang, Lang::Swift);
+        assert!(info.root.ends_with("package"), "got: {}", info.root);
+    }
+
     #[test]
     fn find_project_root_go_monorepo() {
         let fixture_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -0,0 +1,115 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::origin;
+
+/// Start of the note shown when a quarantined linter is skipped
+pub const NOTE_PREFIX: &str = "[ralph-hook-lint] quarantined";
+
+/// Failures after which a linter is skipped for the rest of the session, unless
+/// `[process] quarantine_after` says otherwise
+pub const DEFAULT_AFTER: usize = 3;
+
+/// Failure log for a session: `<temp_dir>/ralph-lint-<session_id>-failures.txt`, one linter
+/// identity, such as `ruff` or `cargo clippy`, per failure.
+pub fn failures_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-failures.txt"))
+}
+
+/// Note that `linter` crashed, timed out, or exited with an error code (as opposed to
+/// reporting issues) in the current session.
+pub fn record_failure(linter: &str) {
+    let Some(session_id) = origin::session() else {
+        return;
+    };
+    if let Ok(mut file) = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(failures_path(session_id))
+    {
+        let _ = writeln!(file, "{linter}");
+    }
+}
+
+/// How often `linter` failed in the current session.
+pub fn failures(linter: &str) -> usize {
+    origin::session()
+        .and_then(|session_id| fs::read_to_string(failures_path(session_id)).ok())
+        .map_or(0, |log| log.lines().filter(|line| *line == linter).count())
+}
+
+/// The error a quarantined linter's command fails with, or `None` while it may still run.
+/// `after` of 0 disables quarantine.
+pub fn check(linter: &str, after: usize) -> Option<Quarantined> {
+    let failures = failures(linter);
+    (after > 0 && failures >= after).then(|| Quarantined {
+        linter: linter.to_string(),
+        failures,
+    })
+}
+
+/// A linter skipped because it failed repeatedly in this session.
+#[derive(Debug)]
+pub struct Quarantined {
+    pub linter: String,
+    pub failures: usize,
+}
+
+impl Quarantined {
+    /// The quarantine behind `error`: the error itself, or the I/O error a linter's spawn
+    /// failed with.
+    pub fn find<'a>(error: &'a (dyn std::error::Error + 'static)) -> Option<&'a Self> {
+        error.downcast_ref::<Self>().or_else(|| {
+            error
+                .downcast_ref::<std::io::Error>()?
+                .get_ref()?
+                .downcast_ref::<Self>()
+        })
+    }
+}
+
+impl std::fmt::Display for Quarantined {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{NOTE_PREFIX} {}: it crashed, timed out or errored {} times this session, so it is skipped until the session ends. Check the linter's installation and config.",
+            self.linter, self.failures
+        )
+    }
+}
+
+impl std::error::Error for Quarantined {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quarantine_counts_failures_per_linter() {
+        origin::set_session(&format!("test-quarantine-{}", std::process::id()));
+        let session_id = origin::session().unwrap();
+        let _ = fs::remove_file(failures_path(session_id));
+
+        record_failure("ruff");
+        record_failure("eslint");
+        record_failure("ruff");
+        assert_eq!(failures("ruff"), 2);
+        assert!(check("ruff", 3).is_none());
+        record_failure("ruff");
+        let quarantined = check("ruff", 3).unwrap();
+        assert!(quarantined.to_string().starts_with(NOTE_PREFIX));
+        assert!(check("ruff", 0).is_none());
+        assert!(check("eslint", 3).is_none());
+
+        let spawn_error: Box<dyn std::error::Error> = std::io::Error::other(quarantined).into();
+        assert_eq!(
+            Quarantined::find(spawn_error.as_ref()).map(|q| q.failures),
+            Some(3)
+        );
+        let other: Box<dyn std::error::Error> = std::io::Error::other("not found").into();
+        assert!(Quarantined::find(other.as_ref()).is_none());
+
+        let _ = fs::remove_file(failures_path(session_id));
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::collect;
+use crate::config::Config;
+use crate::project::{Lang, ProjectInfo};
+use crate::skip::SkipReason;
+use crate::tier;
+
+/// The `[rate_limit]` interval for a project's language: the least time between two of its
+/// lints in a session, such as `java = 300` for one Maven or Gradle run every five minutes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    lang: &'static str,
+    interval: Duration,
+}
+
+/// Note added to a verdict reused while the project is rate limited.
+struct Reused {
+    limit: RateLimit,
+    age: Duration,
+}
+
+impl fmt::Display for Reused {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[ralph-hook-lint] rate limited: reusing the verdict of the {} lint {}s ago for these contents ([rate_limit] {} = {}).",
+            self.limit.lang,
+            self.age.as_secs(),
+            self.limit.lang,
+            self.limit.interval.as_secs()
+        )
+    }
+}
+
+/// The rate limit for `project`, if its config sets one for the language. Standalone files
+/// get a single-file check, which is never limited.
+pub fn for_project(project: &ProjectInfo) -> Option<RateLimit> {
+    if project.standalone {
+        return None;
+    }
+    from_config(&Config::load(&project.root), project.lang)
+}
+
+fn from_config(config: &Config, lang: Lang) -> Option<RateLimit> {
+    let lang = lang.name();
+    let secs = config
+        .get_int(&format!("rate_limit.{lang}"))
+        .and_then(|secs| u64::try_from(secs).ok())
+        .filter(|secs| *secs > 0)?;
+    Some(RateLimit {
+        lang,
+        interval: Duration::from_secs(secs),
+    })
+}
+
+/// Lints of rate-limited projects in a session:
+/// `<temp_dir>/ralph-lint-<session_id>-rate-limit.txt`, one `<unix secs>\t<project root>\t
+/// <content hash>\t<file>\t<response>` line per file.
+pub fn state_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-rate-limit.txt"))
+}
+
+struct Entry {
+    at: u64,
+    root: String,
+    hash: u64,
+    file: String,
+    response: String,
+}
+
+fn read_entries(session_id: &str) -> Vec<Entry> {
+    fs::read_to_string(state_path(session_id))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(5, '\t');
+            Some(Entry {
+                at: fields.next()?.parse().ok()?,
+                root: fields.next()?.to_string(),
+                hash: fields.next()?.parse().ok()?,
+                file: fields.next()?.to_string(),
+                response: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+impl RateLimit {
+    /// The response to give instead of linting `file_path` while the project's last lint in
+    /// the session is more recent than the interval: the verdict it gave for the file's
+    /// current contents, else a skip. `None` when the project may be linted.
+    pub fn check(
+        &self,
+        session_id: &str,
+        project_root: &str,
+        file_path: &str,
+        debug: bool,
+    ) -> Option<String> {
+        let entries = read_entries(session_id);
+        let last = entries
+            .iter()
+            .filter(|e| e.root == project_root)
+            .map(|e| e.at)
+            .max()?;
+        let age = Duration::from_secs(now_secs().saturating_sub(last));
+        if age >= self.interval {
+            return None;
+        }
+        let hash = collect::content_hash(file_path);
+        if let Some(entry) = entries
+            .iter()
+            .find(|e| e.file == file_path && Some(e.hash) == hash)
+        {
+            let reused = Reused {
+                limit: *self,
+                age: Duration::from_secs(now_secs().saturating_sub(entry.at)),
+            };
+            return Some(tier::annotate(&entry.response, &reused));
+        }
+        Some(SkipReason::RateLimited.result(
+            debug,
+            &format!(
+                "{} was last linted {}s ago, under [rate_limit] {} = {}; {file_path} is linted on the next edit after that, or by lint-collected.",
+                project_root,
+                age.as_secs(),
+                self.lang,
+                self.interval.as_secs()
+            ),
+        ))
+    }
+}
+
+/// Record a lint of `file_path` and its response, starting the project's interval.
+pub fn record(
+    session_id: &str,
+    project_root: &str,
+    file_path: &str,
+    response: &str,
+) -> std::io::Result<()> {
+    let Some(hash) = collect::content_hash(file_path) else {
+        return Ok(());
+    };
+    let mut entries = read_entries(session_id);
+    entries.retain(|e| e.file != file_path);
+    entries.push(Entry {
+        at: now_secs(),
+        root: project_root.to_string(),
+        hash,
+        file: file_path.to_string(),
+        response: response.replace('\n', " "),
+    });
+    let mut file = fs::File::create(state_path(session_id))?;
+    for e in &entries {
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}",
+            e.at, e.root, e.hash, e.file, e.response
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_only_configured_languages() {
+        let config = Config::parse("[rate_limit]\njava = 300\ngo = 0\n");
+        assert_eq!(
+            from_config(&config, Lang::Java),
+            Some(RateLimit {
+                lang: "java",
+                interval: Duration::from_secs(300)
+            })
+        );
+        assert_eq!(from_config(&config, Lang::Go), None);
+        assert_eq!(from_config(&config, Lang::Rust), None);
+    }
+
+    #[test]
+    fn reuses_the_verdict_for_unchanged_contents() {
+        let sid = format!("rate-limit-test-{}", std::process::id());
+        let dir = std::env::temp_dir().join(&sid);
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("App.java");
+        let other = dir.join("Other.java");
+        fs::write(&file, "class App {}").unwrap();
+        fs::write(&other, "class Other {}").unwrap();
+        let (file, other) = (file.to_string_lossy(), other.to_string_lossy());
+        let root = dir.to_string_lossy();
+        let limit = RateLimit {
+            lang: "java",
+            interval: Duration::from_secs(300),
+        };
+
+        assert_eq!(limit.check(&sid, &root, &file, false), None);
+        record(&sid, &root, &file, r#"{"decision":"block","reason":"bad"}"#).unwrap();
+        let reused = limit.check(&sid, &root, &file, false).unwrap();
+        assert!(reused.contains("bad") && reused.contains("rate limited"));
+        // Another file of the project waits for the interval
+        assert_eq!(
+            limit.check(&sid, &root, &other, false).as_deref(),
+            Some(r#"{"continue":true}"#)
+        );
+        // As does the file once it changes
+        fs::write(&*file, "class App { }").unwrap();
+        assert!(
+            !limit
+                .check(&sid, &root, &file, false)
+                .unwrap()
+                .contains("bad")
+        );
+        // Other projects aren't limited
+        assert_eq!(limit.check(&sid, "/elsewhere", &file, false), None);
+
+        let _ = fs::remove_file(state_path(&sid));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
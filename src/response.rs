@@ -0,0 +1,599 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::extract::extract_reason_field;
+use crate::json::{self, Json};
+use crate::lint::escape_json;
+use crate::quarantine;
+use crate::sandbox;
+use crate::tier;
+
+const PASS_PREFIX: &str = "[ralph-hook-lint] lint passed for ";
+/// Prefix of the `--lint-collected` note listing files skipped by the time budget
+pub const BUDGET_NOTE_PREFIX: &str = "[ralph-hook-lint] time budget";
+/// Prefix of the `--lint-collected` summary of a clean run, with `[lint_collected] summary`
+pub const SUMMARY_NOTE_PREFIX: &str = "[ralph-hook-lint] verified:";
+/// Diagnostics listed per file in a block reason unless `[output] max_diagnostics_per_file`
+/// says otherwise
+pub const DEFAULT_MAX_DIAGNOSTICS_PER_FILE: usize = 20;
+
+/// How responses are shaped, from the `[output]` config section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputPolicy {
+    /// Add `suppressOutput: true` so continue responses stay out of the transcript
+    pub suppress_output: bool,
+    /// Report successful lints with a compact `systemMessage` even without `--debug`
+    pub success_message: bool,
+    /// Diagnostics listed per file in a block reason; 0 lists them all
+    pub max_diagnostics_per_file: usize,
+    /// Shorten paths in block reasons: relative to the working directory, or `~/` under home
+    pub relative_paths: bool,
+    /// What to answer when the hook itself fails
+    pub on_error: OnError,
+}
+
+/// The response to an internal error, from `[output] on_error`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnError {
+    /// Continue, so a broken hook never holds up the agent (`fail-open`)
+    #[default]
+    Open,
+    /// Block (`fail-closed`)
+    Closed,
+    /// Continue until the hook has failed this many times in a row, then block
+    /// (`fail-closed-after-N`)
+    ClosedAfter(u32),
+}
+
+impl OnError {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "fail-open" => Some(Self::Open),
+            "fail-closed" => Some(Self::Closed),
+            _ => value
+                .strip_prefix("fail-closed-after-")
+                .and_then(|n| n.parse().ok())
+                .filter(|n| *n > 0)
+                .map(Self::ClosedAfter),
+        }
+    }
+}
+
+impl std::fmt::Display for OnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Open => write!(f, "fail-open"),
+            Self::Closed => write!(f, "fail-closed"),
+            Self::ClosedAfter(n) => write!(f, "fail-closed-after-{n}"),
+        }
+    }
+}
+
+/// Counts the hook's failures in a row for a session (or for payloads without one):
+/// `<temp_dir>/ralph-lint-<session_id>-hook-errors.txt`
+fn hook_errors_path(session_id: Option<&str>) -> std::path::PathBuf {
+    std::env::temp_dir().join(session_id.map_or_else(
+        || "ralph-lint-hook-errors.txt".to_string(),
+        |sid| format!("ralph-lint-{sid}-hook-errors.txt"),
+    ))
+}
+
+impl Default for OutputPolicy {
+    fn default() -> Self {
+        Self {
+            suppress_output: false,
+            success_message: false,
+            max_diagnostics_per_file: DEFAULT_MAX_DIAGNOSTICS_PER_FILE,
+            relative_paths: true,
+            on_error: OnError::Open,
+        }
+    }
+}
+
+impl OutputPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            suppress_output: config.get_bool("output.suppress_output").unwrap_or(false),
+            success_message: config.get_bool("output.success_message").unwrap_or(false),
+            max_diagnostics_per_file: config
+                .get_int("output.max_diagnostics_per_file")
+                .and_then(|max| usize::try_from(max).ok())
+                .unwrap_or(DEFAULT_MAX_DIAGNOSTICS_PER_FILE),
+            relative_paths: config.get_bool("output.relative_paths").unwrap_or(true),
+            on_error: config
+                .get_str("output.on_error")
+                .and_then(OnError::parse)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The response when the hook fails with `error`. Fail-open continues; fail-closed blocks,
+    /// or does once the hook has failed the configured number of times in a row for the session.
+    pub fn error_response(self, session_id: Option<&str>, error: &str, debug: bool) -> String {
+        let message = format!("[ralph-hook-lint] lint hook error: {error}");
+        let path = hook_errors_path(session_id);
+        let failures = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|count| count.trim().parse::<u32>().ok())
+            .unwrap_or(0)
+            + 1;
+        let _ = std::fs::write(&path, failures.to_string());
+        let blocks = match self.on_error {
+            OnError::Open => false,
+            OnError::Closed => true,
+            OnError::ClosedAfter(n) => failures >= n,
+        };
+        if !blocks {
+            return crate::lint::continue_result(debug, &message);
+        }
+        format!(
+            r#"{{"decision":"block","reason":"{}"}}"#,
+            escape_json(&format!(
+                "{message}\n\nThe lint hook has failed {failures} time(s) in a row, and [output] on_error = \"{}\" blocks until it works again. Fix the hook's setup, or set on_error = \"fail-open\" to let edits through.",
+                self.on_error
+            ))
+        )
+    }
+
+    /// Reset the failure count after the hook handled an event.
+    pub fn record_success(session_id: Option<&str>) {
+        let _ = std::fs::remove_file(hook_errors_path(session_id));
+    }
+
+    /// Whether lint functions need to produce messages: success messages are only built
+    /// in verbose mode and trimmed by `apply`.
+    pub const fn verbose(self, debug: bool) -> bool {
+        debug || self.success_message
+    }
+
+    /// Rewrite a response for this policy: continue responses get the configured message and
+    /// flags, and block reasons get short paths and at most `max_diagnostics_per_file` issues
+    /// per file.
+    pub fn apply(self, output: String, debug: bool) -> String {
+        if output.starts_with(r#"{"decision":"block""#) {
+            return self.rewrite_block(output);
+        }
+        if !output.starts_with(r#"{"continue":true"#) {
+            return output;
+        }
+        let message = json::parse(&output).and_then(|json| {
+            json.get("systemMessage")
+                .and_then(Json::as_str)
+                .map(str::to_string)
+        });
+        let message = if debug {
+            message
+        } else if self.success_message {
+            message.as_deref().and_then(compact_success_message)
+        } else {
+            // Notices about unchecked files are shown even without --debug
+            message.filter(|m| {
+                m.starts_with(BUDGET_NOTE_PREFIX)
+                    || m.starts_with(SUMMARY_NOTE_PREFIX)
+                    || m.starts_with(quarantine::NOTE_PREFIX)
+                    || m.starts_with(tier::NOTE_PREFIX)
+                    || m.starts_with(sandbox::NOTE_PREFIX)
+            })
+        };
+
+        let mut result = r#"{"continue":true"#.to_string();
+        if self.suppress_output {
+            result.push_str(r#","suppressOutput":true"#);
+        }
+        if let Some(message) = message {
+            let _ = write!(result, r#","systemMessage":"{}""#, escape_json(&message));
+        }
+        result.push('}');
+        result
+    }
+}
+
+impl OutputPolicy {
+    /// Sort the diagnostics of a block reason, shorten its paths, and cap each file's
+    /// diagnostics, so one file with hundreds of issues doesn't drown out the others in a
+    /// `--lint-collected` report.
+    fn rewrite_block(self, output: String) -> String {
+        let Some(reason) = extract_reason_field(&output) else {
+            return output;
+        };
+        let mut rewritten = sort_issues(&reason);
+        if self.relative_paths {
+            let cwd = std::env::current_dir().ok();
+            let home = std::env::var_os("HOME").map(std::path::PathBuf::from);
+            rewritten = shorten_paths(&rewritten, cwd.as_deref(), home.as_deref());
+        }
+        if self.max_diagnostics_per_file > 0 {
+            rewritten = cap_per_file(&rewritten, self.max_diagnostics_per_file);
+        }
+        if rewritten == reason {
+            return output;
+        }
+        format!(
+            r#"{{"decision":"block","reason":"{}"}}"#,
+            escape_json(&rewritten)
+        )
+    }
+}
+
+/// Make paths under `cwd` relative to it, and other paths under `home` start with `~/`, so
+/// reasons stay short and don't put the user's name in the transcript.
+fn shorten_paths(reason: &str, cwd: Option<&Path>, home: Option<&Path>) -> String {
+    let prefix = |dir: &Path| {
+        let dir = dir.to_string_lossy();
+        (dir.len() > 1).then(|| format!("{}/", dir.trim_end_matches('/')))
+    };
+    let mut shortened = reason.to_string();
+    if let Some(cwd) = cwd.and_then(prefix) {
+        shortened = shortened.replace(&cwd, "");
+    }
+    if let Some(home) = home.and_then(prefix) {
+        shortened = shortened.replace(&home, "~/");
+    }
+    shortened
+}
+
+/// Keep the first `max` diagnostics of each file in `reason`, with a `+N more in this file`
+/// line where the rest were. A diagnostic is a `<file>:<line>:` line plus any lines that
+/// follow it up to the next diagnostic or a blank line, such as clippy's source snippets.
+fn cap_per_file(reason: &str, max: usize) -> String {
+    let mut kept: Vec<String> = Vec::new();
+    // Per file: diagnostics seen, and where the `+N more` line goes
+    let mut files: Vec<(&str, usize, Option<usize>)> = Vec::new();
+    let mut dropping = false;
+    for line in reason.lines() {
+        if line.trim().is_empty() {
+            dropping = false;
+        } else if let Some(file) = issue_file(line) {
+            let index = files
+                .iter()
+                .position(|(f, ..)| *f == file)
+                .unwrap_or_else(|| {
+                    files.push((file, 0, None));
+                    files.len() - 1
+                });
+            let (_, seen, note) = &mut files[index];
+            *seen += 1;
+            dropping = *seen > max;
+            if dropping && note.is_none() {
+                *note = Some(kept.len());
+                kept.push(String::new());
+            }
+        }
+        if !dropping {
+            kept.push(line.to_string());
+        }
+    }
+    for (_, seen, note) in files {
+        if let Some(index) = note {
+            kept[index] = format!("+{} more in this file", seen - max);
+        }
+    }
+    kept.join("\n")
+}
+
+/// Order the diagnostics of plain-text linter output by file, line, and column, as
+/// [`crate::diagnostic::format_diagnostics`] does for parsed ones, so repeated runs give
+/// identical reasons. A diagnostic keeps the lines that follow it, as in [`cap_per_file`];
+/// diagnostics are only reordered within a paragraph, and ties keep the linter's order.
+fn sort_issues(reason: &str) -> String {
+    type Record<'a> = ((&'a str, u32, u32), Vec<&'a str>);
+    fn flush<'a>(records: &mut Vec<Record<'a>>, sorted: &mut Vec<&'a str>) {
+        records.sort_by_key(|(location, _)| *location);
+        sorted.extend(records.drain(..).flat_map(|(_, lines)| lines));
+    }
+
+    let mut sorted: Vec<&str> = Vec::new();
+    // The current paragraph's diagnostics, each with its location and lines
+    let mut records: Vec<Record> = Vec::new();
+    for line in reason.lines() {
+        if line.trim().is_empty() {
+            flush(&mut records, &mut sorted);
+            sorted.push(line);
+        } else if let Some(location) = issue_location(line) {
+            records.push((location, vec![line]));
+        } else if let Some((_, lines)) = records.last_mut() {
+            lines.push(line);
+        } else {
+            sorted.push(line);
+        }
+    }
+    flush(&mut records, &mut sorted);
+    let mut result = sorted.join("\n");
+    if reason.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// The file, line, and column (0 when missing) of a `<file>:<line>:[<col>:]` diagnostic line.
+fn issue_location(line: &str) -> Option<(&str, u32, u32)> {
+    let file = issue_file(line)?;
+    let rest = &line.trim_start()[file.len() + 1..];
+    let (row, rest) = leading_number(rest)?;
+    let column = rest
+        .strip_prefix(':')
+        .and_then(leading_number)
+        .filter(|(_, after)| after.starts_with(':'))
+        .map_or(0, |(column, _)| column);
+    Some((file, row, column))
+}
+
+/// The number `text` starts with, and the rest of it.
+fn leading_number(text: &str) -> Option<(u32, &str)> {
+    let digits = text.find(|c: char| !c.is_ascii_digit())?;
+    Some((text[..digits].parse().ok()?, &text[digits..]))
+}
+
+/// The file of a `<file>:<line>:` diagnostic line.
+fn issue_file(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    line.match_indices(':').find_map(|(index, _)| {
+        let file = &line[..index];
+        if file.is_empty() || file.contains(": ") {
+            return None;
+        }
+        let rest = &line[index + 1..];
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        (digits > 0 && rest[digits..].starts_with(':')).then_some(file)
+    })
+}
+
+/// The linter named by a pass response such as `lint passed for src/a.rs using clippy.`, or
+/// `None` for blocks, skips, and responses without a message.
+pub fn passing_linter(output: &str) -> Option<String> {
+    let response = json::parse(output)?;
+    let message = response.get("systemMessage").and_then(Json::as_str)?;
+    let rest = message.lines().next()?.strip_prefix(PASS_PREFIX)?;
+    let (_, linter) = rest.trim_end_matches('.').rsplit_once(" using ")?;
+    Some(linter.to_string())
+}
+
+/// Shorten a pass message such as `lint passed for src/a.rs using clippy.` to
+/// `clippy clean: src/a.rs`. Skip and diagnostic messages return `None`.
+fn compact_success_message(message: &str) -> Option<String> {
+    if message.contains("collected file(s) passed lint")
+        || message.starts_with(BUDGET_NOTE_PREFIX)
+        || message.starts_with(SUMMARY_NOTE_PREFIX)
+    {
+        return Some(message.to_string());
+    }
+    let rest = message.strip_prefix(PASS_PREFIX)?.trim_end_matches('.');
+    let (label, linter) = rest.rsplit_once(" using ")?;
+    Some(format!("[ralph-hook-lint] {linter} clean: {label}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASS: &str = r#"{"continue":true,"systemMessage":"[ralph-hook-lint] lint passed for src/a.rs using clippy."}"#;
+    const SKIP: &str =
+        r#"{"continue":true,"systemMessage":"[ralph-hook-lint] no linter found for a.ts."}"#;
+
+    #[test]
+    fn default_policy_matches_debug_flag() {
+        let policy = OutputPolicy::default();
+        assert_eq!(policy.apply(PASS.to_string(), true), PASS);
+        assert_eq!(
+            policy.apply(PASS.to_string(), false),
+            r#"{"continue":true}"#
+        );
+        assert!(!policy.verbose(false));
+    }
+
+    #[test]
+    fn passing_linter_is_read_from_pass_messages_only() {
+        assert_eq!(passing_linter(PASS).as_deref(), Some("clippy"));
+        assert_eq!(
+            passing_linter(
+                r#"{"continue":true,"systemMessage":"[ralph-hook-lint] lint passed for 2 files using clippy --workspace.\n[ralph-hook-lint] fast tier"}"#
+            )
+            .as_deref(),
+            Some("clippy --workspace")
+        );
+        assert_eq!(passing_linter(SKIP), None);
+        assert_eq!(passing_linter(r#"{"continue":true}"#), None);
+        assert_eq!(
+            passing_linter(r#"{"decision":"block","reason":"lint errors"}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn success_message_is_compact_and_skips_are_dropped() {
+        let policy = OutputPolicy {
+            success_message: true,
+            ..OutputPolicy::default()
+        };
+        assert!(policy.verbose(false));
+        assert_eq!(
+            policy.apply(PASS.to_string(), false),
+            r#"{"continue":true,"systemMessage":"[ralph-hook-lint] clippy clean: src/a.rs"}"#
+        );
+        assert_eq!(
+            policy.apply(SKIP.to_string(), false),
+            r#"{"continue":true}"#
+        );
+    }
+
+    #[test]
+    fn budget_note_is_always_kept() {
+        let note = r#"{"continue":true,"systemMessage":"[ralph-hook-lint] time budget of 1s reached; not linted: a.ts"}"#;
+        assert_eq!(OutputPolicy::default().apply(note.to_string(), false), note);
+        let policy = OutputPolicy {
+            success_message: true,
+            ..OutputPolicy::default()
+        };
+        assert_eq!(policy.apply(note.to_string(), false), note);
+        let summary = r#"{"continue":true,"systemMessage":"[ralph-hook-lint] verified: linted 2 file(s) across 1 project(s): clean."}"#;
+        assert_eq!(
+            OutputPolicy::default().apply(summary.to_string(), false),
+            summary
+        );
+        assert_eq!(policy.apply(summary.to_string(), false), summary);
+    }
+
+    #[test]
+    fn suppress_output_on_continue_only() {
+        let policy = OutputPolicy {
+            suppress_output: true,
+            ..OutputPolicy::default()
+        };
+        assert_eq!(
+            policy.apply(r#"{"continue":true}"#.to_string(), false),
+            r#"{"continue":true,"suppressOutput":true}"#
+        );
+        let block = r#"{"decision":"block","reason":"x"}"#;
+        assert_eq!(policy.apply(block.to_string(), false), block);
+    }
+
+    #[test]
+    fn block_reason_is_capped_per_file() {
+        let policy = OutputPolicy {
+            max_diagnostics_per_file: 2,
+            ..OutputPolicy::default()
+        };
+        let reason = "[ralph-hook-lint] lint errors in 2 files using clippy:\n\n\
+src/a.rs:1:1: error: one\n  | snippet\nsrc/a.rs:2:1: error: two\nsrc/a.rs:3:1: error: three\n  | snippet\n\
+src/b.rs:1:1: error: other\nsrc/a.rs:4:1: error: four\n\nFix lint errors.";
+        let output = format!(
+            r#"{{"decision":"block","reason":"{}"}}"#,
+            escape_json(reason)
+        );
+        let capped = extract_reason_field(&policy.apply(output, false)).unwrap();
+        assert_eq!(
+            capped,
+            "[ralph-hook-lint] lint errors in 2 files using clippy:\n\n\
+src/a.rs:1:1: error: one\n  | snippet\nsrc/a.rs:2:1: error: two\n+2 more in this file\n\
+src/b.rs:1:1: error: other\n\nFix lint errors."
+        );
+
+        let unlimited = OutputPolicy {
+            max_diagnostics_per_file: 0,
+            ..OutputPolicy::default()
+        };
+        let output = format!(
+            r#"{{"decision":"block","reason":"{}"}}"#,
+            escape_json(reason)
+        );
+        let all = extract_reason_field(&unlimited.apply(output, false)).unwrap();
+        assert!(all.contains("four") && !all.contains("more in this file"));
+    }
+
+    #[test]
+    fn paths_are_shortened_against_cwd_and_home() {
+        let reason = "lint errors in /home/ana/ws/src/a.rs using clippy:\n/home/ana/ws/src/a.rs:1:1: x\n/home/ana/lib/b.rs:2:2: y\n/opt/c.rs:3:3: z";
+        assert_eq!(
+            shorten_paths(
+                reason,
+                Some(Path::new("/home/ana/ws")),
+                Some(Path::new("/home/ana"))
+            ),
+            "lint errors in src/a.rs using clippy:\nsrc/a.rs:1:1: x\n~/lib/b.rs:2:2: y\n/opt/c.rs:3:3: z"
+        );
+        // A working directory of `/` would strip every leading slash
+        assert_eq!(
+            shorten_paths("/opt/c.rs", Some(Path::new("/")), None),
+            "/opt/c.rs"
+        );
+    }
+
+    #[test]
+    fn text_diagnostics_are_sorted_within_a_paragraph() {
+        let reason = "[ralph-hook-lint] lint errors in app.py using mypy:\n\n\
+app.py:12: error: Incompatible return value\n\
+app.py:3: error: Name \"x\" is not defined\n\
+app.py:3: note: Did you mean \"y\"?\n\
+lib.py:1: error: Missing import\n\
+app.py:3:2: error: Another\n\n\
+Fix lint errors.";
+        assert_eq!(
+            sort_issues(reason),
+            "[ralph-hook-lint] lint errors in app.py using mypy:\n\n\
+app.py:3: error: Name \"x\" is not defined\n\
+app.py:3: note: Did you mean \"y\"?\n\
+app.py:3:2: error: Another\n\
+app.py:12: error: Incompatible return value\n\
+lib.py:1: error: Missing import\n\n\
+Fix lint errors."
+        );
+
+        // Lines after a diagnostic move with it
+        assert_eq!(
+            sort_issues("b.rs:2:1: two\n  | snippet\na.rs:1:1: one"),
+            "a.rs:1:1: one\nb.rs:2:1: two\n  | snippet"
+        );
+        let block = r#"{"decision":"block","reason":"b.py:2: x\na.py:1: y"}"#;
+        let unsorted = OutputPolicy {
+            max_diagnostics_per_file: 0,
+            relative_paths: false,
+            ..OutputPolicy::default()
+        };
+        assert_eq!(
+            unsorted.apply(block.to_string(), false),
+            r#"{"decision":"block","reason":"a.py:1: y\nb.py:2: x"}"#
+        );
+    }
+
+    #[test]
+    fn issue_file_reads_location_prefix() {
+        assert_eq!(issue_file("src/a.rs:10:5: error: x"), Some("src/a.rs"));
+        assert_eq!(issue_file("/p/a.py:3: note"), Some("/p/a.py"));
+        assert_eq!(issue_file("error: expected `;`, found 12:3:"), None);
+        assert_eq!(issue_file("Fix lint errors."), None);
+    }
+
+    #[test]
+    fn from_config_reads_output_section() {
+        let config = Config::parse("[output]\nsuppress_output = true\nsuccess_message = true");
+        assert_eq!(
+            OutputPolicy::from_config(&config),
+            OutputPolicy {
+                suppress_output: true,
+                success_message: true,
+                max_diagnostics_per_file: DEFAULT_MAX_DIAGNOSTICS_PER_FILE,
+                relative_paths: true,
+                on_error: OnError::Open,
+            }
+        );
+    }
+
+    #[test]
+    fn on_error_policies() {
+        let policy = |value: &str| {
+            OutputPolicy::from_config(&Config::parse(&format!(
+                "[output]\non_error = \"{value}\"\n"
+            )))
+        };
+        assert_eq!(policy("fail-closed").on_error, OnError::Closed);
+        assert_eq!(policy("fail-closed-after-0").on_error, OnError::Open);
+        assert_eq!(policy("bogus").on_error, OnError::Open);
+
+        let sid = format!("test-on-error-{}", std::process::id());
+        let after_two = policy("fail-closed-after-2");
+        assert_eq!(after_two.on_error, OnError::ClosedAfter(2));
+        assert!(
+            after_two
+                .error_response(Some(&sid), "boom", false)
+                .starts_with(r#"{"continue":true"#)
+        );
+        let blocked = after_two.error_response(Some(&sid), "boom", false);
+        assert!(
+            blocked.contains("failed 2 time(s) in a row"),
+            "got: {blocked}"
+        );
+        OutputPolicy::record_success(Some(&sid));
+        assert!(
+            after_two
+                .error_response(Some(&sid), "boom", false)
+                .starts_with(r#"{"continue":true"#)
+        );
+        assert!(
+            policy("fail-open")
+                .error_response(Some(&sid), "boom", false)
+                .starts_with(r#"{"continue":true"#)
+        );
+        OutputPolicy::record_success(Some(&sid));
+    }
+}
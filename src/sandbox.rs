@@ -0,0 +1,349 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::config::Config;
+use crate::lint::is_executable_available;
+use crate::origin;
+
+/// Start of the note shown when the configured wrapper isn't installed
+pub const NOTE_PREFIX: &str = "[ralph-hook-lint] sandbox";
+
+/// The configured wrapper this process found missing, so linters ran without it
+static MISSING_WRAPPER: OnceLock<Wrapper> = OnceLock::new();
+
+/// Optional containment for linter processes, from the `[sandbox]` config section.
+/// `npm run lint` and build-tool plugins execute project-defined code inside the hook.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Address-space limit (`ulimit -v`); JVM and Node reserve a lot, so keep this generous
+    pub memory_mb: Option<u64>,
+    /// CPU time limit (`ulimit -t`)
+    pub cpu_secs: Option<u64>,
+    pub wrapper: Option<Wrapper>,
+    /// Extra writable paths for the wrapper besides the working directory and temp dir
+    pub writable: Vec<PathBuf>,
+    /// Cut the wrapped linter off from the network (`[network] policy = "none"`)
+    pub no_network: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrapper {
+    /// bubblewrap (Linux): read-only root filesystem
+    Bwrap,
+    /// `sandbox-exec` (macOS): deny file writes outside allowed paths
+    SandboxExec,
+}
+
+impl Wrapper {
+    const fn program(self) -> &'static str {
+        match self {
+            Self::Bwrap => "bwrap",
+            Self::SandboxExec => "sandbox-exec",
+        }
+    }
+}
+
+impl Limits {
+    pub fn from_config(config: &Config) -> Self {
+        let positive = |key: &str| {
+            config
+                .get_int(key)
+                .and_then(|n| u64::try_from(n).ok())
+                .filter(|n| *n > 0)
+        };
+        Self {
+            memory_mb: positive("sandbox.memory_mb"),
+            cpu_secs: positive("sandbox.cpu_secs"),
+            wrapper: match config.get_str("sandbox.wrapper") {
+                Some("bwrap") => Some(Wrapper::Bwrap),
+                Some("sandbox-exec") => Some(Wrapper::SandboxExec),
+                _ => None,
+            },
+            writable: config
+                .get_array("sandbox.writable")
+                .unwrap_or_default()
+                .iter()
+                .map(|p| expand_home(p))
+                .collect(),
+            no_network: config.get_str("network.policy") == Some("none"),
+        }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.memory_mb.is_none() && self.cpu_secs.is_none() && self.wrapper.is_none()
+    }
+
+    /// Rebuild `command` to run inside the configured limits and wrapper. A wrapper that isn't
+    /// installed is left out, with a note (see [`take_missing_wrapper_note`]), rather than
+    /// failing every lint. Returns `None` when nothing applies.
+    pub fn contain(&self, command: &Command) -> Option<Command> {
+        self.contain_with(command, |wrapper| {
+            is_executable_available(wrapper.program())
+        })
+    }
+
+    fn contain_with(
+        &self,
+        command: &Command,
+        is_installed: impl Fn(Wrapper) -> bool,
+    ) -> Option<Command> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut prefix: Vec<String> = Vec::new();
+
+        let wrapper = self.wrapper.filter(|wrapper| {
+            let installed = is_installed(*wrapper);
+            if !installed {
+                let _ = MISSING_WRAPPER.set(*wrapper);
+            }
+            installed
+        });
+        if let Some(wrapper) = wrapper {
+            let dir = command
+                .get_current_dir()
+                .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+            let mut writable = vec![dir, std::env::temp_dir()];
+            writable.extend(self.writable.iter().cloned());
+            prefix.push(wrapper.program().to_string());
+            prefix.extend(wrapper_args(wrapper, &writable, self.no_network));
+        }
+
+        if self.memory_mb.is_some() || self.cpu_secs.is_some() {
+            let mut script = String::new();
+            if let Some(mb) = self.memory_mb {
+                let _ = write!(script, "ulimit -v {} && ", mb * 1024);
+            }
+            if let Some(secs) = self.cpu_secs {
+                let _ = write!(script, "ulimit -t {secs} && ");
+            }
+            script.push_str("exec \"$@\"");
+            prefix.extend(["sh".to_string(), "-c".to_string(), script, "sh".to_string()]);
+        }
+        if prefix.is_empty() {
+            return None;
+        }
+
+        let mut contained = Command::new(&prefix[0]);
+        contained
+            .args(&prefix[1..])
+            .arg(command.get_program())
+            .args(command.get_args());
+        if let Some(dir) = command.get_current_dir() {
+            contained.current_dir(dir);
+        }
+        for (key, value) in command.get_envs() {
+            match value {
+                Some(value) => contained.env(key, value),
+                None => contained.env_remove(key),
+            };
+        }
+        Some(contained)
+    }
+}
+
+/// Marker that the session was told its wrapper is missing:
+/// `<temp_dir>/ralph-lint-<session_id>-sandbox-noted.txt`
+pub fn noted_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ralph-lint-{session_id}-sandbox-noted.txt"))
+}
+
+/// A note that linters ran without the configured wrapper because it isn't installed. Given
+/// once per session, or once per run outside one.
+pub fn take_missing_wrapper_note() -> Option<String> {
+    let wrapper = MISSING_WRAPPER.get()?;
+    if let Some(session_id) = origin::session() {
+        let marker = noted_path(session_id);
+        if marker.exists() {
+            return None;
+        }
+        let _ = fs::write(marker, "");
+    }
+    Some(format!(
+        "{NOTE_PREFIX}: {} is not installed, so linters run without it. Install it or remove `[sandbox] wrapper`.",
+        wrapper.program()
+    ))
+}
+
+fn wrapper_args(wrapper: Wrapper, writable: &[PathBuf], no_network: bool) -> Vec<String> {
+    match wrapper {
+        Wrapper::Bwrap => {
+            let mut args: Vec<String> = [
+                "--ro-bind",
+                "/",
+                "/",
+                "--dev",
+                "/dev",
+                "--proc",
+                "/proc",
+                "--die-with-parent",
+            ]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+            for path in writable.iter().filter(|p| p.exists()) {
+                let path = path.to_string_lossy().to_string();
+                args.extend(["--bind".to_string(), path.clone(), path]);
+            }
+            if no_network {
+                args.push("--unshare-net".to_string());
+            }
+            args.push("--".to_string());
+            args
+        }
+        Wrapper::SandboxExec => {
+            let allowed: Vec<String> = writable
+                .iter()
+                .map(|p| {
+                    let p = p.canonicalize().unwrap_or_else(|_| p.clone());
+                    format!("(subpath \"{}\")", p.to_string_lossy().replace('"', "\\\""))
+                })
+                .collect();
+            let mut profile = format!(
+                "(version 1)(allow default)(deny file-write*)(allow file-write* {} (literal \"/dev/null\"))",
+                allowed.join(" ")
+            );
+            if no_network {
+                profile.push_str("(deny network-outbound (remote ip))");
+            }
+            vec!["-p".to_string(), profile]
+        }
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(command: &Command) -> Vec<String> {
+        std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|a| a.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn no_limits_leaves_command_alone() {
+        let limits = Limits::from_config(&Config::parse(""));
+        assert!(limits.is_empty());
+        assert!(limits.contain(&Command::new("eslint")).is_none());
+    }
+
+    #[test]
+    fn ulimits_wrap_with_sh_and_keep_dir_and_env() {
+        let limits =
+            Limits::from_config(&Config::parse("[sandbox]\nmemory_mb = 2\ncpu_secs = 30\n"));
+        let mut command = Command::new("eslint");
+        command.arg("a.js").current_dir("/ws").env("CI", "1");
+        let contained = limits.contain(&command).unwrap();
+        assert_eq!(
+            argv(&contained),
+            vec![
+                "sh",
+                "-c",
+                "ulimit -v 2048 && ulimit -t 30 && exec \"$@\"",
+                "sh",
+                "eslint",
+                "a.js"
+            ]
+        );
+        assert_eq!(contained.get_current_dir(), Some(Path::new("/ws")));
+        assert_eq!(contained.get_envs().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cpu_limit_is_applied() {
+        let limits = Limits {
+            cpu_secs: Some(7),
+            ..Limits::default()
+        };
+        let mut command = Command::new("sh");
+        command.args(["-c", "ulimit -t"]);
+        let output = limits.contain(&command).unwrap().output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "7");
+    }
+
+    #[test]
+    fn bwrap_binds_working_directory_writable() {
+        let dir = std::env::temp_dir();
+        let limits = Limits {
+            wrapper: Some(Wrapper::Bwrap),
+            ..Limits::default()
+        };
+        let mut command = Command::new("npm");
+        command.args(["run", "lint"]).current_dir(&dir);
+        let args = argv(&limits.contain_with(&command, |_| true).unwrap());
+        assert_eq!(args[0], "bwrap");
+        let dir = dir.to_string_lossy().to_string();
+        assert!(args.windows(3).any(|w| w == ["--bind", &dir, &dir]));
+        assert_eq!(args[args.len() - 3..], ["npm", "run", "lint"]);
+    }
+
+    #[test]
+    fn missing_wrapper_is_left_out() {
+        let limits = Limits {
+            wrapper: Some(Wrapper::Bwrap),
+            cpu_secs: Some(7),
+            ..Limits::default()
+        };
+        let command = Command::new("eslint");
+        let args = argv(&limits.contain_with(&command, |_| false).unwrap());
+        assert_eq!(args[0], "sh");
+        assert!(!args.contains(&"bwrap".to_string()));
+
+        let wrapper_only = Limits {
+            wrapper: Some(Wrapper::Bwrap),
+            ..Limits::default()
+        };
+        assert!(wrapper_only.contain_with(&command, |_| false).is_none());
+        assert!(take_missing_wrapper_note().is_some_and(|note| note.contains("bwrap")));
+    }
+
+    #[test]
+    fn sandbox_exec_profile_denies_writes_elsewhere() {
+        let args = wrapper_args(
+            Wrapper::SandboxExec,
+            &[PathBuf::from("/nonexistent/ws")],
+            false,
+        );
+        assert_eq!(args[0], "-p");
+        assert!(args[1].contains("(deny file-write*)"));
+        assert!(args[1].contains(r#"(subpath "/nonexistent/ws")"#));
+        assert!(!args[1].contains("network"));
+    }
+
+    #[test]
+    fn no_network_policy_cuts_wrapped_linters_off() {
+        let limits = Limits::from_config(&Config::parse(
+            "[sandbox]\nwrapper = \"bwrap\"\n[network]\npolicy = \"none\"\n",
+        ));
+        assert!(limits.no_network);
+        let args = argv(
+            &limits
+                .contain_with(&Command::new("gradle"), |_| true)
+                .unwrap(),
+        );
+        assert!(args.contains(&"--unshare-net".to_string()));
+        let args = wrapper_args(Wrapper::SandboxExec, &[], true);
+        assert!(args[1].contains("(deny network-outbound (remote ip))"));
+    }
+
+    #[test]
+    fn expand_home_prefix() {
+        if let Some(home) = std::env::var_os("HOME") {
+            assert_eq!(expand_home("~/.cache"), Path::new(&home).join(".cache"));
+        }
+        assert_eq!(expand_home("/abs"), PathBuf::from("/abs"));
+    }
+}
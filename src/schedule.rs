@@ -0,0 +1,431 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Write};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+use crate::procgroup;
+use crate::quarantine::Quarantined;
+
+/// Default wall-clock budget for `--lint-collected`, overridable with `lint_collected.budget_secs`.
+pub const DEFAULT_BUDGET: Duration = Duration::from_secs(120);
+
+/// Lines kept in the job accounting log
+const ACCOUNTING_LOG_LINES: usize = 1000;
+
+static MAX_PARALLEL: OnceLock<usize> = OnceLock::new();
+
+/// Set the `--max-parallel` limit, which takes precedence over the config.
+pub fn configure_max_parallel(max: usize) {
+    let _ = MAX_PARALLEL.set(max);
+}
+
+/// How many jobs run at once: `--max-parallel`, else `lint_collected.max_parallel`, else one
+/// per CPU.
+pub fn max_parallel(config: &Config) -> usize {
+    MAX_PARALLEL
+        .get()
+        .copied()
+        .or_else(|| {
+            config
+                .get_int("lint_collected.max_parallel")
+                .and_then(|max| usize::try_from(max).ok())
+        })
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, usize::from))
+        .max(1)
+}
+
+/// A job's result, wall time, and CPU time
+type Finished = (Result<String, JobError>, Duration, Duration);
+
+type JobFn = Box<dyn FnOnce() -> Result<String, JobError> + Send>;
+
+/// The error a job failed with, and whether it was a quarantined linter being skipped rather
+/// than a lint that broke.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobError {
+    pub message: String,
+    pub quarantined: bool,
+}
+
+impl From<Box<dyn std::error::Error>> for JobError {
+    fn from(error: Box<dyn std::error::Error>) -> Self {
+        Self {
+            quarantined: Quarantined::find(error.as_ref()).is_some(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// One project or file lint in a `--lint-collected` run.
+pub struct Job {
+    pub label: String,
+    /// Key for duration history, e.g. the project root
+    pub key: String,
+    /// Expected duration; faster jobs start first
+    pub cost: Duration,
+    run: JobFn,
+}
+
+impl Job {
+    pub fn new(
+        label: String,
+        key: String,
+        cost: Duration,
+        run: impl FnOnce() -> Result<String, JobError> + Send + 'static,
+    ) -> Self {
+        Self {
+            label,
+            key,
+            cost,
+            run: Box::new(run),
+        }
+    }
+}
+
+/// A job that finished within the budget.
+#[derive(Debug)]
+pub struct Completed {
+    pub label: String,
+    pub key: String,
+    pub result: Result<String, JobError>,
+    pub elapsed: Duration,
+    /// CPU time of the linter processes the job ran
+    pub cpu: Duration,
+}
+
+/// Results of a budgeted run, in job submission order.
+#[derive(Debug, Default)]
+pub struct Outcome {
+    pub completed: Vec<Completed>,
+    /// Labels of jobs that didn't finish within the budget
+    pub skipped: Vec<String>,
+}
+
+/// Cache of how long each project's lint took in earlier runs.
+fn durations_path() -> PathBuf {
+    std::env::temp_dir().join("ralph-lint-durations.txt")
+}
+
+/// A job's last recorded lint duration and when it was recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recorded {
+    pub elapsed: Duration,
+    /// Unix seconds; 0 for entries written before the time was recorded
+    pub at: u64,
+}
+
+/// Last recorded lint duration per job key.
+pub fn load_durations() -> HashMap<String, Duration> {
+    load_records()
+        .into_iter()
+        .map(|(key, recorded)| (key, recorded.elapsed))
+        .collect()
+}
+
+/// Last recorded lint duration per job key, with when it was recorded.
+pub fn load_records() -> HashMap<String, Recorded> {
+    read_durations(&durations_path())
+}
+
+/// Merge the durations of completed jobs into the cache, except for `frozen` keys: projects
+/// linted with the fast tier keep their full-tier time, or the next run would switch back.
+pub fn record_durations(completed: &[Completed], frozen: &[String]) {
+    let recorded: Vec<&Completed> = completed
+        .iter()
+        .filter(|job| !frozen.contains(&job.key))
+        .collect();
+    if !recorded.is_empty() {
+        merge_durations(&durations_path(), recorded);
+    }
+}
+
+/// Parse `<millis>\t<unix secs>\t<key>` lines, and the older `<millis>\t<key>`.
+fn read_durations(path: &Path) -> HashMap<String, Recorded> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (millis, rest) = line.split_once('\t')?;
+            let elapsed = Duration::from_millis(millis.parse().ok()?);
+            let (at, key) = rest
+                .split_once('\t')
+                .and_then(|(at, key)| Some((at.parse().ok()?, key)))
+                .unwrap_or((0, rest));
+            Some((key.to_string(), Recorded { elapsed, at }))
+        })
+        .collect()
+}
+
+fn merge_durations<'a>(path: &Path, completed: impl IntoIterator<Item = &'a Completed>) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut durations = read_durations(path);
+    for job in completed {
+        durations.insert(
+            job.key.clone(),
+            Recorded {
+                elapsed: job.elapsed,
+                at: now,
+            },
+        );
+    }
+    let mut contents = String::new();
+    for (key, recorded) in &durations {
+        let _ = writeln!(
+            contents,
+            "{}\t{}\t{key}",
+            recorded.elapsed.as_millis(),
+            recorded.at
+        );
+    }
+    let _ = fs::write(path, contents);
+}
+
+/// Where each completed job's wall and CPU time is logged.
+pub fn accounting_log_path() -> PathBuf {
+    std::env::temp_dir().join("ralph-lint-jobs.log")
+}
+
+/// Append one `<unix secs>\t<wall ms>\t<cpu ms>\t<parallelism>\t<label>` line per completed
+/// job to the accounting log, keeping the most recent lines.
+pub fn record_accounting(completed: &[Completed], parallelism: usize) {
+    if !completed.is_empty() {
+        append_accounting(&accounting_log_path(), completed, parallelism);
+    }
+}
+
+fn append_accounting(path: &Path, completed: &[Completed], parallelism: usize) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    let mut lines: Vec<String> = existing.lines().map(str::to_string).collect();
+    lines.extend(completed.iter().map(|job| {
+        format!(
+            "{now}\t{}\t{}\t{parallelism}\t{}",
+            job.elapsed.as_millis(),
+            job.cpu.as_millis(),
+            job.label
+        )
+    }));
+    let start = lines.len().saturating_sub(ACCOUNTING_LOG_LINES);
+    let mut contents = lines[start..].join("\n");
+    contents.push('\n');
+    let _ = fs::write(path, contents);
+}
+
+/// Run `jobs` on up to `parallelism` worker threads, cheapest first, and return whatever
+/// finished within `budget`. Quick verdicts land even when a slow build would use up the
+/// budget. Workers stop picking up jobs once the budget is spent; jobs still running are
+/// abandoned and reported as skipped.
+pub fn run_with_budget(jobs: Vec<Job>, budget: Duration, parallelism: usize) -> Outcome {
+    let deadline = Instant::now() + budget;
+    let names: Vec<(String, String)> = jobs
+        .iter()
+        .map(|j| (j.label.clone(), j.key.clone()))
+        .collect();
+
+    let mut indexed: Vec<(usize, Job)> = jobs.into_iter().enumerate().collect();
+    indexed.sort_by_key(|(_, job)| job.cost);
+    let queue = Arc::new(Mutex::new(VecDeque::from(indexed)));
+
+    let (tx, rx) = mpsc::channel();
+    for _ in 0..parallelism.clamp(1, names.len().max(1)) {
+        let queue = Arc::clone(&queue);
+        let tx = tx.clone();
+        thread::spawn(move || {
+            while Instant::now() < deadline {
+                let Some((index, job)) = queue.lock().ok().and_then(|mut q| q.pop_front()) else {
+                    break;
+                };
+                procgroup::take_cpu_time();
+                let started = Instant::now();
+                let result = (job.run)();
+                let cpu = procgroup::take_cpu_time();
+                if tx.send((index, result, started.elapsed(), cpu)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    let mut results: Vec<Option<Finished>> = names.iter().map(|_| None).collect();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match rx.recv_timeout(remaining) {
+            Ok((index, result, elapsed, cpu)) => results[index] = Some((result, elapsed, cpu)),
+            // All workers finished, or the budget ran out
+            Err(_) => break,
+        }
+    }
+
+    let mut outcome = Outcome::default();
+    for ((label, key), result) in names.into_iter().zip(results) {
+        match result {
+            Some((result, elapsed, cpu)) => outcome.completed.push(Completed {
+                label,
+                key,
+                result,
+                elapsed,
+                cpu,
+            }),
+            None => outcome.skipped.push(label),
+        }
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sleeper(label: &str, cost: u64, millis: u64) -> Job {
+        let label_owned = label.to_string();
+        Job::new(
+            label.to_string(),
+            label.to_string(),
+            Duration::from_secs(cost),
+            move || {
+                thread::sleep(Duration::from_millis(millis));
+                Ok(label_owned)
+            },
+        )
+    }
+
+    #[test]
+    fn completes_all_jobs_in_submission_order() {
+        let jobs = vec![sleeper("a", 1, 10), sleeper("b", 5, 30), sleeper("c", 3, 0)];
+        let outcome = run_with_budget(jobs, Duration::from_secs(10), 2);
+        let labels: Vec<&str> = outcome.completed.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["a", "b", "c"]);
+        assert!(outcome.skipped.is_empty());
+        assert_eq!(outcome.completed[1].result, Ok("b".to_string()));
+        assert!(outcome.completed[1].elapsed >= Duration::from_millis(30));
+    }
+
+    #[test]
+    fn budget_skips_unfinished_jobs() {
+        let jobs = vec![sleeper("fast", 1, 0), sleeper("slow", 1, 2_000)];
+        let started = Instant::now();
+        let outcome = run_with_budget(jobs, Duration::from_millis(200), 2);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(outcome.completed.len(), 1);
+        assert_eq!(outcome.skipped, vec!["slow".to_string()]);
+    }
+
+    #[test]
+    fn cheapest_jobs_start_first() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let jobs = ["gradle", "ruff", "clippy"]
+            .into_iter()
+            .zip([60, 1, 30])
+            .map(|(label, cost)| {
+                let order = Arc::clone(&order);
+                Job::new(
+                    label.to_string(),
+                    label.to_string(),
+                    Duration::from_secs(cost),
+                    move || {
+                        order.lock().unwrap().push(label);
+                        Ok(String::new())
+                    },
+                )
+            })
+            .collect();
+        run_with_budget(jobs, Duration::from_secs(10), 1);
+        assert_eq!(*order.lock().unwrap(), vec!["ruff", "clippy", "gradle"]);
+    }
+
+    #[test]
+    fn durations_round_trip_and_merge() {
+        let path =
+            std::env::temp_dir().join(format!("ralph-lint-durations-test-{}", std::process::id()));
+        let completed = |key: &str, millis: u64| Completed {
+            label: key.to_string(),
+            key: key.to_string(),
+            result: Ok(String::new()),
+            elapsed: Duration::from_millis(millis),
+            cpu: Duration::ZERO,
+        };
+
+        merge_durations(&path, &[completed("/ws/a", 1500), completed("/ws/b", 20)]);
+        merge_durations(&path, &[completed("/ws/b", 40)]);
+        let durations = read_durations(&path);
+        let elapsed = |key: &str| durations.get(key).map(|recorded| recorded.elapsed);
+        assert_eq!(elapsed("/ws/a"), Some(Duration::from_millis(1500)));
+        assert_eq!(elapsed("/ws/b"), Some(Duration::from_millis(40)));
+        assert!(durations["/ws/b"].at > 0);
+
+        // Entries without a recording time read as recorded at 0
+        fs::write(&path, "300\t/ws/old\n").unwrap();
+        assert_eq!(
+            read_durations(&path).get("/ws/old"),
+            Some(&Recorded {
+                elapsed: Duration::from_millis(300),
+                at: 0
+            })
+        );
+
+        let _ = fs::remove_file(&path);
+        assert!(read_durations(&path).is_empty());
+    }
+
+    #[test]
+    fn accounting_log_keeps_recent_lines() {
+        let path =
+            std::env::temp_dir().join(format!("ralph-lint-jobs-test-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let completed: Vec<Completed> = (0..ACCOUNTING_LOG_LINES + 5)
+            .map(|i| Completed {
+                label: format!("/ws/{i}"),
+                key: String::new(),
+                result: Ok(String::new()),
+                elapsed: Duration::from_millis(1500),
+                cpu: Duration::from_millis(700),
+            })
+            .collect();
+
+        append_accounting(&path, &completed[..1], 2);
+        let log = fs::read_to_string(&path).unwrap();
+        assert!(log.ends_with("\t1500\t700\t2\t/ws/0\n"), "{log}");
+
+        append_accounting(&path, &completed[1..], 2);
+        let log = fs::read_to_string(&path).unwrap();
+        assert_eq!(log.lines().count(), ACCOUNTING_LOG_LINES);
+        assert!(log.lines().next().unwrap().ends_with("\t/ws/5"));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn max_parallel_from_config() {
+        let config = Config::parse("[lint_collected]\nmax_parallel = 2\n");
+        assert_eq!(max_parallel(&config), 2);
+        let config = Config::parse("[lint_collected]\nmax_parallel = 0\n");
+        assert_eq!(max_parallel(&config), 1);
+        assert!(max_parallel(&Config::default()) >= 1);
+    }
+
+    #[test]
+    fn empty_job_list() {
+        let outcome = run_with_budget(Vec::new(), Duration::from_secs(1), 4);
+        assert!(outcome.completed.is_empty() && outcome.skipped.is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::extract::{extract_hook_event_name, extract_reason_field};
+use crate::lint::escape_json;
+
+/// Hook payload schema, detected from the shape of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schema {
+    /// Older payloads without `hook_event_name`: respond with `decision`/`reason` only
+    Legacy,
+    /// Payloads that name their event; events that accept `hookSpecificOutput` also get
+    /// the reason as `additionalContext`
+    Current { event: String },
+}
+
+impl Schema {
+    pub fn detect(input: &str) -> Self {
+        match extract_hook_event_name(input) {
+            Some(event) if !event.is_empty() => Self::Current { event },
+            _ => Self::Legacy,
+        }
+    }
+
+    /// Rewrite a response for this schema. Continue responses are the same in every version.
+    pub fn adapt(&self, output: String) -> String {
+        let Self::Current { event } = self else {
+            return output;
+        };
+        // Stop and SubagentStop only understand `decision`/`reason`
+        if !matches!(event.as_str(), "PostToolUse" | "PreToolUse")
+            || !output.contains(r#""decision":"block"#)
+        {
+            return output;
+        }
+        let Some(reason) = extract_reason_field(&output) else {
+            return output;
+        };
+        let reason = escape_json(&reason);
+        format!(
+            r#"{{"decision":"block","reason":"{reason}","hookSpecificOutput":{{"hookEventName":"{}","additionalContext":"{reason}"}}}}"#,
+            escape_json(event)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK: &str = r#"{"decision":"block","reason":"a.ts:1:1: error \"x\""}"#;
+
+    #[test]
+    fn detect_by_hook_event_name() {
+        assert_eq!(
+            Schema::detect(r#"{"tool_input":{"file_path":"/a.ts"}}"#),
+            Schema::Legacy
+        );
+        assert_eq!(
+            Schema::detect(r#"{"hook_event_name":"PostToolUse","tool_input":{}}"#),
+            Schema::Current {
+                event: "PostToolUse".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn legacy_and_continue_responses_are_unchanged() {
+        assert_eq!(Schema::Legacy.adapt(BLOCK.to_string()), BLOCK);
+        let current = Schema::Current {
+            event: "PostToolUse".to_string(),
+        };
+        assert_eq!(
+            current.adapt(r#"{"continue":true}"#.to_string()),
+            r#"{"continue":true}"#
+        );
+    }
+
+    #[test]
+    fn post_tool_use_block_gets_hook_specific_output() {
+        let current = Schema::Current {
+            event: "PostToolUse".to_string(),
+        };
+        assert_eq!(
+            current.adapt(BLOCK.to_string()),
+            r#"{"decision":"block","reason":"a.ts:1:1: error \"x\"","hookSpecificOutput":{"hookEventName":"PostToolUse","additionalContext":"a.ts:1:1: error \"x\""}}"#
+        );
+    }
+
+    #[test]
+    fn stop_block_keeps_decision_and_reason() {
+        let stop = Schema::Current {
+            event: "Stop".to_string(),
+        };
+        assert_eq!(stop.adapt(BLOCK.to_string()), BLOCK);
+    }
+}
@@ -0,0 +1,360 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::extract::extract_reason_field;
+use crate::json::{self, Json};
+use crate::lint::escape_json;
+
+/// A throwaway project with a deliberate lint error.
+struct Scaffold {
+    name: &'static str,
+    /// Files to create, relative to the project directory
+    files: &'static [(&'static str, &'static str)],
+    /// The file the hook is run against
+    target: &'static str,
+}
+
+const SCAFFOLDS: &[Scaffold] = &[
+    Scaffold {
+        name: "Rust",
+        files: &[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"selftest\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+            ),
+            (
+                "src/main.rs",
+                "fn main() {\n    let x: u32 = \"nope\";\n}\n",
+            ),
+        ],
+        target: "src/main.rs",
+    },
+    Scaffold {
+        name: "JavaScript",
+        files: &[
+            ("package.json", "{\"name\":\"selftest\",\"private\":true}\n"),
+            ("index.js", "const x = ;\n"),
+        ],
+        target: "index.js",
+    },
+    Scaffold {
+        name: "Python",
+        files: &[
+            ("pyproject.toml", "[project]\nname = \"selftest\"\n"),
+            ("main.py", "import os\nprint(undefined_name)\n"),
+        ],
+        target: "main.py",
+    },
+    Scaffold {
+        name: "Go",
+        files: &[
+            ("go.mod", "module selftest\n\ngo 1.21\n"),
+            (
+                "main.go",
+                "package main\n\nimport \"os\"\n\nfunc main() {}\n",
+            ),
+        ],
+        target: "main.go",
+    },
+    Scaffold {
+        name: "C",
+        files: &[
+            ("Makefile", "main: main.c\n"),
+            ("main.c", "int main(void) {\n    return 0\n}\n"),
+        ],
+        target: "main.c",
+    },
+    Scaffold {
+        name: "Kotlin",
+        files: &[
+            ("settings.gradle.kts", "rootProject.name = \"selftest\"\n"),
+            ("build.gradle.kts", "plugins {\n    java;\n}\n"),
+        ],
+        target: "build.gradle.kts",
+    },
+    Scaffold {
+        name: "PHP",
+        files: &[
+            ("composer.json", "{\"name\": \"selftest/selftest\"}\n"),
+            ("index.php", "<?php\n\necho 'selftest'\n"),
+        ],
+        target: "index.php",
+    },
+    Scaffold {
+        name: "CSharp",
+        files: &[
+            (
+                "Selftest.csproj",
+                "<Project Sdk=\"Microsoft.NET.Sdk\">\n  <PropertyGroup>\n    <OutputType>Exe</OutputType>\n    <TargetFramework>net8.0</TargetFramework>\n  </PropertyGroup>\n</Project>\n",
+            ),
+            (
+                "Program.cs",
+                "class Program\n{\n    static void Main()\n    {\n        return 0\n    }\n}\n",
+            ),
+        ],
+        target: "Program.cs",
+    },
+    Scaffold {
+        name: "Swift",
+        files: &[
+            ("Package.swift", "// swift-tools-version:5.9\n"),
+            ("main.swift", "let answer = 42 ;\n"),
+        ],
+        target: "main.swift",
+    },
+    Scaffold {
+        name: "Shell",
+        files: &[("install.sh", "#!/bin/sh\nif true; then\n  echo hi\n")],
+        target: "install.sh",
+    },
+    Scaffold {
+        name: "Markdown",
+        files: &[("README.md", "# Title\nText right under the heading.\n")],
+        target: "README.md",
+    },
+    Scaffold {
+        name: "Elixir",
+        files: &[
+            (
+                "mix.exs",
+                "defmodule Selftest.MixProject do\n  use Mix.Project\n\n  def project, do: [app: :selftest, version: \"0.1.0\"]\nend\n",
+            ),
+            (
+                "lib/selftest.ex",
+                "defmodule Selftest do\n  def run do\n    unused = 1\n    :ok\n  end\nend\n",
+            ),
+        ],
+        target: "lib/selftest.ex",
+    },
+    Scaffold {
+        name: "Haskell",
+        files: &[
+            (
+                "selftest.cabal",
+                "cabal-version: 2.4\nname: selftest\nversion: 0.1\n",
+            ),
+            (
+                "src/Main.hs",
+                "module Main where\n\nmain :: IO ()\nmain = mapM_ (\\x -> print x) [1 :: Int, 2]\n",
+            ),
+        ],
+        target: "src/Main.hs",
+    },
+    Scaffold {
+        name: "Zig",
+        files: &[
+            (
+                "build.zig",
+                "const std = @import(\"std\");\n\npub fn build(b: *std.Build) void {\n    _ = b;\n}\n",
+            ),
+            (
+                "src/main.zig",
+                "pub fn main() void {\n    const unused = 1;\n}\n",
+            ),
+        ],
+        target: "src/main.zig",
+    },
+    Scaffold {
+        name: "Lua",
+        files: &[
+            ("selene.toml", "std = \"lua51\"\n"),
+            ("init.lua", "local unused = 1\nprint(\"hi\")\n"),
+        ],
+        target: "init.lua",
+    },
+    Scaffold {
+        name: "Scala",
+        files: &[
+            ("build.sbt", "scalaVersion := \"2.13.14\"\n"),
+            (
+                "project/plugins.sbt",
+                "addSbtPlugin(\"ch.epfl.scala\" % \"sbt-scalafix\" % \"0.12.1\")\n",
+            ),
+            (
+                ".scalafix.conf",
+                "rules = [DisableSyntax]\nDisableSyntax.noVars = true\n",
+            ),
+            (
+                "src/main/scala/App.scala",
+                "object App {\n  def main(args: Array[String]): Unit = {\n    var count = args.length\n    println(count)\n  }\n}\n",
+            ),
+        ],
+        target: "src/main/scala/App.scala",
+    },
+    Scaffold {
+        name: "PowerShell",
+        files: &[(
+            "deploy.ps1",
+            "$password = ConvertTo-SecureString -String 'hunter2' -AsPlainText -Force\nWrite-Output $password\n",
+        )],
+        target: "deploy.ps1",
+    },
+    Scaffold {
+        name: "Nix",
+        files: &[(
+            "default.nix",
+            "let\n  pkgs = import <nixpkgs> { };\n  hello = pkgs.hello;\nin\n{ hello = hello; }\n",
+        )],
+        target: "default.nix",
+    },
+    Scaffold {
+        name: "Solidity",
+        files: &[
+            ("foundry.toml", "[profile.default]\nsrc = \"src\"\n"),
+            (
+                "src/Counter.sol",
+                "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n\ncontract Counter {\n    function increment() public {\n        count += 1;\n    }\n}\n",
+            ),
+        ],
+        target: "src/Counter.sol",
+    },
+    Scaffold {
+        name: "Terraform",
+        files: &[(
+            "main.tf",
+            "resource \"aws_s3_bucket\" \"logs\" {\n  bucket = \"logs\"\n  acl = \"public-read\n}\n",
+        )],
+        target: "main.tf",
+    },
+    Scaffold {
+        name: "Java",
+        files: &[
+            (
+                "pom.xml",
+                "<project><modelVersion>4.0.0</modelVersion><groupId>selftest</groupId><artifactId>selftest</artifactId><version>1</version>\
+<build><plugins><plugin><groupId>org.apache.maven.plugins</groupId><artifactId>maven-pmd-plugin</artifactId><version>3.21.2</version></plugin></plugins></build></project>\n",
+            ),
+            (
+                "src/main/java/App.java",
+                "public class App {\n    public static void main(String[] args) {\n        try {\n            System.out.println(args[0]);\n        } catch (Exception e) {\n        }\n    }\n}\n",
+            ),
+        ],
+        target: "src/main/java/App.java",
+    },
+];
+
+/// Outcome of running the hook against a scaffolded project.
+#[derive(Debug, PartialEq, Eq)]
+enum Status {
+    /// The deliberate error was caught
+    Ok(String),
+    /// No linter or toolchain available, so the pipeline skipped the file
+    Skipped(String),
+    /// The pipeline ran but let the error through
+    Broken(String),
+}
+
+/// Scaffold a project per supported language, run `lint` (the full hook pipeline, given a
+/// payload) against its deliberate error, and print which pipelines work on this machine.
+/// Returns false if any pipeline ran but missed the error.
+pub fn run(lint: impl Fn(&str) -> String) -> Result<bool, Box<dyn std::error::Error>> {
+    let base = std::env::temp_dir().join(format!("ralph-lint-selftest-{}", std::process::id()));
+    let mut all_ok = true;
+
+    for scaffold in SCAFFOLDS {
+        let dir = base.join(scaffold.name.to_lowercase());
+        let target = write_scaffold(scaffold, &dir)?;
+        let payload = format!(
+            r#"{{"tool_input":{{"file_path":"{}"}}}}"#,
+            escape_json(&target.to_string_lossy())
+        );
+        let status = classify(&lint(&payload));
+        let (label, detail) = match &status {
+            Status::Ok(detail) => ("ok", detail),
+            Status::Skipped(detail) => ("skipped", detail),
+            Status::Broken(detail) => ("BROKEN", detail),
+        };
+        println!("{:<11} {label:<8} {detail}", scaffold.name);
+        all_ok &= !matches!(status, Status::Broken(_));
+    }
+
+    let _ = fs::remove_dir_all(&base);
+    Ok(all_ok)
+}
+
+fn write_scaffold(scaffold: &Scaffold, dir: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    for (name, contents) in scaffold.files {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)?;
+    }
+    Ok(dir.join(scaffold.target))
+}
+
+/// Classify a debug-mode hook response.
+fn classify(response: &str) -> Status {
+    if let Some(reason) = extract_reason_field(response) {
+        let linter = reason
+            .lines()
+            .next()
+            .and_then(|l| l.split(" using ").nth(1))
+            .map_or("", |l| l.trim_end_matches(':'));
+        return Status::Ok(format!("caught by {linter}"));
+    }
+    let message = json::parse(response)
+        .and_then(|j| {
+            j.get("systemMessage")
+                .and_then(Json::as_str)
+                .map(str::to_string)
+        })
+        .unwrap_or_default();
+    let message = message.trim_start_matches("[ralph-hook-lint] ").to_string();
+    if message.starts_with("lint passed") {
+        Status::Broken(format!("deliberate error not detected: {message}"))
+    } else {
+        Status::Skipped(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_responses() {
+        assert_eq!(
+            classify(
+                r#"{"decision":"block","reason":"[ralph-hook-lint] lint errors in /t/main.py using ruff:\n\nF401"}"#
+            ),
+            Status::Ok("caught by ruff".to_string())
+        );
+        assert_eq!(
+            classify(
+                r#"{"continue":true,"systemMessage":"[ralph-hook-lint] no linter found for /t/index.js."}"#
+            ),
+            Status::Skipped("no linter found for /t/index.js.".to_string())
+        );
+        assert!(matches!(
+            classify(
+                r#"{"continue":true,"systemMessage":"[ralph-hook-lint] lint passed for /t/a.go using go vet."}"#
+            ),
+            Status::Broken(_)
+        ));
+    }
+
+    #[test]
+    fn scaffold_writes_nested_files() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-scaffold-{}", std::process::id()));
+        let java = SCAFFOLDS.iter().find(|s| s.name == "Java").unwrap();
+        let target = write_scaffold(java, &dir).unwrap();
+        assert!(target.ends_with("src/main/java/App.java") && target.is_file());
+        assert!(dir.join("pom.xml").is_file());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn every_scaffold_target_is_a_written_file() {
+        for scaffold in SCAFFOLDS {
+            assert!(
+                scaffold
+                    .files
+                    .iter()
+                    .any(|(name, _)| *name == scaffold.target),
+                "{} target not scaffolded",
+                scaffold.name
+            );
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::io::{self, BufRead, Write};
+
+use crate::json::{self, Json};
+use crate::lint::escape_json;
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+
+/// Serve lint requests as JSON-RPC 2.0 over stdio, one message per line, so editor extensions
+/// and other harnesses reuse the hook's detection, config and filtering without spawning the
+/// binary for every file. `lint` runs the full hook pipeline on a payload, in lenient mode if
+/// asked. Methods:
+///
+/// - `lintFile` `{"path", "lenient"?}`: one result
+/// - `lintPaths` `{"paths", "lenient"?}`: one result per path, in order
+/// - `shutdown`: answers `null` and stops
+///
+/// A result is `{"path", "blocked", "reason", "message"}`: the block reason when a linter
+/// blocked, else the pass or skip message.
+pub fn run(lint: impl Fn(&str, bool) -> String) -> Result<(), Box<dyn std::error::Error>> {
+    let mut stdout = io::stdout().lock();
+    for message in io::stdin().lock().lines() {
+        let message = message?;
+        if message.trim().is_empty() {
+            continue;
+        }
+        let (response, shutdown) = handle(&message, &lint);
+        if let Some(response) = response {
+            writeln!(stdout, "{response}")?;
+            stdout.flush()?;
+        }
+        if shutdown {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// The response to one message (none for notifications), and whether to stop serving.
+fn handle(message: &str, lint: impl Fn(&str, bool) -> String) -> (Option<String>, bool) {
+    let Some(request) = json::parse(message) else {
+        return (Some(error("null", PARSE_ERROR, "parse error")), false);
+    };
+    let id = request.get("id").map(id_json);
+    let Some(method) = request.get("method").and_then(Json::as_str) else {
+        let id = id.as_deref().unwrap_or("null");
+        return (Some(error(id, INVALID_REQUEST, "missing method")), false);
+    };
+    let params = request.get("params");
+    let lenient = params
+        .and_then(|p| p.get("lenient"))
+        .and_then(Json::as_bool)
+        .unwrap_or(false);
+    let lint_path = |path: &str| {
+        let payload = format!(
+            r#"{{"tool_input":{{"file_path":"{}"}}}}"#,
+            escape_json(path)
+        );
+        lint_result(path, &lint(&payload, lenient))
+    };
+
+    let result = match method {
+        "lintFile" => params
+            .and_then(|p| p.get("path"))
+            .and_then(Json::as_str)
+            .map(lint_path)
+            .ok_or("lintFile requires params.path"),
+        "lintPaths" => params
+            .and_then(|p| p.get("paths"))
+            .and_then(Json::as_array)
+            .and_then(|paths| paths.iter().map(Json::as_str).collect::<Option<Vec<_>>>())
+            .map(|paths| {
+                format!(
+                    "[{}]",
+                    paths
+                        .into_iter()
+                        .map(lint_path)
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )
+            })
+            .ok_or("lintPaths requires params.paths, an array of strings"),
+        "shutdown" => Ok("null".to_string()),
+        _ => {
+            let Some(id) = id else {
+                return (None, false);
+            };
+            let message = format!("unknown method '{method}'");
+            return (Some(error(&id, METHOD_NOT_FOUND, &message)), false);
+        }
+    };
+    let shutdown = method == "shutdown";
+    let Some(id) = id else {
+        return (None, shutdown);
+    };
+    let response = match result {
+        Ok(result) => format!(r#"{{"jsonrpc":"2.0","id":{id},"result":{result}}}"#),
+        Err(message) => error(&id, INVALID_PARAMS, message),
+    };
+    (Some(response), shutdown)
+}
+
+/// A `lintFile` result from the hook's response for `path`.
+fn lint_result(path: &str, response: &str) -> String {
+    let response = json::parse(response);
+    let field = |key: &str| {
+        response
+            .as_ref()
+            .and_then(|r| r.get(key))
+            .and_then(Json::as_str)
+            .map(str::to_string)
+    };
+    let blocked = field("decision").as_deref() == Some("block");
+    let quoted = |value: Option<String>| {
+        value.map_or_else(
+            || "null".to_string(),
+            |v| format!(r#""{}""#, escape_json(&v)),
+        )
+    };
+    format!(
+        r#"{{"path":"{}","blocked":{blocked},"reason":{},"message":{}}}"#,
+        escape_json(path),
+        quoted(field("reason")),
+        quoted(field("systemMessage"))
+    )
+}
+
+/// A request id as it is echoed back: a number, a string, or `null`.
+fn id_json(id: &Json) -> String {
+    match id {
+        Json::Number(n) if n.fract() == 0.0 => format!("{n:.0}"),
+        Json::Number(n) => n.to_string(),
+        Json::String(s) => format!(r#""{}""#, escape_json(s)),
+        _ => "null".to_string(),
+    }
+}
+
+fn error(id: &str, code: i32, message: &str) -> String {
+    format!(
+        r#"{{"jsonrpc":"2.0","id":{id},"error":{{"code":{code},"message":"{}"}}}}"#,
+        escape_json(message)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_lint(payload: &str, lenient: bool) -> String {
+        if payload.contains("bad.py") && !lenient {
+            r#"{"decision":"block","reason":"bad.py:1:1: error: unused import"}"#.to_string()
+        } else {
+            r#"{"continue":true,"systemMessage":"lint passed"}"#.to_string()
+        }
+    }
+
+    #[test]
+    fn lint_file_and_paths() {
+        let (response, shutdown) = handle(
+            r#"{"jsonrpc":"2.0","id":1,"method":"lintFile","params":{"path":"/ws/bad.py"}}"#,
+            fake_lint,
+        );
+        assert!(!shutdown);
+        assert_eq!(
+            response.unwrap(),
+            r#"{"jsonrpc":"2.0","id":1,"result":{"path":"/ws/bad.py","blocked":true,"reason":"bad.py:1:1: error: unused import","message":null}}"#
+        );
+
+        let (response, _) = handle(
+            r#"{"jsonrpc":"2.0","id":"a","method":"lintPaths","params":{"paths":["/ws/bad.py","/ws/ok.py"],"lenient":true}}"#,
+            fake_lint,
+        );
+        let response = json::parse(&response.unwrap()).unwrap();
+        assert_eq!(response.get("id").and_then(Json::as_str), Some("a"));
+        let results = response.get("result").and_then(Json::as_array).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].get("blocked"), Some(&Json::Bool(false)));
+    }
+
+    #[test]
+    fn errors_notifications_and_shutdown() {
+        let (response, _) = handle("{not json", fake_lint);
+        assert!(response.unwrap().contains("-32700"));
+        let (response, _) = handle(r#"{"id":2,"method":"lintFile","params":{}}"#, fake_lint);
+        assert!(response.unwrap().contains("-32602"));
+        let (response, _) = handle(r#"{"id":3,"method":"format"}"#, fake_lint);
+        assert!(response.unwrap().contains("-32601"));
+        // Notifications get no response
+        let (response, _) = handle(
+            r#"{"method":"lintFile","params":{"path":"/a.py"}}"#,
+            fake_lint,
+        );
+        assert_eq!(response, None);
+        let (response, shutdown) = handle(r#"{"id":4,"method":"shutdown"}"#, fake_lint);
+        assert_eq!(
+            response.unwrap(),
+            r#"{"jsonrpc":"2.0","id":4,"result":null}"#
+        );
+        assert!(shutdown);
+    }
+}
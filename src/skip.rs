@@ -0,0 +1,72 @@
+use std::fmt;
+
+use crate::lint::continue_result;
+
+/// Why the hook let an event through without linting. The code appears in the skip
+/// message, e.g. `[ralph-hook-lint] skip (no-linter): no Go linter found for main.go.`, so
+/// debug output and replay logs can be grouped by reason.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The payload has no `tool_input.file_path`, or nothing was collected
+    NoFilePath,
+    /// The payload has no `session_id` to collect under
+    NoSession,
+    /// No language handles the file, or not in this mode
+    UnsupportedType,
+    /// No project (and no standalone check) could be found for the file
+    NoProjectRoot,
+    /// No linter or toolchain for the file is installed or enabled
+    NoLinter,
+    /// `[hooks]` rules or collection filters leave the file out
+    Excluded,
+    /// The file is over `[hooks] max_file_kb`
+    TooLarge,
+    /// The file already passed lint and hasn't changed since
+    Debounced,
+    /// The project was linted too recently for its `[rate_limit]`
+    RateLimited,
+}
+
+impl SkipReason {
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::NoFilePath => "no-file-path",
+            Self::NoSession => "no-session",
+            Self::UnsupportedType => "unsupported-type",
+            Self::NoProjectRoot => "no-project-root",
+            Self::NoLinter => "no-linter",
+            Self::Excluded => "excluded",
+            Self::TooLarge => "too-large",
+            Self::Debounced => "debounced",
+            Self::RateLimited => "rate-limited",
+        }
+    }
+
+    /// The continue response for this skip, with `detail` after the reason code.
+    pub fn result(self, debug: bool, detail: &str) -> String {
+        continue_result(debug, &format!("[ralph-hook-lint] skip ({self}): {detail}"))
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn result_tags_message_with_code() {
+        assert_eq!(
+            SkipReason::NoLinter.result(true, "no linter found for a.ts."),
+            r#"{"continue":true,"systemMessage":"[ralph-hook-lint] skip (no-linter): no linter found for a.ts."}"#
+        );
+        assert_eq!(
+            SkipReason::Debounced.result(false, "unchanged"),
+            r#"{"continue":true}"#
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+use crate::json::{self, Json};
+use crate::lint::escape_json;
+use crate::project::Lang;
+use crate::schedule;
+
+/// Start of the note shown when a project is linted with the fast tier
+pub const NOTE_PREFIX: &str = "[ralph-hook-lint] fast tier";
+
+/// Linters the fast tier skips per language. Each language keeps a quicker check: ruff or
+/// flake8 for Python, revive/staticcheck/go vet for Go, the JS linters without `tsc -b`, and
+/// the compile check for C/C++.
+const SLOW_LINTERS: [(Lang, &[&str]); 4] = [
+    (Lang::Python, &["mypy", "pylint"]),
+    (Lang::JavaScript, &["tsc"]),
+    (Lang::Go, &["golangci-lint"]),
+    (Lang::C, &["clang-tidy"]),
+];
+
+/// How long a recorded full-tier time decides the tier. After that the full tier runs again,
+/// so one slow run (a cold cache, a loaded machine) doesn't downgrade a project for good.
+const FULL_TIER_RECHECK: Duration = Duration::from_secs(60 * 60);
+
+/// A project whose last recorded lint took longer than its `[process] max_lint_seconds`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Downgrade {
+    root: String,
+    lang: Lang,
+    recorded: Duration,
+    max: Duration,
+}
+
+impl fmt::Display for Downgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{NOTE_PREFIX} for {}: its last lint took {}s, over max_lint_seconds = {}, so {} were skipped.",
+            self.root,
+            self.recorded.as_secs(),
+            self.max.as_secs(),
+            slow_linters(self.lang).join(", ")
+        )
+    }
+}
+
+/// The downgrade for a `lang` project, if the language has a fast tier and the project's lint
+/// history exceeds its time budget.
+pub fn downgrade(project_root: &str, lang: Lang) -> Option<Downgrade> {
+    (!slow_linters(lang).is_empty())
+        .then(|| {
+            downgrade_from(
+                &Config::load(project_root),
+                &schedule::load_records(),
+                project_root,
+                lang,
+                now(),
+            )
+        })
+        .flatten()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn downgrade_from(
+    config: &Config,
+    records: &HashMap<String, schedule::Recorded>,
+    project_root: &str,
+    lang: Lang,
+    now: u64,
+) -> Option<Downgrade> {
+    let max = config
+        .get_int("process.max_lint_seconds")
+        .and_then(|secs| u64::try_from(secs).ok())
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)?;
+    let recorded = records.get(project_root)?;
+    let fresh = now.saturating_sub(recorded.at) < FULL_TIER_RECHECK.as_secs();
+    (fresh && recorded.elapsed > max).then(|| Downgrade {
+        root: project_root.to_string(),
+        lang,
+        recorded: recorded.elapsed,
+        max,
+    })
+}
+
+/// The linters the fast tier skips for `lang`.
+fn slow_linters(lang: Lang) -> &'static [&'static str] {
+    SLOW_LINTERS
+        .iter()
+        .find(|(slow_lang, _)| *slow_lang == lang)
+        .map_or(&[], |(_, linters)| linters)
+}
+
+/// Whether the fast tier skips `linter` for `lang`.
+pub fn is_slow(lang: Lang, linter: &str) -> bool {
+    slow_linters(lang).contains(&linter)
+}
+
+/// Add a note, such as a downgrade, to a lint response: after a block reason, or as the
+/// `systemMessage` of a continue response.
+pub fn annotate(output: &str, downgrade: &impl fmt::Display) -> String {
+    let Some(response) = json::parse(output) else {
+        return output.to_string();
+    };
+    let field = |name: &str| response.get(name).and_then(Json::as_str);
+    if let Some(reason) = field("reason") {
+        return format!(
+            r#"{{"decision":"block","reason":"{}"}}"#,
+            escape_json(&format!("{reason}\n\n{downgrade}"))
+        );
+    }
+    let message = field("systemMessage").map_or_else(
+        || downgrade.to_string(),
+        |message| format!("{message}\n{downgrade}"),
+    );
+    format!(
+        r#"{{"continue":true,"systemMessage":"{}"}}"#,
+        escape_json(&message)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downgrades_only_past_the_budget() {
+        let config = Config::parse("[process]\nmax_lint_seconds = 20\n");
+        let recorded = |secs| schedule::Recorded {
+            elapsed: Duration::from_secs(secs),
+            at: 1000,
+        };
+        let records = HashMap::from([
+            ("/slow".to_string(), recorded(45)),
+            ("/quick".to_string(), recorded(3)),
+        ]);
+        let check = |config: &Config, root: &str| {
+            downgrade_from(config, &records, root, Lang::Python, 1010)
+        };
+        let slow = check(&config, "/slow").unwrap();
+        assert_eq!(
+            slow.to_string(),
+            "[ralph-hook-lint] fast tier for /slow: its last lint took 45s, over \
+             max_lint_seconds = 20, so mypy, pylint were skipped."
+        );
+        assert_eq!(check(&config, "/quick"), None);
+        assert_eq!(check(&config, "/new"), None);
+        assert_eq!(check(&Config::default(), "/slow"), None);
+    }
+
+    #[test]
+    fn full_tier_runs_again_once_the_recorded_time_is_stale() {
+        let config = Config::parse("[process]\nmax_lint_seconds = 20\n");
+        let records = HashMap::from([(
+            "/slow".to_string(),
+            schedule::Recorded {
+                elapsed: Duration::from_secs(45),
+                at: 1000,
+            },
+        )]);
+        let recheck = 1000 + FULL_TIER_RECHECK.as_secs();
+        assert!(downgrade_from(&config, &records, "/slow", Lang::Go, recheck - 1).is_some());
+        assert_eq!(
+            downgrade_from(&config, &records, "/slow", Lang::Go, recheck),
+            None
+        );
+    }
+
+    #[test]
+    fn only_languages_with_a_fast_tier_skip_linters() {
+        assert!(is_slow(Lang::JavaScript, "tsc"));
+        assert!(!is_slow(Lang::Python, "tsc"));
+        assert!(slow_linters(Lang::Rust).is_empty());
+    }
+
+    #[test]
+    fn annotate_appends_the_note() {
+        let downgrade = Downgrade {
+            root: "/p".to_string(),
+            lang: Lang::Go,
+            recorded: Duration::from_secs(30),
+            max: Duration::from_secs(10),
+        };
+        let blocked = annotate(r#"{"decision":"block","reason":"bad"}"#, &downgrade);
+        let reason = json::parse(&blocked).unwrap();
+        let reason = reason.get("reason").and_then(Json::as_str).unwrap();
+        assert!(reason.starts_with("bad\n\n[ralph-hook-lint] fast tier for /p"));
+
+        let passed = annotate(r#"{"continue":true}"#, &downgrade);
+        assert!(
+            passed.starts_with(r#"{"continue":true,"systemMessage":"[ralph-hook-lint] fast tier"#)
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::collect;
+use crate::config::Config;
+use crate::extract::extract_reason_field;
+use crate::lint::{continue_result, escape_json};
+
+const ERRORS_PREFIX: &str = "[ralph-hook-lint] lint errors in ";
+const SECTION_SEPARATOR: &str = "\n\n---\n\n";
+
+/// Record a verdict for every file a block response reports on and, for files whose nearest
+/// config sets `[output] report = "new"`, drop the diagnostics the file already had when it was
+/// last clean (or first linted), as many times as it had them. Returns the rewritten response and the files it blocked on.
+/// Clean files are recorded by the caller, which knows what was linted.
+pub fn apply(session_id: &str, output: String, debug: bool) -> (String, Vec<String>) {
+    if !output.contains(r#""decision":"block"#) {
+        return (output, Vec::new());
+    }
+    let Some(reason) = extract_reason_field(&output) else {
+        return (output, Vec::new());
+    };
+
+    let mut blocked = Vec::new();
+    let mut hidden = 0;
+    let mut sections = Vec::new();
+    for section in reason.split(SECTION_SEPARATOR) {
+        let Some(file) = section_file(section) else {
+            sections.push(section.to_string());
+            continue;
+        };
+        let issues: Vec<&str> = section.lines().filter(|line| is_issue(line)).collect();
+        let Ok(verdict) = collect::record_verdict(session_id, file, &issues) else {
+            sections.push(section.to_string());
+            continue;
+        };
+        blocked.push(file.to_string());
+        if !reports_new_only(file) || verdict.baseline.is_empty() {
+            sections.push(section.to_string());
+            continue;
+        }
+        // Each baseline occurrence hides one matching line, so a repeat of a known issue is new
+        let mut baseline: HashMap<u64, usize> = HashMap::new();
+        for key in verdict.baseline {
+            *baseline.entry(key).or_default() += 1;
+        }
+        let mut is_known = |line: &str| {
+            is_issue(line)
+                && baseline
+                    .get_mut(&collect::issue_key(line))
+                    .filter(|count| **count > 0)
+                    .map(|count| *count -= 1)
+                    .is_some()
+        };
+        let kept: Vec<&str> = section.lines().filter(|line| !is_known(line)).collect();
+        let known = section.lines().count() - kept.len();
+        hidden += known;
+        if known == issues.len() {
+            continue;
+        }
+        sections.push(kept.join("\n"));
+    }
+
+    if hidden == 0 {
+        return (output, blocked);
+    }
+    let note = format!("[ralph-hook-lint] {hidden} pre-existing issue(s) not reported.");
+    if sections.is_empty() {
+        return (continue_result(debug, &note), blocked);
+    }
+    sections.push(note);
+    let response = format!(
+        r#"{{"decision":"block","reason":"{}"}}"#,
+        escape_json(&sections.join(SECTION_SEPARATOR))
+    );
+    (response, blocked)
+}
+
+/// The file a `lint errors in <file> using <linter>:` section reports on.
+fn section_file(section: &str) -> Option<&str> {
+    let header = section.lines().next()?.strip_prefix(ERRORS_PREFIX)?;
+    Some(header.rsplit_once(" using ")?.0)
+}
+
+/// Diagnostic lines, as opposed to the hook's own header and trailer.
+fn is_issue(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with("[ralph-hook-lint]") && line != "Fix lint errors."
+}
+
+fn reports_new_only(file_path: &str) -> bool {
+    Path::new(file_path).parent().is_some_and(|dir| {
+        Config::load(&dir.to_string_lossy()).get_str("output.report") == Some("new")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn section_file_reads_header() {
+        let section = "[ralph-hook-lint] lint errors in /p/a using b.py using ruff:\n\nx";
+        assert_eq!(section_file(section), Some("/p/a using b.py"));
+        assert_eq!(
+            section_file("[ralph-hook-lint] error linting /p: boom"),
+            None
+        );
+    }
+
+    #[test]
+    fn header_and_trailer_are_not_issues() {
+        assert!(is_issue("a.py:1:1: F401 unused"));
+        assert!(!is_issue(
+            "[ralph-hook-lint] lint errors in a.py using ruff:"
+        ));
+        assert!(!is_issue("Fix lint errors."));
+        assert!(!is_issue("  "));
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::extract::extract_reason_field;
+use crate::json::{self, Json};
+use crate::lint::escape_json;
+use crate::project::detect_lang;
+
+/// How often the tree is rescanned.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Directories never scanned: VCS metadata, build output, and installed dependencies.
+const IGNORED_DIRS: &[&str] = &[
+    "target",
+    "node_modules",
+    "vendor",
+    "build",
+    "dist",
+    "__pycache__",
+];
+
+/// Modification times of the lintable files under a directory.
+type Snapshot = HashMap<PathBuf, SystemTime>;
+
+/// Watch `dir` and run `lint` (the full hook pipeline, given a payload) on each file that
+/// changes, printing one result per file. Polls modification times rather than subscribing to
+/// OS notifications, and waits for a quiet poll before linting so a burst of saves (a
+/// formatter, a branch switch) lints each file once.
+pub fn run(dir: &str, lint: impl Fn(&str) -> String) -> Result<(), Box<dyn std::error::Error>> {
+    let root = fs::canonicalize(dir).map_err(|e| format!("cannot watch {dir}: {e}"))?;
+    let mut known = snapshot(&root);
+    let mut pending = BTreeSet::new();
+    println!(
+        "watching {} ({} files), Ctrl-C to stop",
+        root.display(),
+        known.len()
+    );
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = snapshot(&root);
+        let changes = changed(&known, &current);
+        known = current;
+        if !changes.is_empty() {
+            pending.extend(changes);
+            continue;
+        }
+        for file in std::mem::take(&mut pending) {
+            // Deleted while the burst settled
+            if !file.is_file() {
+                continue;
+            }
+            let payload = format!(
+                r#"{{"tool_input":{{"file_path":"{}"}}}}"#,
+                escape_json(&file.to_string_lossy())
+            );
+            let shown = file.strip_prefix(&root).unwrap_or(&file);
+            println!("{}: {}", shown.display(), summarize(&lint(&payload)));
+        }
+    }
+}
+
+/// Lintable files under `root` and their modification times.
+fn snapshot(root: &Path) -> Snapshot {
+    let mut files = Snapshot::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if !is_ignored_dir(&entry.file_name().to_string_lossy()) {
+                    dirs.push(path);
+                }
+            } else if file_type.is_file() && detect_lang(&path.to_string_lossy()).is_some() {
+                if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                    files.insert(path, modified);
+                }
+            }
+        }
+    }
+    files
+}
+
+fn is_ignored_dir(name: &str) -> bool {
+    name.starts_with('.') || IGNORED_DIRS.contains(&name)
+}
+
+/// Files that are new in `after` or whose modification time moved.
+fn changed(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+/// One line for a hook response: the block reason, or the debug message.
+fn summarize(response: &str) -> String {
+    if let Some(reason) = extract_reason_field(response) {
+        return reason;
+    }
+    json::parse(response)
+        .and_then(|j| {
+            j.get("systemMessage")
+                .and_then(Json::as_str)
+                .map(|m| m.trim_start_matches("[ralph-hook-lint] ").to_string())
+        })
+        .unwrap_or_else(|| "ok".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_skips_ignored_dirs_and_unknown_files() {
+        let dir = std::env::temp_dir().join(format!("ralph-lint-watch-{}", std::process::id()));
+        for name in ["src/main.rs", "node_modules/x/index.js", ".git/hooks/a.py"] {
+            let path = dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "").unwrap();
+        }
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let files: Vec<_> = snapshot(&dir).into_keys().collect();
+        assert_eq!(files, vec![dir.join("src/main.rs")]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn changes_are_new_or_touched_files() {
+        let t0 = SystemTime::UNIX_EPOCH;
+        let t1 = t0 + Duration::from_secs(1);
+        let before = Snapshot::from([("a.rs".into(), t0), ("b.rs".into(), t0)]);
+        let after = Snapshot::from([
+            ("a.rs".into(), t0),
+            ("b.rs".into(), t1),
+            ("c.rs".into(), t0),
+        ]);
+        let mut changes = changed(&before, &after);
+        changes.sort();
+        assert_eq!(changes, vec![PathBuf::from("b.rs"), PathBuf::from("c.rs")]);
+        assert!(changed(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn summarize_responses() {
+        assert_eq!(
+            summarize(r#"{"decision":"block","reason":"lint errors in a.rs"}"#),
+            "lint errors in a.rs"
+        );
+        assert_eq!(
+            summarize(
+                r#"{"continue":true,"systemMessage":"[ralph-hook-lint] lint passed for a.rs."}"#
+            ),
+            "lint passed for a.rs."
+        );
+        assert_eq!(summarize(r#"{"continue":true}"#), "ok");
+    }
+}
@@ -0,0 +1,108 @@
+use std::path::Path;
+
+/// Translate a file path from the other side of a WSL boundary into one this process can open:
+/// under WSL, `C:\src\app.ts` becomes `/mnt/c/src/app.ts` and `\\wsl$\Ubuntu\home\me\app.ts`
+/// becomes `/home/me/app.ts`; on Windows, `/mnt/c/src/app.ts` becomes `C:\src\app.ts`.
+/// Paths that need no translation are returned unchanged.
+pub fn translate(path: &str) -> String {
+    if cfg!(windows) {
+        return wsl_to_windows(path).unwrap_or_else(|| path.to_string());
+    }
+    if let Some(linux) = unc_to_linux(path) {
+        return linux;
+    }
+    windows_to_wsl(path)
+        // Only under WSL, where the drive is mounted; elsewhere the path is just unusable
+        .filter(|mounted| Path::new(&mounted[.."/mnt/c".len()]).is_dir())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// `C:\dir\file` or `C:/dir/file` to `/mnt/c/dir/file`.
+fn windows_to_wsl(path: &str) -> Option<String> {
+    let bytes = path.as_bytes();
+    if bytes.len() < 3
+        || !bytes[0].is_ascii_alphabetic()
+        || bytes[1] != b':'
+        || !matches!(bytes[2], b'\\' | b'/')
+    {
+        return None;
+    }
+    let drive = char::from(bytes[0]).to_ascii_lowercase();
+    let rest = path[3..].replace('\\', "/");
+    Some(format!("/mnt/{drive}/{rest}"))
+}
+
+/// `\\wsl$\<distro>\home\me` or `\\wsl.localhost\<distro>\home\me` to `/home/me`.
+fn unc_to_linux(path: &str) -> Option<String> {
+    let normalized = path.replace('\\', "/");
+    let rest = normalized
+        .strip_prefix("//wsl$/")
+        .or_else(|| normalized.strip_prefix("//wsl.localhost/"))?;
+    let (_distro, inner) = rest.split_once('/')?;
+    Some(format!("/{inner}"))
+}
+
+/// `/mnt/c/dir/file` to `C:\dir\file`.
+fn wsl_to_windows(path: &str) -> Option<String> {
+    let rest = path.strip_prefix("/mnt/")?;
+    let mut chars = rest.chars();
+    let drive = chars.next().filter(char::is_ascii_alphabetic)?;
+    let rest = chars.as_str();
+    if !(rest.is_empty() || rest.starts_with('/')) {
+        return None;
+    }
+    Some(format!(
+        "{}:\\{}",
+        drive.to_ascii_uppercase(),
+        rest.trim_start_matches('/').replace('/', "\\")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_paths_map_to_mnt() {
+        assert_eq!(
+            windows_to_wsl(r"C:\Users\me\app\src\index.ts").as_deref(),
+            Some("/mnt/c/Users/me/app/src/index.ts")
+        );
+        assert_eq!(
+            windows_to_wsl("d:/work/main.go").as_deref(),
+            Some("/mnt/d/work/main.go")
+        );
+        assert_eq!(windows_to_wsl("/home/me/main.go"), None);
+        assert_eq!(windows_to_wsl("C:relative"), None);
+    }
+
+    #[test]
+    fn wsl_unc_paths_map_to_linux() {
+        assert_eq!(
+            unc_to_linux(r"\\wsl$\Ubuntu\home\me\app\main.rs").as_deref(),
+            Some("/home/me/app/main.rs")
+        );
+        assert_eq!(
+            unc_to_linux(r"\\wsl.localhost\Debian\srv\x.py").as_deref(),
+            Some("/srv/x.py")
+        );
+        assert_eq!(unc_to_linux(r"\\server\share\x.py"), None);
+    }
+
+    #[test]
+    fn mnt_paths_map_to_windows() {
+        assert_eq!(
+            wsl_to_windows("/mnt/c/Users/me/app.ts").as_deref(),
+            Some(r"C:\Users\me\app.ts")
+        );
+        assert_eq!(wsl_to_windows("/mnt/data/app.ts"), None);
+        assert_eq!(wsl_to_windows("/home/me/app.ts"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn translate_leaves_native_paths_alone() {
+        assert_eq!(translate("/home/me/app.ts"), "/home/me/app.ts");
+        assert_eq!(translate(r"\\wsl$\Ubuntu\home\me\a.ts"), "/home/me/a.ts");
+    }
+}
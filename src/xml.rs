@@ -0,0 +1,201 @@
+/// A token from an XML document, for linters that write XML reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Opening (or self-closing) tag with its attributes
+    Start {
+        name: String,
+        attrs: Vec<(String, String)>,
+    },
+    /// Closing tag; also emitted right after a self-closing tag
+    End(String),
+    /// Non-whitespace text between tags, with entities decoded
+    Text(String),
+}
+
+/// Look up an attribute value by name.
+pub fn attr<'a>(attrs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(k, _)| k == name)
+        .map(|(_, v)| v.as_str())
+}
+
+/// Tokenize an XML document. Comments, processing instructions, and doctypes are skipped;
+/// CDATA sections become text. Malformed trailing input is ignored.
+pub fn events(xml: &str) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut rest = xml;
+
+    while let Some(lt) = rest.find('<') {
+        push_text(&mut events, &rest[..lt]);
+        rest = &rest[lt..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            let Some(end) = after.find("-->") else { break };
+            rest = &after[end + 3..];
+        } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let Some(end) = after.find("]]>") else { break };
+            let text = &after[..end];
+            if !text.trim().is_empty() {
+                events.push(Event::Text(text.trim().to_string()));
+            }
+            rest = &after[end + 3..];
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            let Some(end) = rest.find('>') else { break };
+            rest = &rest[end + 1..];
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let Some(end) = after.find('>') else { break };
+            events.push(Event::End(after[..end].trim().to_string()));
+            rest = &after[end + 1..];
+        } else {
+            let Some(end) = find_tag_end(rest) else { break };
+            let inner = &rest[1..end];
+            let (inner, self_closing) = inner
+                .strip_suffix('/')
+                .map_or((inner, false), |i| (i, true));
+            let (name, attrs) = parse_tag(inner);
+            events.push(Event::Start {
+                name: name.clone(),
+                attrs,
+            });
+            if self_closing {
+                events.push(Event::End(name));
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+    push_text(&mut events, rest);
+    events
+}
+
+fn push_text(events: &mut Vec<Event>, raw: &str) {
+    let text = raw.trim();
+    if !text.is_empty() {
+        events.push(Event::Text(decode_entities(text)));
+    }
+}
+
+/// Index of the `>` closing a start tag, skipping `>` inside quoted attribute values.
+fn find_tag_end(s: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '>' => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_tag(inner: &str) -> (String, Vec<(String, String)>) {
+    let inner = inner.trim();
+    let name_end = inner.find(char::is_whitespace).unwrap_or(inner.len());
+    let name = inner[..name_end].to_string();
+
+    let mut attrs = Vec::new();
+    let mut rest = inner[name_end..].trim_start();
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim().to_string();
+        let value_part = rest[eq + 1..].trim_start();
+        let Some(quote) = value_part
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+        else {
+            break;
+        };
+        let Some(close) = value_part[1..].find(quote) else {
+            break;
+        };
+        attrs.push((key, decode_entities(&value_part[1..=close])));
+        rest = value_part[close + 2..].trim_start();
+    }
+    (name, attrs)
+}
+
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(name: &str, attrs: &[(&str, &str)]) -> Event {
+        Event::Start {
+            name: name.to_string(),
+            attrs: attrs
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn tokenizes_nested_elements() {
+        let xml = r#"<?xml version="1.0"?>
+            <pmd version="7">
+              <!-- comment -->
+              <file name="/a/App.java">
+                <violation beginline="3" rule="UnusedPrivateField">
+                  Avoid unused private fields &amp; such
+                </violation>
+              </file>
+            </pmd>"#;
+        assert_eq!(
+            events(xml),
+            vec![
+                start("pmd", &[("version", "7")]),
+                start("file", &[("name", "/a/App.java")]),
+                start(
+                    "violation",
+                    &[("beginline", "3"), ("rule", "UnusedPrivateField")]
+                ),
+                Event::Text("Avoid unused private fields & such".to_string()),
+                Event::End("violation".to_string()),
+                Event::End("file".to_string()),
+                Event::End("pmd".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn self_closing_tags_and_quoted_gt() {
+        let xml = r#"<SourceLine start="3" note="a > b" primary='true'/><x/>"#;
+        assert_eq!(
+            events(xml),
+            vec![
+                start(
+                    "SourceLine",
+                    &[("start", "3"), ("note", "a > b"), ("primary", "true")]
+                ),
+                Event::End("SourceLine".to_string()),
+                start("x", &[]),
+                Event::End("x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn cdata_becomes_text() {
+        let xml = "<m><![CDATA[a < b]]></m>";
+        assert_eq!(events(xml)[1], Event::Text("a < b".to_string()));
+    }
+
+    #[test]
+    fn attr_lookup() {
+        let attrs = vec![("a".to_string(), "1".to_string())];
+        assert_eq!(attr(&attrs, "a"), Some("1"));
+        assert_eq!(attr(&attrs, "b"), None);
+    }
+}
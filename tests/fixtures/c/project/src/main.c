@@ -0,0 +1,5 @@
+#include "util.h"
+
+int twice(int x) { return x * 2; }
+
+int main(void) { return twice(0); }
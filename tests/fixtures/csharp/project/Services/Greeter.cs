@@ -0,0 +1,6 @@
+namespace App.Services;
+
+public static class Greeter
+{
+    public static string Greet(string name) => $"Hello, {name}";
+}
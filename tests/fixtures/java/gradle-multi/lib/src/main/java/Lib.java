@@ -0,0 +1,5 @@
+public class Lib {
+    public String greet() {
+        return "Hello";
+    }
+}
@@ -1,6 +1,6 @@
 use std::fs;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 fn run_binary(input: &str) -> String {
@@ -36,6 +36,48 @@ fn run_binary_with_args(input: &str, args: &[&str]) -> String {
     String::from_utf8_lossy(&output.stdout).to_string()
 }
 
+/// Run the binary with `PATH` set to `path`, so stand-in linters are found first.
+fn run_binary_with_path(input: &str, args: &[&str], path: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ralph-hook-lint"))
+        .args(args)
+        .env("PATH", path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn binary");
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().expect("Failed to read output");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+/// `dir` ahead of the inherited `PATH`.
+fn path_with(dir: &Path) -> String {
+    format!(
+        "{}:{}",
+        dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    )
+}
+
+/// Write an executable stand-in for a linter at `dir/name`.
+fn write_stub(dir: &Path, name: &str, script: &str) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::create_dir_all(dir).unwrap();
+    let path = dir.join(name);
+    fs::write(&path, script).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
 #[test]
 fn finds_package_json_directory() {
     let fixture_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ts/project");
@@ -62,7 +104,7 @@ fn no_package_json_skips() {
     let output = run_binary_debug(input);
 
     assert!(
-        output.contains("no package.json found") || output.contains("skipping lint"),
+        output.contains("skip (no-project-root)"),
         "Expected skip message, got: {output}"
     );
 }
@@ -84,7 +126,7 @@ fn missing_file_path_skips() {
     let output = run_binary_debug(input);
 
     assert!(
-        output.contains("no file_path provided"),
+        output.contains("skip (no-file-path): no file_path provided"),
         "Expected no file_path message, got: {output}"
     );
 }
@@ -332,6 +374,38 @@ fn collect_deduplicates() {
     let _ = fs::remove_file(collect_temp_path(&sid));
 }
 
+#[test]
+fn bash_edits_are_collected_from_the_command() {
+    let sid = format!("integ-bash-{}", std::process::id());
+    let dir = std::env::temp_dir().join(format!("ralph-lint-bash-integ-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("app.py"), "x = 1\n").unwrap();
+    fs::write(dir.join("notes.txt"), "").unwrap();
+    let _ = fs::remove_file(collect_temp_path(&sid));
+
+    let input = format!(
+        r#"{{"session_id":"{sid}","hook_event_name":"PostToolUse","tool_name":"Bash","cwd":"{}","tool_input":{{"command":"sed -i 's/1/2/' app.py && echo hi > notes.txt"}}}}"#,
+        dir.display()
+    );
+    // Off until configured
+    run_binary(&input);
+    assert!(!collect_temp_path(&sid).exists());
+
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[bash]\ncollect = \"command\"\n",
+    )
+    .unwrap();
+    let output = run_binary(&input);
+    assert_eq!(output.trim(), r#"{"continue":true}"#, "got: {output}");
+    let contents = fs::read_to_string(collect_temp_path(&sid)).unwrap();
+    assert!(contents.contains("app.py"), "got: {contents}");
+    assert!(!contents.contains("notes.txt"), "got: {contents}");
+
+    let _ = fs::remove_file(collect_temp_path(&sid));
+    let _ = fs::remove_dir_all(&dir);
+}
+
 #[test]
 fn lint_collected_no_files() {
     // Use a fresh session_id with no collected files
@@ -375,3 +449,3091 @@ fn lint_collected_cleans_up() {
         "temp file should be deleted after lint-collected"
     );
 }
+
+#[cfg(unix)]
+#[test]
+fn lint_collected_skips_files_clean_from_immediate_lint() {
+    let sid = format!("integ-crossmode-{}", std::process::id());
+    let clean_path = std::env::temp_dir().join(format!("ralph-lint-{sid}-clean.txt"));
+    let _ = fs::remove_file(collect_temp_path(&sid));
+    let _ = fs::remove_file(&clean_path);
+
+    let dir =
+        std::env::temp_dir().join(format!("ralph-lint-integ-crossmode-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    let file_path = dir.join("main.py");
+    fs::write(&file_path, "x = 1\n").unwrap();
+    write_stub(
+        &dir.join(".venv/bin"),
+        "ruff",
+        "#!/bin/sh\necho '[]'\nexit 0\n",
+    );
+    let input = format!(
+        r#"{{"session_id":"{sid}","tool_name":"Edit","tool_input":{{"file_path":"{}"}}}}"#,
+        file_path.display()
+    );
+
+    // Immediate lint passes and records the file as clean
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""continue":true"#),
+        "immediate lint should continue, got: {output}"
+    );
+    assert!(clean_path.exists(), "clean-hash file should be recorded");
+
+    // The unchanged file is skipped by lint-collected
+    run_binary_with_args(&input, &["--collect"]);
+    let lint_input = format!(r#"{{"session_id":"{sid}"}}"#);
+    let output = run_binary_with_args(&lint_input, &["--lint-collected", "--debug"]);
+    assert!(
+        output.contains("skip (debounced)"),
+        "unchanged clean file should be skipped, got: {output}"
+    );
+
+    let _ = fs::remove_file(&clean_path);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn files_no_linter_checked_are_not_recorded_clean() {
+    let sid = format!("integ-nolinter-clean-{}", std::process::id());
+    let clean_path = std::env::temp_dir().join(format!("ralph-lint-{sid}-clean.txt"));
+    let _ = fs::remove_file(&clean_path);
+
+    let dir =
+        std::env::temp_dir().join(format!("ralph-lint-integ-nolinter-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    let file_path = dir.join("main.py");
+    fs::write(&file_path, "x = 1\n").unwrap();
+    let input = format!(
+        r#"{{"session_id":"{sid}","tool_name":"Edit","tool_input":{{"file_path":"{}"}}}}"#,
+        file_path.display()
+    );
+
+    // No Python linter on PATH: the edit continues, but nothing vouches for the file
+    let output = run_binary_with_path(&input, &[], "/nonexistent");
+    assert_eq!(output.trim(), r#"{"continue":true}"#);
+    assert!(
+        !clean_path.exists(),
+        "a file no linter checked should not be recorded as clean"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn standalone_rust_file_checked_with_rustc() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-scratch-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let good = dir.join("good.rs");
+    fs::write(&good, "fn main() {\n    println!(\"hi\");\n}\n").unwrap();
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, good.display());
+    let output = run_binary_debug(&input);
+    assert!(
+        output.contains("lint passed") && output.contains("rustc"),
+        "clean scratch file should pass rustc, got: {output}"
+    );
+
+    let bad = dir.join("bad.rs");
+    fs::write(&bad, "fn main() {\n    let x: u32 = \"nope\";\n}\n").unwrap();
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, bad.display());
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("rustc"),
+        "type error in scratch file should block, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn cargo_script_without_nightly_is_skipped() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-nightly-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let script = dir.join("tool.rs");
+    fs::write(
+        &script,
+        "#!/usr/bin/env -S cargo +nightly -Zscript\nfn main() {}\n",
+    )
+    .unwrap();
+
+    // Stand-ins for a stable-only rustup, and a cargo that logs being run
+    write_stub(
+        &dir.join("bin"),
+        "rustup",
+        "#!/bin/sh\necho 'stable-x86_64-unknown-linux-gnu (default)'\n",
+    );
+    write_stub(
+        &dir.join("bin"),
+        "cargo",
+        &format!(
+            "#!/bin/sh\necho run >> '{}'\necho \"error: toolchain 'nightly' is not installed\" >&2\nexit 1\n",
+            dir.join("cargo.log").display()
+        ),
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, script.display());
+    let output = run_binary_with_path(&input, &["--debug"], &path_with(&dir.join("bin")));
+    assert!(
+        output.contains("skip (no-linter)") && output.contains("nightly toolchain"),
+        "a cargo script without nightly should be skipped, got: {output}"
+    );
+    assert!(!dir.join("cargo.log").exists(), "cargo must not run");
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn standalone_js_and_python_files_get_syntax_checked() {
+    let dir = std::env::temp_dir().join(format!(
+        "ralph-lint-integ-standalone-{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+
+    let js = dir.join("scratch.js");
+    fs::write(&js, "function (\n").unwrap();
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, js.display());
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("node"),
+        "JS syntax error should block via node --check, got: {output}"
+    );
+
+    let py = dir.join("scratch.py");
+    fs::write(&py, "def broken(:\n").unwrap();
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, py.display());
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#),
+        "Python syntax error should block, got: {output}"
+    );
+
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[standalone]\nenabled = false\n",
+    )
+    .unwrap();
+    let output = run_binary(&input);
+    assert_eq!(
+        output.trim(),
+        r#"{"continue":true}"#,
+        "standalone checks disabled by config should skip, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn post_tool_use_payload_gets_hook_specific_output() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-schema-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let bad = dir.join("bad.js");
+    fs::write(&bad, "function (\n").unwrap();
+    let input = format!(
+        r#"{{"hook_event_name":"PostToolUse","tool_input":{{"file_path":"{}"}}}}"#,
+        bad.display()
+    );
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains(
+                r#""hookSpecificOutput":{"hookEventName":"PostToolUse","additionalContext":"#
+            ),
+        "current-schema block should include hookSpecificOutput, got: {output}"
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, bad.display());
+    let output = run_binary(&input);
+    assert!(
+        !output.contains("hookSpecificOutput"),
+        "legacy payload should get decision/reason only, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn output_policy_from_working_directory_config() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-policy-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[output]\nsuppress_output = true\nsuccess_message = true\n",
+    )
+    .unwrap();
+    let good = dir.join("good.js");
+    fs::write(&good, "const a = 1;\n").unwrap();
+
+    let binary = env!("CARGO_BIN_EXE_ralph-hook-lint");
+    let mut child = Command::new(binary)
+        .current_dir(&dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn binary");
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, good.display());
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let output = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        output.contains(r#""suppressOutput":true"#) && output.contains("node clean: "),
+        "expected suppressed output with a compact success message, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn hook_errors_block_when_configured_to_fail_closed() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-onerror-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let run = || {
+        let output = Command::new(env!("CARGO_BIN_EXE_ralph-hook-lint"))
+            .args(["--input", "/nonexistent/payload.json"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let output = run();
+    assert!(
+        output.starts_with(r#"{"continue":true"#),
+        "fail-open by default, got: {output}"
+    );
+
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[output]\non_error = \"fail-closed\"\n",
+    )
+    .unwrap();
+    let output = run();
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("cannot read --input"),
+        "expected a block on the hook error, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn input_flag_reads_payload_from_file() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-input-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let payload = dir.join("payload.json");
+    fs::write(&payload, r#"{"tool_input":{"other":"value"}}"#).unwrap();
+
+    // Empty stdin: the binary doesn't read it when --input is given
+    let output = run_binary_with_args("", &["--debug", "--input", &payload.to_string_lossy()]);
+    assert!(
+        output.contains("no file_path provided"),
+        "expected payload from --input file, got: {output}"
+    );
+
+    let output = run_binary_with_args("", &["--debug", "--input", "/nonexistent/payload.json"]);
+    assert!(
+        output.contains("cannot read --input"),
+        "expected read error, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn replay_reruns_logged_events() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-replay-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let bad = dir.join("bad.js");
+    fs::write(&bad, "function (\n").unwrap();
+    let sid = format!("replay-{}", std::process::id());
+
+    let log = dir.join("session.jsonl");
+    fs::write(
+        &log,
+        format!(
+            "{{\"session_id\":\"{sid}\",\"hook_event_name\":\"PostToolUse\",\"tool_input\":{{\"file_path\":\"{}\"}}}}\n\n{{\"session_id\":\"{sid}\",\"hook_event_name\":\"Stop\"}}\n",
+            bad.display()
+        ),
+    )
+    .unwrap();
+
+    let output = run_binary_with_args("", &["replay", &log.to_string_lossy(), "--collect"]);
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 4, "expected two events, got: {output}");
+    assert!(lines[0].starts_with("#1 PostToolUse ") && lines[0].ends_with("bad.js"));
+    assert_eq!(lines[1], r#"{"continue":true}"#);
+    assert_eq!(lines[2], "#3 Stop");
+    assert!(
+        lines[3].contains(r#""decision":"block""#),
+        "collected file should block at Stop, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn unknown_flag_exits_with_usage() {
+    let binary = env!("CARGO_BIN_EXE_ralph-hook-lint");
+    let output = Command::new(binary)
+        .arg("--lenint")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr.contains("unknown option '--lenint'") && stderr.contains("Usage:"),
+        "expected usage error, got: {stderr}"
+    );
+}
+
+#[test]
+fn init_writes_starter_config_once() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-init-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let binary = env!("CARGO_BIN_EXE_ralph-hook-lint");
+
+    let first = Command::new(binary)
+        .arg("init")
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    assert!(first.status.success());
+    assert!(dir.join(".ralph-hook-lint.toml").is_file());
+
+    let second = Command::new(binary)
+        .arg("init")
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    assert_eq!(second.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&second.stderr).contains("already exists"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lint_collected_reports_files_skipped_by_time_budget() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-budget-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[lint_collected]\nbudget_secs = 0\n",
+    )
+    .unwrap();
+    let bad = dir.join("bad.js");
+    fs::write(&bad, "function (\n").unwrap();
+    let sid = format!("budget-{}", std::process::id());
+
+    let binary = env!("CARGO_BIN_EXE_ralph-hook-lint");
+    let run = |args: &[&str], input: String| {
+        let mut child = Command::new(binary)
+            .args(args)
+            .current_dir(&dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        String::from_utf8_lossy(&child.wait_with_output().unwrap().stdout).to_string()
+    };
+
+    run(
+        &["--collect"],
+        format!(
+            r#"{{"session_id":"{sid}","tool_input":{{"file_path":"{}"}}}}"#,
+            bad.display()
+        ),
+    );
+    let output = run(
+        &["--lint-collected"],
+        format!(r#"{{"session_id":"{sid}"}}"#),
+    );
+    assert!(
+        output.contains(r#""continue":true"#)
+            && output.contains("time budget of 0s reached")
+            && output.contains("bad.js"),
+        "expected skipped file in systemMessage, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn project_pipeline_runs_lint_staged_commands() {
+    let dir =
+        std::env::temp_dir().join(format!("ralph-lint-integ-pipeline-{}", std::process::id()));
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"name":"x","lint-staged":{"*.js":["! grep -n TODO","prettier --write"]}}"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[js]\nproject_pipeline = true\n",
+    )
+    .unwrap();
+    let file = dir.join("src/index.js");
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+
+    fs::write(&file, "// TODO: finish\n").unwrap();
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("lint-staged"),
+        "expected lint-staged command to block, got: {output}"
+    );
+
+    fs::write(&file, "export const done = true;\n").unwrap();
+    let output = run_binary(&input);
+    assert_eq!(output.trim(), r#"{"continue":true}"#);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn makefile_c_project_gets_syntax_checked() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-c-{}", std::process::id()));
+    fs::create_dir_all(dir.join("include")).unwrap();
+    fs::write(dir.join("Makefile"), "all:\n\tcc -Iinclude main.c\n").unwrap();
+    fs::write(dir.join("include/util.h"), "int twice(int x);\n").unwrap();
+    let file = dir.join("main.c");
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+
+    // util.h is only found through the guessed include/ directory
+    fs::write(
+        &file,
+        "#include \"util.h\"\nint main(void) { return twice(0); }\n",
+    )
+    .unwrap();
+    let output = run_binary(&input);
+    assert_eq!(output.trim(), r#"{"continue":true}"#);
+
+    fs::write(
+        &file,
+        "#include \"util.h\"\nint main(void) { return twice(0) }\n",
+    )
+    .unwrap();
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("-fsyntax-only"),
+        "expected syntax error to block, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn c_header_is_checked_through_including_translation_unit() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-header-{}", std::process::id()));
+    fs::create_dir_all(dir.join("include")).unwrap();
+    fs::create_dir_all(dir.join("src")).unwrap();
+    let root = dir.display();
+    fs::write(
+        dir.join("compile_commands.json"),
+        format!(
+            r#"[{{"directory":"{root}","file":"src/main.c","command":"cc -Iinclude -o main.o -c src/main.c"}}]"#
+        ),
+    )
+    .unwrap();
+    // The translation unit's own error is not the header's problem
+    fs::write(
+        dir.join("src/main.c"),
+        "#include \"util.h\"\nint main(void) { return twice(0) }\n",
+    )
+    .unwrap();
+    let header = dir.join("include/util.h");
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, header.display());
+
+    fs::write(&header, "int twice(int x);\n").unwrap();
+    let output = run_binary(&input);
+    assert_eq!(output.trim(), r#"{"continue":true}"#);
+
+    fs::write(&header, "int twice(nope x);\n").unwrap();
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("via src/main.c")
+            && output.contains("util.h:1:")
+            && !output.contains("main.c:2:"),
+        "expected only the header's error, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn gradle_kotlin_build_script_goes_to_kotlin_pipeline() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-kts-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let script = dir.join("build.gradle.kts");
+    fs::write(&script, "plugins {\n    java\n}\n").unwrap();
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, script.display());
+
+    let output = run_binary_debug(&input);
+    assert!(
+        output.contains("ktlint"),
+        "expected the Kotlin pipeline, got: {output}"
+    );
+    assert!(
+        !output.contains("mvn") && !output.contains("gradle tasks"),
+        "the Java pipeline should not run for build scripts, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn cargo_manifest_edit_runs_cargo_check() {
+    let dir =
+        std::env::temp_dir().join(format!("ralph-lint-integ-manifest-{}", std::process::id()));
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("src/lib.rs"), "pub fn one() -> u32 { 1 }\n").unwrap();
+    let manifest = dir.join("Cargo.toml");
+    let input = format!(
+        r#"{{"tool_input":{{"file_path":"{}"}}}}"#,
+        manifest.display()
+    );
+
+    fs::write(
+        &manifest,
+        "[package]\nname = \"manifest-check\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    let output = run_binary(&input);
+    assert_eq!(output.trim(), r#"{"continue":true}"#);
+
+    fs::write(
+        &manifest,
+        "[package]\nname = \"manifest-check\"\nversion = \"0.1.0\"\nedition = \"2021\"\n[features]\ndefault = [\"missing\"]\n",
+    )
+    .unwrap();
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("cargo check"),
+        "expected broken manifest to block, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn package_json_edit_is_validated() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-pkg-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let manifest = dir.join("package.json");
+    let input = format!(
+        r#"{{"tool_input":{{"file_path":"{}"}}}}"#,
+        manifest.display()
+    );
+
+    fs::write(&manifest, r#"{"name":"pkg-check","version":"1.0.0"}"#).unwrap();
+    let output = run_binary(&input);
+    assert_eq!(output.trim(), r#"{"continue":true}"#);
+
+    fs::write(&manifest, r#"{"name":"pkg-check","version":"1.0.0",}"#).unwrap();
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("not valid JSON"),
+        "expected corrupt package.json to block, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lint_collected_groups_errors_per_repository() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-repos-{}", std::process::id()));
+    let sid = format!("repos-{}", std::process::id());
+    let mut files = Vec::new();
+    for repo in ["app", "infra"] {
+        fs::create_dir_all(dir.join(repo).join(".git")).unwrap();
+        let bad = dir.join(repo).join("bad.js");
+        fs::write(&bad, "function (\n").unwrap();
+        files.push(bad);
+    }
+
+    for file in &files {
+        run_binary_with_args(
+            &format!(
+                r#"{{"session_id":"{sid}","tool_input":{{"file_path":"{}"}}}}"#,
+                file.display()
+            ),
+            &["collect"],
+        );
+    }
+    let output = run_binary_with_args(&format!(r#"{{"session_id":"{sid}"}}"#), &["lint-collected"]);
+    for repo in ["app", "infra"] {
+        let header = format!("repository {}", dir.join(repo).display());
+        assert!(
+            output.contains(&header),
+            "expected a section for {repo}, got: {output}"
+        );
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn payload_with_bom_is_accepted() {
+    let binary = env!("CARGO_BIN_EXE_ralph-hook-lint");
+    let mut child = Command::new(binary)
+        .arg("--debug")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let payload = r#"{"tool_input":{"file_path":"/nonexistent/notes.txt"}}"#;
+    let utf16: Vec<u8> = [0xFF, 0xFE]
+        .into_iter()
+        .chain(payload.encode_utf16().flat_map(u16::to_le_bytes))
+        .collect();
+    child.stdin.take().unwrap().write_all(&utf16).unwrap();
+    let output = String::from_utf8_lossy(&child.wait_with_output().unwrap().stdout).to_string();
+    assert!(
+        output.contains("skip (unsupported-type): skipping lint for /nonexistent/notes.txt"),
+        "expected the UTF-16 payload's file_path, got: {output}"
+    );
+}
+
+#[test]
+fn hook_event_name_selects_mode_without_flags() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-auto-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    // PreToolUse Write: the proposed contents are linted before the file exists
+    let js = dir.join("proposed.js");
+    let input = format!(
+        r#"{{"hook_event_name":"PreToolUse","tool_name":"Write","tool_input":{{"file_path":"{}","content":"function (\n"}}}}"#,
+        js.display()
+    );
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("proposed.js"),
+        "PreToolUse Write of bad JS should block, got: {output}"
+    );
+    assert!(
+        output.contains(r#""hookEventName":"PreToolUse""#),
+        "got: {output}"
+    );
+    assert!(
+        !output.contains("ralph-pre-"),
+        "staged path leaked: {output}"
+    );
+    assert!(!js.exists(), "content lint must not write the file");
+    assert_eq!(
+        fs::read_dir(&dir).unwrap().count(),
+        0,
+        "staged copy left behind"
+    );
+
+    // PreToolUse Edit: applied to the current file before linting
+    let py = dir.join("edited.py");
+    fs::write(&py, "def ok():\n    return 1\n").unwrap();
+    let input = format!(
+        r#"{{"hook_event_name":"PreToolUse","tool_name":"Edit","tool_input":{{"file_path":"{}","old_string":"def ok():","new_string":"def ok(:"}}}}"#,
+        py.display()
+    );
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#),
+        "PreToolUse Edit introducing a syntax error should block, got: {output}"
+    );
+    assert_eq!(
+        fs::read_to_string(&py).unwrap(),
+        "def ok():\n    return 1\n"
+    );
+
+    // Stop: lint-collected, which has nothing recorded for this session
+    let input = format!(
+        r#"{{"session_id":"auto-{}","hook_event_name":"Stop"}}"#,
+        std::process::id()
+    );
+    let output = run_binary_debug(&input);
+    assert!(
+        output.contains("no files collected"),
+        "Stop without flags should run lint-collected, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn hooks_config_filters_tools_and_files() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-filter-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let js = dir.join("scratch.js");
+    fs::write(&js, "function (\n").unwrap();
+    let input = |tool: &str| {
+        format!(
+            r#"{{"hook_event_name":"PostToolUse","tool_name":"{tool}","tool_input":{{"file_path":"{}"}}}}"#,
+            js.display()
+        )
+    };
+    assert!(run_binary(&input("Write")).contains(r#""decision":"block""#));
+
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[hooks]\ntools = [\"Write\", \"Edit\"]\n",
+    )
+    .unwrap();
+    let output = run_binary_debug(&input("NotebookEdit"));
+    assert!(
+        output.contains("excluded by [hooks] rules"),
+        "tool outside [hooks] tools should be skipped, got: {output}"
+    );
+    assert!(run_binary(&input("Edit")).contains(r#""decision":"block""#));
+
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[hooks]\nexclude = [\"scratch.*\"]\n",
+    )
+    .unwrap();
+    assert_eq!(run_binary(&input("Write")).trim(), r#"{"continue":true}"#);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn files_over_max_file_kb_are_skipped() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-size-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[hooks]\nmax_file_kb = 1\n",
+    )
+    .unwrap();
+    let small = dir.join("small.js");
+    let large = dir.join("large.js");
+    fs::write(&small, "function (\n").unwrap();
+    fs::write(
+        &large,
+        format!("{}function (\n", "// padding\n".repeat(200)),
+    )
+    .unwrap();
+    let input = |file: &Path| {
+        format!(
+            r#"{{"hook_event_name":"PostToolUse","tool_name":"Write","tool_input":{{"file_path":"{}"}}}}"#,
+            file.display()
+        )
+    };
+
+    assert!(run_binary(&input(&small)).contains(r#""decision":"block""#));
+    let output = run_binary_debug(&input(&large));
+    assert!(
+        output.contains("skip (too-large)") && output.contains("3 KiB is over [hooks] max_file_kb"),
+        "a file over the limit should be skipped, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn files_config_distinguishes_new_from_modified_files() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-origin-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-c", "user.email=t@t", "-c", "user.name=t"])
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    git(&["init", "-q"]);
+    let source = "fn main() {\n    let x = 1;\n}\n";
+    fs::write(dir.join("legacy.rs"), source).unwrap();
+    git(&["add", "legacy.rs"]);
+    git(&["commit", "-qm", "legacy"]);
+    fs::write(dir.join("fresh.rs"), source).unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[files]\nnew = \"strict\"\nmodified = \"lenient\"\n",
+    )
+    .unwrap();
+
+    let input = |name: &str| {
+        format!(
+            r#"{{"tool_input":{{"file_path":"{}"}}}}"#,
+            dir.join(name).display()
+        )
+    };
+    let output = run_binary(&input("legacy.rs"));
+    assert_eq!(
+        output.trim(),
+        r#"{"continue":true}"#,
+        "modified file should be linted leniently, got: {output}"
+    );
+    let output = run_binary(&input("fresh.rs"));
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("unused variable"),
+        "new file should be linted strictly, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lint_collected_drops_deleted_and_ignored_files() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-gitdrop-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    fs::write(dir.join(".gitignore"), "build/\n").unwrap();
+    fs::create_dir_all(dir.join("build")).unwrap();
+    let ignored = dir.join("build/bundle.js");
+    let deleted = dir.join("gone.js");
+    fs::write(&ignored, "function (\n").unwrap();
+    fs::write(&deleted, "function (\n").unwrap();
+
+    let sid = format!("gitdrop-{}", std::process::id());
+    for file in [&ignored, &deleted] {
+        run_binary_with_args(
+            &format!(
+                r#"{{"session_id":"{sid}","tool_input":{{"file_path":"{}"}}}}"#,
+                file.display()
+            ),
+            &["collect"],
+        );
+    }
+    fs::remove_file(&deleted).unwrap();
+
+    let output = run_binary_with_args(
+        &format!(r#"{{"session_id":"{sid}"}}"#),
+        &["lint-collected", "--debug"],
+    );
+    assert!(
+        output.contains("skip (excluded)"),
+        "deleted and gitignored files should be dropped, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lint_collected_from_git_catches_uncollected_files() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-fromgit-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[lint_collected]\nlint_temp_files = true\n",
+    )
+    .unwrap();
+    // Dirty before the session starts, so not the session's change
+    fs::write(dir.join("before.js"), "function (\n").unwrap();
+
+    let sid = format!("fromgit-{}", std::process::id());
+    let edited = dir.join("edited.js");
+    fs::write(&edited, "const a = 1;\nconsole.log(a);\n").unwrap();
+    run_binary_with_args(
+        &format!(
+            r#"{{"session_id":"{sid}","cwd":"{}","tool_input":{{"file_path":"{}"}}}}"#,
+            dir.display(),
+            edited.display()
+        ),
+        &["collect"],
+    );
+    // Written behind the hook's back, say by a script
+    fs::write(dir.join("generated.js"), "function (\n").unwrap();
+
+    let stop = format!(r#"{{"session_id":"{sid}"}}"#);
+    let output = run_binary_with_args(&stop, &["lint-collected", "--collect-from-git"]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("generated.js")
+            && !output.contains("before.js"),
+        "files changed since the session started should be linted, got: {output}"
+    );
+
+    let output = run_binary_with_args(&stop, &["lint-collected"]);
+    assert!(
+        !output.contains("generated.js"),
+        "without the flag only collected files are linted, got: {output}"
+    );
+
+    let _ = fs::remove_file(std::env::temp_dir().join(format!("ralph-lint-{sid}-baseline.txt")));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lint_collected_follows_git_renames() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-rename-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-c", "user.email=t@t", "-c", "user.name=t"])
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    git(&["init", "-q"]);
+    let old = dir.join("old.js");
+    fs::write(&old, "const a = 1;\n").unwrap();
+    git(&["add", "old.js"]);
+    git(&["commit", "-qm", "init"]);
+
+    let sid = format!("rename-{}", std::process::id());
+    run_binary_with_args(
+        &format!(
+            r#"{{"session_id":"{sid}","tool_input":{{"file_path":"{}"}}}}"#,
+            old.display()
+        ),
+        &["collect"],
+    );
+    git(&["mv", "old.js", "renamed.js"]);
+    fs::write(dir.join("renamed.js"), "function (\n").unwrap();
+
+    let output = run_binary_with_args(&format!(r#"{{"session_id":"{sid}"}}"#), &["lint-collected"]);
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("renamed.js"),
+        "the renamed file should be linted in place of the old path, got: {output}"
+    );
+    assert!(!output.contains("old.js"), "stale path linted: {output}");
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lint_collected_runs_clippy_once_per_cargo_workspace() {
+    let dir =
+        std::env::temp_dir().join(format!("ralph-lint-integ-workspace-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::write(
+        {
+            fs::create_dir_all(&dir).unwrap();
+            dir.join("Cargo.toml")
+        },
+        "[workspace]\nmembers = [\"a\", \"b\"]\nresolver = \"2\"\n",
+    )
+    .unwrap();
+    let mut files = Vec::new();
+    for name in ["a", "b"] {
+        fs::create_dir_all(dir.join(name).join("src")).unwrap();
+        fs::write(
+            dir.join(name).join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n"),
+        )
+        .unwrap();
+        let lib = dir.join(name).join("src/lib.rs");
+        fs::write(&lib, "pub fn f() {\n    let unused = 1;\n}\n").unwrap();
+        files.push(lib);
+    }
+
+    let sid = format!("workspace-{}", std::process::id());
+    for file in &files {
+        run_binary_with_args(
+            &format!(
+                r#"{{"session_id":"{sid}","tool_input":{{"file_path":"{}"}}}}"#,
+                file.display()
+            ),
+            &["collect"],
+        );
+    }
+    let output = run_binary_with_args(&format!(r#"{{"session_id":"{sid}"}}"#), &["lint-collected"]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("2 files using clippy")
+            && output.contains("a/src/lib.rs:2:9")
+            && output.contains("b/src/lib.rs:2:9"),
+        "expected one clippy run reporting both members, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn lint_collected_runs_ruff_once_for_a_project() {
+    let dir =
+        std::env::temp_dir().join(format!("ralph-lint-integ-ruffbatch-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    let good = dir.join("good.py");
+    let bad = dir.join("bad.py");
+    fs::write(&good, "x = 1\n").unwrap();
+    fs::write(&bad, "import os\n").unwrap();
+
+    // A stand-in ruff that logs each invocation and reports one violation in bad.py
+    write_stub(
+        &dir.join(".venv/bin"),
+        "ruff",
+        &format!(
+            "#!/bin/sh\necho \"$@\" >> '{log}'\necho '[{{\"filename\":\"{bad}\",\"location\":{{\"row\":1,\"column\":8}},\"code\":\"F401\",\"message\":\"`os` imported but unused\"}}]'\nexit 1\n",
+            log = dir.join("ruff.log").display(),
+            bad = bad.display()
+        ),
+    );
+
+    let sid = format!("ruffbatch-{}", std::process::id());
+    for file in [&good, &bad] {
+        run_binary_with_args(
+            &format!(
+                r#"{{"session_id":"{sid}","tool_input":{{"file_path":"{}"}}}}"#,
+                file.display()
+            ),
+            &["collect"],
+        );
+    }
+    let output = run_binary_with_args(&format!(r#"{{"session_id":"{sid}"}}"#), &["lint-collected"]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains(&format!("lint errors in {} using ruff", bad.display()))
+            && !output.contains(&format!("lint errors in {}", good.display())),
+        "expected only bad.py to fail, got: {output}"
+    );
+    let log = fs::read_to_string(dir.join("ruff.log")).unwrap();
+    assert_eq!(log.lines().count(), 1, "ruff should run once, got: {log}");
+    assert!(log.contains("good.py") && log.contains("bad.py"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn collect_warms_rust_build_when_configured() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-warm-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"warm\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    fs::write(dir.join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[hooks]\ndedup_window_ms = 0\n",
+    )
+    .unwrap();
+    let input = format!(
+        r#"{{"session_id":"warm-{}","tool_input":{{"file_path":"{}"}}}}"#,
+        std::process::id(),
+        dir.join("src/lib.rs").display()
+    );
+
+    let output = run_binary_with_args(&input, &["collect", "--debug"]);
+    assert!(
+        !output.contains("warm the build"),
+        "warm-up is opt-in, got: {output}"
+    );
+
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[hooks]\ndedup_window_ms = 0\n[rust]\nwarm_check = true\n",
+    )
+    .unwrap();
+    let output = run_binary_with_args(&input, &["collect", "--debug"]);
+    assert!(
+        output.contains("started cargo check to warm the build"),
+        "expected a warm-up check, got: {output}"
+    );
+    // A second edit right away doesn't start another check
+    let output = run_binary_with_args(&input, &["collect", "--debug"]);
+    assert!(!output.contains("warm the build"), "got: {output}");
+
+    let _ = fs::remove_file(collect_temp_path(&format!("warm-{}", std::process::id())));
+    // Leave the directory for the background check; it's under the temp dir
+}
+
+#[test]
+fn background_lint_results_are_used_at_stop() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-bg-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[lint_collected]\nbackground = true\n",
+    )
+    .unwrap();
+    let bad = dir.join("bad.js");
+    let fixed = dir.join("fixed.js");
+    fs::write(&bad, "function (\n").unwrap();
+    fs::write(&fixed, "function (\n").unwrap();
+
+    let sid = format!("bg-{}", std::process::id());
+    let results = std::env::temp_dir().join(format!("ralph-lint-{sid}-results"));
+    for file in [&bad, &fixed] {
+        let output = run_binary_with_args(
+            &format!(
+                r#"{{"session_id":"{sid}","tool_input":{{"file_path":"{}"}}}}"#,
+                file.display()
+            ),
+            &["collect", "--debug"],
+        );
+        assert!(output.contains("started background lint"), "got: {output}");
+    }
+    let started = std::time::Instant::now();
+    while fs::read_dir(&results).map_or(0, Iterator::count) < 2
+        && started.elapsed() < std::time::Duration::from_secs(20)
+    {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    // Editing after the background lint makes its result stale, so Stop lints this one again
+    fs::write(&fixed, "function ok() {}\n").unwrap();
+
+    let output = run_binary_with_args(&format!(r#"{{"session_id":"{sid}"}}"#), &["lint-collected"]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("bad.js")
+            && !output.contains("fixed.js"),
+        "expected only the still-broken file to block, got: {output}"
+    );
+    assert!(!results.exists(), "results should be cleared at Stop");
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn report_new_hides_issues_the_file_already_had() {
+    let dir =
+        std::env::temp_dir().join(format!("ralph-lint-integ-reportnew-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        // The same event is replayed with new linter output, so keep it from being deduplicated
+        "[output]\nreport = \"new\"\n[hooks]\ndedup_window_ms = 0\n",
+    )
+    .unwrap();
+    let file = dir.join("legacy.py");
+    fs::write(&file, "import os\n").unwrap();
+
+    // A stand-in ruff that prints whatever violations the test puts in ruff.json
+    write_stub(
+        &dir.join(".venv/bin"),
+        "ruff",
+        &format!(
+            "#!/bin/sh\ncat '{}'\nexit 1\n",
+            dir.join("ruff.json").display()
+        ),
+    );
+    let violation = |row: u32, code: &str, message: &str| {
+        format!(
+            r#"{{"filename":"{}","location":{{"row":{row},"column":1}},"code":"{code}","message":"{message}"}}"#,
+            file.display()
+        )
+    };
+
+    let input = format!(
+        r#"{{"session_id":"reportnew-{}","tool_input":{{"file_path":"{}"}}}}"#,
+        std::process::id(),
+        file.display()
+    );
+    fs::write(
+        dir.join("ruff.json"),
+        format!("[{}]", violation(1, "F401", "unused")),
+    )
+    .unwrap();
+    let output = run_binary_with_args(&input, &["--debug"]);
+    assert!(
+        output.contains(r#""continue":true"#) && output.contains("1 pre-existing issue(s)"),
+        "issues present at the first lint are not reported, got: {output}"
+    );
+
+    // The old issue moved down a line and a new one appeared
+    fs::write(
+        dir.join("ruff.json"),
+        format!(
+            "[{},{}]",
+            violation(3, "F401", "unused"),
+            violation(4, "E711", "comparison to None")
+        ),
+    )
+    .unwrap();
+    let output = run_binary_with_args(&input, &[]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("E711")
+            && !output.contains("F401"),
+        "only the new issue blocks, got: {output}"
+    );
+
+    // A second occurrence of a known issue is new
+    fs::write(
+        dir.join("ruff.json"),
+        format!(
+            "[{},{}]",
+            violation(3, "F401", "unused"),
+            violation(7, "F401", "unused")
+        ),
+    )
+    .unwrap();
+    let output = run_binary_with_args(&input, &[]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("legacy.py:7:1")
+            && !output.contains("legacy.py:3:1"),
+        "only the repeated issue blocks, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn missing_sandbox_wrapper_is_noted_once_and_skipped() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-nowrap-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[hooks]\ndedup_window_ms = 0\n[sandbox]\nwrapper = \"bwrap\"\n",
+    )
+    .unwrap();
+    let file = dir.join("app.py");
+    fs::write(&file, "x = 1\n").unwrap();
+    write_stub(
+        &dir.join(".venv/bin"),
+        "ruff",
+        "#!/bin/sh\necho '[]'\nexit 0\n",
+    );
+
+    let sid = format!("nowrap-{}", std::process::id());
+    let input = format!(
+        r#"{{"session_id":"{sid}","tool_input":{{"file_path":"{}"}}}}"#,
+        file.display()
+    );
+    // Nothing on PATH, so bwrap can't be found
+    let output = run_binary_with_path(&input, &[], "/nonexistent");
+    assert!(
+        output.contains(r#""continue":true"#) && output.contains("sandbox: bwrap is not installed"),
+        "the lint should run unwrapped with a note, got: {output}"
+    );
+    let output = run_binary_with_path(&input, &[], "/nonexistent");
+    assert_eq!(
+        output.trim(),
+        r#"{"continue":true}"#,
+        "the note is given once per session"
+    );
+
+    let _ =
+        fs::remove_file(std::env::temp_dir().join(format!("ralph-lint-{sid}-sandbox-noted.txt")));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn repeatedly_failing_linter_is_quarantined() {
+    let dir = std::env::temp_dir().join(format!(
+        "ralph-lint-integ-quarantine-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    let file = dir.join("app.py");
+    fs::write(&file, "x = 1\n").unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[hooks]\ndedup_window_ms = 0\n",
+    )
+    .unwrap();
+
+    // A stand-in ruff that rejects its config every time, as ruff does with exit code 2
+    write_stub(
+        &dir.join(".venv/bin"),
+        "ruff",
+        &format!(
+            "#!/bin/sh\necho run >> '{}'\necho 'ruff failed: invalid pyproject.toml' >&2\nexit 2\n",
+            dir.join("ruff.log").display()
+        ),
+    );
+
+    let input = format!(
+        r#"{{"session_id":"quarantine-{}","tool_input":{{"file_path":"{}"}}}}"#,
+        std::process::id(),
+        file.display()
+    );
+    for _ in 0..3 {
+        let output = run_binary_with_args(&input, &[]);
+        assert!(
+            output.contains(r#""decision":"block""#),
+            "a failing linter blocks until quarantined, got: {output}"
+        );
+    }
+    let output = run_binary_with_args(&input, &[]);
+    assert!(
+        output.contains(r#""continue":true"#) && output.contains("quarantined ruff"),
+        "expected a quarantine warning, got: {output}"
+    );
+    let runs = fs::read_to_string(dir.join("ruff.log")).unwrap();
+    assert_eq!(runs.lines().count(), 3, "quarantined ruff must not run");
+
+    // The Stop hook reports the quarantine as a note, not a lint error
+    run_binary_with_args(&input, &["--collect"]);
+    let stop = format!(r#"{{"session_id":"quarantine-{}"}}"#, std::process::id());
+    let output = run_binary_with_args(&stop, &["--lint-collected"]);
+    assert!(
+        output.contains(r#""continue":true"#)
+            && output.contains("quarantined ruff")
+            && !output.contains("error linting"),
+        "expected a quarantine note from lint-collected, got: {output}"
+    );
+
+    // A new session under the same id starts with a clean slate
+    run_binary_with_args(
+        &format!(
+            r#"{{"session_id":"quarantine-{}","hook_event_name":"SessionStart","source":"startup","cwd":"{}"}}"#,
+            std::process::id(),
+            dir.display()
+        ),
+        &[],
+    );
+    let output = run_binary_with_args(&input, &[]);
+    assert!(
+        output.contains(r#""decision":"block""#),
+        "a new session should run ruff again, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn inline_noqa_suppresses_fallback_linter_output() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-noqa-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    let file = dir.join("app.py");
+    fs::write(&file, "import os  # noqa\nimport re\n").unwrap();
+
+    // A stand-in pylint, which ignores `# noqa`, reporting both imports
+    write_stub(
+        &dir.join(".venv/bin"),
+        "pylint",
+        &format!(
+            "#!/bin/sh\necho '************* Module app'\necho '{f}:1:0: W0611: Unused import os (unused-import)'\nif grep -q 're$' '{f}'; then echo '{f}:2:0: W0611: Unused import re (unused-import)'; fi\nexit 4\n",
+            f = file.display()
+        ),
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let output = run_binary_with_args(&input, &[]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("Unused import re")
+            && !output.contains("Unused import os"),
+        "the suppressed line is dropped, got: {output}"
+    );
+
+    fs::write(&file, "import os  # noqa\n").unwrap();
+    let output = run_binary_with_args(&input, &[]);
+    assert!(
+        output.contains(r#""continue":true"#),
+        "only suppressed issues left, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lenient_rules_come_from_config() {
+    let dir = std::env::temp_dir().join(format!(
+        "ralph-lint-integ-lenientrules-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    let file = dir.join("app.py");
+    fs::write(&file, "import os\nx = 1\n").unwrap();
+
+    // A stand-in ruff reporting an unused import and an unused variable
+    write_stub(
+        &dir.join(".venv/bin"),
+        "ruff",
+        &format!(
+            "#!/bin/sh\necho '[{{\"filename\":\"{f}\",\"location\":{{\"row\":1,\"column\":8}},\"code\":\"F401\",\"message\":\"unused import\"}},{{\"filename\":\"{f}\",\"location\":{{\"row\":2,\"column\":1}},\"code\":\"F841\",\"message\":\"unused variable\"}}]'\nexit 1\n",
+            f = file.display()
+        ),
+    );
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+
+    let output = run_binary_with_args(&input, &["--lenient"]);
+    assert_eq!(
+        output.trim(),
+        r#"{"continue":true}"#,
+        "built-in list ignores both"
+    );
+
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[lenient.python]\nallow = [\"F841\"]\n",
+    )
+    .unwrap();
+    let output = run_binary_with_args(&input, &["--lenient"]);
+    assert!(
+        output.contains("F401") && !output.contains("F841"),
+        "only the configured rule is ignored, got: {output}"
+    );
+
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[lenient.python]\nonly = [\"F841\"]\n",
+    )
+    .unwrap();
+    let output = run_binary_with_args(&input, &["--lenient"]);
+    assert!(
+        output.contains("F841") && !output.contains("F401"),
+        "only the listed rule blocks, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn every_file_in_a_files_array_is_linted() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-files-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    let clean = dir.join("clean.py");
+    let dirty = dir.join("dirty.py");
+    fs::write(&clean, "print(1)\n").unwrap();
+    fs::write(&dirty, "import re\n").unwrap();
+
+    // A stand-in pylint reporting unused imports in whichever file it is given
+    write_stub(
+        &dir.join(".venv/bin"),
+        "pylint",
+        "#!/bin/sh\nfor f; do :; done\nif grep -q '^import' \"$f\"; then echo \"$f:1:0: W0611: Unused import re (unused-import)\"; exit 4; fi\n",
+    );
+
+    let input = format!(
+        r#"{{"tool_input":{{"files":[{{"file_path":"{}"}},{{"file_path":"{}"}}]}}}}"#,
+        clean.display(),
+        dirty.display()
+    );
+    let output = run_binary_with_args(&input, &[]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains(&format!("lint errors in {}", dirty.display()))
+            && !output.contains(&format!("lint errors in {}", clean.display())),
+        "the second file is linted too, got: {output}"
+    );
+
+    fs::write(&dirty, "print(2)\n").unwrap();
+    let output = run_binary_with_args(&input, &["--debug"]);
+    assert!(
+        output.contains("all 2 file(s) in the payload passed lint"),
+        "both files pass, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn text_linter_output_is_sorted_by_location() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-sorted-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    let file = dir.join("app.py");
+    fs::write(&file, "x: int = ''\n").unwrap();
+
+    // A stand-in mypy reporting its errors out of line order
+    write_stub(
+        &dir.join(".venv/bin"),
+        "mypy",
+        "#!/bin/sh\nfor f; do :; done\necho \"$f:12: error: Incompatible return value\"\necho \"$f:3: error: Name is not defined\"\necho \"$f:3: note: Did you mean y?\"\nexit 1\n",
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let output = run_binary_with_args(&input, &[]);
+    let position = |text: &str| output.find(text).unwrap_or(usize::MAX);
+    assert!(
+        output.contains("using mypy")
+            && position("not defined") < position("Did you mean")
+            && position("Did you mean") < position("Incompatible return"),
+        "expected mypy's errors in line order, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn retried_event_is_linted_once() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-dedup-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+    let file = dir.join("app.py");
+    fs::write(&file, "x = 1\n").unwrap();
+
+    // A stand-in pylint that logs each run
+    write_stub(
+        &dir.join(".venv/bin"),
+        "pylint",
+        &format!(
+            "#!/bin/sh\necho run >> '{}'\n",
+            dir.join("pylint.log").display()
+        ),
+    );
+
+    let input = format!(
+        r#"{{"session_id":"dedup-{}","tool_name":"Write","tool_input":{{"file_path":"{}"}}}}"#,
+        std::process::id(),
+        file.display()
+    );
+    run_binary_with_args(&input, &[]);
+    let output = run_binary_with_args(&input, &["--debug"]);
+    assert!(
+        output.contains("skip (debounced)"),
+        "the retry is skipped, got: {output}"
+    );
+    let runs = fs::read_to_string(dir.join("pylint.log")).unwrap_or_default();
+    assert_eq!(runs.lines().count(), 1, "pylint should run once");
+
+    // New contents make it a new event
+    fs::write(&file, "x = 2\n").unwrap();
+    run_binary_with_args(&input, &[]);
+    let runs = fs::read_to_string(dir.join("pylint.log")).unwrap_or_default();
+    assert_eq!(runs.lines().count(), 2);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn clang_tidy_findings_are_scoped_to_the_edited_file() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-tidy-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::write(dir.join("Makefile"), "all:\n").unwrap();
+    fs::write(dir.join(".clang-tidy"), "Checks: 'bugprone-*'\n").unwrap();
+    let file = dir.join("main.c");
+    fs::write(&file, "#include \"util.h\"\nint main(void) { return 0; }\n").unwrap();
+
+    // A stand-in clang-tidy reporting one finding in the file and one in a header it includes
+    write_stub(
+        &dir.join("bin"),
+        "clang-tidy",
+        "#!/bin/sh\necho 'util.h:1:1: warning: header finding [bugprone-reserved-identifier]'\necho 'main.c:2:5: warning: file finding [bugprone-easily-swappable-parameters]'\n",
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+    let output = run_binary_with_path(&input, &[], &path);
+    assert!(
+        output.contains("using clang-tidy")
+            && output.contains("file finding")
+            && !output.contains("header finding"),
+        "only the edited file's finding is reported, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn php_file_is_linted_with_vendored_phpstan() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-php-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("vendor/bin")).unwrap();
+    fs::write(dir.join("composer.json"), "{}\n").unwrap();
+    let file = dir.join("index.php");
+    let other = dir.join("other.php");
+    fs::write(&file, "<?php\necho $x;\n").unwrap();
+
+    // A stand-in PHPStan that reports one error in the edited file and one elsewhere
+    write_stub(
+        &dir.join("vendor/bin"),
+        "phpstan",
+        &format!(
+            "#!/bin/sh\necho '{{\"totals\":{{\"errors\":0,\"file_errors\":2}},\"files\":{{\"{file}\":{{\"errors\":1,\"messages\":[{{\"message\":\"Undefined variable: $x\",\"line\":2,\"identifier\":\"variable.undefined\"}}]}},\"{other}\":{{\"errors\":1,\"messages\":[{{\"message\":\"Elsewhere\",\"line\":1}}]}}}},\"errors\":[]}}'\nexit 1\n",
+            file = file.display(),
+            other = other.display()
+        ),
+    );
+
+    let output = run_binary(&format!(
+        r#"{{"tool_input":{{"file_path":"{}"}}}}"#,
+        file.display()
+    ));
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("using phpstan")
+            && output.contains("Undefined variable")
+            && !output.contains("Elsewhere"),
+        "expected a PHPStan block scoped to index.php, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn csharp_file_is_checked_with_dotnet_format_then_build() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-csharp-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        // The same event is sent twice with a different stand-in dotnet
+        "[hooks]\ndedup_window_ms = 0\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("App.csproj"),
+        "<Project Sdk=\"Microsoft.NET.Sdk\" />\n",
+    )
+    .unwrap();
+    let file = dir.join("Program.cs");
+    fs::write(&file, "using System.IO;\n").unwrap();
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+    let run = |script: &str| {
+        write_stub(&dir.join("bin"), "dotnet", script);
+        run_binary_with_path(&input, &[], &path)
+    };
+
+    // Analyzers flag the edited file, and another file that isn't reported
+    let output = run(&format!(
+        "#!/bin/sh\n[ \"$1\" = format ] || exit 9\necho '{}(1,1): warning IDE0005: Using directive is unnecessary. [App.csproj]'\necho 'Other.cs(2,1): warning IDE0005: elsewhere [App.csproj]'\nexit 2\n",
+        file.display()
+    ));
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("using dotnet format")
+            && output.contains("IDE0005")
+            && !output.contains("elsewhere"),
+        "expected a dotnet format block, got: {output}"
+    );
+
+    // An SDK without `dotnet format` falls back to a build with warnings as errors
+    let output = run(
+        "#!/bin/sh\n[ \"$1\" = format ] && exit 1\necho 'Program.cs(1,7): error CS0234: The type or namespace name does not exist [App.csproj]'\nexit 1\n",
+    );
+    assert!(
+        output.contains("using dotnet build") && output.contains("CS0234"),
+        "expected a dotnet build block, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn swift_file_is_linted_strictly_with_swiftlint() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-swift-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::write(dir.join("Package.swift"), "// swift-tools-version:5.9\n").unwrap();
+    let file = dir.join("main.swift");
+    fs::write(&file, "let x = 1 \n").unwrap();
+
+    // A stand-in swiftlint that logs its arguments and reports a warning in the file
+    write_stub(
+        &dir.join("bin"),
+        "swiftlint",
+        &format!(
+            "#!/bin/sh\necho \"$@\" > '{log}'\necho '[{{\"character\":10,\"file\":\"{file}\",\"line\":1,\"reason\":\"Lines should not have trailing whitespace\",\"rule_id\":\"trailing_whitespace\",\"severity\":\"Warning\"}}]'\nexit 2\n",
+            log = dir.join("swiftlint.log").display(),
+            file = file.display()
+        ),
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+    let output = run_binary_with_path(&input, &[], &path);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("using swiftlint")
+            && output.contains("trailing_whitespace"),
+        "expected a swiftlint block, got: {output}"
+    );
+    let log = fs::read_to_string(dir.join("swiftlint.log")).unwrap();
+    assert!(
+        log.contains("--strict") && log.contains(&format!("--path {}", file.display())),
+        "got: {log}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn shell_script_is_checked_with_shellcheck_blocking_on_errors() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-shell-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::create_dir_all(dir.join(".git")).unwrap();
+    // No extension: recognized by its shebang
+    let script = dir.join("install");
+    fs::write(&script, "#!/usr/bin/env bash\nif true; then\n  echo $1\n").unwrap();
+    let prompt = dir.join("prompt.zsh");
+    fs::write(&prompt, "echo $1\n").unwrap();
+
+    // A stand-in shellcheck that logs its arguments; only the extensionless script has an error
+    write_stub(
+        &dir.join("bin"),
+        "shellcheck",
+        &format!(
+            "#!/bin/sh\necho \"$@\" >> '{log}'\nfor f; do :; done\necho \"$f:3:8: warning: Double quote to prevent globbing. [SC2086]\"\ncase \"$f\" in *install) echo \"$f:4:1: error: Couldn't find 'fi' for this 'if'. [SC1046]\";; esac\nexit 1\n",
+            log = dir.join("shellcheck.log").display(),
+        ),
+    );
+
+    let path = path_with(&dir.join("bin"));
+    let run = |file: &std::path::Path| {
+        let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+        run_binary_with_path(&input, &[], &path)
+    };
+
+    let output = run(&script);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("using shellcheck")
+            && output.contains("SC1046"),
+        "expected a shellcheck block, got: {output}"
+    );
+    // Warnings alone don't block
+    let output = run(&prompt);
+    assert!(
+        !output.contains(r#""decision":"block""#),
+        "expected no block, got: {output}"
+    );
+    let log = fs::read_to_string(dir.join("shellcheck.log")).unwrap();
+    assert!(
+        log.contains("--format=gcc") && log.contains("--shell=bash"),
+        "got: {log}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn bench_times_installed_linters_and_the_hook() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-bench-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "").unwrap();
+    let file = dir.join("main.py");
+    fs::write(&file, "import os\n").unwrap();
+
+    // A stand-in ruff that counts its runs and reports one finding
+    write_stub(
+        &dir.join(".venv/bin"),
+        "ruff",
+        &format!(
+            "#!/bin/sh\necho run >> '{log}'\necho 'main.py:1:8: F401 `os` imported but unused'\nexit 1\n",
+            log = dir.join("ruff.log").display()
+        ),
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ralph-hook-lint"))
+        .args(["bench", &file.to_string_lossy()])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "got: {stdout}");
+    let ruff_row = stdout.lines().find(|l| l.starts_with("ruff ")).unwrap();
+    assert!(ruff_row.trim_end().ends_with(" 1"), "got: {ruff_row}");
+    assert!(
+        stdout.lines().any(|l| l.starts_with("hook ")),
+        "got: {stdout}"
+    );
+    // Five timed runs of ruff itself, plus the hook's own
+    let runs = fs::read_to_string(dir.join("ruff.log"))
+        .unwrap()
+        .lines()
+        .count();
+    assert!(runs >= 5, "ruff ran {runs} times");
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn project_over_its_time_budget_skips_slow_linters() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-tier-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".venv/bin")).unwrap();
+    fs::create_dir_all(dir.join("tmp")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "").unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[process]\nmax_lint_seconds = 20\n",
+    )
+    .unwrap();
+    let file = dir.join("main.py");
+    fs::write(&file, "x = 1\n").unwrap();
+    // The last recorded lint of this project, just now, took 45s
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    fs::write(
+        dir.join("tmp/ralph-lint-durations.txt"),
+        format!("45000\t{now}\t{}\n", dir.display()),
+    )
+    .unwrap();
+
+    // mypy would block; flake8 passes
+    for (name, script) in [
+        (
+            "mypy",
+            "#!/bin/sh\necho 'main.py:1: error: mypy ran'\nexit 1\n",
+        ),
+        ("flake8", "#!/bin/sh\nexit 0\n"),
+    ] {
+        write_stub(&dir.join(".venv/bin"), name, script);
+    }
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ralph-hook-lint"))
+        .env("TMPDIR", dir.join("tmp"))
+        .current_dir(&dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = String::from_utf8_lossy(&child.wait_with_output().unwrap().stdout).to_string();
+    assert!(
+        !output.contains(r#""decision":"block""#)
+            && output.contains("fast tier for")
+            && output.contains("its last lint took 45s")
+            && output.contains("so mypy, pylint were skipped"),
+        "expected a fast-tier pass, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn markdown_is_linted_with_markdownlint_cli2() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-md-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("node_modules/.bin")).unwrap();
+    fs::create_dir_all(dir.join("docs")).unwrap();
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    let doc = dir.join("docs/guide.md");
+    fs::write(&doc, "# Guide\ntext\n").unwrap();
+
+    // A stand-in markdownlint-cli2 reporting a long line and a missing blank line on stderr
+    write_stub(
+        &dir.join("node_modules/.bin"),
+        "markdownlint-cli2",
+        "#!/bin/sh\necho \"Finding: $1\" >&2\necho \"$1:1 MD022/blanks-around-headings Headings should be surrounded by blank lines\" >&2\necho \"$1:2:81 error MD013/line-length Line length [Expected: 80; Actual: 99]\" >&2\nexit 1\n",
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, doc.display());
+    let output = run_binary(&input);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("markdownlint-cli2")
+            && output.contains("MD022")
+            && output.contains("MD013"),
+        "expected a markdownlint block, got: {output}"
+    );
+    let output = run_binary_lenient(&input);
+    assert!(
+        output.contains("MD022") && !output.contains("MD013"),
+        "lenient mode should drop line-length, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn session_start_records_the_baseline_and_clears_stale_state() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-start-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[lint_collected]\nlint_temp_files = true\n",
+    )
+    .unwrap();
+    fs::write(dir.join("before.js"), "function (\n").unwrap();
+
+    let sid = format!("start-{}", std::process::id());
+    // Left over from an earlier run under the same id
+    fs::write(collect_temp_path(&sid), "/nonexistent/stale.js\n").unwrap();
+
+    let start = format!(
+        r#"{{"session_id":"{sid}","hook_event_name":"SessionStart","source":"startup","cwd":"{}"}}"#,
+        dir.display()
+    );
+    let output = run_binary_debug(&start);
+    assert!(
+        output.contains("session started: recorded git baseline"),
+        "got: {output}"
+    );
+    assert!(!collect_temp_path(&sid).exists());
+
+    fs::write(dir.join("later.js"), "function (\n").unwrap();
+    let output = run_binary_with_args(
+        &format!(r#"{{"session_id":"{sid}"}}"#),
+        &["lint-collected", "--collect-from-git"],
+    );
+    assert!(
+        output.contains("later.js") && !output.contains("before.js"),
+        "only files changed after SessionStart should be linted, got: {output}"
+    );
+
+    let _ = fs::remove_file(std::env::temp_dir().join(format!("ralph-lint-{sid}-baseline.txt")));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn subagent_stop_lints_only_that_subagents_files() {
+    let dir =
+        std::env::temp_dir().join(format!("ralph-lint-integ-subagent-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let sid = format!("subagent-{}", std::process::id());
+    let _ = fs::remove_file(collect_temp_path(&sid));
+
+    for (agent, name) in [("alpha", "alpha.js"), ("beta", "beta.js")] {
+        let file = dir.join(name);
+        fs::write(&file, "function (\n").unwrap();
+        run_binary_with_args(
+            &format!(
+                r#"{{"session_id":"{sid}","agent_id":"{agent}","tool_name":"Write","tool_input":{{"file_path":"{}"}}}}"#,
+                file.display()
+            ),
+            &["collect"],
+        );
+    }
+
+    let stop = |agent: &str| {
+        run_binary(&format!(
+            r#"{{"session_id":"{sid}","hook_event_name":"SubagentStop","agent_id":"{agent}"}}"#
+        ))
+    };
+    let output = stop("alpha");
+    assert!(
+        output.contains("alpha.js") && !output.contains("beta.js"),
+        "alpha's SubagentStop should lint alpha's file only, got: {output}"
+    );
+
+    // The main agent's Stop still covers the other subagent's file
+    let output = run_binary(&format!(
+        r#"{{"session_id":"{sid}","hook_event_name":"Stop"}}"#
+    ));
+    assert!(
+        output.contains("beta.js") && !output.contains("alpha.js"),
+        "got: {output}"
+    );
+
+    let _ = fs::remove_file(std::env::temp_dir().join(format!("ralph-lint-{sid}-agent-beta.txt")));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn elixir_is_linted_with_credo_or_the_compiler() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-ex-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::create_dir_all(dir.join("app/lib")).unwrap();
+    fs::write(
+        dir.join("app/mix.exs"),
+        "defmodule App.MixProject do\nend\n",
+    )
+    .unwrap();
+    let file = dir.join("app/lib/app.ex");
+    fs::write(&file, "defmodule App do\nend\n").unwrap();
+
+    // A stand-in mix: credo reports a readability issue, the compiler an unused variable in
+    // the edited file and a warning in another one
+    write_stub(
+        &dir.join("bin"),
+        "mix",
+        "#!/bin/sh\nif [ \"$1\" = credo ]; then echo \"$5:1:11: R: Modules should have a @moduledoc tag.\"; exit 8; fi\n\
+echo '    warning: variable \"x\" is unused'\necho '    └─ lib/app.ex:3:5: App.run/0'\n\
+echo '    warning: unused import Enum'\necho '    └─ lib/other.ex:2:3: Other'\nexit 1\n",
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+    let run = |args: &[&str]| run_binary_with_path(&input, args, &path);
+
+    let output = run(&[]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("is unused")
+            && !output.contains("unused import"),
+        "expected the compiler's warning for the edited file only, got: {output}"
+    );
+    let output = run(&["--lenient", "--debug"]);
+    assert!(
+        !output.contains(r#""decision":"block""#),
+        "lenient mode should drop unused variables, got: {output}"
+    );
+
+    fs::write(
+        dir.join("app/mix.exs"),
+        "defmodule App.MixProject do\n  defp deps, do: [{:credo, \"~> 1.7\"}]\nend\n",
+    )
+    .unwrap();
+    let output = run(&[]);
+    assert!(
+        output.contains("credo") && output.contains("@moduledoc"),
+        "expected credo's finding, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn haskell_is_linted_with_ghc_or_hlint() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-hs-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::create_dir_all(dir.join("src/Data")).unwrap();
+    fs::write(dir.join("tree.cabal"), "name: tree\n").unwrap();
+    let file = dir.join("src/Data/Tree.hs");
+    fs::write(&file, "module Data.Tree where\n\nhelper = 1\n").unwrap();
+
+    // A stand-in ghc reporting an unused binding, and echoing the import path it was given
+    write_stub(
+        &dir.join("bin"),
+        "ghc",
+        "#!/bin/sh\nfor a; do case $a in -i*) echo \"$a\" > \"$(dirname \"$0\")/import-path\";; esac; done\n\
+echo \"src/Data/Tree.hs:3:1: warning: [GHC-40910] [-Wunused-top-binds]\" >&2\n\
+echo \"    Defined but not used: helper\" >&2\nexit 0\n",
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+    let run = |args: &[&str]| run_binary_with_path(&input, args, &path);
+
+    let output = run(&[]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("using ghc")
+            && output.contains("Defined but not used"),
+        "expected ghc's warning to block, got: {output}"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("bin/import-path"))
+            .unwrap()
+            .trim(),
+        format!("-i{}", dir.join("src").display())
+    );
+    let output = run(&["--lenient", "--debug"]);
+    assert!(
+        !output.contains(r#""decision":"block""#),
+        "lenient mode shouldn't block on unused bindings, got: {output}"
+    );
+
+    // hlint is preferred when installed
+    write_stub(
+        &dir.join("bin"),
+        "hlint",
+        "#!/bin/sh\necho '[{\"severity\":\"Warning\",\"hint\":\"Redundant bracket\",\"file\":\"src/Data/Tree.hs\",\"startLine\":3,\"startColumn\":10,\"to\":\"1\"}]'\nexit 1\n",
+    );
+    let output = run(&[]);
+    assert!(
+        output.contains("using hlint") && output.contains("Redundant bracket"),
+        "expected hlint's hint, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn rate_limited_projects_reuse_the_last_verdict() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-rate-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::create_dir_all(dir.join("scripts")).unwrap();
+    Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[hooks]\ndedup_window_ms = 0\n\n[rate_limit]\nshell = 300\n",
+    )
+    .unwrap();
+    let script = dir.join("scripts/run.sh");
+    let other = dir.join("scripts/other.sh");
+    fs::write(&script, "#!/bin/sh\necho $1\n").unwrap();
+    fs::write(&other, "#!/bin/sh\n").unwrap();
+
+    // A stand-in shellcheck counting its runs
+    write_stub(
+        &dir.join("bin"),
+        "shellcheck",
+        "#!/bin/sh\necho run >> \"$(dirname \"$0\")/runs\"\nfor a; do f=$a; done\necho \"$f:2:6: error: Double quote to prevent globbing [SC2086]\"\nexit 1\n",
+    );
+    let path = path_with(&dir.join("bin"));
+    let sid = format!("rate-{}", std::process::id());
+    let run = |file: &std::path::Path| {
+        let input = format!(
+            r#"{{"session_id":"{sid}","tool_input":{{"file_path":"{}"}}}}"#,
+            file.display()
+        );
+        run_binary_with_path(&input, &["--debug"], &path)
+    };
+    let runs = || {
+        fs::read_to_string(dir.join("bin/runs"))
+            .unwrap_or_default()
+            .lines()
+            .count()
+    };
+
+    let first = run(&script);
+    assert!(first.contains("SC2086"), "expected a block, got: {first}");
+    assert_eq!(runs(), 1);
+
+    // Unchanged contents get the same verdict without a second run
+    let again = run(&script);
+    assert!(
+        again.contains("SC2086") && again.contains("rate limited"),
+        "expected the reused verdict, got: {again}"
+    );
+    let skipped = run(&other);
+    assert!(
+        skipped.contains("skip (rate-limited)"),
+        "expected a rate-limited skip, got: {skipped}"
+    );
+    assert_eq!(runs(), 1);
+
+    let _ = fs::remove_file(std::env::temp_dir().join(format!("ralph-lint-{sid}-rate-limit.txt")));
+    let _ = fs::remove_file(std::env::temp_dir().join(format!("ralph-lint-{sid}-verdicts.txt")));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn eslint_runs_through_eslint_d_when_installed() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-eslintd-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("node_modules/.bin")).unwrap();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"name":"x","version":"1.0.0"}"#,
+    )
+    .unwrap();
+    let file = dir.join("app.js");
+    fs::write(&file, "var x = 1;\n").unwrap();
+    for (name, script) in [
+        ("eslint", "#!/bin/sh\necho 'eslint itself ran'\nexit 1\n"),
+        (
+            "eslint_d",
+            "#!/bin/sh\nif [ \"$1\" = status ]; then echo 'eslint_d: Running'; exit 0; fi\necho 'linted by eslint_d'\nexit 1\n",
+        ),
+    ] {
+        write_stub(&dir.join("node_modules/.bin"), name, script);
+    }
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let output = run_binary(&input);
+    assert!(
+        output.contains("linted by eslint_d") && !output.contains("eslint itself ran"),
+        "expected eslint_d to run, got: {output}"
+    );
+
+    fs::write(dir.join(".ralph-hook-lint.toml"), "[js]\ndaemon = false\n").unwrap();
+    let output = run_binary(&input);
+    assert!(
+        output.contains("eslint itself ran"),
+        "expected eslint with the daemon turned off, got: {output}"
+    );
+    fs::remove_file(dir.join(".ralph-hook-lint.toml")).unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_ralph-hook-lint"))
+        .args(["daemon", "status"])
+        .arg(&dir)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&status.stdout);
+    assert!(
+        status.status.success() && stdout.contains("eslint_d: eslint_d: Running"),
+        "expected eslint_d's status, got: {stdout}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn zig_is_checked_with_ast_check_and_fmt() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-zig-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("build.zig"), "").unwrap();
+    let file = dir.join("src/main.zig");
+    fs::write(&file, "pub fn main() void {}\n").unwrap();
+
+    // A stand-in zig: ast-check reports an unused constant, fmt --check lists the file
+    write_stub(
+        &dir.join("bin"),
+        "zig",
+        "#!/bin/sh\nif [ \"$1\" = ast-check ]; then echo \"$2:2:11: error: unused local constant\" >&2; exit 1; fi\n\
+if [ \"$1\" = fmt ]; then echo \"$3\"; exit 1; fi\nexit 2\n",
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+    let run = |args: &[&str]| run_binary_with_path(&input, args, &path);
+
+    let output = run(&[]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("unused local constant")
+            && output.contains("not formatted"),
+        "expected both checks to report, got: {output}"
+    );
+    let output = run(&["--lenient", "--debug"]);
+    assert!(
+        !output.contains("unused local constant") && output.contains("not formatted"),
+        "lenient mode should drop the unused constant only, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lua_is_linted_with_luacheck_or_selene() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-lua-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::write(dir.join(".luacheckrc"), "std = \"lua51\"\n").unwrap();
+    let file = dir.join("init.lua");
+    fs::write(&file, "local x = 1\n").unwrap();
+
+    // A stand-in luacheck: a finding unless --no-unused is given
+    write_stub(
+        &dir.join("bin"),
+        "luacheck",
+        "#!/bin/sh\nfor a; do [ \"$a\" = --no-unused ] && exit 0; f=$a; done\necho \"$f:1:7: (W211) unused variable 'x'\"\nexit 1\n",
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+    let run = |args: &[&str]| run_binary_with_path(&input, args, &path);
+
+    let output = run(&[]);
+    assert!(
+        output.contains("using luacheck") && output.contains("W211"),
+        "expected luacheck's warning, got: {output}"
+    );
+    let output = run(&["--lenient", "--debug"]);
+    assert!(
+        !output.contains(r#""decision":"block""#),
+        "lenient mode should pass --no-unused, got: {output}"
+    );
+
+    // selene is preferred when installed
+    write_stub(
+        &dir.join("bin"),
+        "selene",
+        "#!/bin/sh\nfor a; do f=$a; done\necho \"$f:1:7: warning[unused_variable]: x is defined, but never used\"\nexit 0\n",
+    );
+    let output = run(&[]);
+    assert!(
+        output.contains("using selene") && output.contains("unused_variable"),
+        "expected selene's warning to block, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn js_linters_can_run_from_the_repository_root() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-jsroot-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".git")).unwrap();
+    fs::create_dir_all(dir.join("node_modules/.bin")).unwrap();
+    fs::create_dir_all(dir.join("packages/web")).unwrap();
+    fs::write(
+        dir.join("package.json"),
+        r#"{"name":"mono","private":true}"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("packages/web/package.json"),
+        r#"{"name":"web","version":"1.0.0"}"#,
+    )
+    .unwrap();
+    let file = dir.join("packages/web/app.js");
+    fs::write(&file, "var x = 1;\n").unwrap();
+
+    // Only the root has eslint; it reports the directory it ran from
+    write_stub(
+        &dir.join("node_modules/.bin"),
+        "eslint",
+        "#!/bin/sh\necho \"ran in $(pwd)\"\nexit 1\n",
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let output = run_binary_debug(&input);
+    assert!(
+        !output.contains("ran in"),
+        "by default the package's own linters are used, got: {output}"
+    );
+
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[js]\nrun_from = \"root\"\n",
+    )
+    .unwrap();
+    let output = run_binary(&input);
+    assert!(
+        output.contains(&format!("ran in {}", dir.display()))
+            && !output.contains("packages/web\\n"),
+        "expected eslint to run from the repository root, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lint_collected_summarizes_clean_runs() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-summary-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    let mut files = Vec::new();
+    for project in ["a", "b"] {
+        fs::create_dir_all(dir.join(project)).unwrap();
+        fs::write(dir.join(project).join(".luacheckrc"), "std = \"lua51\"\n").unwrap();
+        let file = dir.join(project).join("init.lua");
+        fs::write(&file, "return {}\n").unwrap();
+        files.push(file);
+    }
+    write_stub(&dir.join("bin"), "luacheck", "#!/bin/sh\nexit 0\n");
+    let sid = format!("summary-{}", std::process::id());
+
+    let path = path_with(&dir.join("bin"));
+    let run = |args: &[&str], input: String| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ralph-hook-lint"))
+            .args(args)
+            .env("PATH", &path)
+            .current_dir(&dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        String::from_utf8_lossy(&child.wait_with_output().unwrap().stdout).to_string()
+    };
+    let collect_all = || {
+        for file in &files {
+            run(
+                &["--collect"],
+                format!(
+                    r#"{{"session_id":"{sid}","tool_input":{{"file_path":"{}"}}}}"#,
+                    file.display()
+                ),
+            );
+        }
+    };
+
+    collect_all();
+    let output = run(
+        &["--lint-collected"],
+        format!(r#"{{"session_id":"{sid}"}}"#),
+    );
+    assert_eq!(output.trim(), r#"{"continue":true}"#);
+
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[lint_collected]\nsummary = true\n",
+    )
+    .unwrap();
+    for file in &files {
+        fs::write(file, "return { 1 }\n").unwrap();
+    }
+    collect_all();
+    let output = run(
+        &["--lint-collected", "--lenient"],
+        format!(r#"{{"session_id":"{sid}"}}"#),
+    );
+    assert!(
+        output.contains("verified: linted 2 file(s) across 2 project(s): clean")
+            && output.contains("lenient profile"),
+        "expected a summary of the clean run, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn managed_policy_overrides_project_config() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-policy-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let policy = dir.join("policy.toml");
+    fs::write(&policy, "[policy]\ndisabled_languages = [\"python\"]\n").unwrap();
+    let file = dir.join("app.py");
+    fs::write(&file, "import os\n").unwrap();
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let run = |checksum: Option<&str>| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_ralph-hook-lint"));
+        command
+            .arg("--debug")
+            .env("RALPH_HOOK_LINT_POLICY", &policy)
+            .env_remove("RALPH_HOOK_LINT_POLICY_SHA256")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped());
+        if let Some(checksum) = checksum {
+            command.env("RALPH_HOOK_LINT_POLICY_SHA256", checksum);
+        }
+        let mut child = command.spawn().unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        String::from_utf8_lossy(&child.wait_with_output().unwrap().stdout).to_string()
+    };
+
+    let output = run(None);
+    assert!(
+        output.contains("skip (excluded)") && output.contains("managed policy"),
+        "expected the policy to disable Python, got: {output}"
+    );
+    let output = run(Some(&"0".repeat(64)));
+    assert!(
+        output.contains("lint hook error") && output.contains("checksum mismatch"),
+        "expected a tampered policy to be rejected, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn broken_maven_build_blocks_instead_of_skipping() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-mvn-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src/main/java")).unwrap();
+    fs::write(dir.join("pom.xml"), "<project></project>\n").unwrap();
+    let file = dir.join("src/main/java/App.java");
+    fs::write(&file, "class App {}\n").unwrap();
+
+    // A stand-in mvn that can't even read the pom, as when it's broken or the machine is offline
+    write_stub(
+        &dir.join("bin"),
+        "mvn",
+        &format!(
+            "#!/bin/sh\necho run >> '{}'\necho '[ERROR] Non-parseable POM pom.xml: expected START_TAG'\nexit 1\n",
+            dir.join("mvn.log").display()
+        ),
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+    for _ in 0..2 {
+        let output = run_binary_with_path(&input, &[], &path);
+        assert!(
+            output.contains(r#""decision":"block""#) && output.contains("Non-parseable POM"),
+            "a broken build should block, got: {output}"
+        );
+    }
+    let runs = fs::read_to_string(dir.join("mvn.log")).unwrap();
+    assert_eq!(runs.lines().count(), 2, "a failed probe must not be cached");
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn bloop_compile_blocks_only_on_errors_in_the_edited_file() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-bloop-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join(".bloop")).unwrap();
+    fs::create_dir_all(dir.join("src/main/java")).unwrap();
+    fs::write(dir.join("pom.xml"), "<project></project>\n").unwrap();
+    fs::write(dir.join(".ralph-hook-lint.toml"), "[java]\nbloop = true\n").unwrap();
+    fs::write(
+        dir.join(".bloop/core.json"),
+        format!(
+            r#"{{"project":{{"name":"core","directory":"{}"}}}}"#,
+            dir.display()
+        ),
+    )
+    .unwrap();
+    let file = dir.join("src/main/java/App.java");
+    fs::write(&file, "class App {}\n").unwrap();
+
+    // A stand-in bloop that reports whatever the test puts in bloop.out, and a stand-in mvn
+    // whose build has no lint plugin
+    write_stub(
+        &dir.join("bin"),
+        "bloop",
+        &format!(
+            "#!/bin/sh\ncat '{}'\nexit 1\n",
+            dir.join("bloop.out").display()
+        ),
+    );
+    write_stub(
+        &dir.join("bin"),
+        "mvn",
+        &format!(
+            "#!/bin/sh\necho run >> '{}'\necho \"[ERROR] No plugin found for prefix 'pmd'\"\nexit 1\n",
+            dir.join("mvn.log").display()
+        ),
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+
+    fs::write(
+        dir.join("bloop.out"),
+        "[E1] src/main/java/Other.java:2:1\n     cannot find symbol\n",
+    )
+    .unwrap();
+    let output = run_binary_with_path(&input, &[], &path);
+    assert!(
+        !output.contains(r#""decision":"block""#),
+        "errors in other files should not block, got: {output}"
+    );
+    assert!(
+        dir.join("mvn.log").exists(),
+        "the configured lint should still run after bloop"
+    );
+
+    fs::write(
+        dir.join("bloop.out"),
+        "[E1] src/main/java/App.java:1:7\n     cannot find symbol\n[E1] src/main/java/Other.java:2:1\n     class expected\n",
+    )
+    .unwrap();
+    let output = run_binary_with_path(&input, &[], &path);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("cannot find symbol")
+            && !output.contains("class expected"),
+        "expected only App.java's compile error, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn scala_is_linted_through_sbt() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-scala-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::create_dir_all(dir.join("src/main/scala")).unwrap();
+    fs::write(dir.join("build.sbt"), "scalaVersion := \"2.13.14\"\n").unwrap();
+    let file = dir.join("src/main/scala/App.scala");
+    fs::write(&file, "import scala.util.Try\nobject App\n").unwrap();
+
+    // A stand-in sbt whose build defines scalafix, which reports an unused import in App.scala
+    // and a finding in another file
+    write_stub(
+        &dir.join("bin"),
+        "sbt",
+        &format!(
+            "#!/bin/sh\nfor a; do last=$a; done\ncase \"$last\" in\n\
+'tasks -V') printf '  compile   Compiles sources.\\n  scalafix  Run scalafix rule(s).\\n' ;;\n\
+'scalafix --check') echo '[error] {0}:1:1: error: [RemoveUnused] Unused import'; echo '[error] {1}:2:1: error: [DisableSyntax] var is disabled'; exit 1 ;;\n\
+*) exit 2 ;;\nesac\n",
+            file.display(),
+            dir.join("src/main/scala/Other.scala").display()
+        ),
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+    let run = |args: &[&str]| run_binary_with_path(&input, args, &path);
+
+    let output = run(&[]);
+    assert!(
+        output.contains("sbt scalafix --check")
+            && output.contains("RemoveUnused")
+            && !output.contains("DisableSyntax"),
+        "expected scalafix's finding in App.scala only, got: {output}"
+    );
+    let output = run(&["--lenient", "--debug"]);
+    assert!(
+        !output.contains(r#""decision":"block""#),
+        "lenient mode should drop RemoveUnused, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn powershell_is_linted_with_psscriptanalyzer() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-pwsh-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::create_dir(dir.join(".git")).unwrap();
+    let file = dir.join("deploy.ps1");
+    fs::write(
+        &file,
+        "$password = ConvertTo-SecureString -String 'x' -AsPlainText -Force\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[lenient.powershell]\nallow = [\"PSAvoidUsingConvertToSecureStringWithPlainText\"]\n",
+    )
+    .unwrap();
+
+    // A stand-in pwsh printing what the analyzer script serializes for one finding
+    write_stub(
+        &dir.join("bin"),
+        "pwsh",
+        &format!(
+            "#!/bin/sh\necho '[{{\"RuleName\":\"PSAvoidUsingConvertToSecureStringWithPlainText\",\"Line\":1,\"Column\":13,\"Message\":\"File uses ConvertTo-SecureString with plaintext.\",\"ScriptPath\":\"{}\"}}]'\n",
+            file.display()
+        ),
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = path_with(&dir.join("bin"));
+    let run = |args: &[&str]| run_binary_with_path(&input, args, &path);
+
+    let output = run(&[]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("PSAvoidUsingConvertToSecureStringWithPlainText"),
+        "expected PSScriptAnalyzer's finding to block, got: {output}"
+    );
+    let output = run(&["--lenient"]);
+    assert!(
+        !output.contains(r#""decision":"block""#),
+        "lenient mode should drop the allowed rule, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn nix_is_linted_with_statix_or_parsed() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-nix-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::write(dir.join("flake.nix"), "{ outputs = _: { }; }\n").unwrap();
+    let file = dir.join("shell.nix");
+    fs::write(&file, "let hello = pkgs.hello; in { hello = hello; }\n").unwrap();
+
+    // A stand-in statix reporting a finding in shell.nix and one in another file, and a
+    // stand-in nix-instantiate that fails to parse
+    let statix = write_stub(
+        &dir.join("bin"),
+        "statix",
+        &format!(
+            "#!/bin/sh\necho '{}>1:26:W:03:Assignment instead of inherit'\necho '{}>1:1:W:01:Unnecessary comparison'\nexit 1\n",
+            file.display(),
+            dir.join("flake.nix").display()
+        ),
+    );
+    write_stub(
+        &dir.join("bin"),
+        "nix-instantiate",
+        "#!/bin/sh\necho 'error: syntax error, unexpected end of file' >&2\nexit 1\n",
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = format!("{}:/usr/bin:/bin", dir.join("bin").display());
+    let run = || run_binary_with_path(&input, &[], &path);
+
+    let output = run();
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("W03")
+            && !output.contains("W01"),
+        "expected statix's finding in shell.nix only, got: {output}"
+    );
+    fs::remove_file(&statix).unwrap();
+    let output = run();
+    assert!(
+        output.contains("nix-instantiate --parse") && output.contains("syntax error"),
+        "expected nix-instantiate's syntax error, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn session_rules_are_passed_to_ruff() {
+    let dir = std::env::temp_dir().join(format!(
+        "ralph-lint-integ-session-rules-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"app\"\n").unwrap();
+    let file = dir.join("app.py");
+    fs::write(&file, "import subprocess\n").unwrap();
+
+    // A stand-in ruff recording its arguments and reporting nothing
+    write_stub(
+        &dir.join("bin"),
+        "ruff",
+        &format!(
+            "#!/bin/sh\necho \"$@\" >> {}\necho '[]'\n",
+            dir.join("args.txt").display()
+        ),
+    );
+
+    let session_id = format!("integ-session-rules-{}", std::process::id());
+    let input = format!(
+        r#"{{"session_id":"{session_id}","tool_input":{{"file_path":"{}"}}}}"#,
+        file.display()
+    );
+    let path = path_with(&dir.join("bin"));
+    let run = |args: &[&str]| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ralph-hook-lint"))
+            .args(args)
+            .env("PATH", &path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        // `session` doesn't read the payload and may exit before it is written
+        let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+        String::from_utf8_lossy(&child.wait_with_output().unwrap().stdout).to_string()
+    };
+
+    run(&["lint"]);
+    run(&["session", "add", &session_id, "ruff", "S"]);
+    run(&["session", "add", &session_id, "ruff", "T20"]);
+    assert_eq!(
+        run(&["session", "list", &session_id]),
+        "ruff\tS\nruff\tT20\n"
+    );
+    run(&["lint"]);
+    run(&["session", "clear", &session_id]);
+    run(&["lint"]);
+
+    let args = fs::read_to_string(dir.join("args.txt")).unwrap();
+    let runs: Vec<&str> = args.lines().collect();
+    assert_eq!(runs.len(), 3, "expected three ruff runs, got: {args}");
+    assert!(!runs[0].contains("--extend-select"), "{args}");
+    assert!(runs[1].contains("--extend-select=S,T20"), "{args}");
+    assert!(!runs[2].contains("--extend-select"), "{args}");
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn markdown_code_blocks_are_linted_when_enabled() {
+    let dir = std::env::temp_dir().join(format!(
+        "ralph-lint-integ-code-blocks-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::create_dir_all(dir.join("docs")).unwrap();
+    fs::create_dir(dir.join(".git")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\nname = \"app\"\n").unwrap();
+    let file = dir.join("docs/guide.md");
+    fs::write(
+        &file,
+        "# Guide\n\n```python\nimport os\nif os.name == None:\n    pass\n```\n",
+    )
+    .unwrap();
+
+    // A stand-in ruff reporting a comparison to None on line 2 of the file it is given
+    write_stub(
+        &dir.join("bin"),
+        "ruff",
+        "#!/bin/sh\nfor a; do last=$a; done\necho \"[{\\\"filename\\\":\\\"$last\\\",\\\"location\\\":{\\\"row\\\":2,\\\"column\\\":12},\\\"code\\\":\\\"E711\\\",\\\"message\\\":\\\"Comparison to None\\\"}]\"\nexit 1\n",
+    );
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = format!("{}:/usr/bin:/bin", dir.join("bin").display());
+    let run = || run_binary_with_path(&input, &["lint"], &path);
+
+    let output = run();
+    assert!(
+        !output.contains(r#""decision":"block""#),
+        "code blocks are only linted when enabled, got: {output}"
+    );
+    fs::write(
+        dir.join(".ralph-hook-lint.toml"),
+        "[markdown]\ncode_blocks = true\n",
+    )
+    .unwrap();
+    let output = run();
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("guide.md (code block at line 3):2:12")
+            && output.contains("E711"),
+        "expected ruff's finding at block-relative line 2, got: {output}"
+    );
+    let staged = fs::read_dir(dir.join("docs"))
+        .unwrap()
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("ralph_block_"))
+        .count();
+    assert_eq!(staged, 0, "staged code blocks should be removed");
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn templates_use_the_linter_configured_for_them() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-astro-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("node_modules/.bin")).unwrap();
+    fs::create_dir_all(dir.join("src/pages")).unwrap();
+    fs::write(dir.join("package.json"), r#"{"name":"site"}"#).unwrap();
+    let file = dir.join("src/pages/index.astro");
+    fs::write(&file, "---\nconst title = 1\n---\n<h1>{title}</h1>\n").unwrap();
+
+    // oxlint can't parse templates; eslint can once the plugin is installed
+    for (name, report) in [
+        ("oxlint", "oxlint ran"),
+        ("eslint", "1:7 error no-unused-vars"),
+    ] {
+        write_stub(
+            &dir.join("node_modules/.bin"),
+            name,
+            &format!("#!/bin/sh\necho '{report}'\nexit 1\n"),
+        );
+    }
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let run = || run_binary_with_args(&input, &["lint", "--debug"]);
+
+    let output = run();
+    assert!(
+        !output.contains(r#""decision":"block""#)
+            && !output.contains("oxlint ran")
+            && output.contains("no astro linter configured"),
+        "expected the template to be skipped, got: {output}"
+    );
+    fs::write(
+        dir.join("package.json"),
+        r#"{"name":"site","devDependencies":{"eslint-plugin-astro":"^1.0.0"}}"#,
+    )
+    .unwrap();
+    let output = run();
+    assert!(
+        output.contains(r#""decision":"block""#) && output.contains("using eslint"),
+        "expected eslint to lint the template, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn vue_files_are_type_checked_with_vue_tsc() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-vue-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("node_modules/.bin")).unwrap();
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("package.json"), r#"{"name":"app"}"#).unwrap();
+    let file = dir.join("src/App.vue");
+    fs::write(&file, "<template><h1>{{ title }}</h1></template>\n").unwrap();
+
+    // oxlint skips templates; vue-tsc reports a template error here and one in another file
+    for (name, report) in [
+        ("oxlint", "oxlint ran"),
+        (
+            "vue-tsc",
+            "src/App.vue(1,17): error TS2339: Property 'title' does not exist.\n\
+src/Other.vue(2,1): error TS2304: Cannot find name 'x'.",
+        ),
+    ] {
+        write_stub(
+            &dir.join("node_modules/.bin"),
+            name,
+            &format!("#!/bin/sh\necho \"{report}\"\nexit 1\n"),
+        );
+    }
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let output = run_binary_with_args(&input, &["lint"]);
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("vue-tsc")
+            && output.contains("TS2339")
+            && !output.contains("TS2304")
+            && !output.contains("oxlint ran"),
+        "expected vue-tsc's error in App.vue alone, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn solidity_is_linted_with_solhint_and_forge() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-sol-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("foundry.toml"), "[profile.default]\n").unwrap();
+    let file = dir.join("src/Counter.sol");
+    fs::write(&file, "contract Counter {}\n").unwrap();
+
+    // A stand-in solhint with a warning in Counter.sol and an error in another file, and a
+    // stand-in forge with a compile error in Counter.sol
+    let scripts = [
+        (
+            "solhint",
+            "echo 'src/Counter.sol:3:9: Variable \"x\" is unused [Warning/no-unused-vars]'\n\
+echo 'src/Other.sol:1:1: Compiler version mismatch [Error/compiler-version]'\nexit 1",
+        ),
+        (
+            "forge",
+            "echo 'Error: Compiler run failed:' >&2\necho 'Error (7576): Undeclared identifier.' >&2\n\
+echo '  --> src/Counter.sol:5:9:' >&2\nexit 1",
+        ),
+    ];
+    for (name, script) in scripts {
+        write_stub(&dir.join("bin"), name, &format!("#!/bin/sh\n{script}\n"));
+    }
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = format!("{}:/usr/bin:/bin", dir.join("bin").display());
+    let run = || run_binary_with_path(&input, &["lint"], &path);
+
+    let output = run();
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("solhint and forge build")
+            && output.contains("Undeclared identifier")
+            && output.contains("no-unused-vars")
+            && !output.contains("compiler-version"),
+        "expected forge's error and solhint's warning in Counter.sol, got: {output}"
+    );
+    fs::remove_file(dir.join("bin/forge")).unwrap();
+    let output = run();
+    assert!(
+        !output.contains(r#""decision":"block""#),
+        "solhint's warnings alone shouldn't block, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn terraform_is_linted_with_tflint_and_conftest() {
+    let dir = std::env::temp_dir().join(format!("ralph-lint-integ-tf-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::create_dir_all(dir.join("infra")).unwrap();
+    fs::create_dir_all(dir.join("policy")).unwrap();
+    fs::create_dir_all(dir.join(".git")).unwrap();
+    let file = dir.join("infra/main.tf");
+    fs::write(&file, "resource \"aws_s3_bucket\" \"logs\" {}\n").unwrap();
+
+    // A stand-in tflint with a warning in main.tf, and a stand-in conftest whose policy finds
+    // a public bucket
+    let scripts = [
+        (
+            "tflint",
+            "echo 'main.tf:1:1: Warning - Missing version constraint (terraform_required_providers)'\nexit 2",
+        ),
+        (
+            "conftest",
+            r#"echo '[{"filename":"main.tf","namespace":"s3","successes":0,"failures":[{"msg":"S3 bucket logs is public"}]}]'
+exit 1"#,
+        ),
+    ];
+    for (name, script) in scripts {
+        write_stub(&dir.join("bin"), name, &format!("#!/bin/sh\n{script}\n"));
+    }
+
+    let input = format!(r#"{{"tool_input":{{"file_path":"{}"}}}}"#, file.display());
+    let path = format!("{}:/usr/bin:/bin", dir.join("bin").display());
+    let run = || run_binary_with_path(&input, &["lint"], &path);
+
+    let output = run();
+    assert!(
+        output.contains(r#""decision":"block""#)
+            && output.contains("tflint and conftest")
+            && output.contains("S3 bucket logs is public")
+            && output.contains("terraform_required_providers"),
+        "expected the policy failure and tflint's warning, got: {output}"
+    );
+    fs::remove_dir_all(dir.join("policy")).unwrap();
+    let output = run();
+    assert!(
+        !output.contains(r#""decision":"block""#),
+        "without a policy directory tflint's warnings alone shouldn't block, got: {output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn serve_answers_json_rpc_requests() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ralph-hook-lint"))
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(
+            concat!(
+                r#"{"jsonrpc":"2.0","id":1,"method":"lintFile","params":{"path":"/tmp/notes.txt"}}"#,
+                "\n",
+                r#"{"jsonrpc":"2.0","id":2,"method":"lintPaths","params":{"paths":["/tmp/a.txt","/tmp/b.txt"]}}"#,
+                "\n",
+                r#"{"jsonrpc":"2.0","id":3,"method":"shutdown"}"#,
+                "\n",
+                r#"{"jsonrpc":"2.0","id":4,"method":"lintFile","params":{"path":"/tmp/late.txt"}}"#,
+                "\n",
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines.len(),
+        3,
+        "expected no answer after shutdown, got: {stdout}"
+    );
+    assert!(
+        lines[0].starts_with(
+            r#"{"jsonrpc":"2.0","id":1,"result":{"path":"/tmp/notes.txt","blocked":false"#
+        ) && lines[0].contains("unsupported file type"),
+        "got: {}",
+        lines[0]
+    );
+    assert_eq!(lines[1].matches(r#""blocked":false"#).count(), 2);
+    assert_eq!(lines[2], r#"{"jsonrpc":"2.0","id":3,"result":null}"#);
+}